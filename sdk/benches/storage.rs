@@ -0,0 +1,95 @@
+//! Benchmarks for storing values and delivering changes to subscribers.
+//!
+//! Browser storage is only available in a browser, so these measure the file backing used on desktop. Both backings
+//! share how writes are coalesced and how changes reach other windows.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use dioxus::prelude::*;
+use dioxus_sdk::storage::{
+    set_directory, use_storage, LocalStorage, StorageBacking, StorageSubscriber,
+};
+
+/// Sizes of the stored strings, from a setting to a cached document.
+const SIZES: [usize; 3] = [16, 4 * 1024, 256 * 1024];
+
+fn set_and_get(c: &mut Criterion) {
+    let mut group = c.benchmark_group("set_and_get");
+    for size in SIZES {
+        let key = format!("bench-set-{size}");
+        let value = "a".repeat(size);
+        group.bench_with_input(BenchmarkId::from_parameter(size), &value, |b, value| {
+            b.iter(|| {
+                LocalStorage::set(key.clone(), value);
+                LocalStorage::get::<String>(&key)
+            })
+        });
+        LocalStorage::remove(&key);
+    }
+    group.finish();
+}
+
+fn notify_subscribers(c: &mut Criterion) {
+    let mut group = c.benchmark_group("notify_subscribers");
+    for subscribers in [1, 16, 64] {
+        let key = format!("bench-notify-{subscribers}");
+        let receivers: Vec<_> = (0..subscribers)
+            .map(|_| LocalStorage::subscribe::<u32>(&key))
+            .collect();
+        let mut value = 0u32;
+        group.bench_function(BenchmarkId::from_parameter(subscribers), |b| {
+            b.iter(|| {
+                value += 1;
+                LocalStorage::set(key.clone(), &value);
+            })
+        });
+        drop(receivers);
+        LocalStorage::remove(&key);
+    }
+    group.finish();
+}
+
+/// Many writes to one stored signal in a single tick, which are stored once.
+fn coalesced_writes(c: &mut Criterion) {
+    fn app(writes: u32) -> Element {
+        let mut value = use_storage::<LocalStorage, _>("bench-coalesce".to_string(), || 0u32);
+        use_hook(|| {
+            spawn(async move {
+                for i in 0..writes {
+                    value.set(i);
+                }
+            })
+        });
+        rsx! {}
+    }
+
+    let mut group = c.benchmark_group("coalesced_writes");
+    for writes in [1, 100, 1000] {
+        group.bench_function(BenchmarkId::from_parameter(writes), |b| {
+            b.iter_batched(
+                || {
+                    let mut dom = VirtualDom::new_with_props(app, writes);
+                    dom.rebuild_in_place();
+                    dom
+                },
+                |mut dom| dom.process_events(),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    LocalStorage::remove(&"bench-coalesce".to_string());
+    group.finish();
+}
+
+fn setup() {
+    set_directory(std::env::temp_dir().join("dioxus-sdk-storage-bench"));
+}
+
+fn benches(c: &mut Criterion) {
+    setup();
+    set_and_get(c);
+    notify_subscribers(c);
+    coalesced_writes(c);
+}
+
+criterion_group!(storage, benches);
+criterion_main!(storage);
@@ -0,0 +1,114 @@
+//! The analytics types shared by every platform.
+
+use serde::{Deserialize, Serialize};
+use std::{fmt, time::Duration};
+
+/// Where and how analytics events are sent.
+///
+/// Every flush posts a JSON array of events to the endpoint. Each event has a `name`, the `props` it was tracked
+/// with, and a `timestamp` in milliseconds since the Unix epoch:
+///
+/// ```json
+/// [{ "name": "signup", "props": { "plan": "pro" }, "timestamp": 1700000000000 }]
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnalyticsConfig {
+    pub(crate) endpoint: String,
+    pub(crate) headers: Vec<(String, String)>,
+    pub(crate) batch_size: usize,
+    pub(crate) flush_interval: Duration,
+    pub(crate) max_queued: usize,
+    pub(crate) storage_key: String,
+}
+
+impl AnalyticsConfig {
+    /// Send events to `endpoint`.
+    pub fn new(endpoint: impl ToString) -> Self {
+        Self {
+            endpoint: endpoint.to_string(),
+            headers: Vec::new(),
+            batch_size: 20,
+            flush_interval: Duration::from_secs(10),
+            max_queued: 1000,
+            storage_key: "dioxus-analytics-queue".to_string(),
+        }
+    }
+
+    /// Add a header to every request, like an API key.
+    pub fn header(mut self, name: impl ToString, value: impl ToString) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Send at most this many events per request, and send right away once this many are queued. Defaults to 20.
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// How often queued events are sent. Defaults to 10 seconds.
+    pub fn flush_interval(mut self, flush_interval: Duration) -> Self {
+        self.flush_interval = flush_interval;
+        self
+    }
+
+    /// Drop the oldest events once more than this many are queued, for example after a long time offline.
+    /// Defaults to 1000.
+    pub fn max_queued(mut self, max_queued: usize) -> Self {
+        self.max_queued = max_queued;
+        self
+    }
+
+    /// The storage key the queue is persisted under. Defaults to `dioxus-analytics-queue`.
+    pub fn storage_key(mut self, storage_key: impl ToString) -> Self {
+        self.storage_key = storage_key.to_string();
+        self
+    }
+}
+
+/// A tracked event waiting to be sent.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct AnalyticsEvent {
+    pub name: String,
+    /// The properties of the event, encoded as JSON.
+    ///
+    /// They are kept encoded since the storage format can't hold arbitrary JSON values.
+    pub props: String,
+    /// When the event was tracked, in milliseconds since the Unix epoch.
+    pub timestamp: u64,
+}
+
+impl AnalyticsEvent {
+    pub(crate) fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "name": self.name,
+            "props": serde_json::from_str::<serde_json::Value>(&self.props).unwrap_or_default(),
+            "timestamp": self.timestamp,
+        })
+    }
+}
+
+/// Describes errors that may occur when sending analytics events.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnalyticsError {
+    /// The device is offline. The events stay queued until it is back online.
+    Offline,
+    /// The request could not be sent.
+    Request(String),
+    /// The endpoint responded with an unsuccessful status code.
+    Status(u16),
+    /// Sending events is not supported on this platform.
+    Unsupported,
+}
+
+impl std::error::Error for AnalyticsError {}
+impl fmt::Display for AnalyticsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Offline => write!(f, "the device is offline"),
+            Self::Request(e) => write!(f, "request failed: {e}"),
+            Self::Status(status) => write!(f, "endpoint responded with status {status}"),
+            Self::Unsupported => write!(f, "analytics are not supported on this platform"),
+        }
+    }
+}
@@ -0,0 +1,11 @@
+//! A client-side queue for analytics events.
+//!
+//! Events are batched, kept in persistent storage until they were sent, and posted as JSON to an endpoint of your
+//! choice, so any analytics backend can receive them.
+
+mod core;
+mod platform;
+mod use_analytics;
+
+pub use self::core::*;
+pub use self::use_analytics::*;
@@ -0,0 +1,50 @@
+use reqwest::{header::CONTENT_TYPE, Client};
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::analytics::AnalyticsError;
+
+/// The client shared by all flushes so connections can be reused.
+static CLIENT: OnceLock<Client> = OnceLock::new();
+
+/// Post a batch of events, encoded as JSON, to `endpoint`.
+pub(crate) async fn send(
+    endpoint: &str,
+    headers: &[(String, String)],
+    body: String,
+) -> Result<(), AnalyticsError> {
+    let mut request = CLIENT
+        .get_or_init(Client::new)
+        .post(endpoint)
+        .header(CONTENT_TYPE, "application/json")
+        .body(body);
+    for (name, value) in headers {
+        request = request.header(name, value);
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| AnalyticsError::Request(e.to_string()))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(AnalyticsError::Status(status.as_u16()));
+    }
+    Ok(())
+}
+
+/// Whether the device is connected to a network.
+///
+/// Native platforms can't tell reliably, so this is always `true` and failed requests keep their events queued.
+pub(crate) fn is_online() -> bool {
+    true
+}
+
+/// The current time in milliseconds since the Unix epoch.
+pub(crate) fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
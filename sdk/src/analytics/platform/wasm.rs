@@ -0,0 +1,52 @@
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Request, RequestInit};
+
+use crate::analytics::AnalyticsError;
+
+/// Post a batch of events, encoded as JSON, to `endpoint`.
+pub(crate) async fn send(
+    endpoint: &str,
+    headers: &[(String, String)],
+    body: String,
+) -> Result<(), AnalyticsError> {
+    let window = web_sys::window().ok_or(AnalyticsError::Unsupported)?;
+
+    let init = RequestInit::new();
+    init.set_method("POST");
+    init.set_body(&JsValue::from_str(&body));
+    let request = Request::new_with_str_and_init(endpoint, &init).map_err(js_error)?;
+    request
+        .headers()
+        .set("Content-Type", "application/json")
+        .map_err(js_error)?;
+    for (name, value) in headers {
+        request.headers().set(name, value).map_err(js_error)?;
+    }
+
+    let response = JsFuture::from(window.fetch_with_request(&request))
+        .await
+        .map_err(js_error)?;
+    let response = response.dyn_into::<web_sys::Response>().map_err(js_error)?;
+
+    if !response.ok() {
+        return Err(AnalyticsError::Status(response.status()));
+    }
+    Ok(())
+}
+
+/// Whether the browser is connected to a network.
+pub(crate) fn is_online() -> bool {
+    web_sys::window()
+        .map(|window| window.navigator().on_line())
+        .unwrap_or(true)
+}
+
+/// The current time in milliseconds since the Unix epoch.
+pub(crate) fn now() -> u64 {
+    js_sys::Date::now() as u64
+}
+
+fn js_error(e: JsValue) -> AnalyticsError {
+    AnalyticsError::Request(format!("{:?}", e))
+}
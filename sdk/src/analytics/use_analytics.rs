@@ -0,0 +1,204 @@
+//! Provides the analytics hooks.
+
+use dioxus::prelude::*;
+use serde::Serialize;
+
+use super::core::{AnalyticsConfig, AnalyticsError, AnalyticsEvent};
+use super::platform;
+use crate::{
+    storage::{new_storage, LocalStorage},
+    utils::timing::clock,
+};
+
+/// A handle to the analytics queue.
+///
+/// See [`use_analytics`] for more information.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Analytics {
+    queue: Signal<Vec<AnalyticsEvent>>,
+    config: CopyValue<AnalyticsConfig>,
+    flushing: Signal<bool>,
+    error: Signal<Option<AnalyticsError>>,
+}
+
+impl Analytics {
+    /// Queue an event with some properties, like a struct or `serde_json::json!({ ... })`.
+    ///
+    /// The queue is sent once it holds a full batch, or on the next flush interval.
+    pub fn track(&self, name: impl ToString, props: impl Serialize) {
+        let mut queue = self.queue;
+        let config = self.config.read();
+        let event = AnalyticsEvent {
+            name: name.to_string(),
+            props: serde_json::to_string(&props).unwrap_or_else(|_| "null".to_string()),
+            timestamp: platform::now(),
+        };
+
+        let full = {
+            let mut queue = queue.write();
+            queue.push(event);
+            let overflow = queue.len().saturating_sub(config.max_queued);
+            queue.drain(..overflow);
+            queue.len() >= config.batch_size
+        };
+        if full {
+            self.flush();
+        }
+    }
+
+    /// Send the queued events now.
+    ///
+    /// Events that fail to send stay queued and are retried on the next flush.
+    pub fn flush(&self) {
+        let this = *self;
+        spawn(async move {
+            this.send_queue().await;
+        });
+    }
+
+    /// Get the events that haven't been sent yet.
+    pub fn queue(&self) -> ReadOnlySignal<Vec<AnalyticsEvent>> {
+        self.queue.into()
+    }
+
+    /// Get the error of the last flush, if it failed.
+    pub fn error(&self) -> ReadOnlySignal<Option<AnalyticsError>> {
+        self.error.into()
+    }
+
+    /// Send batches until the queue is empty or a request fails.
+    async fn send_queue(mut self) {
+        if *self.flushing.peek() {
+            return;
+        }
+        if !platform::is_online() {
+            self.error.set(Some(AnalyticsError::Offline));
+            return;
+        }
+
+        self.flushing.set(true);
+        let config = self.config.read().clone();
+        loop {
+            let batch: Vec<_> = self
+                .queue
+                .peek()
+                .iter()
+                .take(config.batch_size)
+                .cloned()
+                .collect();
+            if batch.is_empty() {
+                self.error.set(None);
+                break;
+            }
+
+            let body =
+                serde_json::Value::Array(batch.iter().map(AnalyticsEvent::to_json).collect());
+            if let Err(e) =
+                platform::send(&config.endpoint, &config.headers, body.to_string()).await
+            {
+                tracing::warn!("Failed to send analytics events: {}", e);
+                self.error.set(Some(e));
+                break;
+            }
+            remove_sent(&mut self.queue.write(), &batch);
+        }
+        self.flushing.set(false);
+    }
+}
+
+/// Remove the events that were sent from the queue.
+///
+/// Events tracked or dropped while the batch was sent shift the queue, so the sent events are looked up instead of
+/// removed by position. Equal events are interchangeable, so the first match is removed.
+fn remove_sent(queue: &mut Vec<AnalyticsEvent>, sent: &[AnalyticsEvent]) {
+    for event in sent {
+        if let Some(index) = queue.iter().position(|queued| queued == event) {
+            queue.remove(index);
+        }
+    }
+}
+
+/// Start sending analytics events. Must be called before [`use_analytics`], usually in the root component.
+///
+/// Events tracked in a previous session that weren't sent yet are sent on the first flush.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::analytics::{init_analytics, AnalyticsConfig};
+///
+/// fn App() -> Element {
+///     init_analytics(
+///         AnalyticsConfig::new("https://example.com/events").header("Authorization", "Bearer KEY"),
+///     );
+///
+///     rsx! {}
+/// }
+/// ```
+pub fn init_analytics(config: AnalyticsConfig) -> Analytics {
+    use_hook(|| {
+        let analytics = Analytics {
+            // Local storage outlives the session, so events that weren't sent yet are sent after a restart.
+            queue: new_storage::<LocalStorage, _>(config.storage_key.clone(), Vec::new),
+            config: CopyValue::new(config),
+            flushing: Signal::new(false),
+            error: Signal::new(None),
+        };
+
+        spawn(async move {
+            loop {
+                let interval = analytics.config.read().flush_interval;
+                clock::sleep(interval).await;
+                analytics.send_queue().await;
+            }
+        });
+
+        provide_context(analytics)
+    })
+}
+
+/// A hook to track analytics events.
+///
+/// Events are batched and kept in persistent storage until they are sent, so events tracked while offline or right
+/// before the app is closed aren't lost. Requires [`init_analytics`] to be called first.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::analytics::use_analytics;
+/// use serde_json::json;
+///
+/// fn SignupButton() -> Element {
+///     let analytics = use_analytics();
+///
+///     rsx! {
+///         button {
+///             onclick: move |_| analytics.track("signup", json!({ "plan": "pro" })),
+///             "Sign up"
+///         }
+///     }
+/// }
+/// ```
+pub fn use_analytics() -> Analytics {
+    use_hook(|| {
+        try_consume_context::<Analytics>()
+            .expect("init_analytics must be called before use_analytics")
+    })
+}
+
+#[test]
+fn test_remove_sent_events() {
+    let event = |name: &str| AnalyticsEvent {
+        name: name.to_string(),
+        ..Default::default()
+    };
+
+    // The oldest event was dropped and a new one was tracked while "b" and "c" were sent.
+    let mut queue = vec![event("b"), event("c"), event("d"), event("e")];
+    queue.remove(0);
+    queue.push(event("f"));
+    remove_sent(&mut queue, &[event("b"), event("c")]);
+    assert_eq!(queue, vec![event("d"), event("e"), event("f")]);
+}
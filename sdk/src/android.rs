@@ -0,0 +1,20 @@
+//! Helpers shared by the Android implementations.
+
+use jni::{objects::JObject, JNIEnv, JavaVM};
+
+/// Run `f` with the app's activity, turning Java exceptions into errors.
+pub(crate) fn with_activity<R>(
+    f: impl FnOnce(&mut JNIEnv, &JObject) -> jni::errors::Result<R>,
+) -> Result<R, String> {
+    let context = ndk_context::android_context();
+    let vm = unsafe { JavaVM::from_raw(context.vm().cast()) }.map_err(|e| e.to_string())?;
+    let activity = unsafe { JObject::from_raw(context.context().cast()) };
+    let mut env = vm.attach_current_thread().map_err(|e| e.to_string())?;
+
+    let result = env.with_local_frame(16, |env| f(env, &activity));
+    if env.exception_check().unwrap_or(false) {
+        env.exception_describe().ok();
+        env.exception_clear().ok();
+    }
+    result.map_err(|e| e.to_string())
+}
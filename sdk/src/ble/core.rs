@@ -0,0 +1,177 @@
+//! The Bluetooth Low Energy types shared by every platform.
+
+use futures::Stream;
+use std::{collections::HashMap, fmt};
+use uuid::Uuid;
+
+use super::platform;
+use crate::permissions::{report_permission, Permission, PermissionStatus};
+
+/// Describes which devices to look for while scanning.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScanFilter {
+    /// Only find devices that advertise at least one of these services. Finds every device if empty.
+    pub services: Vec<Uuid>,
+    /// Only find devices whose name starts with this prefix.
+    pub name_prefix: Option<String>,
+    /// Services the app uses that aren't used for filtering.
+    ///
+    /// The web only allows access to services that were listed when the device was picked.
+    pub optional_services: Vec<Uuid>,
+}
+
+/// A device found while scanning.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BleAdvertisement {
+    /// The device that sent the advertisement.
+    pub device: BleDevice,
+    /// The name the device advertises, if any.
+    pub name: Option<String>,
+    /// The signal strength in dBm, if the platform reports it.
+    pub rssi: Option<i16>,
+    /// The services the device advertises.
+    pub services: Vec<Uuid>,
+    /// Manufacturer specific data, by company identifier.
+    pub manufacturer_data: HashMap<u16, Vec<u8>>,
+}
+
+/// Something that happened while scanning.
+pub(crate) enum ScanEvent {
+    Advertisement(BleAdvertisement),
+    AdapterState(AdapterState),
+}
+
+/// Describes the state of the Bluetooth adapter.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum AdapterState {
+    /// The state hasn't been checked yet, or the platform can't tell.
+    #[default]
+    Unknown,
+    PoweredOn,
+    PoweredOff,
+    /// The device has no Bluetooth adapter, or the platform doesn't support Bluetooth Low Energy.
+    Unavailable,
+}
+
+/// A Bluetooth Low Energy device.
+///
+/// Devices are found with [`use_ble_scanner`](super::use_ble_scanner). Services and characteristics are
+/// identified by their UUIDs.
+#[derive(Clone)]
+pub struct BleDevice {
+    pub(crate) inner: platform::Device,
+}
+
+impl BleDevice {
+    /// An identifier for the device that stays the same while the app is running.
+    pub fn id(&self) -> String {
+        self.inner.id()
+    }
+
+    /// Connect to the device.
+    pub async fn connect(&self) -> Result<(), BleError> {
+        report(self.inner.connect().await)
+    }
+
+    /// Disconnect from the device.
+    pub async fn disconnect(&self) -> Result<(), BleError> {
+        self.inner.disconnect().await
+    }
+
+    /// Whether the device is connected.
+    pub async fn is_connected(&self) -> bool {
+        self.inner.is_connected().await
+    }
+
+    /// Read the value of a characteristic.
+    pub async fn read(&self, service: Uuid, characteristic: Uuid) -> Result<Vec<u8>, BleError> {
+        self.inner.read(service, characteristic).await
+    }
+
+    /// Write the value of a characteristic and wait for the device to confirm it.
+    pub async fn write(
+        &self,
+        service: Uuid,
+        characteristic: Uuid,
+        data: &[u8],
+    ) -> Result<(), BleError> {
+        self.inner.write(service, characteristic, data).await
+    }
+
+    /// Subscribe to the values a characteristic notifies.
+    pub async fn notify(
+        &self,
+        service: Uuid,
+        characteristic: Uuid,
+    ) -> Result<impl Stream<Item = Vec<u8>>, BleError> {
+        self.inner.notify(service, characteristic).await
+    }
+}
+
+impl PartialEq for BleDevice {
+    fn eq(&self, other: &Self) -> bool {
+        self.id() == other.id()
+    }
+}
+
+impl fmt::Debug for BleDevice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BleDevice").field("id", &self.id()).finish()
+    }
+}
+
+/// Get the state of the Bluetooth adapter.
+pub async fn adapter_state() -> AdapterState {
+    platform::adapter_state().await
+}
+
+/// Share the outcome of using Bluetooth with the permission center.
+pub(crate) fn report<T>(result: Result<T, BleError>) -> Result<T, BleError> {
+    match &result {
+        Ok(_) => report_permission(Permission::Bluetooth, PermissionStatus::Granted),
+        Err(BleError::PermissionDenied) => {
+            report_permission(Permission::Bluetooth, PermissionStatus::Denied)
+        }
+        Err(_) => {}
+    }
+    result
+}
+
+/// Describes errors that may occur when using Bluetooth Low Energy.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BleError {
+    /// The platform doesn't support Bluetooth Low Energy.
+    Unsupported,
+    /// The user or the system denied access to Bluetooth.
+    PermissionDenied,
+    /// There is no Bluetooth adapter, or it is turned off.
+    AdapterUnavailable,
+    /// The device isn't connected.
+    NotConnected,
+    /// The device doesn't have the characteristic, or the app isn't allowed to access it.
+    CharacteristicNotFound { service: Uuid, characteristic: Uuid },
+    /// The platform reported an error.
+    Failed(String),
+}
+
+impl std::error::Error for BleError {}
+impl fmt::Display for BleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Unsupported => {
+                write!(f, "bluetooth low energy is not supported on this platform")
+            }
+            Self::PermissionDenied => write!(f, "access to bluetooth was denied"),
+            Self::AdapterUnavailable => write!(f, "no bluetooth adapter is available"),
+            Self::NotConnected => write!(f, "the device is not connected"),
+            Self::CharacteristicNotFound {
+                service,
+                characteristic,
+            } => write!(
+                f,
+                "characteristic {characteristic} of service {service} was not found"
+            ),
+            Self::Failed(reason) => write!(f, "bluetooth error: {reason}"),
+        }
+    }
+}
@@ -0,0 +1,10 @@
+//! Find and talk to Bluetooth Low Energy devices.
+//!
+//! Uses Web Bluetooth on the web and the system Bluetooth stack on desktop.
+
+mod core;
+mod platform;
+mod use_ble_scanner;
+
+pub use self::core::*;
+pub use self::use_ble_scanner::*;
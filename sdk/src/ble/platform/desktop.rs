@@ -0,0 +1,198 @@
+use btleplug::{
+    api::{
+        Central, CentralEvent, CentralState, Characteristic, Manager as _, Peripheral as _,
+        ScanFilter as PlatformScanFilter, WriteType,
+    },
+    platform::{Adapter, Manager, Peripheral},
+};
+use futures::{future, Stream, StreamExt};
+use tokio::sync::OnceCell;
+use uuid::Uuid;
+
+use crate::ble::{AdapterState, BleAdvertisement, BleDevice, BleError, ScanEvent, ScanFilter};
+
+/// The first Bluetooth adapter of the system, shared by every scanner and device.
+static ADAPTER: OnceCell<Result<Adapter, BleError>> = OnceCell::const_new();
+
+async fn adapter() -> Result<Adapter, BleError> {
+    ADAPTER
+        .get_or_init(|| async {
+            let manager = Manager::new().await.map_err(to_error)?;
+            let adapters = manager.adapters().await.map_err(to_error)?;
+            adapters
+                .into_iter()
+                .next()
+                .ok_or(BleError::AdapterUnavailable)
+        })
+        .await
+        .clone()
+}
+
+pub async fn adapter_state() -> AdapterState {
+    match adapter().await {
+        Ok(adapter) => adapter
+            .adapter_state()
+            .await
+            .map(from_central_state)
+            .unwrap_or_default(),
+        Err(_) => AdapterState::Unavailable,
+    }
+}
+
+/// Start scanning and stream the devices that are found.
+pub async fn scan(filter: ScanFilter) -> Result<impl Stream<Item = ScanEvent>, BleError> {
+    let adapter = adapter().await?;
+    let events = adapter.events().await.map_err(to_error)?;
+    adapter
+        .start_scan(PlatformScanFilter {
+            services: filter.services,
+        })
+        .await
+        .map_err(to_error)?;
+
+    let name_prefix = filter.name_prefix;
+    Ok(events.filter_map(move |event| {
+        let adapter = adapter.clone();
+        let name_prefix = name_prefix.clone();
+        async move {
+            let id = match event {
+                CentralEvent::StateUpdate(state) => {
+                    return Some(ScanEvent::AdapterState(from_central_state(state)))
+                }
+                CentralEvent::DeviceDiscovered(id)
+                | CentralEvent::DeviceUpdated(id)
+                | CentralEvent::ManufacturerDataAdvertisement { id, .. }
+                | CentralEvent::ServicesAdvertisement { id, .. } => id,
+                _ => return None,
+            };
+
+            let peripheral = adapter.peripheral(&id).await.ok()?;
+            let properties = peripheral.properties().await.ok()??;
+            if let Some(prefix) = &name_prefix {
+                if !properties
+                    .local_name
+                    .as_ref()
+                    .is_some_and(|name| name.starts_with(prefix))
+                {
+                    return None;
+                }
+            }
+
+            Some(ScanEvent::Advertisement(BleAdvertisement {
+                device: BleDevice {
+                    inner: Device { peripheral },
+                },
+                name: properties.local_name,
+                rssi: properties.rssi,
+                services: properties.services,
+                manufacturer_data: properties.manufacturer_data,
+            }))
+        }
+    }))
+}
+
+pub async fn stop_scan() {
+    if let Ok(adapter) = adapter().await {
+        adapter.stop_scan().await.ok();
+    }
+}
+
+#[derive(Clone)]
+pub struct Device {
+    peripheral: Peripheral,
+}
+
+impl Device {
+    pub fn id(&self) -> String {
+        self.peripheral.id().to_string()
+    }
+
+    pub async fn connect(&self) -> Result<(), BleError> {
+        self.peripheral.connect().await.map_err(to_error)?;
+        self.peripheral.discover_services().await.map_err(to_error)
+    }
+
+    pub async fn disconnect(&self) -> Result<(), BleError> {
+        self.peripheral.disconnect().await.map_err(to_error)
+    }
+
+    pub async fn is_connected(&self) -> bool {
+        self.peripheral.is_connected().await.unwrap_or(false)
+    }
+
+    pub async fn read(&self, service: Uuid, characteristic: Uuid) -> Result<Vec<u8>, BleError> {
+        let characteristic = self.characteristic(service, characteristic).await?;
+        self.peripheral
+            .read(&characteristic)
+            .await
+            .map_err(to_error)
+    }
+
+    pub async fn write(
+        &self,
+        service: Uuid,
+        characteristic: Uuid,
+        data: &[u8],
+    ) -> Result<(), BleError> {
+        let characteristic = self.characteristic(service, characteristic).await?;
+        self.peripheral
+            .write(&characteristic, data, WriteType::WithResponse)
+            .await
+            .map_err(to_error)
+    }
+
+    pub async fn notify(
+        &self,
+        service: Uuid,
+        characteristic: Uuid,
+    ) -> Result<impl Stream<Item = Vec<u8>>, BleError> {
+        let characteristic = self.characteristic(service, characteristic).await?;
+        // Get the stream before subscribing, so the first notification isn't missed.
+        let notifications = self.peripheral.notifications().await.map_err(to_error)?;
+        self.peripheral
+            .subscribe(&characteristic)
+            .await
+            .map_err(to_error)?;
+
+        // The device sends the notifications of every characteristic through the same stream.
+        Ok(notifications.filter_map(move |notification| {
+            future::ready((notification.uuid == characteristic.uuid).then_some(notification.value))
+        }))
+    }
+
+    async fn characteristic(
+        &self,
+        service: Uuid,
+        characteristic: Uuid,
+    ) -> Result<Characteristic, BleError> {
+        if !self.is_connected().await {
+            return Err(BleError::NotConnected);
+        }
+
+        self.peripheral
+            .characteristics()
+            .into_iter()
+            .find(|c| c.service_uuid == service && c.uuid == characteristic)
+            .ok_or(BleError::CharacteristicNotFound {
+                service,
+                characteristic,
+            })
+    }
+}
+
+fn from_central_state(state: CentralState) -> AdapterState {
+    match state {
+        CentralState::PoweredOn => AdapterState::PoweredOn,
+        CentralState::PoweredOff => AdapterState::PoweredOff,
+        _ => AdapterState::Unknown,
+    }
+}
+
+fn to_error(error: btleplug::Error) -> BleError {
+    match error {
+        btleplug::Error::PermissionDenied => BleError::PermissionDenied,
+        btleplug::Error::NotConnected => BleError::NotConnected,
+        btleplug::Error::NotSupported(_) => BleError::Unsupported,
+        error => BleError::Failed(error.to_string()),
+    }
+}
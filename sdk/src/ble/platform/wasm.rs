@@ -0,0 +1,306 @@
+use futures::{
+    channel::mpsc::{self, UnboundedReceiver},
+    stream, Stream, StreamExt,
+};
+use js_sys::{Array, DataView, Function, Object, Promise, Reflect, Uint8Array};
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use uuid::Uuid;
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+use crate::ble::{AdapterState, BleAdvertisement, BleDevice, BleError, ScanEvent, ScanFilter};
+
+pub async fn adapter_state() -> AdapterState {
+    let Some(bluetooth) = bluetooth() else {
+        return AdapterState::Unavailable;
+    };
+
+    // Browsers only tell whether an adapter can be used, not whether it is turned off.
+    match call_async(&bluetooth, "getAvailability", &[]).await {
+        Some(Ok(available)) if available.is_truthy() => AdapterState::PoweredOn,
+        Some(Ok(_)) => AdapterState::Unavailable,
+        _ => AdapterState::Unknown,
+    }
+}
+
+/// Ask the user to pick a device.
+///
+/// Web Bluetooth doesn't let pages scan in the background, so this shows the browser's device chooser and
+/// streams the device the user picked.
+pub async fn scan(filter: ScanFilter) -> Result<impl Stream<Item = ScanEvent>, BleError> {
+    let bluetooth = bluetooth().ok_or(BleError::Unsupported)?;
+    let device = match call_async(&bluetooth, "requestDevice", &[request_options(&filter)]).await {
+        Some(Ok(device)) => Some(device),
+        // The user closed the chooser without picking a device.
+        Some(Err(e)) if error_name(&e).as_deref() == Some("NotFoundError") => None,
+        Some(Err(e)) => return Err(to_error(&e)),
+        None => return Err(BleError::Unsupported),
+    };
+
+    let advertisement = device.map(|device| {
+        ScanEvent::Advertisement(BleAdvertisement {
+            name: get(&device, "name").and_then(|name| name.as_string()),
+            rssi: None,
+            services: Vec::new(),
+            manufacturer_data: HashMap::new(),
+            device: BleDevice {
+                inner: Device { device },
+            },
+        })
+    });
+    Ok(stream::iter(advertisement))
+}
+
+/// The web has nothing to stop since the device chooser closes by itself.
+pub async fn stop_scan() {}
+
+fn request_options(filter: &ScanFilter) -> JsValue {
+    let options = Object::new();
+    let filters = Array::new();
+    for service in &filter.services {
+        let entry = Object::new();
+        let services = Array::of1(&service.to_string().into());
+        Reflect::set(&entry, &"services".into(), &services).ok();
+        if let Some(prefix) = &filter.name_prefix {
+            Reflect::set(&entry, &"namePrefix".into(), &prefix.into()).ok();
+        }
+        filters.push(&entry);
+    }
+    if filter.services.is_empty() {
+        if let Some(prefix) = &filter.name_prefix {
+            let entry = Object::new();
+            Reflect::set(&entry, &"namePrefix".into(), &prefix.into()).ok();
+            filters.push(&entry);
+        }
+    }
+
+    match filters.length() {
+        0 => Reflect::set(&options, &"acceptAllDevices".into(), &JsValue::TRUE),
+        _ => Reflect::set(&options, &"filters".into(), &filters),
+    }
+    .ok();
+
+    let optional_services: Array = filter
+        .services
+        .iter()
+        .chain(&filter.optional_services)
+        .map(|service| JsValue::from(service.to_string()))
+        .collect();
+    Reflect::set(&options, &"optionalServices".into(), &optional_services).ok();
+
+    options.into()
+}
+
+#[derive(Clone)]
+pub struct Device {
+    device: JsValue,
+}
+
+impl Device {
+    pub fn id(&self) -> String {
+        get(&self.device, "id")
+            .and_then(|id| id.as_string())
+            .unwrap_or_default()
+    }
+
+    pub async fn connect(&self) -> Result<(), BleError> {
+        let gatt = get(&self.device, "gatt").ok_or(BleError::Unsupported)?;
+        match call_async(&gatt, "connect", &[]).await {
+            Some(Ok(_)) => Ok(()),
+            Some(Err(e)) => Err(to_error(&e)),
+            None => Err(BleError::Unsupported),
+        }
+    }
+
+    pub async fn disconnect(&self) -> Result<(), BleError> {
+        if let Some(gatt) = get(&self.device, "gatt") {
+            call(&gatt, "disconnect", &[]);
+        }
+        Ok(())
+    }
+
+    pub async fn is_connected(&self) -> bool {
+        get(&self.device, "gatt")
+            .and_then(|gatt| get(&gatt, "connected"))
+            .is_some_and(|connected| connected.is_truthy())
+    }
+
+    pub async fn read(&self, service: Uuid, characteristic: Uuid) -> Result<Vec<u8>, BleError> {
+        let characteristic = self.characteristic(service, characteristic).await?;
+        let value = call_async(&characteristic, "readValue", &[])
+            .await
+            .ok_or(BleError::Unsupported)?
+            .map_err(|e| to_error(&e))?;
+        Ok(to_bytes(&value))
+    }
+
+    pub async fn write(
+        &self,
+        service: Uuid,
+        characteristic: Uuid,
+        data: &[u8],
+    ) -> Result<(), BleError> {
+        let characteristic = self.characteristic(service, characteristic).await?;
+        let data: JsValue = Uint8Array::from(data).into();
+        // Older browsers only have `writeValue`, which also waits for the response.
+        let result =
+            match call_async(&characteristic, "writeValueWithResponse", &[data.clone()]).await {
+                Some(result) => result,
+                None => call_async(&characteristic, "writeValue", &[data])
+                    .await
+                    .ok_or(BleError::Unsupported)?,
+            };
+        result.map(|_| ()).map_err(|e| to_error(&e))
+    }
+
+    pub async fn notify(
+        &self,
+        service: Uuid,
+        characteristic: Uuid,
+    ) -> Result<impl Stream<Item = Vec<u8>>, BleError> {
+        let characteristic = self.characteristic(service, characteristic).await?;
+        let (tx, rx) = mpsc::unbounded();
+        let listener = Closure::wrap(Box::new(move |event: JsValue| {
+            if let Some(value) = get(&event, "target").and_then(|target| get(&target, "value")) {
+                tx.unbounded_send(to_bytes(&value)).ok();
+            }
+        }) as Box<dyn FnMut(JsValue)>);
+        call(
+            &characteristic,
+            "addEventListener",
+            &[
+                "characteristicvaluechanged".into(),
+                listener.as_ref().clone(),
+            ],
+        );
+        // Created before starting, so the listener is removed again if starting fails.
+        let notifications = Notifications {
+            values: rx,
+            characteristic,
+            listener,
+        };
+
+        call_async(&notifications.characteristic, "startNotifications", &[])
+            .await
+            .ok_or(BleError::Unsupported)?
+            .map_err(|e| to_error(&e))?;
+
+        Ok(notifications)
+    }
+
+    async fn characteristic(
+        &self,
+        service: Uuid,
+        characteristic: Uuid,
+    ) -> Result<JsValue, BleError> {
+        if !self.is_connected().await {
+            return Err(BleError::NotConnected);
+        }
+        let not_found = BleError::CharacteristicNotFound {
+            service,
+            characteristic,
+        };
+
+        let gatt = get(&self.device, "gatt").ok_or(BleError::Unsupported)?;
+        let service = call_async(&gatt, "getPrimaryService", &[service.to_string().into()])
+            .await
+            .ok_or(BleError::Unsupported)?
+            .map_err(|_| not_found.clone())?;
+        call_async(
+            &service,
+            "getCharacteristic",
+            &[characteristic.to_string().into()],
+        )
+        .await
+        .ok_or(BleError::Unsupported)?
+        .map_err(|_| not_found)
+    }
+}
+
+/// The values notified by a characteristic. Stops listening when dropped.
+struct Notifications {
+    values: UnboundedReceiver<Vec<u8>>,
+    characteristic: JsValue,
+    listener: Closure<dyn FnMut(JsValue)>,
+}
+
+impl Stream for Notifications {
+    type Item = Vec<u8>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Vec<u8>>> {
+        self.get_mut().values.poll_next_unpin(cx)
+    }
+}
+
+impl Drop for Notifications {
+    fn drop(&mut self) {
+        call(
+            &self.characteristic,
+            "removeEventListener",
+            &[
+                "characteristicvaluechanged".into(),
+                self.listener.as_ref().clone(),
+            ],
+        );
+        call(&self.characteristic, "stopNotifications", &[]);
+    }
+}
+
+fn bluetooth() -> Option<JsValue> {
+    get(&get(&web_sys::window()?, "navigator")?, "bluetooth")
+}
+
+/// Copy the bytes of a `DataView`.
+fn to_bytes(value: &JsValue) -> Vec<u8> {
+    let view: DataView = value.clone().unchecked_into();
+    Uint8Array::new_with_byte_offset_and_length(
+        &view.buffer(),
+        view.byte_offset() as u32,
+        view.byte_length() as u32,
+    )
+    .to_vec()
+}
+
+fn error_name(error: &JsValue) -> Option<String> {
+    get(error, "name")?.as_string()
+}
+
+fn to_error(error: &JsValue) -> BleError {
+    match error_name(error).as_deref() {
+        Some("SecurityError") | Some("NotAllowedError") => BleError::PermissionDenied,
+        Some("NetworkError") => BleError::NotConnected,
+        Some("NotSupportedError") => BleError::Unsupported,
+        _ => BleError::Failed(
+            get(error, "message")
+                .and_then(|message| message.as_string())
+                .unwrap_or_else(|| format!("{error:?}")),
+        ),
+    }
+}
+
+/// Get a property, treating `undefined` and `null` as missing.
+fn get(target: &JsValue, key: &str) -> Option<JsValue> {
+    Reflect::get(target, &key.into())
+        .ok()
+        .filter(|value| !value.is_undefined() && !value.is_null())
+}
+
+/// Call a method, returning `None` if it doesn't exist or throws.
+fn call(target: &JsValue, method: &str, args: &[JsValue]) -> Option<JsValue> {
+    let function: Function = get(target, method)?.dyn_into().ok()?;
+    function.apply(target, &args.iter().collect::<Array>()).ok()
+}
+
+/// Call a method that returns a promise and wait for it, returning `None` if the method doesn't exist.
+async fn call_async(
+    target: &JsValue,
+    method: &str,
+    args: &[JsValue],
+) -> Option<Result<JsValue, JsValue>> {
+    let promise: Promise = call(target, method, args)?.dyn_into().ok()?;
+    Some(JsFuture::from(promise).await)
+}
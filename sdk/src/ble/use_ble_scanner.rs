@@ -0,0 +1,151 @@
+//! Provides the use_ble_scanner hook.
+
+use dioxus::prelude::*;
+use futures::StreamExt;
+
+use super::core::{report, AdapterState, BleAdvertisement, BleError, ScanEvent, ScanFilter};
+use super::platform;
+use crate::permissions::{use_permission, Permission, PermissionStatus, UsePermission};
+
+/// The interface for scanning for Bluetooth Low Energy devices.
+///
+/// See [`use_ble_scanner`] for more information.
+#[derive(Clone, Copy, PartialEq)]
+pub struct UseBleScanner {
+    devices: Signal<Vec<BleAdvertisement>>,
+    scanning: Signal<bool>,
+    adapter_state: Signal<AdapterState>,
+    error: Signal<Option<BleError>>,
+    permission: UsePermission,
+    task: Signal<Option<Task>>,
+}
+
+impl UseBleScanner {
+    /// Start scanning for devices that match the filter, replacing the previous scan.
+    ///
+    /// On the web this shows the browser's device chooser, so it must be called in response to a user interaction.
+    pub fn start(&mut self, filter: ScanFilter) {
+        self.stop();
+        self.error.set(None);
+        self.scanning.set(true);
+
+        let mut devices = self.devices;
+        let mut scanning = self.scanning;
+        let mut adapter_state = self.adapter_state;
+        let mut error = self.error;
+        let task = spawn(async move {
+            match report(platform::scan(filter).await) {
+                Ok(events) => {
+                    let mut events = std::pin::pin!(events);
+                    while let Some(event) = events.next().await {
+                        match event {
+                            ScanEvent::Advertisement(advertisement) => {
+                                let mut devices = devices.write();
+                                // Keep the latest advertisement of each device.
+                                match devices
+                                    .iter_mut()
+                                    .find(|d| d.device == advertisement.device)
+                                {
+                                    Some(device) => *device = advertisement,
+                                    None => devices.push(advertisement),
+                                }
+                            }
+                            ScanEvent::AdapterState(state) => adapter_state.set(state),
+                        }
+                    }
+                }
+                Err(e) => error.set(Some(e)),
+            }
+            scanning.set(false);
+        });
+        self.task.set(Some(task));
+    }
+
+    /// Stop scanning. The devices that were found are kept.
+    pub fn stop(&mut self) {
+        if let Some(task) = self.task.write().take() {
+            task.cancel();
+            spawn(platform::stop_scan());
+        }
+        self.scanning.set(false);
+    }
+
+    /// The devices found so far, with the latest advertisement of each.
+    pub fn devices(&self) -> ReadOnlySignal<Vec<BleAdvertisement>> {
+        ReadOnlySignal::new(self.devices)
+    }
+
+    /// Whether a scan is running.
+    pub fn is_scanning(&self) -> ReadOnlySignal<bool> {
+        ReadOnlySignal::new(self.scanning)
+    }
+
+    /// The state of the Bluetooth adapter.
+    pub fn adapter_state(&self) -> ReadOnlySignal<AdapterState> {
+        ReadOnlySignal::new(self.adapter_state)
+    }
+
+    /// Whether the app has access to Bluetooth.
+    pub fn permission(&self) -> ReadOnlySignal<PermissionStatus> {
+        self.permission.status()
+    }
+
+    /// The error that stopped the last scan, if any.
+    pub fn error(&self) -> ReadOnlySignal<Option<BleError>> {
+        ReadOnlySignal::new(self.error)
+    }
+}
+
+/// A hook for finding Bluetooth Low Energy devices.
+///
+/// Scanning starts with [`UseBleScanner::start`] and stops with [`UseBleScanner::stop`] or when the component
+/// is dropped. Every device found is a [`BleDevice`](super::BleDevice) that can be connected to.
+///
+/// - On desktop, the scan streams every advertisement until it is stopped.
+/// - On the web, pages aren't allowed to scan in the background. Starting a scan shows the browser's device
+///   chooser instead, and the device the user picks is added to the list.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::ble::{use_ble_scanner, ScanFilter};
+///
+/// fn App() -> Element {
+///     let mut scanner = use_ble_scanner();
+///
+///     rsx! {
+///         button {
+///             onclick: move |_| scanner.start(ScanFilter::default()),
+///             "Scan"
+///         }
+///         for advertisement in scanner.devices().read().iter() {
+///             p { {advertisement.name.clone().unwrap_or("Unknown device".to_string())} }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_ble_scanner() -> UseBleScanner {
+    let permission = use_permission(Permission::Bluetooth);
+    let scanner = use_hook(|| {
+        let mut adapter_state = Signal::new(AdapterState::Unknown);
+        spawn(async move {
+            adapter_state.set(platform::adapter_state().await);
+        });
+
+        UseBleScanner {
+            devices: Signal::new(Vec::new()),
+            scanning: Signal::new(false),
+            adapter_state,
+            error: Signal::new(None),
+            permission,
+            task: Signal::new(None),
+        }
+    });
+    // The scan task is cancelled with the component, but the adapter has to be told to stop scanning.
+    use_drop(|| {
+        spawn_forever(platform::stop_scan());
+    });
+
+    scanner
+}
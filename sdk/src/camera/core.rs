@@ -0,0 +1,145 @@
+//! The camera types shared by every platform.
+
+use std::{fmt, path::Path};
+
+use super::platform;
+
+/// The state of a camera.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CameraState {
+    /// The camera is off.
+    #[default]
+    Idle,
+    /// The camera is being started, which includes waiting for the user to allow it.
+    Starting,
+    /// The camera is running.
+    Active,
+}
+
+/// Which way a camera faces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CameraFacing {
+    /// The camera facing the user, like the front camera of a phone.
+    User,
+    /// The camera facing away from the user, like the back camera of a phone.
+    Environment,
+}
+
+/// The camera to use and the video to get from it.
+///
+/// The size and frame rate are ideals. The camera picks the closest it supports.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CameraConstraints {
+    /// The [`CameraDevice::id`] of the camera to use, or `None` to use the default camera.
+    pub device_id: Option<String>,
+    /// Which way the camera should face. This is ignored when a device is given.
+    pub facing: Option<CameraFacing>,
+    /// The width of the video in pixels.
+    pub width: Option<u32>,
+    /// The height of the video in pixels.
+    pub height: Option<u32>,
+    /// The frames per second of the video.
+    pub frame_rate: Option<u32>,
+}
+
+/// A camera connected to the device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CameraDevice {
+    /// The ID of the camera, for [`CameraConstraints::device_id`].
+    pub id: String,
+    /// The name of the camera, like `FaceTime HD Camera`.
+    ///
+    /// Browsers only tell the name once the user allowed the camera, and leave it empty before.
+    pub label: String,
+}
+
+/// The format to encode a photo in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PhotoFormat {
+    /// JPEG with a quality between `0.0` and `1.0`.
+    Jpeg { quality: f32 },
+    /// Lossless PNG.
+    Png,
+}
+
+impl Default for PhotoFormat {
+    fn default() -> Self {
+        Self::Jpeg { quality: 0.92 }
+    }
+}
+
+impl PhotoFormat {
+    /// The MIME type of the format, like `image/jpeg`.
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            Self::Jpeg { .. } => "image/jpeg",
+            Self::Png => "image/png",
+        }
+    }
+}
+
+/// A photo taken with [`UseCamera::take_photo`](super::UseCamera::take_photo).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Photo {
+    /// The format of the image, like `image/jpeg`.
+    pub mime_type: String,
+    /// The encoded image.
+    pub data: Vec<u8>,
+    /// The width of the image in pixels.
+    pub width: u32,
+    /// The height of the image in pixels.
+    pub height: u32,
+}
+
+impl Photo {
+    /// The file extension matching the format of the image, without a dot.
+    pub fn extension(&self) -> &'static str {
+        match self.mime_type.as_str() {
+            "image/png" => "png",
+            _ => "jpg",
+        }
+    }
+
+    /// Write the image to a file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), CameraError> {
+        std::fs::write(path, &self.data).map_err(|e| CameraError::Failed(e.to_string()))
+    }
+}
+
+/// List the cameras connected to the device.
+///
+/// Browsers only tell the names of the cameras once the user allowed the camera.
+pub async fn camera_devices() -> Result<Vec<CameraDevice>, CameraError> {
+    platform::devices().await
+}
+
+/// Describes errors that may occur when using the camera.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CameraError {
+    /// The platform can't capture the camera.
+    Unsupported,
+    /// The user didn't allow the camera.
+    PermissionDenied,
+    /// No camera matches the constraints.
+    NotFound,
+    /// The camera is used by another app.
+    InUse,
+    /// The camera isn't running.
+    NotStarted,
+    /// The platform reported an error.
+    Failed(String),
+}
+
+impl std::error::Error for CameraError {}
+impl fmt::Display for CameraError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Unsupported => write!(f, "the camera is not supported on this platform"),
+            Self::PermissionDenied => write!(f, "permission to use the camera was denied"),
+            Self::NotFound => write!(f, "no camera matches the constraints"),
+            Self::InUse => write!(f, "the camera is used by another app"),
+            Self::NotStarted => write!(f, "the camera isn't running"),
+            Self::Failed(reason) => write!(f, "camera error: {reason}"),
+        }
+    }
+}
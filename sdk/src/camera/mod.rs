@@ -0,0 +1,10 @@
+//! Preview the camera and take photos.
+//!
+//! The camera is captured with the `getUserMedia` API of the browser on the web, and of the webview elsewhere.
+
+mod core;
+mod platform;
+mod use_camera;
+
+pub use self::core::*;
+pub use self::use_camera::*;
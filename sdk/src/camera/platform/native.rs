@@ -0,0 +1,308 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use dioxus::prelude::{current_scope_id, eval, spawn};
+use serde_json::{json, Map, Value};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::camera::{
+    CameraConstraints, CameraDevice, CameraError, CameraFacing, Photo, PhotoFormat,
+};
+
+/// Starts a camera stream in the webview and keeps it under its id. Sends once the stream started or failed to,
+/// and again if the video track ends on its own, which doesn't happen when the stream is stopped.
+const START: &str = r#"
+    const [id, video] = await dioxus.recv();
+    if (!navigator.mediaDevices?.getUserMedia) {
+        dioxus.send({ error: "NotSupportedError" });
+    } else {
+        try {
+            const stream = await navigator.mediaDevices.getUserMedia({ video, audio: false });
+            const camera = { stream };
+            window.__dioxusCameras ??= {};
+            window.__dioxusCameras[id] = camera;
+            dioxus.send({});
+            const ended = await new Promise((resolve) => {
+                camera.stop = () => resolve(false);
+                for (const track of stream.getVideoTracks()) {
+                    track.addEventListener("ended", () => resolve(true));
+                }
+            });
+            if (ended) {
+                dioxus.send({ ended });
+            }
+        } catch (e) {
+            dioxus.send({ error: e?.name ?? "", message: e?.message ?? String(e) });
+        }
+    }
+"#;
+
+/// Shows a camera stream in a `<video>` element.
+const ATTACH: &str = r#"
+    const [id, elementId] = await dioxus.recv();
+    const camera = window.__dioxusCameras?.[id];
+    const element = document.getElementById(elementId);
+    if (camera && element) {
+        element.srcObject = camera.stream;
+    }
+"#;
+
+/// Draws the current frame of a camera stream on a canvas and sends it as a data URL.
+const TAKE_PHOTO: &str = r#"
+    const [id, type, quality] = await dioxus.recv();
+    const camera = window.__dioxusCameras?.[id];
+    if (!camera) {
+        dioxus.send({ error: "NotStarted" });
+    } else {
+        // Play the stream in a video element of its own, so photos don't depend on the preview being visible.
+        const video = document.createElement("video");
+        video.muted = true;
+        video.playsInline = true;
+        video.srcObject = camera.stream;
+        try {
+            await video.play();
+            const canvas = document.createElement("canvas");
+            canvas.width = video.videoWidth;
+            canvas.height = video.videoHeight;
+            if (canvas.width && canvas.height) {
+                canvas.getContext("2d").drawImage(video, 0, 0);
+            }
+            dioxus.send({
+                url: canvas.width && canvas.height ? canvas.toDataURL(type, quality) : "",
+                width: canvas.width,
+                height: canvas.height,
+            });
+        } catch (e) {
+            dioxus.send({ error: e?.name ?? "", message: e?.message ?? String(e) });
+        } finally {
+            video.pause();
+            video.srcObject = null;
+        }
+    }
+"#;
+
+/// Stops a camera stream and forgets it.
+const STOP: &str = r#"
+    const id = await dioxus.recv();
+    const camera = window.__dioxusCameras?.[id];
+    if (camera) {
+        delete window.__dioxusCameras[id];
+        camera.stream.getTracks().forEach((track) => track.stop());
+        camera.stop();
+    }
+"#;
+
+/// Lists the cameras the webview can see.
+const DEVICES: &str = r#"
+    if (!navigator.mediaDevices?.enumerateDevices) {
+        dioxus.send({ error: "NotSupportedError" });
+    } else {
+        try {
+            const devices = await navigator.mediaDevices.enumerateDevices();
+            dioxus.send({
+                devices: devices
+                    .filter((device) => device.kind === "videoinput")
+                    .map((device) => [device.deviceId, device.label]),
+            });
+        } catch (e) {
+            dioxus.send({ error: e?.name ?? "", message: e?.message ?? String(e) });
+        }
+    }
+"#;
+
+/// A running camera stream, captured in the webview with `getUserMedia` like on the web.
+#[derive(Clone)]
+pub struct Camera {
+    id: u64,
+}
+
+impl Camera {
+    pub async fn start(
+        constraints: &CameraConstraints,
+        mut on_end: impl FnMut() + 'static,
+    ) -> Result<Self, CameraError> {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+        // There is no webview to capture the camera with outside of a running app.
+        current_scope_id().ok_or(CameraError::Unsupported)?;
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let mut eval = eval(START);
+        eval.send(json!([id, video_constraints(constraints)]))
+            .map_err(|e| CameraError::Failed(format!("{e:?}")))?;
+        let started = eval
+            .recv()
+            .await
+            .map_err(|e| CameraError::Failed(format!("{e:?}")))?;
+        if let Some(e) = to_error(&started) {
+            return Err(e);
+        }
+
+        // The camera can be unplugged or taken away by the system, which ends the video track.
+        spawn(async move {
+            if eval.recv().await.is_ok() {
+                on_end();
+            }
+        });
+
+        Ok(Self { id })
+    }
+
+    /// Show the camera in the `<video>` element with the given id.
+    pub fn attach(&self, element_id: &str) -> Result<(), CameraError> {
+        current_scope_id().ok_or(CameraError::Unsupported)?;
+        eval(ATTACH)
+            .send(json!([self.id, element_id]))
+            .map_err(|e| CameraError::Failed(format!("{e:?}")))
+    }
+
+    pub async fn take_photo(&self, format: PhotoFormat) -> Result<Photo, CameraError> {
+        current_scope_id().ok_or(CameraError::Unsupported)?;
+        let quality = match format {
+            PhotoFormat::Jpeg { quality } => json!(quality),
+            PhotoFormat::Png => Value::Null,
+        };
+        let mut eval = eval(TAKE_PHOTO);
+        eval.send(json!([self.id, format.mime_type(), quality]))
+            .map_err(|e| CameraError::Failed(format!("{e:?}")))?;
+        let taken = eval
+            .recv()
+            .await
+            .map_err(|e| CameraError::Failed(format!("{e:?}")))?;
+        if let Some(e) = to_error(&taken) {
+            return Err(e);
+        }
+
+        let dimension = |name: &str| taken.get(name).and_then(Value::as_u64).unwrap_or(0) as u32;
+        let (width, height) = (dimension("width"), dimension("height"));
+        if width == 0 || height == 0 {
+            return Err(CameraError::Failed(
+                "the camera hasn't delivered a frame".to_string(),
+            ));
+        }
+
+        // The webview falls back to PNG for formats it can't encode, which the data URL tells.
+        let (mime_type, data) = taken
+            .get("url")
+            .and_then(Value::as_str)
+            .and_then(|url| url.strip_prefix("data:"))
+            .and_then(|url| url.split_once(";base64,"))
+            .and_then(|(mime_type, data)| {
+                Some((mime_type.to_string(), STANDARD.decode(data).ok()?))
+            })
+            .ok_or_else(|| CameraError::Failed("the photo couldn't be encoded".to_string()))?;
+
+        Ok(Photo {
+            mime_type,
+            data,
+            width,
+            height,
+        })
+    }
+
+    pub fn stop(&self) {
+        // The webview is gone once the app exits, and the stream with it.
+        if current_scope_id().is_some() {
+            eval(STOP).send(json!(self.id)).ok();
+        }
+    }
+}
+
+pub async fn devices() -> Result<Vec<CameraDevice>, CameraError> {
+    current_scope_id().ok_or(CameraError::Unsupported)?;
+    let listed = eval(DEVICES)
+        .recv()
+        .await
+        .map_err(|e| CameraError::Failed(format!("{e:?}")))?;
+    if let Some(e) = to_error(&listed) {
+        return Err(e);
+    }
+
+    let string = |device: &Value, index: usize| {
+        device
+            .get(index)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    };
+    Ok(listed
+        .get("devices")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(|device| CameraDevice {
+            id: string(device, 0),
+            label: string(device, 1),
+        })
+        .collect())
+}
+
+/// The `video` constraints to pass to `getUserMedia`.
+fn video_constraints(constraints: &CameraConstraints) -> Value {
+    let mut video = Map::new();
+    if let Some(device_id) = &constraints.device_id {
+        video.insert("deviceId".to_string(), json!({ "exact": device_id }));
+    } else if let Some(facing) = constraints.facing {
+        let facing = match facing {
+            CameraFacing::User => "user",
+            CameraFacing::Environment => "environment",
+        };
+        video.insert("facingMode".to_string(), json!(facing));
+    }
+    if let Some(width) = constraints.width {
+        video.insert("width".to_string(), json!({ "ideal": width }));
+    }
+    if let Some(height) = constraints.height {
+        video.insert("height".to_string(), json!({ "ideal": height }));
+    }
+    if let Some(frame_rate) = constraints.frame_rate {
+        video.insert("frameRate".to_string(), json!({ "ideal": frame_rate }));
+    }
+    Value::Object(video)
+}
+
+/// The error the webview sent, if any.
+fn to_error(response: &Value) -> Option<CameraError> {
+    let name = response.get("error")?.as_str().unwrap_or_default();
+    Some(match name {
+        "NotAllowedError" | "SecurityError" => CameraError::PermissionDenied,
+        "NotFoundError" | "OverconstrainedError" => CameraError::NotFound,
+        "NotReadableError" => CameraError::InUse,
+        "NotSupportedError" => CameraError::Unsupported,
+        "NotStarted" => CameraError::NotStarted,
+        _ => CameraError::Failed(
+            response
+                .get("message")
+                .and_then(Value::as_str)
+                .unwrap_or(name)
+                .to_string(),
+        ),
+    })
+}
+
+#[test]
+fn test_video_constraints() {
+    let constraints = CameraConstraints {
+        facing: Some(CameraFacing::Environment),
+        width: Some(1280),
+        ..Default::default()
+    };
+    assert_eq!(
+        video_constraints(&constraints),
+        json!({ "facingMode": "environment", "width": { "ideal": 1280 } })
+    );
+
+    // A device wins over the facing mode.
+    let constraints = CameraConstraints {
+        device_id: Some("front".to_string()),
+        facing: Some(CameraFacing::User),
+        ..Default::default()
+    };
+    assert_eq!(
+        video_constraints(&constraints),
+        json!({ "deviceId": { "exact": "front" } })
+    );
+
+    assert_eq!(
+        to_error(&json!({ "error": "NotAllowedError", "message": "denied" })),
+        Some(CameraError::PermissionDenied)
+    );
+    assert_eq!(to_error(&json!({})), None);
+}
@@ -0,0 +1,254 @@
+use futures::channel::oneshot;
+use js_sys::{Array, Function, Object, Promise, Reflect, Uint8Array};
+use std::rc::Rc;
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+use crate::camera::{
+    CameraConstraints, CameraDevice, CameraError, CameraFacing, Photo, PhotoFormat,
+};
+
+/// A running camera stream.
+#[derive(Clone)]
+pub struct Camera {
+    stream: JsValue,
+    // The listener is kept alive for as long as the camera.
+    _on_ended: Rc<Closure<dyn FnMut(JsValue)>>,
+}
+
+impl Camera {
+    pub async fn start(
+        constraints: &CameraConstraints,
+        mut on_end: impl FnMut() + 'static,
+    ) -> Result<Self, CameraError> {
+        let media_devices = media_devices()?;
+
+        let video = Object::new();
+        let ideal = |value: u32| {
+            let ideal = Object::new();
+            Reflect::set(&ideal, &"ideal".into(), &value.into()).ok();
+            ideal
+        };
+        if let Some(device_id) = &constraints.device_id {
+            let exact = Object::new();
+            Reflect::set(&exact, &"exact".into(), &device_id.into()).ok();
+            Reflect::set(&video, &"deviceId".into(), &exact).ok();
+        } else if let Some(facing) = constraints.facing {
+            let facing = match facing {
+                CameraFacing::User => "user",
+                CameraFacing::Environment => "environment",
+            };
+            Reflect::set(&video, &"facingMode".into(), &facing.into()).ok();
+        }
+        if let Some(width) = constraints.width {
+            Reflect::set(&video, &"width".into(), &ideal(width)).ok();
+        }
+        if let Some(height) = constraints.height {
+            Reflect::set(&video, &"height".into(), &ideal(height)).ok();
+        }
+        if let Some(frame_rate) = constraints.frame_rate {
+            Reflect::set(&video, &"frameRate".into(), &ideal(frame_rate)).ok();
+        }
+
+        let options = Object::new();
+        Reflect::set(&options, &"video".into(), &video).ok();
+        Reflect::set(&options, &"audio".into(), &JsValue::FALSE).ok();
+        let stream = call_async(&media_devices, "getUserMedia", &[options.into()])
+            .await
+            .ok_or(CameraError::Unsupported)?
+            .map_err(|e| to_error(&e))?;
+
+        // The camera can be unplugged or taken away by the browser, which ends the video track.
+        let on_ended =
+            Closure::wrap(Box::new(move |_: JsValue| on_end()) as Box<dyn FnMut(JsValue)>);
+        if let Some(tracks) = call(&stream, "getVideoTracks", &[]) {
+            for track in Array::from(&tracks).iter() {
+                call(
+                    &track,
+                    "addEventListener",
+                    &["ended".into(), on_ended.as_ref().clone()],
+                );
+            }
+        }
+
+        Ok(Self {
+            stream,
+            _on_ended: Rc::new(on_ended),
+        })
+    }
+
+    /// Show the camera in the `<video>` element with the given id.
+    pub fn attach(&self, element_id: &str) -> Result<(), CameraError> {
+        let element = web_sys::window()
+            .and_then(|window| get(&window, "document"))
+            .and_then(|document| call(&document, "getElementById", &[element_id.into()]))
+            .filter(|element| !element.is_null())
+            .ok_or(CameraError::NotFound)?;
+        Reflect::set(&element, &"srcObject".into(), &self.stream).map_err(|e| to_error(&e))?;
+        Ok(())
+    }
+
+    pub async fn take_photo(&self, format: PhotoFormat) -> Result<Photo, CameraError> {
+        let document = web_sys::window()
+            .and_then(|window| get(&window, "document"))
+            .ok_or(CameraError::Unsupported)?;
+
+        // Play the stream in a video element of its own, so photos don't depend on the preview being visible.
+        let video =
+            call(&document, "createElement", &["video".into()]).ok_or(CameraError::Unsupported)?;
+        Reflect::set(&video, &"muted".into(), &JsValue::TRUE).ok();
+        Reflect::set(&video, &"playsInline".into(), &JsValue::TRUE).ok();
+        Reflect::set(&video, &"srcObject".into(), &self.stream).ok();
+        let played = call_async(&video, "play", &[]).await;
+        let photo = match played {
+            Some(Ok(_)) => capture(&document, &video, format).await,
+            Some(Err(e)) => Err(to_error(&e)),
+            None => Err(CameraError::Unsupported),
+        };
+        call(&video, "pause", &[]);
+        Reflect::set(&video, &"srcObject".into(), &JsValue::NULL).ok();
+        photo
+    }
+
+    pub fn stop(&self) {
+        if let Some(tracks) = call(&self.stream, "getTracks", &[]) {
+            for track in Array::from(&tracks).iter() {
+                call(&track, "stop", &[]);
+            }
+        }
+    }
+}
+
+/// Draw the current frame of a playing video on a canvas and encode it.
+async fn capture(
+    document: &JsValue,
+    video: &JsValue,
+    format: PhotoFormat,
+) -> Result<Photo, CameraError> {
+    let dimension = |name: &str| {
+        get(video, name)
+            .and_then(|value| value.as_f64())
+            .unwrap_or(0.0) as u32
+    };
+    let (width, height) = (dimension("videoWidth"), dimension("videoHeight"));
+    if width == 0 || height == 0 {
+        return Err(CameraError::Failed(
+            "the camera hasn't delivered a frame".to_string(),
+        ));
+    }
+
+    let canvas =
+        call(document, "createElement", &["canvas".into()]).ok_or(CameraError::Unsupported)?;
+    Reflect::set(&canvas, &"width".into(), &width.into()).ok();
+    Reflect::set(&canvas, &"height".into(), &height.into()).ok();
+    let context = call(&canvas, "getContext", &["2d".into()]).ok_or(CameraError::Unsupported)?;
+    call(&context, "drawImage", &[video.clone(), 0.into(), 0.into()])
+        .ok_or_else(|| CameraError::Failed("the frame couldn't be drawn".to_string()))?;
+
+    let (tx, rx) = oneshot::channel();
+    let mut tx = Some(tx);
+    let on_blob = Closure::wrap(Box::new(move |blob: JsValue| {
+        if let Some(tx) = tx.take() {
+            tx.send(blob).ok();
+        }
+    }) as Box<dyn FnMut(JsValue)>);
+    let quality = match format {
+        PhotoFormat::Jpeg { quality } => JsValue::from(quality),
+        PhotoFormat::Png => JsValue::UNDEFINED,
+    };
+    call(
+        &canvas,
+        "toBlob",
+        &[on_blob.as_ref().clone(), format.mime_type().into(), quality],
+    )
+    .ok_or(CameraError::Unsupported)?;
+    let blob = rx
+        .await
+        .ok()
+        .filter(|blob| !blob.is_null())
+        .ok_or_else(|| CameraError::Failed("the photo couldn't be encoded".to_string()))?;
+
+    let buffer = call_async(&blob, "arrayBuffer", &[])
+        .await
+        .ok_or(CameraError::Unsupported)?
+        .map_err(|e| to_error(&e))?;
+    let mime_type = get(&blob, "type")
+        .and_then(|mime_type| mime_type.as_string())
+        .filter(|mime_type| !mime_type.is_empty())
+        .unwrap_or_else(|| format.mime_type().to_string());
+
+    Ok(Photo {
+        mime_type,
+        data: Uint8Array::new(&buffer).to_vec(),
+        width,
+        height,
+    })
+}
+
+pub async fn devices() -> Result<Vec<CameraDevice>, CameraError> {
+    let devices = call_async(&media_devices()?, "enumerateDevices", &[])
+        .await
+        .ok_or(CameraError::Unsupported)?
+        .map_err(|e| to_error(&e))?;
+
+    let string = |device: &JsValue, key: &str| {
+        get(device, key)
+            .and_then(|value| value.as_string())
+            .unwrap_or_default()
+    };
+    Ok(Array::from(&devices)
+        .iter()
+        .filter(|device| string(device, "kind") == "videoinput")
+        .map(|device| CameraDevice {
+            id: string(&device, "deviceId"),
+            label: string(&device, "label"),
+        })
+        .collect())
+}
+
+fn media_devices() -> Result<JsValue, CameraError> {
+    web_sys::window()
+        .and_then(|window| get(&window, "navigator"))
+        .and_then(|navigator| get(&navigator, "mediaDevices"))
+        .ok_or(CameraError::Unsupported)
+}
+
+fn to_error(error: &JsValue) -> CameraError {
+    match get(error, "name")
+        .and_then(|name| name.as_string())
+        .as_deref()
+    {
+        Some("NotAllowedError") | Some("SecurityError") => CameraError::PermissionDenied,
+        Some("NotFoundError") | Some("OverconstrainedError") => CameraError::NotFound,
+        Some("NotReadableError") => CameraError::InUse,
+        Some("NotSupportedError") => CameraError::Unsupported,
+        _ => CameraError::Failed(
+            get(error, "message")
+                .and_then(|message| message.as_string())
+                .unwrap_or_else(|| format!("{error:?}")),
+        ),
+    }
+}
+
+/// Get a property, treating `undefined` and `null` as missing.
+fn get(target: &JsValue, key: &str) -> Option<JsValue> {
+    Reflect::get(target, &key.into())
+        .ok()
+        .filter(|value| !value.is_undefined() && !value.is_null())
+}
+
+/// Call a method, returning `None` if it doesn't exist or throws.
+fn call(target: &JsValue, method: &str, args: &[JsValue]) -> Option<JsValue> {
+    let function: Function = get(target, method)?.dyn_into().ok()?;
+    function.apply(target, &args.iter().collect::<Array>()).ok()
+}
+
+/// Call a method that returns a promise and wait for it, returning `None` if the method doesn't exist.
+async fn call_async(
+    target: &JsValue,
+    method: &str,
+    args: &[JsValue],
+) -> Option<Result<JsValue, JsValue>> {
+    let promise: Promise = call(target, method, args)?.dyn_into().ok()?;
+    Some(JsFuture::from(promise).await)
+}
@@ -0,0 +1,186 @@
+//! Provides the use_camera hook.
+
+use dioxus::prelude::*;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::core::{
+    camera_devices, CameraConstraints, CameraDevice, CameraError, CameraState, Photo, PhotoFormat,
+};
+use super::platform::Camera;
+
+/// A handle to a camera, created with [`use_camera`].
+#[derive(Clone, Copy, PartialEq)]
+pub struct UseCamera {
+    camera: Signal<Option<Camera>>,
+    state: Signal<CameraState>,
+    error: Signal<Option<CameraError>>,
+    /// Bumped on every start and stop, so a camera that finishes starting after it was stopped is turned off.
+    generation: Signal<u64>,
+    id: u64,
+}
+
+impl UseCamera {
+    /// Start the camera with new constraints, like to switch to another camera. The running camera is stopped first.
+    pub fn start(&self, constraints: CameraConstraints) {
+        let mut this = *self;
+        this.stop();
+
+        let generation = *this.generation.peek();
+        this.state.set(CameraState::Starting);
+        this.error.set(None);
+        spawn(async move {
+            let on_end = move || {
+                if *this.generation.peek() == generation {
+                    this.stop();
+                }
+            };
+            let camera = Camera::start(&constraints, on_end).await;
+            report_access(&camera);
+            match camera {
+                Ok(camera) if *this.generation.peek() == generation => {
+                    // The video element may not be rendered yet, in which case it attaches itself when mounted.
+                    camera.attach(&this.video_id()).ok();
+                    this.camera.set(Some(camera));
+                    this.state.set(CameraState::Active);
+                }
+                Ok(camera) => camera.stop(),
+                Err(e) if *this.generation.peek() == generation => {
+                    this.error.set(Some(e));
+                    this.state.set(CameraState::Idle);
+                }
+                Err(_) => {}
+            }
+        });
+    }
+
+    /// Turn the camera off.
+    pub fn stop(&self) {
+        let mut this = *self;
+        *this.generation.write() += 1;
+        if let Some(camera) = this.camera.write().take() {
+            camera.stop();
+        }
+        this.state.set(CameraState::Idle);
+    }
+
+    /// Show the camera in the `<video>` element with the id from [`UseCamera::video_id`].
+    ///
+    /// The camera is shown when it starts. Call this from `onmounted` if the element is rendered after that.
+    pub fn attach(&self) -> Result<(), CameraError> {
+        match &*self.camera.peek() {
+            Some(camera) => camera.attach(&self.video_id()),
+            None => Err(CameraError::NotStarted),
+        }
+    }
+
+    /// The id to give the `<video>` element that previews the camera.
+    pub fn video_id(&self) -> String {
+        format!("dioxus-camera-{}", self.id)
+    }
+
+    /// Take a photo with the camera, encoded in `format`.
+    pub async fn take_photo(&self, format: PhotoFormat) -> Result<Photo, CameraError> {
+        let camera = self.camera.peek().clone().ok_or(CameraError::NotStarted)?;
+        camera.take_photo(format).await
+    }
+
+    /// Get the state of the camera.
+    pub fn state(&self) -> ReadOnlySignal<CameraState> {
+        self.state.into()
+    }
+
+    /// Get the error of the last start, if it failed.
+    pub fn error(&self) -> ReadOnlySignal<Option<CameraError>> {
+        self.error.into()
+    }
+}
+
+/// A hook for previewing the camera and taking photos.
+///
+/// The camera starts with `constraints` when the component is mounted and stops when it is dropped. Give a
+/// `<video>` element the id from [`UseCamera::video_id`] to preview it. On the web, the app must be served over
+/// HTTPS. Elsewhere the camera is captured in the webview, and webviews without `getUserMedia` report
+/// [`CameraError::Unsupported`].
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::camera::{use_camera, CameraConstraints, CameraFacing, PhotoFormat};
+///
+/// fn App() -> Element {
+///     let camera = use_camera(CameraConstraints {
+///         facing: Some(CameraFacing::User),
+///         ..Default::default()
+///     });
+///     let mut photo = use_signal(|| None);
+///
+///     rsx! {
+///         video {
+///             id: camera.video_id(),
+///             autoplay: true,
+///             playsinline: true,
+///             onmounted: move |_| { camera.attach().ok(); },
+///         }
+///         button {
+///             onclick: move |_| async move {
+///                 photo.set(camera.take_photo(PhotoFormat::default()).await.ok());
+///             },
+///             "Take photo"
+///         }
+///         if let Some(photo) = photo() {
+///             p { "Took a {photo.width}x{photo.height} photo" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_camera(constraints: CameraConstraints) -> UseCamera {
+    let camera = use_hook(|| {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        let camera = UseCamera {
+            camera: Signal::new(None),
+            state: Signal::new(CameraState::Idle),
+            error: Signal::new(None),
+            generation: Signal::new(0),
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+        };
+        // Signals can't be written while rendering, so the camera is started once the render is done.
+        spawn(async move { camera.start(constraints) });
+        camera
+    });
+
+    // Turn the camera off if the component is dropped while it is running.
+    use_drop(move || {
+        let mut signal = camera.camera;
+        if let Some(camera) = signal.try_write().ok().and_then(|mut c| c.take()) {
+            camera.stop();
+        }
+    });
+
+    camera
+}
+
+/// A hook for the cameras connected to the device.
+///
+/// Browsers only tell the names of the cameras once the user allowed the camera, so restart the resource after
+/// starting one to get them.
+pub fn use_camera_devices() -> Resource<Result<Vec<CameraDevice>, CameraError>> {
+    use_resource(camera_devices)
+}
+
+/// Share whether access to the camera was granted with the permission center.
+#[cfg(feature = "permissions")]
+fn report_access<T>(result: &Result<T, CameraError>) {
+    use crate::permissions::{report_permission, Permission, PermissionStatus};
+
+    match result {
+        Ok(_) => report_permission(Permission::Camera, PermissionStatus::Granted),
+        Err(CameraError::PermissionDenied) => {
+            report_permission(Permission::Camera, PermissionStatus::Denied)
+        }
+        Err(_) => {}
+    }
+}
+
+#[cfg(not(feature = "permissions"))]
+fn report_access<T>(_result: &Result<T, CameraError>) {}
@@ -1,10 +1,14 @@
 //! Interact with the clipboard.
 
+mod use_clipboard;
+pub use use_clipboard::*;
+
+mod use_paste_handler;
+pub use use_paste_handler::*;
+
 cfg_if::cfg_if! {
     if #[cfg(not(target_family = "wasm"))] {
-        mod use_clipboard;
-        pub use use_clipboard::*;
-    } else {
-        compile_error!("the `clipboard` feature is only available on desktop targets");
+        mod use_copy_to_clipboard;
+        pub use use_copy_to_clipboard::*;
     }
 }
@@ -1,6 +1,5 @@
 //! Provides a clipboard abstraction to access the target system's clipboard.
 
-use copypasta::{ClipboardContext, ClipboardProvider};
 use dioxus::prelude::*;
 
 #[derive(Debug, PartialEq, Clone)]
@@ -13,33 +12,71 @@ pub enum ClipboardError {
 /// Handle to access the ClipboardContext.
 #[derive(Clone, Copy, PartialEq)]
 pub struct UseClipboard {
-    clipboard: Signal<Option<ClipboardContext>>,
+    /// The context is created on first use, as connecting to the clipboard can be slow on some platforms.
+    #[cfg(not(target_family = "wasm"))]
+    clipboard: Signal<Option<Option<copypasta::ClipboardContext>>>,
 }
 
+#[cfg(not(target_family = "wasm"))]
 impl UseClipboard {
     // Read from the clipboard
     pub fn get(&mut self) -> Result<String, ClipboardError> {
-        self.clipboard
-            .write()
-            .as_mut()
-            .ok_or(ClipboardError::NotAvailable)?
-            .get_contents()
-            .map_err(|_| ClipboardError::FailedToRead)
+        use copypasta::ClipboardProvider;
+
+        self.with_context(|context| {
+            context
+                .get_contents()
+                .map_err(|_| ClipboardError::FailedToRead)
+        })
     }
 
     // Write to the clipboard
     pub fn set(&mut self, contents: String) -> Result<(), ClipboardError> {
-        self.clipboard
-            .write()
+        use copypasta::ClipboardProvider;
+
+        self.with_context(|context| {
+            context
+                .set_contents(contents)
+                .map_err(|_| ClipboardError::FailedToSet)
+        })
+    }
+
+    fn with_context<R>(
+        &mut self,
+        f: impl FnOnce(&mut copypasta::ClipboardContext) -> Result<R, ClipboardError>,
+    ) -> Result<R, ClipboardError> {
+        let mut clipboard = self.clipboard.write();
+        let context = clipboard
+            .get_or_insert_with(|| copypasta::ClipboardContext::new().ok())
             .as_mut()
-            .ok_or(ClipboardError::NotAvailable)?
-            .set_contents(contents)
-            .map_err(|_| ClipboardError::FailedToSet)
+            .ok_or(ClipboardError::NotAvailable)?;
+        f(context)
+    }
+}
+
+impl UseClipboard {
+    /// Read from the clipboard without blocking the UI.
+    ///
+    /// - On desktop, the clipboard is read on a background thread, as it can take a while when the app owning
+    ///   the clipboard is slow to respond.
+    /// - On web, this uses `navigator.clipboard`, which may ask the user for permission.
+    pub async fn get_async(&self) -> Result<String, ClipboardError> {
+        get_async_platform().await
+    }
+
+    /// Write to the clipboard without blocking the UI.
+    ///
+    /// See [`UseClipboard::get_async`] for more information.
+    pub async fn set_async(&self, contents: String) -> Result<(), ClipboardError> {
+        set_async_platform(contents).await
     }
 }
 
 /// Access the clipboard.
 ///
+/// On web, only [`UseClipboard::get_async`] and [`UseClipboard::set_async`] are available, as browsers don't
+/// allow reading the clipboard synchronously.
+///
 /// # Examples
 ///
 /// ```rust,ignore
@@ -55,15 +92,143 @@ impl UseClipboard {
 ///
 /// // Write to the clipboard
 /// clipboard.set("Hello, Dioxus!".to_string());;
-///  
+///
+/// // Read the clipboard content without blocking the UI
+/// spawn(async move {
+///     if let Ok(content) = clipboard.get_async().await {
+///         println!("{}", content);
+///     }
+/// });
 /// ```
 pub fn use_clipboard() -> UseClipboard {
-    let clipboard = match try_consume_context() {
-        Some(rt) => rt,
-        None => {
-            let clipboard = ClipboardContext::new().ok();
-            provide_root_context(Signal::new(clipboard))
-        }
-    };
-    UseClipboard { clipboard }
+    UseClipboard {
+        #[cfg(not(target_family = "wasm"))]
+        clipboard: match try_consume_context() {
+            Some(rt) => rt,
+            None => provide_root_context(Signal::new_in_scope(None, ScopeId::ROOT)),
+        },
+    }
+}
+
+/// Requests for the clipboard thread used by the desktop implementation.
+#[cfg(not(target_family = "wasm"))]
+enum Request {
+    Get(tokio::sync::oneshot::Sender<Result<String, ClipboardError>>),
+    Set(
+        String,
+        tokio::sync::oneshot::Sender<Result<(), ClipboardError>>,
+    ),
+}
+
+/// Send a request to the clipboard thread, starting it if needed.
+///
+/// The thread owns its own clipboard context, so values set from it stay available after the request is done.
+#[cfg(not(target_family = "wasm"))]
+fn send_request(request: Request) -> Result<(), ClipboardError> {
+    use copypasta::{ClipboardContext, ClipboardProvider};
+    use std::sync::{mpsc, Mutex, OnceLock};
+
+    static WORKER: OnceLock<Mutex<mpsc::Sender<Request>>> = OnceLock::new();
+
+    let worker = WORKER.get_or_init(|| {
+        let (tx, rx) = mpsc::channel::<Request>();
+        std::thread::Builder::new()
+            .name("dioxus-clipboard".to_string())
+            .spawn(move || {
+                let mut context = ClipboardContext::new().ok();
+                for request in rx {
+                    match (request, context.as_mut()) {
+                        (Request::Get(reply), Some(context)) => {
+                            let contents = context
+                                .get_contents()
+                                .map_err(|_| ClipboardError::FailedToRead);
+                            reply.send(contents).ok();
+                        }
+                        (Request::Set(contents, reply), Some(context)) => {
+                            let result = context
+                                .set_contents(contents)
+                                .map_err(|_| ClipboardError::FailedToSet);
+                            reply.send(result).ok();
+                        }
+                        (Request::Get(reply), None) => {
+                            reply.send(Err(ClipboardError::NotAvailable)).ok();
+                        }
+                        (Request::Set(_, reply), None) => {
+                            reply.send(Err(ClipboardError::NotAvailable)).ok();
+                        }
+                    }
+                }
+            })
+            .ok();
+        Mutex::new(tx)
+    });
+
+    worker
+        .lock()
+        .unwrap()
+        .send(request)
+        .map_err(|_| ClipboardError::NotAvailable)
+}
+
+// Desktop implementation of the async getter.
+#[cfg(not(target_family = "wasm"))]
+async fn get_async_platform() -> Result<String, ClipboardError> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    send_request(Request::Get(tx))?;
+    rx.await.map_err(|_| ClipboardError::NotAvailable)?
+}
+
+// Desktop implementation of the async setter.
+#[cfg(not(target_family = "wasm"))]
+async fn set_async_platform(contents: String) -> Result<(), ClipboardError> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    send_request(Request::Set(contents, tx))?;
+    rx.await.map_err(|_| ClipboardError::NotAvailable)?
+}
+
+// Web implementation of the async getter.
+#[cfg(target_family = "wasm")]
+async fn get_async_platform() -> Result<String, ClipboardError> {
+    call_clipboard("readText", &[])
+        .await?
+        .map_err(|_| ClipboardError::FailedToRead)?
+        .as_string()
+        .ok_or(ClipboardError::FailedToRead)
+}
+
+// Web implementation of the async setter.
+#[cfg(target_family = "wasm")]
+async fn set_async_platform(contents: String) -> Result<(), ClipboardError> {
+    call_clipboard("writeText", &[contents.into()])
+        .await?
+        .map(|_| ())
+        .map_err(|_| ClipboardError::FailedToSet)
+}
+
+/// Call a method of `navigator.clipboard` and wait for the promise it returns.
+#[cfg(target_family = "wasm")]
+async fn call_clipboard(
+    method: &str,
+    args: &[wasm_bindgen::JsValue],
+) -> Result<Result<wasm_bindgen::JsValue, wasm_bindgen::JsValue>, ClipboardError> {
+    use js_sys::{Array, Function, Promise, Reflect};
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_futures::JsFuture;
+
+    // The clipboard API is missing outside of secure contexts.
+    let clipboard = web_sys::window()
+        .and_then(|window| Reflect::get(&window, &"navigator".into()).ok())
+        .and_then(|navigator| Reflect::get(&navigator, &"clipboard".into()).ok())
+        .filter(|clipboard| !clipboard.is_undefined())
+        .ok_or(ClipboardError::NotAvailable)?;
+    let function: Function = Reflect::get(&clipboard, &method.into())
+        .ok()
+        .and_then(|function| function.dyn_into().ok())
+        .ok_or(ClipboardError::NotAvailable)?;
+    let promise: Promise = function
+        .apply(&clipboard, &args.iter().collect::<Array>())
+        .ok()
+        .and_then(|promise| promise.dyn_into().ok())
+        .ok_or(ClipboardError::NotAvailable)?;
+    Ok(JsFuture::from(promise).await)
 }
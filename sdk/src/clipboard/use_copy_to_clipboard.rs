@@ -0,0 +1,132 @@
+//! Provides a hook and a button for copying text with a transient "Copied!" state.
+
+use dioxus::prelude::*;
+use std::time::Duration;
+
+use super::use_clipboard::{use_clipboard, ClipboardError, UseClipboard};
+
+/// How long [`use_copy_to_clipboard`] reports the text as copied.
+const DEFAULT_RESET_AFTER: Duration = Duration::from_secs(2);
+
+/// Handle to copy text to the clipboard.
+///
+/// See [`use_copy_to_clipboard`] for more information.
+#[derive(Clone, Copy, PartialEq)]
+pub struct UseCopyToClipboard {
+    clipboard: UseClipboard,
+    copied: Signal<bool>,
+    reset_task: Signal<Option<Task>>,
+    reset_after: Duration,
+}
+
+impl UseCopyToClipboard {
+    /// Copy text to the clipboard and mark it as copied until the reset duration passes.
+    ///
+    /// Copying again while the text is marked as copied restarts the reset duration.
+    pub fn copy(&mut self, text: impl ToString) -> Result<(), ClipboardError> {
+        self.clipboard.set(text.to_string())?;
+
+        if let Some(task) = self.reset_task.write().take() {
+            task.cancel();
+        }
+        self.copied.set(true);
+
+        let mut copied = self.copied;
+        let mut reset_task = self.reset_task;
+        let reset_after = self.reset_after;
+        let task = spawn(async move {
+            tokio::time::sleep(reset_after).await;
+            copied.set(false);
+            reset_task.set(None);
+        });
+        self.reset_task.set(Some(task));
+
+        Ok(())
+    }
+
+    /// Whether text was copied recently.
+    pub fn copied(&self) -> ReadOnlySignal<bool> {
+        ReadOnlySignal::new(self.copied)
+    }
+}
+
+/// A hook for copying text to the clipboard and showing that it was copied.
+///
+/// The `copied` signal is `true` for two seconds after each copy, so a "Copy" button can briefly say "Copied!".
+/// Use [`use_copy_to_clipboard_with_reset`] to change how long it stays `true`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::clipboard::use_copy_to_clipboard;
+///
+/// fn App() -> Element {
+///     let mut clipboard = use_copy_to_clipboard();
+///     let copied = clipboard.copied();
+///
+///     rsx! {
+///         button {
+///             onclick: move |_| { clipboard.copy("npm install dioxus").ok(); },
+///             if copied() { "Copied!" } else { "Copy" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_copy_to_clipboard() -> UseCopyToClipboard {
+    use_copy_to_clipboard_with_reset(DEFAULT_RESET_AFTER)
+}
+
+/// A hook like [`use_copy_to_clipboard`] that reports the text as copied for the given [`Duration`].
+pub fn use_copy_to_clipboard_with_reset(reset_after: Duration) -> UseCopyToClipboard {
+    let clipboard = use_clipboard();
+    use_hook(|| UseCopyToClipboard {
+        clipboard,
+        copied: Signal::new(false),
+        reset_task: Signal::new(None),
+        reset_after,
+    })
+}
+
+/// A button that copies `text` to the clipboard and briefly shows `copied_label` afterwards.
+///
+/// ```rust,ignore
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::clipboard::CopyButton;
+///
+/// fn App() -> Element {
+///     rsx! {
+///         code { "cargo add dioxus" }
+///         CopyButton { text: "cargo add dioxus" }
+///     }
+/// }
+/// ```
+#[component]
+pub fn CopyButton(
+    /// The text to copy.
+    text: String,
+    /// The label shown before copying.
+    #[props(default = "Copy".to_string())]
+    label: String,
+    /// The label shown after the text was copied.
+    #[props(default = "Copied!".to_string())]
+    copied_label: String,
+    /// The class of the button.
+    #[props(default)]
+    class: String,
+) -> Element {
+    let mut clipboard = use_copy_to_clipboard();
+    let copied = clipboard.copied();
+
+    rsx! {
+        button {
+            class,
+            onclick: move |_| {
+                if let Err(err) = clipboard.copy(&text) {
+                    tracing::warn!("Failed to copy to the clipboard: {err:?}");
+                }
+            },
+            if copied() { "{copied_label}" } else { "{label}" }
+        }
+    }
+}
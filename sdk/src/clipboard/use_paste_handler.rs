@@ -0,0 +1,202 @@
+//! Provides a hook for intercepting paste events with their structured content.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use dioxus::prelude::*;
+use serde_json::{json, Value};
+
+/// Sends the content of every paste into an element matching the selector back, until anything is sent to stop it.
+///
+/// The listener is on the document, so it keeps working when the element is added later or rendered again.
+const LISTEN: &str = r#"
+const { selector, intercept } = await dioxus.recv();
+const toBase64 = (bytes) => {
+    let binary = "";
+    for (let i = 0; i < bytes.length; i += 0x8000) {
+        binary += String.fromCharCode(...bytes.subarray(i, i + 0x8000));
+    }
+    return btoa(binary);
+};
+const onPaste = async (event) => {
+    if (!(event.target instanceof Element) || !event.target.closest(selector)) return;
+    const data = event.clipboardData;
+    if (!data) return;
+    const files = Array.from(data.files);
+    if (intercept === "all" || (intercept === "files" && files.length > 0)) {
+        event.preventDefault();
+    }
+    // The clipboard data is cleared once the event is handled, so read it before waiting for the files.
+    const text = data.getData("text/plain");
+    const html = data.getData("text/html");
+    const encoded = await Promise.all(files.map(async (file) => ({
+        name: file.name,
+        type: file.type,
+        data: toBase64(new Uint8Array(await file.arrayBuffer())),
+    })));
+    dioxus.send({ text: text || null, html: html || null, files: encoded });
+};
+document.addEventListener("paste", onPaste, true);
+await dioxus.recv();
+document.removeEventListener("paste", onPaste, true);
+"#;
+
+/// Which pastes [`use_paste_handler_with_intercept`] keeps from reaching the element.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum PasteIntercept {
+    /// Every paste is handled by the app only.
+    #[default]
+    All,
+    /// Pastes that contain files are handled by the app only, text is still pasted into the element as usual.
+    Files,
+    /// Every paste reaches the element. The app is only notified.
+    None,
+}
+
+impl PasteIntercept {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::Files => "files",
+            Self::None => "none",
+        }
+    }
+}
+
+/// A file pasted from the clipboard, like a copied image.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PastedFile {
+    /// The name of the file. Images copied from other apps often have a generic name like `image.png`.
+    pub name: String,
+    /// The MIME type of the file, like `image/png`, or an empty string if it is unknown.
+    pub mime_type: String,
+    pub data: Vec<u8>,
+}
+
+impl PastedFile {
+    /// Whether the file is an image.
+    pub fn is_image(&self) -> bool {
+        self.mime_type.starts_with("image/")
+    }
+}
+
+/// The content of a paste event.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PasteContent {
+    /// The pasted plain text.
+    pub text: Option<String>,
+    /// The pasted HTML, for example when copying from a web page or a word processor.
+    pub html: Option<String>,
+    /// The pasted files.
+    pub files: Vec<PastedFile>,
+}
+
+impl PasteContent {
+    /// The pasted files that are images.
+    pub fn images(&self) -> impl Iterator<Item = &PastedFile> {
+        self.files.iter().filter(|file| file.is_image())
+    }
+
+    fn from_value(value: &Value) -> Option<Self> {
+        let string = |value: &Value, key: &str| value[key].as_str().map(|s| s.to_string());
+        let files = value["files"]
+            .as_array()?
+            .iter()
+            .filter_map(|file| {
+                Some(PastedFile {
+                    name: string(file, "name").unwrap_or_default(),
+                    mime_type: string(file, "type").unwrap_or_default(),
+                    data: STANDARD.decode(file["data"].as_str()?).ok()?,
+                })
+            })
+            .collect();
+
+        Some(Self {
+            text: string(value, "text"),
+            html: string(value, "html"),
+            files,
+        })
+    }
+}
+
+/// A hook for handling pastes into an element instead of letting the element handle them.
+///
+/// The handler receives the pasted text, HTML, and files, so editors can implement their own pasting,
+/// like uploading pasted images. This works on the web and on desktop, where the paste is read from the webview.
+///
+/// The element is found with a CSS selector when something is pasted, so the hook can be used before the element is
+/// rendered, and keeps working when the element is rendered again. See [`use_paste_handler_with_intercept`] to let some pastes through.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::clipboard::use_paste_handler;
+///
+/// fn Editor() -> Element {
+///     let mut text = use_signal(String::new);
+///     let mut images = use_signal(Vec::new);
+///     use_paste_handler("#editor", move |content| {
+///         if let Some(pasted) = content.text {
+///             text.write().push_str(&pasted);
+///         }
+///         images.write().extend(content.images().map(|image| image.name.clone()));
+///     });
+///
+///     rsx! {
+///         textarea { id: "editor", value: "{text}", oninput: move |e| text.set(e.value()) }
+///         for image in images() {
+///             p { "Uploading {image}..." }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_paste_handler(selector: impl ToString, handler: impl FnMut(PasteContent) + 'static) {
+    use_paste_handler_with_intercept(selector, PasteIntercept::All, handler)
+}
+
+/// A hook for handling pastes into an element, choosing which pastes still reach the element.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::clipboard::{use_paste_handler_with_intercept, PasteIntercept};
+///
+/// fn Chat() -> Element {
+///     // Text is pasted into the input as usual, while images are sent as attachments.
+///     use_paste_handler_with_intercept("#message", PasteIntercept::Files, move |content| {
+///         for image in content.images() {
+///             println!("Attaching {} ({} bytes)", image.name, image.data.len());
+///         }
+///     });
+///
+///     rsx! { input { id: "message" } }
+/// }
+/// ```
+pub fn use_paste_handler_with_intercept(
+    selector: impl ToString,
+    intercept: PasteIntercept,
+    mut handler: impl FnMut(PasteContent) + 'static,
+) {
+    let eval = use_hook(|| {
+        let mut eval = eval(LISTEN);
+        let message = json!({ "selector": selector.to_string(), "intercept": intercept.as_str() });
+        match eval.send(message) {
+            Ok(()) => {
+                spawn(async move {
+                    while let Ok(value) = eval.recv().await {
+                        if let Some(content) = PasteContent::from_value(&value) {
+                            handler(content);
+                        }
+                    }
+                });
+            }
+            Err(e) => tracing::error!("Failed to listen for pastes: {:?}", e),
+        }
+        eval
+    });
+
+    // The listener lives in the page, so it would keep intercepting pastes after the component is gone.
+    use_drop(move || {
+        eval.send(Value::Null).ok();
+    });
+}
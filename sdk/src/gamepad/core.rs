@@ -0,0 +1,213 @@
+//! The gamepad types shared by every platform.
+
+/// How far a button must be pressed to count as pressed.
+const PRESS_THRESHOLD: f32 = 0.5;
+
+/// The buttons of a gamepad, in the order of the [standard gamepad](https://w3c.github.io/gamepad/#remapping) layout.
+///
+/// Face buttons are named by their position, so [`GamepadButton::South`] is `A` on an Xbox controller and `✕` on a
+/// PlayStation controller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadButton {
+    South,
+    East,
+    West,
+    North,
+    LeftBumper,
+    RightBumper,
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+    LeftStick,
+    RightStick,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+    Mode,
+}
+
+impl GamepadButton {
+    /// Every button, in the order of [`Gamepad::buttons`].
+    pub const ALL: [Self; 17] = [
+        Self::South,
+        Self::East,
+        Self::West,
+        Self::North,
+        Self::LeftBumper,
+        Self::RightBumper,
+        Self::LeftTrigger,
+        Self::RightTrigger,
+        Self::Select,
+        Self::Start,
+        Self::LeftStick,
+        Self::RightStick,
+        Self::DPadUp,
+        Self::DPadDown,
+        Self::DPadLeft,
+        Self::DPadRight,
+        Self::Mode,
+    ];
+}
+
+/// The axes of a gamepad, in the order of the standard gamepad layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+}
+
+impl GamepadAxis {
+    /// Every axis, in the order of [`Gamepad::axes`].
+    pub const ALL: [Self; 4] = [
+        Self::LeftStickX,
+        Self::LeftStickY,
+        Self::RightStickX,
+        Self::RightStickY,
+    ];
+}
+
+/// The state of a connected gamepad.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gamepad {
+    /// Identifies the gamepad while it stays connected.
+    pub id: usize,
+    /// The name of the gamepad, as reported by the device.
+    pub name: String,
+    /// How far each button is pressed, from 0 to 1, indexed like [`GamepadButton::ALL`].
+    pub buttons: Vec<f32>,
+    /// The position of each axis, from -1 to 1, indexed like [`GamepadAxis::ALL`]. Like on the web, the Y axes are
+    /// positive when the stick is pushed down.
+    pub axes: Vec<f32>,
+}
+
+impl Gamepad {
+    /// How far `button` is pressed, from 0 to 1.
+    pub fn value(&self, button: GamepadButton) -> f32 {
+        self.buttons
+            .get(button as usize)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Whether `button` is pressed.
+    pub fn pressed(&self, button: GamepadButton) -> bool {
+        self.value(button) >= PRESS_THRESHOLD
+    }
+
+    /// The position of `axis`, from -1 to 1.
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        self.axes.get(axis as usize).copied().unwrap_or_default()
+    }
+}
+
+/// A change to the connected gamepads.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GamepadEvent {
+    Connected {
+        gamepad: usize,
+    },
+    Disconnected {
+        gamepad: usize,
+    },
+    ButtonPressed {
+        gamepad: usize,
+        button: GamepadButton,
+    },
+    ButtonReleased {
+        gamepad: usize,
+        button: GamepadButton,
+    },
+    AxisChanged {
+        gamepad: usize,
+        axis: GamepadAxis,
+        value: f32,
+    },
+}
+
+/// The events that turn the `previous` state of the gamepads into the `current` one.
+pub(crate) fn diff(previous: &[Gamepad], current: &[Gamepad]) -> Vec<GamepadEvent> {
+    let mut events = Vec::new();
+
+    for old in previous {
+        if !current.iter().any(|new| new.id == old.id) {
+            events.push(GamepadEvent::Disconnected { gamepad: old.id });
+        }
+    }
+
+    for new in current {
+        let old = match previous.iter().find(|old| old.id == new.id) {
+            Some(old) => old,
+            None => {
+                events.push(GamepadEvent::Connected { gamepad: new.id });
+                continue;
+            }
+        };
+
+        for button in GamepadButton::ALL {
+            match (old.pressed(button), new.pressed(button)) {
+                (false, true) => events.push(GamepadEvent::ButtonPressed {
+                    gamepad: new.id,
+                    button,
+                }),
+                (true, false) => events.push(GamepadEvent::ButtonReleased {
+                    gamepad: new.id,
+                    button,
+                }),
+                _ => {}
+            }
+        }
+
+        for axis in GamepadAxis::ALL {
+            if old.axis(axis) != new.axis(axis) {
+                events.push(GamepadEvent::AxisChanged {
+                    gamepad: new.id,
+                    axis,
+                    value: new.axis(axis),
+                });
+            }
+        }
+    }
+
+    events
+}
+
+#[test]
+fn test_diff() {
+    let gamepad = |buttons: Vec<f32>| Gamepad {
+        id: 0,
+        name: "Pad".to_string(),
+        buttons,
+        axes: vec![0.0; 4],
+    };
+    let idle = gamepad(vec![0.0; 17]);
+    let mut pressed = gamepad(vec![0.0; 17]);
+    pressed.buttons[GamepadButton::East as usize] = 1.0;
+    pressed.axes[GamepadAxis::LeftStickY as usize] = -0.5;
+
+    assert_eq!(
+        diff(&[], &[idle.clone()]),
+        vec![GamepadEvent::Connected { gamepad: 0 }]
+    );
+    assert_eq!(
+        diff(&[idle.clone()], &[pressed.clone()]),
+        vec![
+            GamepadEvent::ButtonPressed {
+                gamepad: 0,
+                button: GamepadButton::East
+            },
+            GamepadEvent::AxisChanged {
+                gamepad: 0,
+                axis: GamepadAxis::LeftStickY,
+                value: -0.5
+            }
+        ]
+    );
+    assert_eq!(
+        diff(&[pressed], &[]),
+        vec![GamepadEvent::Disconnected { gamepad: 0 }]
+    );
+}
@@ -0,0 +1,10 @@
+//! Read the state of connected gamepads, to drive focus or simple games.
+//!
+//! Uses the Gamepad API on the web and gilrs on desktop.
+
+mod core;
+mod platform;
+mod use_gamepads;
+
+pub use self::core::*;
+pub use self::use_gamepads::*;
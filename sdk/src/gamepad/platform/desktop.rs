@@ -0,0 +1,78 @@
+use gilrs::{Axis, Button, Gilrs};
+
+use crate::gamepad::{Gamepad, GamepadAxis, GamepadButton};
+
+/// Reads the state of the gamepads.
+pub struct Poller {
+    gilrs: Option<Gilrs>,
+}
+
+impl Poller {
+    pub fn new() -> Self {
+        let gilrs = match Gilrs::new() {
+            Ok(gilrs) => Some(gilrs),
+            Err(e) => {
+                tracing::warn!("gamepads are not available: {e}");
+                None
+            }
+        };
+        Self { gilrs }
+    }
+
+    pub fn poll(&mut self) -> Vec<Gamepad> {
+        let Some(gilrs) = &mut self.gilrs else {
+            return Vec::new();
+        };
+        // The state of the gamepads is only updated while events are read.
+        while gilrs.next_event().is_some() {}
+
+        gilrs
+            .gamepads()
+            .map(|(id, gamepad)| Gamepad {
+                id: id.into(),
+                name: gamepad.name().to_string(),
+                buttons: GamepadButton::ALL
+                    .iter()
+                    .map(|&button| {
+                        gamepad
+                            .button_data(to_gilrs_button(button))
+                            .map(|data| data.value())
+                            .unwrap_or_default()
+                    })
+                    .collect(),
+                axes: GamepadAxis::ALL
+                    .iter()
+                    .map(|&axis| match axis {
+                        // gilrs Y axes are positive when the stick is pushed up.
+                        GamepadAxis::LeftStickY => -gamepad.value(Axis::LeftStickY),
+                        GamepadAxis::RightStickY => -gamepad.value(Axis::RightStickY),
+                        GamepadAxis::LeftStickX => gamepad.value(Axis::LeftStickX),
+                        GamepadAxis::RightStickX => gamepad.value(Axis::RightStickX),
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+fn to_gilrs_button(button: GamepadButton) -> Button {
+    match button {
+        GamepadButton::South => Button::South,
+        GamepadButton::East => Button::East,
+        GamepadButton::West => Button::West,
+        GamepadButton::North => Button::North,
+        GamepadButton::LeftBumper => Button::LeftTrigger,
+        GamepadButton::RightBumper => Button::RightTrigger,
+        GamepadButton::LeftTrigger => Button::LeftTrigger2,
+        GamepadButton::RightTrigger => Button::RightTrigger2,
+        GamepadButton::Select => Button::Select,
+        GamepadButton::Start => Button::Start,
+        GamepadButton::LeftStick => Button::LeftThumb,
+        GamepadButton::RightStick => Button::RightThumb,
+        GamepadButton::DPadUp => Button::DPadUp,
+        GamepadButton::DPadDown => Button::DPadDown,
+        GamepadButton::DPadLeft => Button::DPadLeft,
+        GamepadButton::DPadRight => Button::DPadRight,
+        GamepadButton::Mode => Button::Mode,
+    }
+}
@@ -0,0 +1,69 @@
+use js_sys::{Array, Function, Reflect};
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::gamepad::Gamepad;
+
+/// Reads the state of the gamepads.
+pub struct Poller;
+
+impl Poller {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn poll(&mut self) -> Vec<Gamepad> {
+        let Some(navigator) = web_sys::window().and_then(|window| get(&window, "navigator")) else {
+            return Vec::new();
+        };
+        let Some(gamepads) = call(&navigator, "getGamepads") else {
+            return Vec::new();
+        };
+
+        // Browsers keep a slot for every gamepad that was connected, which is null once it is disconnected.
+        Array::from(&gamepads)
+            .iter()
+            .filter(|gamepad| {
+                !gamepad.is_null()
+                    && !gamepad.is_undefined()
+                    && get(gamepad, "connected").and_then(|c| c.as_bool()) == Some(true)
+            })
+            .map(|gamepad| Gamepad {
+                id: get(&gamepad, "index")
+                    .and_then(|index| index.as_f64())
+                    .unwrap_or_default() as usize,
+                name: get(&gamepad, "id")
+                    .and_then(|id| id.as_string())
+                    .unwrap_or_default(),
+                buttons: numbers(&gamepad, "buttons", |button| {
+                    get(&button, "value").and_then(|value| value.as_f64())
+                }),
+                axes: numbers(&gamepad, "axes", |axis| axis.as_f64()),
+            })
+            .collect()
+    }
+}
+
+/// Read a list of numbers from an array property.
+fn numbers(gamepad: &JsValue, key: &str, value: impl Fn(JsValue) -> Option<f64>) -> Vec<f32> {
+    get(gamepad, key)
+        .map(|items| {
+            Array::from(&items)
+                .iter()
+                .map(|item| value(item).unwrap_or_default() as f32)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Get a property, treating `undefined` and `null` as missing.
+fn get(target: &JsValue, key: &str) -> Option<JsValue> {
+    Reflect::get(target, &key.into())
+        .ok()
+        .filter(|value| !value.is_undefined() && !value.is_null())
+}
+
+/// Call a method without arguments, returning `None` if it doesn't exist or throws.
+fn call(target: &JsValue, method: &str) -> Option<JsValue> {
+    let function: Function = get(target, method)?.dyn_into().ok()?;
+    function.call0(target).ok()
+}
@@ -0,0 +1,91 @@
+//! Provides the use_gamepads and use_gamepad_events hooks.
+
+use dioxus::prelude::*;
+use std::time::Duration;
+
+use super::core::{diff, Gamepad, GamepadEvent};
+use super::platform::Poller;
+use crate::utils::timing::clock;
+
+/// How often the gamepads are read, about once per frame.
+const POLL_INTERVAL: Duration = Duration::from_millis(16);
+
+/// A hook for the state of the connected gamepads.
+///
+/// The gamepads are read about once per frame, and the component only renders again when their state changes. Browsers
+/// only report a gamepad once a button was pressed while the page is focused.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::gamepad::{use_gamepads, GamepadAxis};
+///
+/// fn Player() -> Element {
+///     let gamepads = use_gamepads();
+///     let x = gamepads
+///         .read()
+///         .first()
+///         .map(|gamepad| gamepad.axis(GamepadAxis::LeftStickX))
+///         .unwrap_or_default();
+///
+///     rsx! {
+///         div { style: "transform: translateX({x * 100.0}px)", "🚀" }
+///     }
+/// }
+/// ```
+pub fn use_gamepads() -> ReadOnlySignal<Vec<Gamepad>> {
+    let mut gamepads = use_signal(Vec::new);
+    use_hook(|| {
+        spawn(async move {
+            let mut poller = Poller::new();
+            loop {
+                let current = poller.poll();
+                if *gamepads.peek() != current {
+                    gamepads.set(current);
+                }
+                clock::sleep(POLL_INTERVAL).await;
+            }
+        });
+    });
+    gamepads.into()
+}
+
+/// A hook that calls `handler` when a gamepad is connected or disconnected, a button is pressed or released, or a
+/// stick moves.
+///
+/// Useful to move focus around the UI without rendering on every frame.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::gamepad::{use_gamepad_events, GamepadButton, GamepadEvent};
+///
+/// fn Menu() -> Element {
+///     let mut selected = use_signal(|| 0);
+///     use_gamepad_events(move |event| match event {
+///         GamepadEvent::ButtonPressed { button: GamepadButton::DPadDown, .. } => selected += 1,
+///         GamepadEvent::ButtonPressed { button: GamepadButton::DPadUp, .. } => selected -= 1,
+///         _ => {}
+///     });
+///
+///     rsx! { "Selected item {selected}" }
+/// }
+/// ```
+pub fn use_gamepad_events(mut handler: impl FnMut(GamepadEvent) + 'static) {
+    use_hook(|| {
+        spawn(async move {
+            let mut poller = Poller::new();
+            let mut previous = Vec::new();
+            loop {
+                let current = poller.poll();
+                for event in diff(&previous, &current) {
+                    handler(event);
+                }
+                previous = current;
+                clock::sleep(POLL_INTERVAL).await;
+            }
+        });
+    });
+}
@@ -1,19 +1,84 @@
 //! Provides access to the target device's geolocation system.
 
 use super::platform;
+use super::provider::{get_fallback_coordinates, has_location_providers};
 use core::fmt;
 use dioxus::prelude::Coroutine;
-use std::sync::Arc;
+use futures::{channel::mpsc, future, Stream, StreamExt};
+use std::{
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
 
 /// Describes a position in the world.
-#[derive(Debug, Clone)]
+///
+/// With the `serde` feature, positions can be serialized, for example to store them or send them to a server. Use
+/// [`geojson`](super::geojson) to serialize them as GeoJSON points instead.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Geocoordinates {
     pub latitude: f64,
     pub longitude: f64,
+    /// The altitude in meters above the WGS 84 ellipsoid, if the device reports it.
+    pub altitude: Option<f64>,
+    /// Whether the platform reports that this position was simulated, if it is able to tell.
+    pub is_mocked: Option<bool>,
+    /// The source that produced this position.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub source: PositionSource,
+    /// When the position was read, in milliseconds since the Unix epoch, if the platform reports it.
+    ///
+    /// Platforms may return a cached position, so this can be used to discard stale readings.
+    pub timestamp: Option<u64>,
+}
+
+/// The mean radius of the Earth in meters.
+const EARTH_RADIUS: f64 = 6_371_000.0;
+
+impl Geocoordinates {
+    /// The distance in meters to another position along the surface of the Earth.
+    ///
+    /// Uses the haversine formula, which treats the Earth as a sphere. The result is within about 0.5% of the real distance.
+    pub fn distance_to(&self, latitude: f64, longitude: f64) -> f64 {
+        let (lat1, lat2) = (self.latitude.to_radians(), latitude.to_radians());
+        let d_lat = lat2 - lat1;
+        let d_lon = (longitude - self.longitude).to_radians();
+
+        let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS * a.sqrt().min(1.0).asin()
+    }
+
+    /// The position as a Well-Known Text point, like `POINT (13.4 52.5)`.
+    ///
+    /// WKT puts the longitude first. The altitude is included if it is known.
+    pub fn to_wkt(&self) -> String {
+        match self.altitude {
+            Some(altitude) => format!("POINT Z ({} {} {altitude})", self.longitude, self.latitude),
+            None => format!("POINT ({} {})", self.longitude, self.latitude),
+        }
+    }
+}
+
+/// Describes which source produced a position.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PositionSource {
+    /// A satellite system such as GPS.
+    Satellite,
+    /// Nearby WiFi access points.
+    WiFi,
+    /// Nearby cell towers.
+    Cellular,
+    /// The IP address of the device. This is usually the least accurate source.
+    IpAddress,
+    /// The platform didn't report the source.
+    #[default]
+    Unknown,
 }
 
 /// To conserve battery, some devices allow setting a desired accuracy based on your use-case.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PowerMode {
     /// Will generally enable the on-board GPS for precise coordinates.
     High,
@@ -21,8 +86,23 @@ pub enum PowerMode {
     Low,
 }
 
+/// Describes what the location is used for, so the platform can tune how often the device reports new positions.
+///
+/// This is only a hint. Platforms that can't make use of it will ignore it.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ActivityType {
+    /// Turn-by-turn navigation that needs frequent updates.
+    Navigation,
+    /// Tracking workouts such as running or cycling, where small movements matter.
+    Fitness,
+    /// Any other use-case. Uses the platform defaults.
+    #[default]
+    Other,
+}
+
 /// Represents a geolocation event.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Event {
     /// The status of the device has changed.
     StatusChanged(Status),
@@ -32,6 +112,7 @@ pub enum Event {
 
 /// Describes whether your application has access or not.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Access {
     Allowed,
     Denied,
@@ -40,7 +121,8 @@ pub enum Access {
 }
 
 /// Describes the geolocation device's status.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Status {
     /// Location service or device is ready and has geo data.
     Ready,
@@ -56,35 +138,173 @@ pub enum Status {
 
 /// Represents the geolocation abstraction.
 pub struct Geolocator {
-    device_geolocator: platform::Geolocator,
+    /// The device's geolocator, or why it couldn't be created if a [`LocationProvider`](super::LocationProvider)
+    /// can be used instead.
+    device_geolocator: Result<platform::Geolocator, Error>,
 }
 
 impl Geolocator {
     /// Create a new geolocator.
+    ///
+    /// If the device has no location services or access was denied, this fails unless a fallback
+    /// [`LocationProvider`](super::LocationProvider) is registered.
     pub fn new(power_mode: PowerMode) -> Result<Self, Error> {
-        let mut device_geolocator = platform::Geolocator::new()?;
-        platform::set_power_mode(&mut device_geolocator, power_mode)?;
+        let device_geolocator = platform::Geolocator::new().and_then(|mut device_geolocator| {
+            platform::set_power_mode(&mut device_geolocator, power_mode)?;
+            Ok(device_geolocator)
+        });
+
+        if let Err(e) = &device_geolocator {
+            if let Error::AccessDenied = e {
+                report_access(false);
+            }
+            // Without a fallback there is nothing to locate the device with.
+            if !has_location_providers() {
+                return Err(e.clone());
+            }
+        }
 
         Ok(Self { device_geolocator })
     }
 
+    /// Hint what the location is used for. See [`ActivityType`] for more information.
+    ///
+    /// This does nothing when only the fallback providers can be used.
+    pub fn set_activity_type(&mut self, activity_type: ActivityType) -> Result<(), Error> {
+        match &mut self.device_geolocator {
+            Ok(device_geolocator) => platform::set_activity_type(device_geolocator, activity_type),
+            Err(_) => Ok(()),
+        }
+    }
+
     /// Get the latest coordinates from the device.
+    ///
+    /// If the device can't provide them, the registered [`LocationProvider`](super::LocationProvider)s are asked
+    /// in order. The error from the device is returned if none of them finds any coordinates either.
     pub async fn get_coordinates(&self) -> Result<Geocoordinates, Error> {
-        platform::get_coordinates(&self.device_geolocator).await
+        let error = match &self.device_geolocator {
+            Ok(device_geolocator) => match platform::get_coordinates(device_geolocator).await {
+                Ok(coordinates) => {
+                    report_access(true);
+                    return Ok(coordinates);
+                }
+                Err(e) => {
+                    if let Error::AccessDenied = e {
+                        report_access(false);
+                    }
+                    e
+                }
+            },
+            Err(e) => e.clone(),
+        };
+        get_fallback_coordinates(error).await
+    }
+
+    /// The device's geolocator, for the features the fallback providers can't offer.
+    fn device(&self) -> Result<&platform::Geolocator, Error> {
+        self.device_geolocator.as_ref().map_err(Clone::clone)
     }
 
-    /// Subscribe a mpsc channel to the events.
-    pub fn listen(&self, listener: Coroutine<Event>) -> Result<(), Error> {
+    /// Subscribe a mpsc channel to the events until the returned [`Subscription`] is dropped.
+    ///
+    /// Events only come from the device, not from the fallback providers.
+    pub fn listen(&self, listener: Coroutine<Event>) -> Result<Subscription, Error> {
         let tx = listener.tx();
-        platform::listen(
-            &self.device_geolocator,
+        self.subscribe(move |event| {
+            tx.unbounded_send(event).ok();
+        })
+    }
+
+    /// Subscribe to the events as a stream.
+    ///
+    /// This is useful for consuming location updates outside of components, such as in a background task.
+    /// The listener is removed when the stream is dropped.
+    pub fn events(&self) -> Result<impl Stream<Item = Event>, Error> {
+        let (tx, rx) = mpsc::unbounded();
+        let subscription = self.subscribe(move |event| {
+            tx.unbounded_send(event).ok();
+        })?;
+        Ok(SubscribedEvents {
+            events: rx,
+            _subscription: subscription,
+        })
+    }
+
+    /// Subscribe to new coordinates as a stream, ignoring status changes.
+    pub fn positions(&self) -> Result<impl Stream<Item = Geocoordinates>, Error> {
+        let events = self.events()?;
+        Ok(events.filter_map(|event| {
+            future::ready(match event {
+                Event::NewGeocoordinates(coords) => Some(coords),
+                Event::StatusChanged(_) => None,
+            })
+        }))
+    }
+
+    /// Register a callback for the device's events.
+    fn subscribe(
+        &self,
+        callback: impl Fn(Event) + Send + Sync + 'static,
+    ) -> Result<Subscription, Error> {
+        let subscription = platform::listen(
+            self.device()?,
             Arc::new(move |event: Event| {
-                tx.unbounded_send(event).ok();
+                if let Event::NewGeocoordinates(_) = event {
+                    report_access(true);
+                }
+                callback(event);
             }),
-        )
+        )?;
+        Ok(Subscription {
+            _inner: subscription,
+        })
     }
 }
 
+/// Keeps a listener registered with [`Geolocator::listen`] until it is dropped.
+///
+/// Listeners of the same [`Geolocator`] share one watcher on the device where the platform allows it.
+pub struct Subscription {
+    _inner: platform::Subscription,
+}
+
+/// The events of a [`Geolocator::events`] stream, which stay subscribed until the stream is dropped.
+struct SubscribedEvents {
+    events: mpsc::UnboundedReceiver<Event>,
+    _subscription: Subscription,
+}
+
+impl Stream for SubscribedEvents {
+    type Item = Event;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.events.poll_next_unpin(cx)
+    }
+}
+
+/// Check whether geolocation can be used before creating a [`Geolocator`].
+///
+/// This detects cases where the platform blocks location access up front, like an insecure context on the web,
+/// so the app can explain the problem instead of waiting for coordinates that will never arrive.
+pub fn is_available() -> bool {
+    platform::check_availability().is_ok()
+}
+
+/// Share whether location access was granted with the permission center.
+#[cfg(feature = "permissions")]
+fn report_access(granted: bool) {
+    use crate::permissions::{report_permission, Permission, PermissionStatus};
+
+    let status = match granted {
+        true => PermissionStatus::Granted,
+        false => PermissionStatus::Denied,
+    };
+    report_permission(Permission::Geolocation, status);
+}
+
+#[cfg(not(feature = "permissions"))]
+fn report_access(_granted: bool) {}
+
 /// Describes errors that may occur when utilizing the geolocation abstraction.
 #[derive(Debug, Clone)]
 pub enum Error {
@@ -92,6 +312,12 @@ pub enum Error {
     AccessDenied,
     Poisoned,
     DeviceError(String),
+    /// The page isn't served from a secure context, so the browser doesn't expose geolocation.
+    InsecureContext,
+    /// Geolocation is disabled for the page by a `Permissions-Policy` header or an iframe `allow` attribute.
+    BlockedByPermissionsPolicy,
+    /// The platform has no location services.
+    Unsupported,
 }
 
 impl std::error::Error for Error {}
@@ -104,6 +330,15 @@ impl fmt::Display for Error {
             }
             Error::Poisoned => write!(f, "the internal read/write lock has been poisioned"),
             Error::DeviceError(e) => write!(f, "a device error has occurred: {}", e),
+            Error::InsecureContext => write!(
+                f,
+                "geolocation requires a secure context (serve the app over https or from localhost)"
+            ),
+            Error::BlockedByPermissionsPolicy => write!(
+                f,
+                "geolocation is blocked by the permissions policy (allow it in the Permissions-Policy header, or with allow=\"geolocation\" on the iframe)"
+            ),
+            Error::Unsupported => write!(f, "location services are not available on this platform"),
         }
     }
 }
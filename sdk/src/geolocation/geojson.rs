@@ -0,0 +1,113 @@
+//! Serializes positions as GeoJSON points.
+//!
+//! Use this module with `#[serde(with = "...")]` on a [`Geocoordinates`] field to store it as a GeoJSON `Point`,
+//! which maps libraries and spatial databases understand:
+//!
+//! ```rust
+//! use dioxus_sdk::geolocation::Geocoordinates;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct CheckIn {
+//!     #[serde(with = "dioxus_sdk::geolocation::geojson")]
+//!     position: Geocoordinates,
+//! }
+//! ```
+//!
+//! Only the latitude, longitude, and altitude are kept, so the other fields are lost when reading the point back.
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{Geocoordinates, PositionSource};
+
+/// A GeoJSON point geometry.
+#[derive(Serialize, Deserialize)]
+struct Point {
+    #[serde(rename = "type")]
+    kind: String,
+    /// The longitude, latitude, and optionally altitude, in that order.
+    coordinates: Vec<f64>,
+}
+
+impl From<&Geocoordinates> for Point {
+    fn from(position: &Geocoordinates) -> Self {
+        let mut coordinates = vec![position.longitude, position.latitude];
+        coordinates.extend(position.altitude);
+        Self {
+            kind: "Point".to_string(),
+            coordinates,
+        }
+    }
+}
+
+impl TryFrom<Point> for Geocoordinates {
+    type Error = String;
+
+    fn try_from(point: Point) -> Result<Self, String> {
+        if point.kind != "Point" {
+            return Err(format!("expected a Point, found a {}", point.kind));
+        }
+        match point.coordinates[..] {
+            [longitude, latitude] | [longitude, latitude, _] => Ok(Self {
+                latitude,
+                longitude,
+                altitude: point.coordinates.get(2).copied(),
+                is_mocked: None,
+                source: PositionSource::Unknown,
+                timestamp: None,
+            }),
+            _ => Err(format!(
+                "expected 2 or 3 coordinates, found {}",
+                point.coordinates.len()
+            )),
+        }
+    }
+}
+
+/// Serialize a position as a GeoJSON point.
+pub fn serialize<S: Serializer>(
+    position: &Geocoordinates,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    Point::from(position).serialize(serializer)
+}
+
+/// Deserialize a position from a GeoJSON point.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Geocoordinates, D::Error> {
+    Point::deserialize(deserializer)?
+        .try_into()
+        .map_err(de::Error::custom)
+}
+
+/// Convert a position to a GeoJSON point.
+pub fn to_value(position: &Geocoordinates) -> serde_json::Value {
+    serde_json::to_value(Point::from(position)).expect("a point can always be serialized")
+}
+
+/// Read a position from a GeoJSON point.
+pub fn from_value(value: serde_json::Value) -> Result<Geocoordinates, serde_json::Error> {
+    deserialize(value)
+}
+
+#[test]
+fn test_geojson_round_trip() {
+    let position = Geocoordinates {
+        latitude: 52.5,
+        longitude: 13.4,
+        altitude: Some(34.0),
+        is_mocked: None,
+        source: PositionSource::Unknown,
+        timestamp: None,
+    };
+
+    let value = to_value(&position);
+    assert_eq!(
+        value,
+        serde_json::json!({ "type": "Point", "coordinates": [13.4, 52.5, 34.0] })
+    );
+    let read = from_value(value).unwrap();
+    assert_eq!(
+        (read.latitude, read.longitude, read.altitude),
+        (52.5, 13.4, Some(34.0))
+    );
+}
@@ -1,14 +1,26 @@
 //! Interact with location services.
+//!
+//! Location services are available on the web and Windows. On other platforms, coordinates can only come from a
+//! [`LocationProvider`].
 
-cfg_if::cfg_if! {
-    if #[cfg(any(windows, target_family = "wasm"))] {
-        pub mod core;
-        pub mod platform;
-        pub mod use_geolocation;
-        pub use self::core::*;
-        pub use self::use_geolocation::*;
-    }
-    else {
-        compile_error!("the `geolocation` feature is only available on wasm and windows targets");
-    }
-}
+pub mod core;
+#[cfg(feature = "serde")]
+pub mod geojson;
+pub mod platform;
+mod provider;
+mod smoothing;
+pub mod use_geolocation;
+pub mod use_location_gate;
+#[cfg(feature = "location_reporter")]
+pub mod use_location_reporter;
+pub mod use_map_viewport;
+pub mod use_nearest;
+pub use self::core::*;
+pub use self::provider::*;
+pub use self::smoothing::*;
+pub use self::use_geolocation::*;
+pub use self::use_location_gate::*;
+#[cfg(feature = "location_reporter")]
+pub use self::use_location_reporter::*;
+pub use self::use_map_viewport::*;
+pub use self::use_nearest::*;
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use crate::geolocation::core::{ActivityType, Error, Event, Geocoordinates, PowerMode};
+
+/// This platform has no location services, so only the fallback providers can be used.
+pub struct Geolocator;
+
+/// Listeners can't be registered on this platform.
+pub struct Subscription;
+
+impl Geolocator {
+    pub fn new() -> Result<Self, Error> {
+        Err(Error::Unsupported)
+    }
+}
+
+pub fn check_availability() -> Result<(), Error> {
+    Err(Error::Unsupported)
+}
+
+pub async fn get_coordinates(_geolocator: &Geolocator) -> Result<Geocoordinates, Error> {
+    Err(Error::Unsupported)
+}
+
+pub fn listen(
+    _geolocator: &Geolocator,
+    _callback: Arc<dyn Fn(Event) + Send + Sync>,
+) -> Result<Subscription, Error> {
+    Err(Error::Unsupported)
+}
+
+pub fn set_power_mode(_geolocator: &mut Geolocator, _power_mode: PowerMode) -> Result<(), Error> {
+    Err(Error::Unsupported)
+}
+
+pub fn set_activity_type(
+    _geolocator: &mut Geolocator,
+    _activity_type: ActivityType,
+) -> Result<(), Error> {
+    Err(Error::Unsupported)
+}
@@ -4,7 +4,7 @@ use std::sync::Arc;
 use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
 use web_sys::PositionOptions;
 
-use crate::geolocation::{Error, Event, Geocoordinates, PowerMode};
+use crate::geolocation::{ActivityType, Error, Event, Geocoordinates, PositionSource, PowerMode};
 
 /// Represents the HAL's geolocator.
 pub struct Geolocator {
@@ -15,6 +15,8 @@ pub struct Geolocator {
 impl Geolocator {
     /// Create a new Geolocator for the device.
     pub fn new() -> Result<Self, Error> {
+        check_availability()?;
+
         let window = web_sys::window().expect("not a wasm context");
         let navigator = window.navigator();
         let locator = navigator
@@ -30,6 +32,57 @@ impl Geolocator {
     }
 }
 
+/// Keeps a position watch running until it is dropped.
+pub struct Subscription {
+    device_geolocator: web_sys::Geolocation,
+    watch_id: i32,
+    _success: Closure<dyn Fn(JsValue)>,
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        self.device_geolocator.clear_watch(self.watch_id);
+    }
+}
+
+/// Check whether the page is allowed to use the geolocation API.
+///
+/// Browsers only expose geolocation to secure contexts, and sites can disable it with a `Permissions-Policy` header.
+/// In both cases the API fails without a useful message, so they are detected up front.
+pub fn check_availability() -> Result<(), Error> {
+    let window = web_sys::window().expect("not a wasm context");
+    if !window.is_secure_context() {
+        return Err(Error::InsecureContext);
+    }
+
+    if let Some(document) = window.document() {
+        if allows_feature(&document, "geolocation") == Some(false) {
+            return Err(Error::BlockedByPermissionsPolicy);
+        }
+    }
+
+    Ok(())
+}
+
+/// Ask the permissions policy of the document whether a feature is allowed.
+///
+/// Returns `None` if the browser doesn't support querying the permissions policy.
+fn allows_feature(document: &web_sys::Document, feature: &str) -> Option<bool> {
+    // `featurePolicy` is the older name of the same API.
+    let policy = ["permissionsPolicy", "featurePolicy"]
+        .into_iter()
+        .filter_map(|name| js_sys::Reflect::get(document, &JsValue::from_str(name)).ok())
+        .find(|policy| policy.is_object())?;
+    let allows_feature = js_sys::Reflect::get(&policy, &JsValue::from_str("allowsFeature"))
+        .ok()?
+        .dyn_into::<js_sys::Function>()
+        .ok()?;
+    allows_feature
+        .call1(&policy, &JsValue::from_str(feature))
+        .ok()?
+        .as_bool()
+}
+
 pub async fn get_coordinates(geolocator: &Geolocator) -> Result<Geocoordinates, Error> {
     // Start channel
     let (mut sender, mut receiver) = mpsc::channel::<Result<Geocoordinates, Error>>(1);
@@ -37,54 +90,10 @@ pub async fn get_coordinates(geolocator: &Geolocator) -> Result<Geocoordinates,
 
     // Success
     let success = Closure::wrap(Box::new(move |pos| {
-        // Lots of casting with soft error handling
-        let coords = match js_sys::Reflect::get(&pos, &JsValue::from_str("coords")) {
-            Ok(v) => v,
-            Err(_) => {
-                let _ = sender.try_send(Err(Error::DeviceError("failed cast".to_string())));
-                return;
-            }
-        };
-
-        let latitude = match js_sys::Reflect::get(&coords, &JsValue::from_str("latitude")) {
-            Ok(v) => v,
-            Err(_) => {
-                let _ = sender.try_send(Err(Error::DeviceError("failed cast".to_string())));
-                return;
-            }
-        };
-
-        let latitude = match latitude.as_f64() {
-            Some(v) => v,
-            None => {
-                let _ = sender.try_send(Err(Error::DeviceError("failed cast".to_string())));
-                return;
-            }
-        };
-
-        let longitude = match js_sys::Reflect::get(&coords, &JsValue::from_str("longitude")) {
-            Ok(v) => v,
-            Err(_) => {
-                let _ = sender.try_send(Err(Error::DeviceError("failed cast".to_string())));
-                return;
-            }
+        let _ = match geocoordinates_from_position(&pos) {
+            Some(geocoords) => sender.try_send(Ok(geocoords)),
+            None => sender.try_send(Err(Error::DeviceError("failed cast".to_string()))),
         };
-
-        let longitude = match longitude.as_f64() {
-            Some(v) => v,
-            None => {
-                let _ = sender.try_send(Err(Error::DeviceError("failed cast".to_string())));
-                return;
-            }
-        };
-        // End casting
-
-        let geocoords = Geocoordinates {
-            latitude,
-            longitude,
-        };
-
-        let _ = sender.try_send(Ok(geocoords));
     }) as Box<dyn FnMut(JsValue)>);
 
     // Error
@@ -105,7 +114,16 @@ pub async fn get_coordinates(geolocator: &Geolocator) -> Result<Geocoordinates,
             }
         };
 
-        let _ = sender1.try_send(Err(Error::DeviceError(message)));
+        // A `GeolocationPositionError` code of 1 means the user denied the permission.
+        let code = js_sys::Reflect::get(&e, &JsValue::from_str("code"))
+            .ok()
+            .and_then(|code| code.as_f64());
+        let error = match code {
+            Some(code) if code == 1.0 => Error::AccessDenied,
+            _ => Error::DeviceError(message),
+        };
+
+        let _ = sender1.try_send(Err(error));
     }) as Box<dyn FnMut(JsValue)>);
 
     // Get position
@@ -126,49 +144,19 @@ pub async fn get_coordinates(geolocator: &Geolocator) -> Result<Geocoordinates,
     Err(Error::DeviceError("async communication failed".to_string()))
 }
 
-/// Listen to new events with a callback.
+/// Listen to new events with a callback until the returned [`Subscription`] is dropped.
 pub fn listen(
     geolocator: &Geolocator,
     callback: Arc<dyn Fn(Event) + Send + Sync>,
-) -> Result<(), Error> {
+) -> Result<Subscription, Error> {
     let success = Closure::wrap(Box::new(move |pos| {
-        // Lots of casting with soft error handling
-        let coords = match js_sys::Reflect::get(&pos, &JsValue::from_str("coords")) {
-            Ok(v) => v,
-            Err(_) => return,
-        };
-
-        let latitude = match js_sys::Reflect::get(&coords, &JsValue::from_str("latitude")) {
-            Ok(v) => v,
-            Err(_) => return,
-        };
-
-        let latitude = match latitude.as_f64() {
-            Some(v) => v,
-            None => return,
-        };
-
-        let longitude = match js_sys::Reflect::get(&coords, &JsValue::from_str("longitude")) {
-            Ok(v) => v,
-            Err(_) => return,
-        };
-
-        let longitude = match longitude.as_f64() {
-            Some(v) => v,
-            None => return,
-        };
-        // End casting
-
-        let geocoords = Geocoordinates {
-            latitude,
-            longitude,
-        };
-
-        (callback)(Event::NewGeocoordinates(geocoords))
+        if let Some(geocoords) = geocoordinates_from_position(&pos) {
+            (callback)(Event::NewGeocoordinates(geocoords))
+        }
     }) as Box<dyn Fn(JsValue)>);
 
     // Subscribe
-    geolocator
+    let watch_id = geolocator
         .device_geolocator
         .watch_position_with_error_callback_and_options(
             success.as_ref().unchecked_ref(),
@@ -177,17 +165,63 @@ pub fn listen(
         )
         .map_err(|e| Error::DeviceError(format!("{:?}", e)))?;
 
-    // Prevent from being dropped.
-    success.forget();
-    Ok(())
+    // The closure is kept alive until the watch is cleared.
+    Ok(Subscription {
+        device_geolocator: geolocator.device_geolocator.clone(),
+        watch_id,
+        _success: success,
+    })
 }
 
 /// Set the device's power mode.
 pub fn set_power_mode(geolocator: &mut Geolocator, power_mode: PowerMode) -> Result<(), Error> {
     match power_mode {
-        PowerMode::High => geolocator.options.enable_high_accuracy(true),
-        PowerMode::Low => geolocator.options.enable_high_accuracy(false),
+        PowerMode::High => geolocator.options.set_enable_high_accuracy(true),
+        PowerMode::Low => geolocator.options.set_enable_high_accuracy(false),
     };
 
     Ok(())
 }
+
+/// Set how often the device reports new positions.
+///
+/// The web geolocation API doesn't support activity hints, so this does nothing.
+pub fn set_activity_type(
+    _geolocator: &mut Geolocator,
+    _activity_type: ActivityType,
+) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Read the coordinates from a `GeolocationPosition`.
+fn geocoordinates_from_position(pos: &JsValue) -> Option<Geocoordinates> {
+    // Lots of casting with soft error handling
+    let coords = js_sys::Reflect::get(pos, &JsValue::from_str("coords")).ok()?;
+    let latitude = js_sys::Reflect::get(&coords, &JsValue::from_str("latitude"))
+        .ok()?
+        .as_f64()?;
+    let longitude = js_sys::Reflect::get(&coords, &JsValue::from_str("longitude"))
+        .ok()?
+        .as_f64()?;
+    // Altitude is null when the device can't provide it.
+    let altitude = js_sys::Reflect::get(&coords, &JsValue::from_str("altitude"))
+        .ok()
+        .and_then(|altitude| altitude.as_f64());
+    // End casting
+
+    let timestamp = js_sys::Reflect::get(pos, &JsValue::from_str("timestamp"))
+        .ok()
+        .and_then(|timestamp| timestamp.as_f64())
+        .map(|timestamp| timestamp as u64);
+
+    Some(Geocoordinates {
+        latitude,
+        longitude,
+        altitude,
+        // Browsers don't report whether a position was simulated.
+        is_mocked: None,
+        // Browsers don't report which source produced a position.
+        source: PositionSource::Unknown,
+        timestamp,
+    })
+}
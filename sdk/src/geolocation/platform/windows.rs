@@ -1,97 +1,187 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use windows::{
     Devices::Geolocation::{
-        BasicGeoposition, GeolocationAccessStatus, Geolocator as WindowsGeolocator,
-        PositionAccuracy, PositionChangedEventArgs, PositionStatus, StatusChangedEventArgs,
+        Geocoordinate, GeolocationAccessStatus, Geolocator as WindowsGeolocator, PositionAccuracy,
+        PositionChangedEventArgs, PositionSource as WindowsPositionSource, PositionStatus,
+        StatusChangedEventArgs,
     },
-    Foundation::TypedEventHandler,
+    Foundation::{EventRegistrationToken, TypedEventHandler},
 };
 
-use crate::geolocation::core::{Error, Event, Geocoordinates, PowerMode, Status};
+use crate::geolocation::core::{
+    ActivityType, Error, Event, Geocoordinates, PositionSource, PowerMode, Status,
+};
+
+type Callback = Arc<dyn Fn(Event) + Send + Sync>;
 
 /// Represents the HAL's geolocator.
 pub struct Geolocator {
     device_geolocator: WindowsGeolocator,
+    listeners: Arc<Mutex<Listeners>>,
 }
 
-impl Geolocator {
-    /// Create a new Geolocator for the device.
-    pub fn new() -> Result<Self, Error> {
-        // Check access
-        let access_status = match WindowsGeolocator::RequestAccessAsync() {
-            Ok(v) => v,
-            Err(e) => return Err(Error::DeviceError(e.to_string())),
+/// The callbacks subscribed to a geolocator, which share one set of event handlers.
+#[derive(Default)]
+struct Listeners {
+    next_id: usize,
+    callbacks: Vec<(usize, Callback)>,
+    /// The registered event handlers, while there are listeners.
+    tokens: Option<(EventRegistrationToken, EventRegistrationToken)>,
+}
+
+impl Listeners {
+    /// Run every callback with the event, without holding the lock while they run.
+    fn dispatch(listeners: &Mutex<Listeners>, event: Event) {
+        let callbacks: Vec<Callback> = match listeners.lock() {
+            Ok(listeners) => listeners.callbacks.iter().map(|(_, c)| c.clone()).collect(),
+            Err(_) => return,
         };
+        for callback in callbacks {
+            (callback)(event.clone());
+        }
+    }
+}
 
-        let access_status = match access_status.get() {
-            Ok(v) => v,
-            Err(e) => return Err(Error::DeviceError(e.to_string())),
+/// Keeps a listener subscribed to the geolocator until it is dropped.
+///
+/// The event handlers are removed from the device once the last listener is dropped.
+pub struct Subscription {
+    id: usize,
+    device_geolocator: WindowsGeolocator,
+    listeners: Arc<Mutex<Listeners>>,
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        let Ok(mut listeners) = self.listeners.lock() else {
+            return;
         };
+        listeners.callbacks.retain(|(id, _)| *id != self.id);
+        if !listeners.callbacks.is_empty() {
+            return;
+        }
 
-        if access_status != GeolocationAccessStatus::Allowed {
-            return Err(Error::AccessDenied);
+        if let Some((status_token, position_token)) = listeners.tokens.take() {
+            let status = self.device_geolocator.RemoveStatusChanged(status_token);
+            let position = self.device_geolocator.RemovePositionChanged(position_token);
+            if let Err(e) = status.and(position) {
+                tracing::warn!("Failed to remove the geolocation event handlers: {}", e);
+            }
         }
+    }
+}
+
+impl Geolocator {
+    /// Create a new Geolocator for the device.
+    pub fn new() -> Result<Self, Error> {
+        check_availability()?;
 
         // Get geolocator
         let device_geolocator =
             WindowsGeolocator::new().map_err(|e| Error::DeviceError(e.to_string()))?;
 
-        Ok(Self { device_geolocator })
+        Ok(Self {
+            device_geolocator,
+            listeners: Arc::default(),
+        })
     }
 }
 
-pub async fn get_coordinates(geolocator: &Geolocator) -> Result<Geocoordinates, Error> {
-    let location = geolocator.device_geolocator.GetGeopositionAsync();
-
-    let location = match location {
+/// Check whether the app is allowed to access the device's location.
+pub fn check_availability() -> Result<(), Error> {
+    let access_status = match WindowsGeolocator::RequestAccessAsync() {
         Ok(v) => v,
         Err(e) => return Err(Error::DeviceError(e.to_string())),
     };
 
-    let location = match location.get() {
+    let access_status = match access_status.get() {
         Ok(v) => v,
         Err(e) => return Err(Error::DeviceError(e.to_string())),
     };
 
-    let location_coordinate = match location.Coordinate() {
+    if access_status != GeolocationAccessStatus::Allowed {
+        return Err(Error::AccessDenied);
+    }
+
+    Ok(())
+}
+
+pub async fn get_coordinates(geolocator: &Geolocator) -> Result<Geocoordinates, Error> {
+    let location = geolocator.device_geolocator.GetGeopositionAsync();
+
+    let location = match location {
         Ok(v) => v,
         Err(e) => return Err(Error::DeviceError(e.to_string())),
     };
 
-    let location_point = match location_coordinate.Point() {
+    let location = match location.get() {
         Ok(v) => v,
         Err(e) => return Err(Error::DeviceError(e.to_string())),
     };
 
-    let position = match location_point.Position() {
+    let location_coordinate = match location.Coordinate() {
         Ok(v) => v,
         Err(e) => return Err(Error::DeviceError(e.to_string())),
     };
 
-    Ok(position.into())
+    geocoordinates_from(&location_coordinate).map_err(|e| Error::DeviceError(e.to_string()))
 }
 
-/// Listen to new events with a callback.
+/// Listen to new events with a callback until the returned [`Subscription`] is dropped.
+///
+/// All listeners of a geolocator share one set of event handlers on the device.
 pub fn listen(
     geolocator: &Geolocator,
     callback: Arc<dyn Fn(Event) + Send + Sync>,
-) -> Result<(), Error> {
-    let callback1 = callback.clone();
-    let callback2 = callback.clone();
+) -> Result<Subscription, Error> {
+    let registered = geolocator
+        .listeners
+        .lock()
+        .map_err(|_| Error::Poisoned)?
+        .tokens
+        .is_some();
+    // Register outside of the lock, as the device may report its status right away.
+    let tokens = match registered {
+        true => None,
+        false => Some(register(geolocator)?),
+    };
+
+    let mut listeners = geolocator.listeners.lock().map_err(|_| Error::Poisoned)?;
+    if tokens.is_some() {
+        listeners.tokens = tokens;
+    }
+
+    let id = listeners.next_id;
+    listeners.next_id += 1;
+    listeners.callbacks.push((id, callback));
+
+    Ok(Subscription {
+        id,
+        device_geolocator: geolocator.device_geolocator.clone(),
+        listeners: geolocator.listeners.clone(),
+    })
+}
+
+/// Register the event handlers that forward events to every listener.
+fn register(
+    geolocator: &Geolocator,
+) -> Result<(EventRegistrationToken, EventRegistrationToken), Error> {
+    let listeners1 = Arc::downgrade(&geolocator.listeners);
+    let listeners2 = Arc::downgrade(&geolocator.listeners);
 
     // Subscribe to status changed
-    geolocator
+    let status_token = geolocator
         .device_geolocator
         .StatusChanged(&TypedEventHandler::new(
             move |_geolocator: &Option<WindowsGeolocator>,
                   event_args: &Option<StatusChangedEventArgs>| {
-                if let Some(status) = event_args {
+                if let (Some(status), Some(listeners)) = (event_args, listeners1.upgrade()) {
                     // Get status
                     let status = status.Status()?;
 
-                    // Run callback
-                    (callback1)(Event::StatusChanged(status.into()))
+                    // Run callbacks
+                    Listeners::dispatch(&listeners, Event::StatusChanged(status.into()))
                 }
                 Ok(())
             },
@@ -99,24 +189,31 @@ pub fn listen(
         .map_err(|e| Error::DeviceError(e.to_string()))?;
 
     // Subscribe to position changed
-    geolocator
+    let position_token = geolocator
         .device_geolocator
         .PositionChanged(&TypedEventHandler::new(
             move |_geolocator: &Option<WindowsGeolocator>,
                   event_args: &Option<PositionChangedEventArgs>| {
-                if let Some(position) = event_args {
+                if let (Some(position), Some(listeners)) = (event_args, listeners2.upgrade()) {
                     // Get coordinate
-                    let position = position.Position()?.Coordinate()?.Point()?.Position()?;
+                    let coordinate = position.Position()?.Coordinate()?;
+                    let geocoordinates = geocoordinates_from(&coordinate)?;
 
-                    // Run callback
-                    (callback2)(Event::NewGeocoordinates(position.into()))
+                    // Run callbacks
+                    Listeners::dispatch(&listeners, Event::NewGeocoordinates(geocoordinates))
                 }
                 Ok(())
             },
         ))
-        .map_err(|e| Error::DeviceError(e.to_string()))?;
+        .map_err(|e| {
+            geolocator
+                .device_geolocator
+                .RemoveStatusChanged(status_token)
+                .ok();
+            Error::DeviceError(e.to_string())
+        })?;
 
-    Ok(())
+    Ok((status_token, position_token))
 }
 
 /// Set the device's power mode.
@@ -135,6 +232,30 @@ pub fn set_power_mode(geolocator: &mut Geolocator, power_mode: PowerMode) -> Res
     Ok(())
 }
 
+/// Set how often the device reports new positions.
+pub fn set_activity_type(
+    geolocator: &mut Geolocator,
+    activity_type: ActivityType,
+) -> Result<(), Error> {
+    // Report interval in milliseconds and movement threshold in meters. Zero uses the system default.
+    let (report_interval, movement_threshold) = match activity_type {
+        ActivityType::Navigation => (1000, 0.0),
+        ActivityType::Fitness => (1000, 5.0),
+        ActivityType::Other => (0, 0.0),
+    };
+
+    geolocator
+        .device_geolocator
+        .SetReportInterval(report_interval)
+        .map_err(|e| Error::DeviceError(e.to_string()))?;
+    geolocator
+        .device_geolocator
+        .SetMovementThreshold(movement_threshold)
+        .map_err(|e| Error::DeviceError(e.to_string()))?;
+
+    Ok(())
+}
+
 impl From<PositionStatus> for Status {
     fn from(value: PositionStatus) -> Self {
         match value.0 {
@@ -147,11 +268,34 @@ impl From<PositionStatus> for Status {
     }
 }
 
-impl From<BasicGeoposition> for Geocoordinates {
-    fn from(position: BasicGeoposition) -> Self {
-        Geocoordinates {
-            latitude: position.Latitude,
-            longitude: position.Longitude,
-        }
-    }
+/// The number of milliseconds between the Windows epoch (1601-01-01) and the Unix epoch.
+const WINDOWS_TO_UNIX_EPOCH_MS: i64 = 11_644_473_600_000;
+
+fn geocoordinates_from(coordinate: &Geocoordinate) -> windows::core::Result<Geocoordinates> {
+    let position = coordinate.Point()?.Position()?;
+
+    let source = match coordinate.PositionSource() {
+        Ok(WindowsPositionSource::Satellite) => PositionSource::Satellite,
+        Ok(WindowsPositionSource::WiFi) => PositionSource::WiFi,
+        Ok(WindowsPositionSource::Cellular) => PositionSource::Cellular,
+        Ok(WindowsPositionSource::IPAddress) => PositionSource::IpAddress,
+        _ => PositionSource::Unknown,
+    };
+
+    // Timestamps are in 100 nanosecond intervals since the Windows epoch.
+    let timestamp = coordinate
+        .Timestamp()
+        .ok()
+        .map(|timestamp| timestamp.UniversalTime / 10_000 - WINDOWS_TO_UNIX_EPOCH_MS)
+        .and_then(|timestamp| u64::try_from(timestamp).ok());
+
+    Ok(Geocoordinates {
+        latitude: position.Latitude,
+        longitude: position.Longitude,
+        altitude: Some(position.Altitude),
+        // Windows doesn't report whether a position was simulated.
+        is_mocked: None,
+        source,
+        timestamp,
+    })
 }
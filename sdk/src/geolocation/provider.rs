@@ -0,0 +1,79 @@
+//! Fallback sources of coordinates for when the device can't provide them.
+
+use futures::future::LocalBoxFuture;
+use std::{cell::RefCell, future::Future, rc::Rc};
+
+use super::core::{Error, Geocoordinates};
+
+/// A source of approximate coordinates, like an IP geolocation service.
+///
+/// Register providers with [`register_location_provider`]. When the device can't provide coordinates, because
+/// the platform has no location services or the user denied access, [`Geolocator::get_coordinates`] asks the
+/// providers in the order they were registered and returns the first coordinates one of them finds.
+///
+/// Closures that return a future implement this trait, so a provider can be as simple as an HTTP request.
+///
+/// [`Geolocator::get_coordinates`]: super::Geolocator::get_coordinates
+pub trait LocationProvider: 'static {
+    /// Look up the current coordinates.
+    fn get_coordinates(&self) -> LocalBoxFuture<'_, Result<Geocoordinates, Error>>;
+}
+
+impl<F, Fut> LocationProvider for F
+where
+    F: Fn() -> Fut + 'static,
+    Fut: Future<Output = Result<Geocoordinates, Error>> + 'static,
+{
+    fn get_coordinates(&self) -> LocalBoxFuture<'_, Result<Geocoordinates, Error>> {
+        Box::pin(self())
+    }
+}
+
+thread_local! {
+    /// The registered providers, in the order they are asked.
+    static PROVIDERS: RefCell<Vec<Rc<dyn LocationProvider>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Register a provider to fall back to when the device can't provide coordinates.
+///
+/// Providers must be registered before the [`Geolocator`](super::Geolocator) is created, so it can be created
+/// even on platforms without location services.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use dioxus_sdk::geolocation::{register_location_provider, Error, Geocoordinates, PositionSource};
+///
+/// register_location_provider(|| async {
+///     let (latitude, longitude) = lookup_ip_location().await.map_err(|e| Error::DeviceError(e.to_string()))?;
+///     Ok(Geocoordinates {
+///         latitude,
+///         longitude,
+///         altitude: None,
+///         is_mocked: None,
+///         source: PositionSource::IpAddress,
+///         timestamp: None,
+///     })
+/// });
+/// ```
+pub fn register_location_provider(provider: impl LocationProvider) {
+    PROVIDERS.with(|providers| providers.borrow_mut().push(Rc::new(provider)));
+}
+
+/// Whether any fallback provider is registered.
+pub(crate) fn has_location_providers() -> bool {
+    PROVIDERS.with(|providers| !providers.borrow().is_empty())
+}
+
+/// Ask the providers for coordinates in order, returning `error` if none of them finds any.
+pub(crate) async fn get_fallback_coordinates(error: Error) -> Result<Geocoordinates, Error> {
+    // Cloned so providers can register more providers while they run.
+    let providers = PROVIDERS.with(|providers| providers.borrow().clone());
+    for provider in providers {
+        match provider.get_coordinates().await {
+            Ok(coordinates) => return Ok(coordinates),
+            Err(e) => tracing::debug!("Location provider failed: {}", e),
+        }
+    }
+    Err(error)
+}
@@ -0,0 +1,185 @@
+//! Smoothing of jittery position fixes.
+
+use std::collections::VecDeque;
+
+use super::core::Geocoordinates;
+
+/// How the positions of [`use_smoothed_geolocation`](super::use_smoothed_geolocation) are smoothed.
+///
+/// Only the latitude and longitude are smoothed. The other fields are taken from the latest fix.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Smoothing {
+    /// Report every fix as it is.
+    #[default]
+    None,
+    /// Report the average of the latest `samples` fixes.
+    ///
+    /// This is simple and removes most jitter, but lags behind a moving device by about half the samples.
+    MovingAverage { samples: usize },
+    /// Filter the fixes with a Kalman filter, which follows a moving device more closely than an average.
+    ///
+    /// `process_noise` is how fast the device is expected to move in meters per second, like `3.0` for walking or
+    /// `30.0` for driving. `measurement_noise` is how far off a fix is expected to be in meters, like `15.0` for
+    /// phone GPS. A higher measurement noise or a lower process noise smooths more.
+    Kalman {
+        process_noise: f64,
+        measurement_noise: f64,
+    },
+}
+
+/// The seconds assumed between fixes without a timestamp.
+const DEFAULT_INTERVAL: f64 = 1.0;
+
+/// Smooths a stream of position fixes with a [`Smoothing`].
+///
+/// [`use_smoothed_geolocation`](super::use_smoothed_geolocation) uses this internally, but it can also smooth
+/// positions from other sources, like a recorded track.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use dioxus_sdk::geolocation::{PositionSmoother, Smoothing};
+///
+/// let mut smoother = PositionSmoother::new(Smoothing::MovingAverage { samples: 5 });
+/// let track: Vec<_> = recorded_fixes().into_iter().map(|fix| smoother.update(fix)).collect();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionSmoother {
+    smoothing: Smoothing,
+    /// The latest fixes, for the moving average.
+    samples: VecDeque<(f64, f64)>,
+    /// The estimate of the Kalman filter.
+    estimate: Option<Estimate>,
+}
+
+/// The position a Kalman filter estimates, along with how uncertain it is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Estimate {
+    latitude: f64,
+    longitude: f64,
+    /// The variance of the estimate in square meters.
+    variance: f64,
+    timestamp: Option<u64>,
+}
+
+impl PositionSmoother {
+    /// Create a smoother that hasn't seen any fixes yet.
+    pub fn new(smoothing: Smoothing) -> Self {
+        Self {
+            smoothing,
+            samples: VecDeque::new(),
+            estimate: None,
+        }
+    }
+
+    /// Add a fix and get the smoothed position.
+    pub fn update(&mut self, raw: Geocoordinates) -> Geocoordinates {
+        let (latitude, longitude) = match self.smoothing {
+            Smoothing::None => (raw.latitude, raw.longitude),
+            Smoothing::MovingAverage { samples } => {
+                if self.samples.len() >= samples.max(1) {
+                    self.samples.pop_front();
+                }
+                self.samples.push_back((raw.latitude, raw.longitude));
+                let count = self.samples.len() as f64;
+                let (latitude, longitude) = self
+                    .samples
+                    .iter()
+                    .fold((0.0, 0.0), |(lat, lon), (la, lo)| (lat + la, lon + lo));
+                (latitude / count, longitude / count)
+            }
+            Smoothing::Kalman {
+                process_noise,
+                measurement_noise,
+            } => {
+                let estimate = self.kalman(&raw, process_noise, measurement_noise);
+                (estimate.latitude, estimate.longitude)
+            }
+        };
+
+        Geocoordinates {
+            latitude,
+            longitude,
+            ..raw
+        }
+    }
+
+    /// Forget the previous fixes, for example after the device was offline for a while.
+    pub fn reset(&mut self) {
+        self.samples.clear();
+        self.estimate = None;
+    }
+
+    fn kalman(
+        &mut self,
+        raw: &Geocoordinates,
+        process_noise: f64,
+        measurement_noise: f64,
+    ) -> Estimate {
+        let measurement_variance = measurement_noise * measurement_noise;
+        let estimate = match self.estimate {
+            None => Estimate {
+                latitude: raw.latitude,
+                longitude: raw.longitude,
+                variance: measurement_variance,
+                timestamp: raw.timestamp,
+            },
+            Some(previous) => {
+                // The device may have moved since the previous fix, which makes the estimate less certain.
+                let elapsed = match (previous.timestamp, raw.timestamp) {
+                    (Some(previous), Some(current)) => {
+                        current.saturating_sub(previous) as f64 / 1000.0
+                    }
+                    _ => DEFAULT_INTERVAL,
+                };
+                let variance = previous.variance + elapsed * process_noise * process_noise;
+
+                let gain = variance / (variance + measurement_variance);
+                Estimate {
+                    latitude: previous.latitude + gain * (raw.latitude - previous.latitude),
+                    longitude: previous.longitude + gain * (raw.longitude - previous.longitude),
+                    variance: (1.0 - gain) * variance,
+                    timestamp: raw.timestamp.or(previous.timestamp),
+                }
+            }
+        };
+        self.estimate = Some(estimate);
+        estimate
+    }
+}
+
+#[test]
+fn test_smoothing() {
+    use super::core::PositionSource;
+
+    let fix = |latitude: f64, timestamp: u64| Geocoordinates {
+        latitude,
+        longitude: 0.0,
+        altitude: None,
+        is_mocked: None,
+        source: PositionSource::Satellite,
+        timestamp: Some(timestamp),
+    };
+
+    let mut average = PositionSmoother::new(Smoothing::MovingAverage { samples: 2 });
+    assert_eq!(average.update(fix(1.0, 0)).latitude, 1.0);
+    assert_eq!(average.update(fix(3.0, 1000)).latitude, 2.0);
+    assert_eq!(average.update(fix(5.0, 2000)).latitude, 4.0);
+
+    // The filter moves part of the way towards each fix, and closes in on the fixes as they repeat.
+    let mut kalman = PositionSmoother::new(Smoothing::Kalman {
+        process_noise: 3.0,
+        measurement_noise: 15.0,
+    });
+    assert_eq!(kalman.update(fix(0.0, 0)).latitude, 0.0);
+    let smoothed = kalman.update(fix(1.0, 1000)).latitude;
+    assert!(smoothed > 0.0 && smoothed < 1.0);
+    let mut latitude = smoothed;
+    for second in 2..60 {
+        latitude = kalman.update(fix(1.0, second * 1000)).latitude;
+    }
+    assert!(latitude > smoothed && latitude < 1.0);
+
+    kalman.reset();
+    assert_eq!(kalman.update(fix(5.0, 0)).latitude, 5.0);
+}
@@ -1,33 +1,92 @@
 //! Provides an initialization and use_geolocation hook.
 
-use super::core::{Error, Event, Geocoordinates, Geolocator, PowerMode, Status};
+use super::{
+    core::{Error, Event, Geocoordinates, Geolocator, PowerMode, Status, Subscription},
+    smoothing::{PositionSmoother, Smoothing},
+};
 use dioxus::{
     prelude::{
-        provide_context, try_consume_context, use_coroutine, use_hook, use_signal, ReadOnlySignal,
+        provide_context, try_consume_context, use_coroutine, use_hook, CopyValue, ReadOnlySignal,
         Signal, UnboundedReceiver,
     },
     signals::{Readable, Writable},
 };
 use futures_util::stream::StreamExt;
-use std::sync::Once;
-
-static INIT: Once = Once::new();
 
 /// Provides the latest geocoordinates. Good for navigation-type apps.
+///
+/// The listener is removed when the component is dropped. See [`use_smoothed_geolocation`] to smooth out jitter.
 pub fn use_geolocation() -> ReadOnlySignal<Result<Geocoordinates, Error>> {
+    use_smoothed_geolocation(Smoothing::None).position()
+}
+
+/// The positions provided by [`use_smoothed_geolocation`].
+#[derive(Clone, Copy, PartialEq)]
+pub struct SmoothedGeolocation {
+    position: Signal<Result<Geocoordinates, Error>>,
+    raw: Signal<Result<Geocoordinates, Error>>,
+}
+
+impl SmoothedGeolocation {
+    /// Get the latest smoothed position.
+    pub fn position(&self) -> ReadOnlySignal<Result<Geocoordinates, Error>> {
+        self.position.into()
+    }
+
+    /// Get the latest position as the device reported it.
+    pub fn raw(&self) -> ReadOnlySignal<Result<Geocoordinates, Error>> {
+        self.raw.into()
+    }
+}
+
+/// Provides the latest geocoordinates, smoothed with the given [`Smoothing`].
+///
+/// Raw fixes jitter by several meters even when the device isn't moving, which makes maps that follow the position
+/// shake. The smoothing is applied before the position updates. The unsmoothed fixes are still available from
+/// [`SmoothedGeolocation::raw`]. The smoothing starts over when location services are turned off.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::geolocation::{use_smoothed_geolocation, Smoothing};
+///
+/// fn Tracker() -> Element {
+///     let geolocation = use_smoothed_geolocation(Smoothing::Kalman {
+///         process_noise: 3.0,
+///         measurement_noise: 15.0,
+///     });
+///
+///     rsx! {
+///         if let Ok(position) = geolocation.position()() {
+///             Marker { latitude: position.latitude, longitude: position.longitude }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_smoothed_geolocation(smoothing: Smoothing) -> SmoothedGeolocation {
     // Store the coords
-    let mut coords: Signal<Result<Geocoordinates, Error>> =
-        use_signal(|| Err(Error::NotInitialized));
+    let mut geolocation = use_hook(|| SmoothedGeolocation {
+        position: Signal::new(Err(Error::NotInitialized)),
+        raw: Signal::new(Err(Error::NotInitialized)),
+    });
+
+    // Dropped with the component, which removes the listener.
+    let mut subscription = use_hook(|| CopyValue::new(None::<Subscription>));
 
     // Initialize the handler of events
-    let listener = use_coroutine(|mut rx: UnboundedReceiver<Event>| async move {
+    let listener = use_coroutine(move |mut rx: UnboundedReceiver<Event>| async move {
+        let mut smoother = PositionSmoother::new(smoothing);
         while let Some(event) = rx.next().await {
             match event {
                 Event::NewGeocoordinates(new_coords) => {
-                    *coords.write() = Ok(new_coords);
+                    *geolocation.position.write() = Ok(smoother.update(new_coords.clone()));
+                    *geolocation.raw.write() = Ok(new_coords);
                 }
                 Event::StatusChanged(Status::Disabled) => {
-                    *coords.write() = Err(Error::AccessDenied);
+                    smoother.reset();
+                    *geolocation.position.write() = Err(Error::AccessDenied);
+                    *geolocation.raw.write() = Err(Error::AccessDenied);
                 }
                 _ => {}
             }
@@ -35,24 +94,27 @@ pub fn use_geolocation() -> ReadOnlySignal<Result<Geocoordinates, Error>> {
     });
 
     // Try getting the geolocator and starting the listener.
-    match try_consume_context::<Signal<Result<Geolocator, Error>>>() {
+    let error = match try_consume_context::<Signal<Result<Geolocator, Error>>>() {
         Some(geo) => {
             let geo = geo.read();
             match geo.as_ref() {
                 Ok(geolocator) => {
-                    INIT.call_once(|| {
-                        geolocator.listen(listener).ok();
-                    });
+                    if subscription.peek().is_none() {
+                        subscription.set(geolocator.listen(listener).ok());
+                    }
+                    None
                 }
-                Err(e) => coords.set(Err(e.clone())),
+                Err(e) => Some(e.clone()),
             }
         }
-        None => {
-            coords.set(Err(Error::NotInitialized));
-        }
+        None => Some(Error::NotInitialized),
+    };
+    if let Some(error) = error {
+        geolocation.position.set(Err(error.clone()));
+        geolocation.raw.set(Err(error));
     }
 
-    use_hook(|| ReadOnlySignal::new(coords))
+    geolocation
 }
 
 /// Must be called before any use of the geolocation abstraction.
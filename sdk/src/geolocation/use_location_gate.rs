@@ -0,0 +1,235 @@
+//! Provides the use_location_gate hook and the GeolocationGate component.
+
+use super::core::{Error, Geolocator, PowerMode};
+use dioxus::prelude::*;
+
+/// Where an app is in asking for the location.
+#[derive(Debug, Clone)]
+pub enum LocationGateState {
+    /// The location hasn't been asked for yet. This is when to explain why the app needs it.
+    NotRequested,
+    /// Waiting for the user to answer the permission prompt.
+    Prompting,
+    /// The user or the system denied access to the location. See [`denied_instructions`] for how to allow it again.
+    Denied,
+    /// The location can't be used here, like on the web outside of a secure context.
+    Unavailable(Error),
+    /// Access to the location was granted.
+    Granted,
+}
+
+/// Instructions for allowing the location again after it was denied, for the platform the app runs on.
+///
+/// Apps can't ask again once the location was denied, so the user has to allow it themselves.
+pub fn denied_instructions() -> &'static str {
+    if cfg!(target_family = "wasm") {
+        "Location access is blocked for this site. Click the icon next to the address bar, allow location \
+         access, and try again."
+    } else if cfg!(windows) {
+        "Location access is turned off for this app. Open Settings > Privacy & security > Location, turn on \
+         location access for this app, and try again."
+    } else {
+        "Location access is turned off for this app. Allow it in the system settings and try again."
+    }
+}
+
+/// A handle to the location permission flow.
+///
+/// See [`use_location_gate`] for more information.
+#[derive(Clone, Copy, PartialEq)]
+pub struct LocationGate {
+    state: Signal<LocationGateState>,
+    geolocator: Signal<Result<Geolocator, Error>>,
+    power_mode: PowerMode,
+}
+
+impl LocationGate {
+    /// Get where the app is in asking for the location.
+    pub fn state(&self) -> ReadOnlySignal<LocationGateState> {
+        self.state.into()
+    }
+
+    /// Ask for the location, showing the permission prompt of the platform if it hasn't been answered yet.
+    ///
+    /// Call this again after the location was denied to check whether the user allowed it since. On the web this must
+    /// be called in response to a user interaction, like a click, or browsers may ignore it.
+    pub fn request(&self) {
+        let mut this = *self;
+        if matches!(*this.state.peek(), LocationGateState::Prompting) {
+            return;
+        }
+
+        this.state.set(LocationGateState::Prompting);
+        spawn(async move {
+            let geolocator = match Geolocator::new(this.power_mode) {
+                Ok(geolocator) => geolocator,
+                Err(e) => {
+                    this.state.set(gate_state(e));
+                    return;
+                }
+            };
+
+            // Asking for a position is what makes the platform prompt. Any other error means access was granted
+            // but the position couldn't be read, which the app learns from `use_geolocation`.
+            let state = match geolocator.get_coordinates().await {
+                Err(Error::AccessDenied) => LocationGateState::Denied,
+                _ => LocationGateState::Granted,
+            };
+            if let LocationGateState::Granted = state {
+                this.geolocator.set(Ok(geolocator));
+            }
+            this.state.set(state);
+        });
+    }
+
+    /// Get the geolocator, which is only created once the location is requested.
+    pub fn geolocator(&self) -> Signal<Result<Geolocator, Error>> {
+        self.geolocator
+    }
+}
+
+/// The state to show for an error creating the geolocator.
+fn gate_state(error: Error) -> LocationGateState {
+    match error {
+        Error::AccessDenied => LocationGateState::Denied,
+        e => LocationGateState::Unavailable(e),
+    }
+}
+
+/// A hook that asks for the location only when the app is ready to explain why it needs it.
+///
+/// This replaces [`init_geolocator`](super::init_geolocator), which prompts as soon as it is called. The geolocator
+/// is provided to [`use_geolocation`](super::use_geolocation) once the location is requested and access is granted.
+/// Until then, `use_geolocation` reports [`Error::NotInitialized`]. See [`GeolocationGate`] for a component built on
+/// this hook.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::geolocation::{
+///     denied_instructions, use_location_gate, LocationGateState, PowerMode,
+/// };
+///
+/// fn App() -> Element {
+///     let gate = use_location_gate(PowerMode::High);
+///
+///     match gate.state()() {
+///         LocationGateState::NotRequested => rsx! {
+///             p { "Share your location to see stores near you." }
+///             button { onclick: move |_| gate.request(), "Share location" }
+///         },
+///         LocationGateState::Prompting => rsx! { "Waiting for permission..." },
+///         LocationGateState::Denied => rsx! {
+///             p { "{denied_instructions()}" }
+///             button { onclick: move |_| gate.request(), "Try again" }
+///         },
+///         LocationGateState::Unavailable(e) => rsx! { "Location is unavailable: {e}" },
+///         LocationGateState::Granted => rsx! { "Showing stores near you" },
+///     }
+/// }
+/// ```
+pub fn use_location_gate(power_mode: PowerMode) -> LocationGate {
+    use_hook(|| {
+        let gate = LocationGate {
+            state: Signal::new(LocationGateState::NotRequested),
+            geolocator: Signal::new(Err(Error::NotInitialized)),
+            power_mode,
+        };
+        provide_context(gate.geolocator);
+        provide_context(gate);
+
+        // Skip the explanation if the user already allowed the location, or explain how to allow it if they didn't.
+        #[cfg(feature = "permissions")]
+        spawn(async move {
+            use crate::permissions::{query_permission, Permission, PermissionStatus};
+
+            match query_permission(Permission::Geolocation).await {
+                PermissionStatus::Granted => gate.request(),
+                PermissionStatus::Denied => {
+                    let mut state = gate.state;
+                    state.set(LocationGateState::Denied);
+                }
+                _ => {}
+            }
+        });
+
+        gate
+    })
+}
+
+/// Shows its children once the user allowed the location, and the steps of asking for it until then.
+///
+/// Each step has a default that can be replaced. Components inside the steps can get the [`LocationGate`] with
+/// `use_context`, for example to request the location from a custom button.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::geolocation::{use_geolocation, GeolocationGate, LocationGate};
+///
+/// fn App() -> Element {
+///     rsx! {
+///         GeolocationGate {
+///             rationale: rsx! { ShareLocation {} },
+///             NearbyStores {}
+///         }
+///     }
+/// }
+///
+/// fn ShareLocation() -> Element {
+///     let gate = use_context::<LocationGate>();
+///     rsx! {
+///         p { "We use your location to find stores near you. It never leaves your device." }
+///         button { onclick: move |_| gate.request(), "Find stores near me" }
+///     }
+/// }
+///
+/// fn NearbyStores() -> Element {
+///     match use_geolocation()() {
+///         Ok(position) => rsx! { "Stores near {position.latitude}, {position.longitude}" },
+///         Err(_) => rsx! { "Locating..." },
+///     }
+/// }
+/// ```
+#[component]
+pub fn GeolocationGate(
+    /// How precise the location should be.
+    #[props(default = PowerMode::High)]
+    power_mode: PowerMode,
+    /// Shown before the location is requested. Defaults to a button that requests it.
+    rationale: Option<Element>,
+    /// Shown while the permission prompt is open.
+    prompting: Option<Element>,
+    /// Shown when access to the location was denied. Defaults to the [`denied_instructions`] and a button that
+    /// requests the location again.
+    denied: Option<Element>,
+    /// Shown when the location can't be used on this device. Defaults to the error.
+    unavailable: Option<Element>,
+    /// Shown once access to the location was granted.
+    children: Element,
+) -> Element {
+    let gate = use_location_gate(power_mode);
+
+    match gate.state()() {
+        LocationGateState::NotRequested => rationale.unwrap_or_else(|| {
+            rsx! {
+                button { onclick: move |_| gate.request(), "Share location" }
+            }
+        }),
+        LocationGateState::Prompting => {
+            prompting.unwrap_or_else(|| rsx! { "Waiting for permission to use the location..." })
+        }
+        LocationGateState::Denied => denied.unwrap_or_else(|| {
+            rsx! {
+                p { "{denied_instructions()}" }
+                button { onclick: move |_| gate.request(), "Try again" }
+            }
+        }),
+        LocationGateState::Unavailable(e) => {
+            unavailable.unwrap_or_else(|| rsx! { "The location is unavailable: {e}" })
+        }
+        LocationGateState::Granted => children,
+    }
+}
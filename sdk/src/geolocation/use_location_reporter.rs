@@ -0,0 +1,135 @@
+//! Provides a hook to upload positions to a server.
+
+use dioxus::prelude::*;
+use std::{fmt::Display, future::Future, time::Duration};
+
+use super::{core::Geocoordinates, use_geolocation::use_geolocation};
+use crate::{
+    storage::{new_storage, LocalStorage},
+    utils::timing::clock,
+};
+
+/// The storage key queued positions are persisted under.
+const QUEUE_KEY: &str = "dioxus-location-reports";
+/// How many positions are queued before the oldest ones are dropped, for example after a long time offline.
+const MAX_QUEUED: usize = 1000;
+/// How many positions are sent in one upload, so a queue that grew while offline doesn't become one huge request.
+const MAX_BATCH: usize = 100;
+
+/// A handle to the positions waiting to be uploaded.
+///
+/// See [`use_location_reporter`] for more information.
+#[derive(Clone, Copy, PartialEq)]
+pub struct LocationReporter {
+    queue: Signal<Vec<Geocoordinates>>,
+    error: Signal<Option<String>>,
+}
+
+impl LocationReporter {
+    /// Get the positions that haven't been uploaded yet.
+    pub fn queue(&self) -> ReadOnlySignal<Vec<Geocoordinates>> {
+        self.queue.into()
+    }
+
+    /// Get the error of the last upload, if it failed.
+    pub fn error(&self) -> ReadOnlySignal<Option<String>> {
+        self.error.into()
+    }
+}
+
+/// A hook that uploads the positions from [`use_geolocation`] to a server function every `interval`.
+///
+/// Positions are queued in local storage until `upload` succeeds, so positions recorded while offline or before the
+/// app is closed are sent later. The queue is uploaded in batches of at most 100 positions, and a failed upload is
+/// retried on the next interval. Requires
+/// [`init_geolocator`](super::init_geolocator) to be called first.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::geolocation::{use_location_reporter, Geocoordinates};
+/// use std::time::Duration;
+///
+/// #[server]
+/// async fn report_positions(positions: Vec<Geocoordinates>) -> Result<(), ServerFnError> {
+///     save_ride_positions(positions).await
+/// }
+///
+/// fn SharedRide() -> Element {
+///     let reporter = use_location_reporter(Duration::from_secs(15), report_positions);
+///
+///     rsx! {
+///         if let Some(error) = reporter.error()() {
+///             p { "Sharing paused: {error}" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_location_reporter<F, Fut, E>(interval: Duration, upload: F) -> LocationReporter
+where
+    F: Fn(Vec<Geocoordinates>) -> Fut + 'static,
+    Fut: Future<Output = Result<(), E>> + 'static,
+    E: Display,
+{
+    let position = use_geolocation();
+
+    let reporter = use_hook(|| {
+        let reporter = LocationReporter {
+            queue: new_storage::<LocalStorage, _>(QUEUE_KEY.to_string(), Vec::new),
+            error: Signal::new(None),
+        };
+
+        let mut queue = reporter.queue;
+        let mut error = reporter.error;
+        spawn(async move {
+            loop {
+                clock::sleep(interval).await;
+
+                loop {
+                    let batch: Vec<_> = queue.peek().iter().take(MAX_BATCH).cloned().collect();
+                    if batch.is_empty() {
+                        break;
+                    }
+                    match upload(batch.clone()).await {
+                        Ok(()) => {
+                            remove_sent(&mut queue.write(), &batch);
+                            error.set(None);
+                        }
+                        Err(e) => {
+                            tracing::warn!("Failed to upload positions: {}", e);
+                            error.set(Some(e.to_string()));
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+
+        reporter
+    });
+
+    use_effect(move || {
+        if let Ok(position) = position() {
+            let mut queue = reporter.queue;
+            let mut queue = queue.write();
+            queue.push(position);
+            let overflow = queue.len().saturating_sub(MAX_QUEUED);
+            queue.drain(..overflow);
+        }
+    });
+
+    reporter
+}
+
+/// Remove the positions that were uploaded from the queue.
+///
+/// Positions recorded or dropped during the upload shift the queue, so the uploaded positions are looked up instead
+/// of removed by position in the queue.
+fn remove_sent(queue: &mut Vec<Geocoordinates>, sent: &[Geocoordinates]) {
+    for position in sent {
+        if let Some(index) = queue.iter().position(|queued| queued == position) {
+            queue.remove(index);
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! Provides a hook for showing the user's position on a tiled map.
+
+use super::use_geolocation::use_geolocation;
+use dioxus::prelude::*;
+use std::f64::consts::PI;
+use std::ops::RangeInclusive;
+
+/// The width and height of map tiles in pixels, as used by OpenStreetMap and most tile servers.
+pub const TILE_SIZE: f64 = 256.0;
+
+/// The highest zoom level tile servers have tiles for. Higher zoom levels are lowered to it.
+pub const MAX_ZOOM: u8 = 22;
+
+/// The furthest latitude Web Mercator maps can show. The map is square at this latitude.
+const MAX_LATITUDE: f64 = 85.051_128_78;
+
+/// The tiles needed to fill a map viewport centered on a position.
+///
+/// Tiles use the Web Mercator `z/x/y` scheme of OpenStreetMap and most other tile servers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapViewport {
+    /// The latitude of the center of the viewport.
+    pub latitude: f64,
+    /// The longitude of the center of the viewport.
+    pub longitude: f64,
+    /// The zoom level, which is the `z` of the tiles.
+    pub zoom: u8,
+    /// The width and height of the viewport in pixels.
+    pub size: (u32, u32),
+    /// The columns of tiles covering the viewport.
+    ///
+    /// Columns can be outside of the map if the viewport crosses the antimeridian. [`MapViewport::tiles`]
+    /// wraps them around.
+    pub x_range: RangeInclusive<i64>,
+    /// The rows of tiles covering the viewport, limited to the rows of the map.
+    pub y_range: RangeInclusive<i64>,
+    /// Where the top left corner of the first tile is, relative to the top left corner of the viewport, in pixels.
+    pub offset: (f64, f64),
+}
+
+/// A tile of a [`MapViewport`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tile {
+    pub x: u32,
+    pub y: u32,
+    pub z: u8,
+    /// The position of the tile relative to the left edge of the viewport, in pixels.
+    pub left: f64,
+    /// The position of the tile relative to the top edge of the viewport, in pixels.
+    pub top: f64,
+}
+
+impl MapViewport {
+    /// Compute the tiles of a viewport of `size` pixels centered on a position.
+    ///
+    /// Zoom levels above [`MAX_ZOOM`] are lowered to it.
+    pub fn new(latitude: f64, longitude: f64, zoom: u8, size: (u32, u32)) -> Self {
+        let zoom = zoom.min(MAX_ZOOM);
+        let (center_x, center_y) = world_pixel(latitude, longitude, zoom);
+        let left = center_x - size.0 as f64 / 2.0;
+        let top = center_y - size.1 as f64 / 2.0;
+        let last_row = (1i64 << zoom) - 1;
+
+        let first_x = (left / TILE_SIZE).floor() as i64;
+        let last_x = ((left + size.0 as f64) / TILE_SIZE).ceil() as i64 - 1;
+        let first_y = ((top / TILE_SIZE).floor() as i64).max(0);
+        let last_y = (((top + size.1 as f64) / TILE_SIZE).ceil() as i64 - 1).min(last_row);
+
+        Self {
+            latitude,
+            longitude,
+            zoom,
+            size,
+            x_range: first_x..=last_x.max(first_x),
+            y_range: first_y..=last_y.max(first_y),
+            offset: (
+                first_x as f64 * TILE_SIZE - left,
+                first_y as f64 * TILE_SIZE - top,
+            ),
+        }
+    }
+
+    /// The tiles covering the viewport, row by row.
+    pub fn tiles(&self) -> impl Iterator<Item = Tile> + '_ {
+        let columns = 1i64 << self.zoom;
+        self.y_range.clone().flat_map(move |y| {
+            self.x_range.clone().map(move |x| Tile {
+                x: x.rem_euclid(columns) as u32,
+                y: y as u32,
+                z: self.zoom,
+                left: self.offset.0 + (x - self.x_range.start()) as f64 * TILE_SIZE,
+                top: self.offset.1 + (y - self.y_range.start()) as f64 * TILE_SIZE,
+            })
+        })
+    }
+
+    /// Where a position is in the viewport, relative to its top left corner, in pixels.
+    ///
+    /// This is useful for placing markers. The result is outside of the viewport if the position isn't visible.
+    pub fn position_of(&self, latitude: f64, longitude: f64) -> (f64, f64) {
+        let (center_x, center_y) = world_pixel(self.latitude, self.longitude, self.zoom);
+        let (x, y) = world_pixel(latitude, longitude, self.zoom);
+        (
+            x - center_x + self.size.0 as f64 / 2.0,
+            y - center_y + self.size.1 as f64 / 2.0,
+        )
+    }
+}
+
+/// The position in pixels on the whole map at a zoom level.
+fn world_pixel(latitude: f64, longitude: f64, zoom: u8) -> (f64, f64) {
+    let world_size = TILE_SIZE * 2f64.powi(zoom.into());
+    let latitude = latitude.clamp(-MAX_LATITUDE, MAX_LATITUDE).to_radians();
+
+    let x = (longitude + 180.0) / 360.0 * world_size;
+    let y = (1.0 - (latitude.tan() + 1.0 / latitude.cos()).ln() / PI) / 2.0 * world_size;
+    (x, y)
+}
+
+/// A hook that computes the map tiles needed to show the area around the user's position.
+///
+/// `zoom` is the zoom level of the map and `size` the width and height of the map in pixels. The viewport is `None`
+/// until the first position arrives.
+///
+/// Requires [`init_geolocator`](super::init_geolocator) to be called first.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::geolocation::use_map_viewport;
+///
+/// fn Map() -> Element {
+///     let zoom = use_signal(|| 15u8);
+///     let viewport = use_map_viewport(zoom, use_signal(|| (800u32, 600u32)));
+///     let Some(viewport) = viewport() else {
+///         return rsx! { p { "Locating..." } };
+///     };
+///
+///     rsx! {
+///         div { style: "position: relative; overflow: hidden; width: 800px; height: 600px;",
+///             for tile in viewport.tiles() {
+///                 img {
+///                     style: "position: absolute; left: {tile.left}px; top: {tile.top}px;",
+///                     src: "https://tile.openstreetmap.org/{tile.z}/{tile.x}/{tile.y}.png",
+///                 }
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_map_viewport(
+    zoom: impl Into<ReadOnlySignal<u8>>,
+    size: impl Into<ReadOnlySignal<(u32, u32)>>,
+) -> Memo<Option<MapViewport>> {
+    let zoom = use_hook(|| zoom.into());
+    let size = use_hook(|| size.into());
+    let position = use_geolocation();
+
+    use_memo(move || {
+        let position = position.read();
+        let position = position.as_ref().ok()?;
+        Some(MapViewport::new(
+            position.latitude,
+            position.longitude,
+            zoom(),
+            size(),
+        ))
+    })
+}
+
+#[test]
+fn test_viewport_tiles() {
+    // The whole world is a single tile at zoom 0.
+    let viewport = MapViewport::new(0.0, 0.0, 0, (256, 256));
+    assert_eq!(
+        (viewport.x_range.clone(), viewport.y_range.clone()),
+        (0..=0, 0..=0)
+    );
+    assert_eq!(viewport.offset, (0.0, 0.0));
+    assert_eq!(viewport.tiles().count(), 1);
+
+    // A viewport crossing the antimeridian wraps around to the first column.
+    let viewport = MapViewport::new(0.0, 180.0, 1, (256, 256));
+    let columns: Vec<_> = viewport.tiles().map(|tile| tile.x).collect();
+    assert_eq!(columns, vec![1, 0, 1, 0]);
+    assert_eq!(viewport.position_of(0.0, 180.0), (128.0, 128.0));
+
+    // Zoom levels past the tiles of the map are lowered instead of overflowing.
+    let viewport = MapViewport::new(0.0, 0.0, 200, (256, 256));
+    assert_eq!(viewport.zoom, MAX_ZOOM);
+    assert!(viewport.tiles().all(|tile| tile.z == MAX_ZOOM));
+}
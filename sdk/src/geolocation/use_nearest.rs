@@ -0,0 +1,126 @@
+//! Provides a hook for sorting items by their distance from the user.
+
+use super::{core::Geocoordinates, use_geolocation::use_geolocation};
+use dioxus::prelude::*;
+
+/// Options for [`use_nearest_with_options`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct NearestOptions {
+    /// Leave out items that are further away than this many meters.
+    pub max_distance: Option<f64>,
+    /// Only keep this many of the nearest items.
+    pub limit: Option<usize>,
+    /// How many meters the user has to move before the items are sorted again.
+    ///
+    /// Positions jitter by a few meters even while the device is still, so this avoids re-sorting large lists for nothing.
+    pub recompute_distance: f64,
+}
+
+impl Default for NearestOptions {
+    fn default() -> Self {
+        Self {
+            max_distance: None,
+            limit: None,
+            recompute_distance: 25.0,
+        }
+    }
+}
+
+/// An item and its distance from the user.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Nearby<T> {
+    pub item: T,
+    /// The distance in meters.
+    pub distance: f64,
+}
+
+/// A hook that sorts items by their distance from the user's position, nearest first.
+///
+/// `coordinates` returns the latitude and longitude of an item. The list is sorted again whenever the items change
+/// or the user moves more than 25 meters. It is empty until the first position arrives.
+///
+/// Requires [`init_geolocator`](super::init_geolocator) to be called first.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::geolocation::use_nearest;
+///
+/// #[derive(Clone, PartialEq)]
+/// struct Store {
+///     name: String,
+///     latitude: f64,
+///     longitude: f64,
+/// }
+///
+/// #[component]
+/// fn StoreList(stores: ReadOnlySignal<Vec<Store>>) -> Element {
+///     let nearest = use_nearest(stores, |store| (store.latitude, store.longitude));
+///
+///     rsx! {
+///         for nearby in nearest.read().iter() {
+///             p { "{nearby.item.name}: {nearby.distance / 1000.0:.1} km" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_nearest<T: Clone + PartialEq + 'static>(
+    items: impl Into<ReadOnlySignal<Vec<T>>>,
+    coordinates: impl Fn(&T) -> (f64, f64) + 'static,
+) -> Memo<Vec<Nearby<T>>> {
+    use_nearest_with_options(items, coordinates, NearestOptions::default())
+}
+
+/// A hook like [`use_nearest`] that can limit the items and configure how often they are sorted again.
+pub fn use_nearest_with_options<T: Clone + PartialEq + 'static>(
+    items: impl Into<ReadOnlySignal<Vec<T>>>,
+    coordinates: impl Fn(&T) -> (f64, f64) + 'static,
+    options: NearestOptions,
+) -> Memo<Vec<Nearby<T>>> {
+    let items = use_hook(|| items.into());
+    let position = use_geolocation();
+
+    // The position the items were last sorted for. It only changes once the user has moved far enough.
+    let mut origin: Signal<Option<Geocoordinates>> = use_signal(|| None);
+    let recompute_distance = options.recompute_distance;
+    use_effect(move || {
+        if let Ok(position) = &*position.read() {
+            let moved = origin.peek().as_ref().map_or(true, |origin| {
+                origin.distance_to(position.latitude, position.longitude) >= recompute_distance
+            });
+            if moved {
+                origin.set(Some(position.clone()));
+            }
+        }
+    });
+
+    use_memo(move || {
+        let Some(origin) = &*origin.read() else {
+            return Vec::new();
+        };
+
+        let mut nearest: Vec<Nearby<T>> = items
+            .read()
+            .iter()
+            .map(|item| {
+                let (latitude, longitude) = coordinates(item);
+                Nearby {
+                    item: item.clone(),
+                    distance: origin.distance_to(latitude, longitude),
+                }
+            })
+            .filter(|nearby| {
+                options
+                    .max_distance
+                    .map_or(true, |max_distance| nearby.distance <= max_distance)
+            })
+            .collect();
+
+        nearest.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        if let Some(limit) = options.limit {
+            nearest.truncate(limit);
+        }
+        nearest
+    })
+}
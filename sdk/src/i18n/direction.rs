@@ -0,0 +1,97 @@
+use dioxus::prelude::*;
+use std::fmt;
+use unic_langid::{CharacterDirection, LanguageIdentifier};
+
+use super::use_i18n::{use_i18, UseI18};
+
+/// The direction text is written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextDirection {
+    /// Left to right, like English.
+    #[default]
+    Ltr,
+    /// Right to left, like Arabic or Hebrew.
+    Rtl,
+}
+
+impl TextDirection {
+    /// The direction of a language, taking its script into account.
+    pub fn of(language: &LanguageIdentifier) -> Self {
+        if matches!(language.character_direction(), CharacterDirection::RTL) {
+            Self::Rtl
+        } else {
+            Self::Ltr
+        }
+    }
+
+    /// The value of the `dir` attribute for this direction.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ltr => "ltr",
+            Self::Rtl => "rtl",
+        }
+    }
+}
+
+impl fmt::Display for TextDirection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl UseI18 {
+    /// The direction of the selected language.
+    ///
+    /// Reads the selected language, so the component renders again when the direction may have changed.
+    pub fn direction(&self) -> TextDirection {
+        TextDirection::of(&self.selected_language.read())
+    }
+}
+
+/// A hook for the direction of the selected language.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::i18n::use_text_direction;
+///
+/// fn Sidebar() -> Element {
+///     let direction = use_text_direction();
+///
+///     rsx! {
+///         div { dir: "{direction}", "..." }
+///     }
+/// }
+/// ```
+pub fn use_text_direction() -> ReadOnlySignal<TextDirection> {
+    let i18 = use_i18();
+    use_memo(move || i18.direction()).into()
+}
+
+/// A hook that sets the `dir` attribute of the document root to the direction of the selected language, and updates
+/// it when the language changes.
+///
+/// Call it once, near the root of the app, after [`use_init_i18n`](super::use_init_i18n).
+pub fn use_document_direction() -> ReadOnlySignal<TextDirection> {
+    let direction = use_text_direction();
+    use_effect(move || {
+        let direction = direction();
+        eval(&format!(
+            "document.documentElement.dir = \"{}\";",
+            direction.as_str()
+        ));
+    });
+    direction
+}
+
+#[test]
+fn test_direction_of_language() {
+    let direction = |id: &str| TextDirection::of(&id.parse().unwrap());
+    assert_eq!(direction("en-US"), TextDirection::Ltr);
+    assert_eq!(direction("ar"), TextDirection::Rtl);
+    assert_eq!(direction("he-IL"), TextDirection::Rtl);
+    // Azerbaijani is written right to left in the Arabic script.
+    assert_eq!(direction("az-Arab"), TextDirection::Rtl);
+    assert_eq!(direction("az-Latn"), TextDirection::Ltr);
+}
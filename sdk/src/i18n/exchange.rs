@@ -0,0 +1,486 @@
+//! Exchange translations with translators as XLIFF or CSV files.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+};
+use unic_langid::LanguageIdentifier;
+
+use super::use_i18n::{Language, UseI18};
+
+/// The file formats translations can be exchanged in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranslationFormat {
+    /// XLIFF 1.2, which most translation tools and services can open.
+    Xliff,
+    /// A CSV file with the columns `key`, the source language, and the target language, for spreadsheets.
+    Csv,
+}
+
+/// Represents errors when importing translation files.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TranslationFileError {
+    /// The file isn't valid XLIFF or CSV.
+    Malformed(String),
+    /// The file doesn't say which language it translates to.
+    MissingLanguage,
+    /// The language of the file isn't a valid language identifier.
+    InvalidLanguage(String),
+}
+
+impl std::error::Error for TranslationFileError {}
+impl fmt::Display for TranslationFileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TranslationFileError::Malformed(s) => write!(f, "malformed translation file: {}", s),
+            TranslationFileError::MissingLanguage => {
+                write!(f, "the translation file has no target language")
+            }
+            TranslationFileError::InvalidLanguage(s) => write!(f, "invalid language: {}", s),
+        }
+    }
+}
+
+/// A text to translate, with its translation if it has one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranslationUnit {
+    /// The dotted path of the text, like `hello.greeting`.
+    pub key: String,
+    /// The text in the source language.
+    pub source: String,
+    /// The text in the target language.
+    pub target: Option<String>,
+}
+
+impl UseI18 {
+    /// Get every text of the fallback language with its translation in `target`.
+    ///
+    /// This merges the global bundle with the namespace bundles, whose keys are prefixed with their namespace like
+    /// `checkout.total`. Those are the keys [`use_i18n_namespace`](super::use_i18n_namespace) falls back to, so the
+    /// translations can be imported into the global bundle.
+    pub fn translation_units(&self, target: &LanguageIdentifier) -> Vec<TranslationUnit> {
+        let data = self.data.read();
+        let texts_of = |languages: &[Language], id: &LanguageIdentifier| {
+            languages
+                .iter()
+                .find(|language| language.id() == id)
+                .map(|language| language.texts().into_iter().collect::<HashMap<_, _>>())
+                .unwrap_or_default()
+        };
+
+        let global_targets = texts_of(&data.languages, target);
+        let mut units = BTreeMap::new();
+        for (key, source) in texts_of(&data.languages, &data.fallback_language) {
+            let target = global_targets.get(&key).cloned();
+            units.insert(
+                key.clone(),
+                TranslationUnit {
+                    key,
+                    source,
+                    target,
+                },
+            );
+        }
+
+        // Namespace bundles are looked up before the global bundle, so their texts win.
+        for (namespace, bundle) in &data.namespaces {
+            let targets = texts_of(bundle, target);
+            for (key, source) in texts_of(bundle, &data.fallback_language) {
+                let namespaced_key = format!("{namespace}.{key}");
+                let target = targets
+                    .get(&key)
+                    .or_else(|| global_targets.get(&namespaced_key))
+                    .cloned();
+                units.insert(
+                    namespaced_key.clone(),
+                    TranslationUnit {
+                        key: namespaced_key,
+                        source,
+                        target,
+                    },
+                );
+            }
+        }
+
+        units.into_values().collect()
+    }
+
+    /// Export every text of the fallback language with its translation in `target`, for sending to translators.
+    ///
+    /// See [`UseI18::translation_units`] for which texts are exported.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use dioxus::prelude::*;
+    /// use dioxus_sdk::i18n::{use_i18, TranslationFormat};
+    ///
+    /// fn ExportButton() -> Element {
+    ///     let i18 = use_i18();
+    ///
+    ///     rsx! {
+    ///         button {
+    ///             onclick: move |_| {
+    ///                 let french = "fr-FR".parse().unwrap();
+    ///                 let xliff = i18.export_translations(&french, TranslationFormat::Xliff);
+    ///                 std::fs::write("fr-FR.xlf", xliff).ok();
+    ///             },
+    ///             "Export French translations"
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn export_translations(
+        &self,
+        target: &LanguageIdentifier,
+        format: TranslationFormat,
+    ) -> String {
+        let source = self.data.read().fallback_language.clone();
+        let units = self.translation_units(target);
+        match format {
+            TranslationFormat::Xliff => export_xliff(&source, target, &units),
+            TranslationFormat::Csv => export_csv(&source, target, &units),
+        }
+    }
+
+    /// Import a file translated by translators, adding its translations to the global bundle.
+    ///
+    /// Texts that weren't translated are skipped, so they keep falling back to the fallback language. Returns the
+    /// language of the file. The file can be fetched in the background and imported once it arrives, on top of the
+    /// languages passed to [`use_init_i18n`](super::use_init_i18n).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use dioxus::prelude::*;
+    /// use dioxus_sdk::i18n::{use_i18, TranslationFormat};
+    ///
+    /// fn Translations() -> Element {
+    ///     let mut i18 = use_i18();
+    ///
+    ///     use_future(move || async move {
+    ///         let xliff = fetch_text("/locales/fr-FR.xlf").await;
+    ///         if let Err(error) = i18.import_translations(TranslationFormat::Xliff, &xliff) {
+    ///             tracing::warn!("Failed to import the French translations: {error}");
+    ///         }
+    ///     });
+    ///
+    ///     rsx! { Body {} }
+    /// }
+    /// ```
+    pub fn import_translations(
+        &mut self,
+        format: TranslationFormat,
+        contents: &str,
+    ) -> Result<LanguageIdentifier, TranslationFileError> {
+        let language = match format {
+            TranslationFormat::Xliff => import_xliff(contents)?,
+            TranslationFormat::Csv => import_csv(contents)?,
+        };
+        let id = language.id().clone();
+        self.merge_language(language);
+        Ok(id)
+    }
+
+    /// Add the texts of a language to the global bundle, replacing the texts at the same paths.
+    pub fn merge_language(&mut self, language: Language) {
+        let mut data = self.data.write();
+        let languages = &mut data.languages;
+        match languages.iter_mut().find(|l| l.id() == language.id()) {
+            Some(existing) => existing.merge(language),
+            None => languages.push(language),
+        }
+    }
+}
+
+/// Write translation units as an XLIFF 1.2 file.
+///
+/// Units without a target are written without a `<target>` element, for the translators to fill in.
+pub fn export_xliff(
+    source: &LanguageIdentifier,
+    target: &LanguageIdentifier,
+    units: &[TranslationUnit],
+) -> String {
+    let mut xliff = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <xliff version=\"1.2\" xmlns=\"urn:oasis:names:tc:xliff:document:1.2\">\n",
+    );
+    xliff.push_str(&format!(
+        "  <file original=\"dioxus-i18n\" datatype=\"plaintext\" source-language=\"{source}\" target-language=\"{target}\">\n    <body>\n"
+    ));
+    for unit in units {
+        xliff.push_str(&format!(
+            "      <trans-unit id=\"{}\">\n        <source>{}</source>\n",
+            escape_xml(&unit.key),
+            escape_xml(&unit.source)
+        ));
+        if let Some(target) = &unit.target {
+            xliff.push_str(&format!(
+                "        <target>{}</target>\n",
+                escape_xml(target)
+            ));
+        }
+        xliff.push_str("      </trans-unit>\n");
+    }
+    xliff.push_str("    </body>\n  </file>\n</xliff>\n");
+    xliff
+}
+
+/// Read the translations of an XLIFF 1.2 file into a [`Language`].
+///
+/// The language is the `target-language` of the first `<file>`. Units without a target, or with an empty one, are
+/// skipped.
+pub fn import_xliff(xliff: &str) -> Result<Language, TranslationFileError> {
+    let file = next_element(xliff, "file")
+        .ok_or_else(|| TranslationFileError::Malformed("no <file> element".to_string()))?;
+    let id = attribute(file.attributes, "target-language")
+        .ok_or(TranslationFileError::MissingLanguage)?;
+    let mut language = Language::new(parse_language(&id)?);
+
+    let mut rest = file.content;
+    while let Some(unit) = next_element(rest, "trans-unit") {
+        rest = unit.rest;
+        let key = attribute(unit.attributes, "id").ok_or_else(|| {
+            TranslationFileError::Malformed("a <trans-unit> has no id".to_string())
+        })?;
+        if let Some(target) = next_element(unit.content, "target") {
+            if !target.content.is_empty() {
+                language.set_text(&key, unescape_xml(target.content));
+            }
+        }
+    }
+    Ok(language)
+}
+
+/// Write translation units as a CSV file.
+///
+/// The header is `key`, the source language, and the target language, so the file says what it translates.
+pub fn export_csv(
+    source: &LanguageIdentifier,
+    target: &LanguageIdentifier,
+    units: &[TranslationUnit],
+) -> String {
+    let mut csv = format!("key,{source},{target}\r\n");
+    for unit in units {
+        let target = unit.target.as_deref().unwrap_or_default();
+        csv.push_str(&format!(
+            "{},{},{}\r\n",
+            escape_csv(&unit.key),
+            escape_csv(&unit.source),
+            escape_csv(target)
+        ));
+    }
+    csv
+}
+
+/// Read the translations of a CSV file written by [`export_csv`] into a [`Language`].
+///
+/// The language is the third column of the header. Rows with an empty translation are skipped.
+pub fn import_csv(csv: &str) -> Result<Language, TranslationFileError> {
+    let mut rows = parse_csv(csv)?.into_iter();
+    let header = rows
+        .next()
+        .ok_or_else(|| TranslationFileError::Malformed("the file is empty".to_string()))?;
+    let id = header
+        .get(2)
+        .filter(|id| !id.is_empty())
+        .ok_or(TranslationFileError::MissingLanguage)?;
+    let mut language = Language::new(parse_language(id)?);
+
+    for row in rows {
+        match row.as_slice() {
+            [key, _, target, ..] if !target.is_empty() => language.set_text(key, target.clone()),
+            // Spreadsheets like to end files with empty rows
+            [key] if key.is_empty() => {}
+            [_, _, _, ..] => {}
+            _ => {
+                return Err(TranslationFileError::Malformed(
+                    "a row has less than three columns".to_string(),
+                ))
+            }
+        }
+    }
+    Ok(language)
+}
+
+fn parse_language(id: &str) -> Result<LanguageIdentifier, TranslationFileError> {
+    id.parse()
+        .map_err(|_| TranslationFileError::InvalidLanguage(id.to_string()))
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// An element found by [`next_element`].
+struct Element<'a> {
+    attributes: &'a str,
+    content: &'a str,
+    /// The XML after the element.
+    rest: &'a str,
+}
+
+/// Find the next `<name>` element. This only understands the parts of XML that XLIFF files use.
+fn next_element<'a>(xml: &'a str, name: &str) -> Option<Element<'a>> {
+    let open = format!("<{name}");
+    let close = format!("</{name}>");
+    let mut xml = xml;
+    loop {
+        let start = xml.find(&open)?;
+        xml = &xml[start + open.len()..];
+        // Skip elements that only start with the name, like `<target-x>` when looking for `<target>`
+        if !xml.starts_with(|c: char| c.is_whitespace() || c == '>' || c == '/') {
+            continue;
+        }
+
+        let end = xml.find('>')?;
+        let attributes = &xml[..end];
+        let body = &xml[end + 1..];
+        if let Some(attributes) = attributes.strip_suffix('/') {
+            return Some(Element {
+                attributes,
+                content: "",
+                rest: body,
+            });
+        }
+
+        let content_end = body.find(&close)?;
+        return Some(Element {
+            attributes,
+            content: &body[..content_end],
+            rest: &body[content_end + close.len()..],
+        });
+    }
+}
+
+/// Get the value of an attribute of an element.
+fn attribute(attributes: &str, name: &str) -> Option<String> {
+    let mut rest = attributes;
+    while let Some(start) = rest.find(name) {
+        let before = &rest[..start];
+        rest = &rest[start + name.len()..];
+        if !before.is_empty() && !before.ends_with(char::is_whitespace) {
+            continue;
+        }
+
+        let Some(value) = rest.trim_start().strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start();
+        let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let value = &value[1..];
+        let end = value.find(quote)?;
+        return Some(unescape_xml(&value[..end]));
+    }
+    None
+}
+
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Split a CSV file into rows of fields, following RFC 4180.
+fn parse_csv(csv: &str) -> Result<Vec<Vec<String>>, TranslationFileError> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = csv.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => quoted = false,
+            (true, c) => field.push(c),
+            (false, '"') if field.is_empty() => quoted = true,
+            (false, ',') => row.push(std::mem::take(&mut field)),
+            (false, '\r') if chars.peek() == Some(&'\n') => {}
+            (false, '\n') => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            (false, c) => field.push(c),
+        }
+    }
+
+    if quoted {
+        return Err(TranslationFileError::Malformed(
+            "a quoted field isn't closed".to_string(),
+        ));
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+#[test]
+fn test_translation_files() {
+    use std::str::FromStr;
+
+    let source: LanguageIdentifier = "en-US".parse().unwrap();
+    let target: LanguageIdentifier = "fr-FR".parse().unwrap();
+    let units = vec![
+        TranslationUnit {
+            key: "hello.greeting".to_string(),
+            source: "Hi \"{name}\", <welcome> & more".to_string(),
+            target: Some("Salut \"{name}\",\nbienvenue & plus".to_string()),
+        },
+        TranslationUnit {
+            key: "hello.untranslated".to_string(),
+            source: "Not yet".to_string(),
+            target: None,
+        },
+    ];
+
+    for language in [
+        import_xliff(&export_xliff(&source, &target, &units)).unwrap(),
+        import_csv(&export_csv(&source, &target, &units)).unwrap(),
+    ] {
+        assert_eq!(language.id(), &target);
+        assert_eq!(
+            language.texts(),
+            vec![(
+                "hello.greeting".to_string(),
+                "Salut \"{name}\",\nbienvenue & plus".to_string()
+            )]
+        );
+    }
+
+    // Imported texts are merged into the nested texts of an existing language.
+    let mut language = Language::from_str(
+        r#"{ "id": "fr-FR", "texts": { "hello": { "bye": "Au revoir" }, "title": "Titre" } }"#,
+    )
+    .unwrap();
+    language.merge(import_csv("key,en-US,fr-FR\r\nhello.greeting,Hi,Salut\r\n").unwrap());
+    assert_eq!(language.texts().len(), 3);
+    assert_eq!(
+        language.find_text("hello.greeting", &HashMap::new()),
+        Some("Salut".to_string())
+    );
+
+    assert_eq!(
+        import_csv("key,en-US\r\n").unwrap_err(),
+        TranslationFileError::MissingLanguage
+    );
+}
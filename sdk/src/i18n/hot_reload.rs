@@ -0,0 +1,179 @@
+//! Reload translations while developing.
+
+use dioxus::prelude::*;
+use std::{path::PathBuf, str::FromStr};
+
+use super::use_i18n::{use_i18, Language, UseI18};
+
+impl UseI18 {
+    /// Replace the translations of a language in place, updating all text that uses it.
+    ///
+    /// If `namespace` is given, the language is replaced in that namespace's bundle instead of the global bundle.
+    /// This can be called from a custom hot-reload integration, like a dev server websocket on the web.
+    pub fn reload_language(&mut self, namespace: Option<&str>, language: Language) {
+        let mut data = self.data.write();
+        let languages = match namespace {
+            Some(namespace) => data.namespaces.entry(namespace.to_string()).or_default(),
+            None => &mut data.languages,
+        };
+
+        match languages.iter_mut().find(|l| l.id() == language.id()) {
+            Some(existing) => *existing = language,
+            None => languages.push(language),
+        }
+    }
+}
+
+/// Reload the language files in a directory whenever they change.
+///
+/// Every `.json` file directly inside `dir` is parsed as a [`Language`] of the global bundle.
+/// Files inside a subdirectory are loaded into the namespace with the same name as the subdirectory,
+/// such as `locales/checkout/en-US.json` for [`use_i18n_namespace("checkout")`](super::use_i18n_namespace).
+///
+/// The directory is watched on desktop. On the web, `dir` is the URL the language files are served from, like
+/// `/locales`. The web can't list a directory, so the files of the languages that are already loaded, named after
+/// their id, are fetched again every second. Other hot-reload integrations can call [`UseI18::reload_language`].
+///
+/// This should only be enabled during development. Call it once, below [`use_init_i18n`](super::use_init_i18n).
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::i18n::use_i18n_hot_reload;
+///
+/// fn Translations() -> Element {
+///     use_i18n_hot_reload(concat!(env!("CARGO_MANIFEST_DIR"), "/locales"));
+///     rsx! { Body {} }
+/// }
+/// # fn Body() -> Element { None }
+/// ```
+#[cfg(not(target_family = "wasm"))]
+pub fn use_i18n_hot_reload(dir: impl Into<PathBuf>) {
+    use crate::utils::fs_watch::{use_file_watcher, FileChangeKind};
+    use std::ffi::OsStr;
+
+    let i18 = use_i18();
+    // Changed paths are reported in their canonical form.
+    let dir = use_hook(|| {
+        let dir = dir.into();
+        std::fs::canonicalize(&dir).unwrap_or(dir)
+    });
+    let watcher = use_file_watcher(dir.clone());
+
+    use_effect(move || {
+        if let Some(error) = &*watcher.error().read() {
+            tracing::warn!("Failed to watch the language files: {error}");
+        }
+    });
+
+    use_effect(move || {
+        let mut files = Vec::new();
+        for change in watcher.changes().read().iter() {
+            if change.kind == FileChangeKind::Removed {
+                continue;
+            }
+
+            for path in &change.paths {
+                if path.extension() != Some(OsStr::new("json")) {
+                    continue;
+                }
+
+                let namespace = path
+                    .parent()
+                    .filter(|parent| *parent != dir)
+                    .and_then(|parent| parent.file_name())
+                    .map(|name| name.to_string_lossy().into_owned());
+                files.push((namespace, path.clone()));
+            }
+        }
+
+        // Language files can be large, so they aren't read on the UI thread.
+        let mut i18 = i18;
+        spawn(async move {
+            for (namespace, path) in files {
+                let Ok(contents) = tokio::fs::read_to_string(&path).await else {
+                    continue;
+                };
+                match Language::from_str(&contents) {
+                    Ok(language) => i18.reload_language(namespace.as_deref(), language),
+                    Err(_) => tracing::warn!("Failed to parse the language file {path:?}"),
+                }
+            }
+        });
+    });
+}
+
+#[cfg(target_family = "wasm")]
+pub fn use_i18n_hot_reload(dir: impl Into<PathBuf>) {
+    let i18 = use_i18();
+    use_hook(|| {
+        let dir: PathBuf = dir.into();
+        let base = dir.to_string_lossy().trim_end_matches('/').to_string();
+        spawn(poll_language_files(base, i18));
+    });
+}
+
+/// Fetch the files of the loaded languages every second, reloading the ones that changed.
+#[cfg(target_family = "wasm")]
+async fn poll_language_files(base: String, mut i18: UseI18) {
+    use crate::utils::timing::clock;
+    use std::{collections::HashMap, time::Duration};
+
+    let mut fetched: HashMap<String, String> = HashMap::new();
+    loop {
+        let files: Vec<(Option<String>, String)> = {
+            let data = i18.data.peek();
+            let global = data
+                .languages
+                .iter()
+                .map(|language| (None, format!("{base}/{}.json", language.id())));
+            let namespaced = data.namespaces.iter().flat_map(|(namespace, languages)| {
+                languages.iter().map(|language| {
+                    let url = format!("{base}/{namespace}/{}.json", language.id());
+                    (Some(namespace.clone()), url)
+                })
+            });
+            global.chain(namespaced).collect()
+        };
+
+        for (namespace, url) in files {
+            let Some(contents) = fetch_text(&url).await else {
+                continue;
+            };
+            let previous = fetched.insert(url.clone(), contents.clone());
+            // The first fetch is what the app already loaded.
+            if previous.is_none() || previous.as_ref() == Some(&contents) {
+                continue;
+            }
+            match Language::from_str(&contents) {
+                Ok(language) => i18.reload_language(namespace.as_deref(), language),
+                Err(_) => tracing::warn!("Failed to parse the language file {url}"),
+            }
+        }
+
+        clock::sleep(Duration::from_secs(1)).await;
+    }
+}
+
+/// Fetch a file, skipping the cache so edits show up.
+#[cfg(target_family = "wasm")]
+async fn fetch_text(url: &str) -> Option<String> {
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_futures::JsFuture;
+    use web_sys::{Request, RequestCache, RequestInit, Response};
+
+    let init = RequestInit::new();
+    init.set_cache(RequestCache::NoStore);
+    let request = Request::new_with_str_and_init(url, &init).ok()?;
+    let response: Response = JsFuture::from(web_sys::window()?.fetch_with_request(&request))
+        .await
+        .ok()?
+        .dyn_into()
+        .ok()?;
+    if !response.ok() {
+        return None;
+    }
+    JsFuture::from(response.text().ok()?)
+        .await
+        .ok()?
+        .as_string()
+}
@@ -1,8 +1,18 @@
 //! Provide translations for your app.
 
+mod direction;
+mod exchange;
+#[cfg(feature = "hot-reload")]
+mod hot_reload;
 mod tanslate;
 mod use_i18n;
+mod use_i18n_namespace;
 mod use_init_i18n;
 
+pub use self::direction::*;
+pub use self::exchange::*;
+#[cfg(feature = "hot-reload")]
+pub use self::hot_reload::*;
 pub use self::use_i18n::*;
+pub use self::use_i18n_namespace::*;
 pub use self::use_init_i18n::*;
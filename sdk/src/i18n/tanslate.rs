@@ -1,18 +1,48 @@
+/// Translate a key with the selected language, like [`UseI18::translate`](crate::i18n::UseI18::translate).
+///
+/// Parameters can be any [`Display`](std::fmt::Display) type. A parameter without a value uses the variable with
+/// the same name. A context, like a grammatical gender or a level of formality, can be passed after an `@`, and
+/// selects the `key@context` variant of the text if the language has one.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::i18n::use_i18;
+/// use dioxus_sdk::translate;
+///
+/// #[component]
+/// fn Greeting(name: String, count: u32, formal: bool) -> Element {
+///     let i18 = use_i18();
+///     let tone = if formal { "formal" } else { "casual" };
+///
+///     rsx! {
+///         // "hello.greeting@formal": "Good day, {name}", "hello.greeting": "Hi {name}"
+///         p { {translate!(i18, "hello.greeting", @tone, name)} }
+///         p { {translate!(i18, "hello.unread", count: count, total: 2 * count)} }
+///     }
+/// }
+/// ```
 #[macro_export]
 macro_rules! translate {
-    ( $i18:expr, $id:expr, $( $name:ident : $value:expr ),* ) => {
+    (@value $name:ident : $value:expr) => {
+        $value.to_string()
+    };
+
+    (@value $name:ident) => {
+        $name.to_string()
+    };
+
+    ( $i18:expr, $id:expr, @ $context:expr $(, $name:ident $( : $value:expr )? )* $(,)? ) => {
+        $crate::translate!($i18, format!("{}@{}", $id, $context).as_str() $(, $name $( : $value )? )*)
+    };
+
+    ( $i18:expr, $id:expr $(, $name:ident $( : $value:expr )? )* $(,)? ) => {
         {
-            let mut params_map = std::collections::HashMap::new();
+            #[allow(unused_mut)]
+            let mut params_map: std::collections::HashMap<&str, String> = std::collections::HashMap::new();
             $(
-                params_map.insert(stringify!($name), $value.to_string());
+                params_map.insert(stringify!($name), $crate::translate!(@value $name $( : $value )?));
             )*
             $i18.translate_with_params($id, params_map)
         }
     };
-
-    ( $i18:expr, $id:expr ) => {
-        {
-            $i18.translate($id)
-        }
-    };
 }
@@ -51,6 +51,49 @@ impl Text {
             Text::Value(value) => Some(value.to_owned()),
         }
     }
+
+    /// Set the text at a dotted path, creating the groups along it.
+    fn insert(&mut self, path: &str, value: String) {
+        if let Text::Value(_) = self {
+            *self = Text::default();
+        }
+        let Text::Texts(texts) = self else {
+            return;
+        };
+
+        match path.split_once('.') {
+            Some((step, rest)) => match texts.get_mut(step) {
+                Some(deep @ Text::Texts(_)) => deep.insert(rest, value),
+                // A text is in the way of the group, so use the whole path as the ID instead
+                Some(Text::Value(_)) => {
+                    texts.insert(path.to_string(), Text::Value(value));
+                }
+                None => texts
+                    .entry(step.to_string())
+                    .or_default()
+                    .insert(rest, value),
+            },
+            None => {
+                texts.insert(path.to_string(), Text::Value(value));
+            }
+        }
+    }
+
+    /// Collect every text below this one with its dotted path.
+    fn flatten(&self, path: &str, entries: &mut Vec<(String, String)>) {
+        match self {
+            Text::Texts(texts) => {
+                for (step, text) in texts {
+                    let path = match path {
+                        "" => step.clone(),
+                        _ => format!("{path}.{step}"),
+                    };
+                    text.flatten(&path, entries);
+                }
+            }
+            Text::Value(value) => entries.push((path.to_string(), value.clone())),
+        }
+    }
 }
 
 impl FromStr for Language {
@@ -61,16 +104,108 @@ impl FromStr for Language {
     }
 }
 
+/// How deeply parameters referencing other parameters are resolved, to stop cycles.
+const MAX_PARAM_DEPTH: usize = 8;
+
 impl Language {
+    /// Create a language without any texts.
+    pub fn new(id: LanguageIdentifier) -> Self {
+        Self {
+            id,
+            texts: Text::default(),
+        }
+    }
+
+    pub fn id(&self) -> &LanguageIdentifier {
+        &self.id
+    }
+
+    /// Every text of this language by its dotted path, sorted by path.
+    pub fn texts(&self) -> Vec<(String, String)> {
+        let mut entries = Vec::new();
+        self.texts.flatten("", &mut entries);
+        entries.sort();
+        entries
+    }
+
+    /// Set the text at `path`, replacing the text that was there.
+    pub fn set_text(&mut self, path: &str, text: impl Into<String>) {
+        self.texts.insert(path, text.into());
+    }
+
+    /// Add the texts of `other`, replacing the texts at the same paths.
+    pub fn merge(&mut self, other: Language) {
+        for (path, text) in other.texts() {
+            self.set_text(&path, text);
+        }
+    }
+
+    /// Get the text at `path` with the `{name}` placeholders replaced by `params`.
+    ///
+    /// A path may end with a context, like `button.save@formal`. If this language has no text for the context,
+    /// the text without the context, `button.save`, is used instead.
     pub fn get_text(&self, path: &str, params: HashMap<&str, String>) -> Option<String> {
-        let mut steps = path.split('.').collect::<Vec<&str>>();
+        let text = self.query(path).unwrap_or_default();
+
+        Some(Self::apply_params(text, &params))
+    }
+
+    /// Like [`Language::get_text`] but returns `None` if the path doesn't exist in this language.
+    pub(crate) fn find_text(&self, path: &str, params: &HashMap<&str, String>) -> Option<String> {
+        let text = self.query(path)?;
+
+        Some(Self::apply_params(text, params))
+    }
+
+    fn query(&self, path: &str) -> Option<String> {
+        let query = |path: &str| {
+            let mut steps = path.split('.').collect::<Vec<&str>>();
+            self.texts.query(&mut steps)
+        };
 
-        let mut text = self.texts.query(&mut steps).unwrap_or_default();
+        query(path).or_else(|| {
+            let (base, _context) = path.rsplit_once('@')?;
+            query(base)
+        })
+    }
+
+    /// Replace every placeholder with its parameter.
+    ///
+    /// Parameters can reference other parameters, so `{count} {unit}` with `unit` set to `{count_word}` also
+    /// replaces `{count_word}`. The text is read once, so placeholders that only appear once a value is inserted,
+    /// like a user name of `{count}`, are kept as they are.
+    fn apply_params(text: String, params: &HashMap<&str, String>) -> String {
+        Self::apply_params_nested(&text, params, MAX_PARAM_DEPTH)
+    }
 
-        for (name, value) in params {
-            text = text.replacen(&format!("{{{name}}}"), &value.to_string(), 1);
+    fn apply_params_nested(text: &str, params: &HashMap<&str, String>, depth: usize) -> String {
+        let mut result = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find('{') {
+            result.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            let param = after
+                .find('}')
+                .map(|end| &after[..end])
+                .and_then(|name| Some((name, params.get(name)?)));
+
+            match param {
+                Some((name, value)) => {
+                    match depth {
+                        0 => result.push_str(value),
+                        _ => result.push_str(&Self::apply_params_nested(value, params, depth - 1)),
+                    }
+                    rest = &after[name.len() + 1..];
+                }
+                // Not a known placeholder, so the brace is kept.
+                None => {
+                    result.push('{');
+                    rest = after;
+                }
+            }
         }
-        Some(text)
+        result.push_str(rest);
+        result
     }
 }
 
@@ -122,3 +257,25 @@ pub fn use_i18() -> UseI18 {
         }
     })
 }
+
+#[test]
+fn test_apply_params() {
+    let params = HashMap::from([
+        ("count", "3".to_string()),
+        ("unit", "{count_word}".to_string()),
+        ("count_word", "items".to_string()),
+    ]);
+    let apply = |text: &str| Language::apply_params(text.to_string(), &params);
+
+    assert_eq!(apply("{count} {unit}"), "3 items");
+    assert_eq!(apply("{missing} {count}}"), "{missing} 3}");
+    // Values are inserted as they are, whatever order the parameters are replaced in.
+    let params = HashMap::from([
+        ("user", "{greeting}".to_string()),
+        ("greeting", "hi".to_string()),
+    ]);
+    assert_eq!(
+        Language::apply_params_nested("{greeting}, {user}", &params, 0),
+        "hi, {greeting}"
+    );
+}
@@ -0,0 +1,139 @@
+use dioxus::prelude::*;
+use std::collections::HashMap;
+use unic_langid::LanguageIdentifier;
+
+use super::use_i18n::{use_i18, Language, UseI18};
+
+/// Translations scoped to a namespace.
+///
+/// See [`use_i18n_namespace`] for more information.
+#[derive(Clone, PartialEq)]
+pub struct UseI18Namespace {
+    namespace: String,
+    i18: UseI18,
+}
+
+impl UseI18Namespace {
+    /// The name of this namespace.
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// Translate a key within this namespace.
+    ///
+    /// The key is looked up in the namespace bundle for the selected language and then the fallback language.
+    /// If the namespace bundle doesn't have it, the key is looked up under `<namespace>.<key>` in the global bundle,
+    /// and finally as `<key>` in the global bundle.
+    pub fn translate_with_params(&self, id: &str, params: HashMap<&str, String>) -> String {
+        let selected_language = self.i18.selected_language.read().clone();
+        let found = {
+            let i18n_data = self.i18.data.read();
+            let namespaced_id = format!("{}.{id}", self.namespace);
+            let bundle = i18n_data.namespaces.get(&self.namespace);
+
+            [&selected_language, &i18n_data.fallback_language]
+                .into_iter()
+                .find_map(|language_id| {
+                    bundle
+                        .and_then(|bundle| find_text(bundle, language_id, id, &params))
+                        .or_else(|| {
+                            find_text(&i18n_data.languages, language_id, &namespaced_id, &params)
+                        })
+                })
+        };
+
+        found.unwrap_or_else(|| self.i18.translate_with_params(id, params))
+    }
+
+    /// Translate a key within this namespace.
+    pub fn translate(&self, id: &str) -> String {
+        self.translate_with_params(id, HashMap::default())
+    }
+
+    /// Get the global translations.
+    pub fn global(&self) -> UseI18 {
+        self.i18
+    }
+}
+
+fn find_text(
+    languages: &[Language],
+    language_id: &LanguageIdentifier,
+    id: &str,
+    params: &HashMap<&str, String>,
+) -> Option<String> {
+    languages
+        .iter()
+        .find(|language| language.id() == language_id)?
+        .find_text(id, params)
+}
+
+/// Translate keys within a namespace, such as the translations owned by one feature or team.
+///
+/// Keys are looked up in the bundle registered for the namespace with [`use_i18n_namespace_bundle`],
+/// and fall back to the global bundle passed to [`use_init_i18n`](super::use_init_i18n).
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::i18n::use_i18n_namespace;
+/// use dioxus_sdk::translate;
+///
+/// #[component]
+/// fn Total(amount: f64) -> Element {
+///     let i18 = use_i18n_namespace("checkout");
+///     // Looks up "total" in the checkout bundle, then "checkout.total" and "total" in the global bundle.
+///     rsx! { p { {translate!(i18, "total", amount: amount)} } }
+/// }
+/// ```
+pub fn use_i18n_namespace(namespace: impl ToString) -> UseI18Namespace {
+    let i18 = use_i18();
+    use_hook(|| UseI18Namespace {
+        namespace: namespace.to_string(),
+        i18,
+    })
+}
+
+/// Register the translations of a namespace when the component is mounted and translate keys within it.
+///
+/// The bundle is only loaded the first time the namespace is registered, so translations for a part of the app
+/// don't need to be loaded until it is rendered. Each bundle contains one [`Language`] per supported language,
+/// with keys relative to the namespace. The bundle is added after the first render, which falls back to the global
+/// bundle, and the component renders again once it is added.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::i18n::{use_i18n_namespace_bundle, Language};
+/// use std::str::FromStr;
+///
+/// static CHECKOUT_EN: &str = r#"{ "id": "en-US", "texts": { "total": "Total: {amount}" } }"#;
+///
+/// #[component]
+/// fn Checkout() -> Element {
+///     let i18 = use_i18n_namespace_bundle("checkout", || {
+///         vec![Language::from_str(CHECKOUT_EN).unwrap()]
+///     });
+///     rsx! { h1 { {i18.translate("title")} } }
+/// }
+/// ```
+pub fn use_i18n_namespace_bundle(
+    namespace: impl ToString,
+    languages: impl FnOnce() -> Vec<Language>,
+) -> UseI18Namespace {
+    let i18 = use_i18n_namespace(namespace);
+    use_hook(|| {
+        let mut data = i18.i18.data;
+        if data.peek().namespaces.contains_key(&i18.namespace) {
+            return;
+        }
+        let namespace = i18.namespace.clone();
+        let languages = languages();
+        // Signals can't be written while rendering, so the bundle is added once the render is done.
+        spawn(async move {
+            data.write()
+                .namespaces
+                .entry(namespace)
+                .or_insert(languages);
+        });
+    });
+    i18
+}
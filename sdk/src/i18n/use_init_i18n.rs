@@ -1,4 +1,5 @@
 use dioxus::prelude::*;
+use std::collections::HashMap;
 use unic_langid::LanguageIdentifier;
 
 use super::use_i18n::Language;
@@ -6,6 +7,8 @@ use super::use_i18n::Language;
 pub struct UseInitI18Data {
     pub(crate) fallback_language: LanguageIdentifier,
     pub(crate) languages: Vec<Language>,
+    /// The bundles registered with [`use_i18n_namespace_bundle`](super::use_i18n_namespace_bundle), by namespace.
+    pub(crate) namespaces: HashMap<String, Vec<Language>>,
 }
 
 pub fn use_init_i18n(
@@ -17,6 +20,7 @@ pub fn use_init_i18n(
     let init_i18_data = use_signal(|| UseInitI18Data {
         languages: languages(),
         fallback_language,
+        namespaces: HashMap::new(),
     });
 
     provide_context(selected_language);
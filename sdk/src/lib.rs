@@ -1,7 +1,7 @@
 //#![warn(missing_debug_implementations, missing_docs)]
 
 cfg_if::cfg_if! {
-    if #[cfg(feature = "system_theme")] {
+    if #[cfg(any(feature = "system_theme", feature = "accent_color"))] {
         pub mod theme;
     }
 }
@@ -13,7 +13,84 @@ cfg_if::cfg_if! {
 }
 
 cfg_if::cfg_if! {
-    if #[cfg(any(feature = "channel", feature = "window_size", feature = "timing"))] {
+    if #[cfg(feature = "permissions")] {
+        pub mod permissions;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "ble")] {
+        pub mod ble;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "serial")] {
+        pub mod serial;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "midi")] {
+        pub mod midi;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "gamepad")] {
+        pub mod gamepad;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "push")] {
+        pub mod push;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "analytics")] {
+        pub mod analytics;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "screen_recorder")] {
+        pub mod screen_recorder;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "camera")] {
+        pub mod camera;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(any(
+        feature = "channel",
+        feature = "window_size",
+        feature = "window_controls",
+        feature = "window_effects",
+        feature = "page_visibility",
+        feature = "document_title",
+        feature = "prevent_close",
+        feature = "window_events",
+        feature = "system_idle",
+        feature = "timing",
+        feature = "sync",
+        feature = "transfer",
+        feature = "js",
+        feature = "process",
+        feature = "fs_watch",
+        feature = "intersection_observer",
+        feature = "mutation_observer",
+        feature = "connect",
+        feature = "reachability",
+        feature = "open",
+        feature = "text_metrics",
+        feature = "accent_color",
+    ))] {
         pub mod utils;
     }
 }
@@ -41,3 +118,9 @@ cfg_if::cfg_if! {
         pub mod notification;
     }
 }
+
+cfg_if::cfg_if! {
+    if #[cfg(all(target_os = "android", any(feature = "notifications", feature = "open")))] {
+        mod android;
+    }
+}
@@ -0,0 +1,166 @@
+//! The MIDI types shared by every platform.
+
+use futures::channel::mpsc::UnboundedReceiver;
+use std::{fmt, time::Duration};
+
+use super::platform;
+
+/// A MIDI input port, like a keyboard or a controller.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MidiPort {
+    /// Identifies the port while the device stays connected.
+    pub id: String,
+    /// The name of the port, as reported by the device.
+    pub name: String,
+    /// The manufacturer of the device, if it is known. Only reported on the web.
+    pub manufacturer: Option<String>,
+}
+
+/// List the MIDI input ports.
+///
+/// On the web, this asks the user for permission to use MIDI devices the first time it is called.
+pub async fn midi_inputs() -> Result<Vec<MidiPort>, MidiError> {
+    platform::inputs().await
+}
+
+/// A message received from a MIDI port.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MidiMessage {
+    /// The raw bytes of the message, starting with the status byte.
+    pub data: Vec<u8>,
+    /// When the message was received, relative to a point that is fixed for the port.
+    pub timestamp: Duration,
+}
+
+impl MidiMessage {
+    /// Decode the message. Messages other than the common channel messages are [`MidiEvent::Other`].
+    pub fn event(&self) -> MidiEvent {
+        let status = self.data.first().copied().unwrap_or_default();
+        let channel = status & 0x0f;
+        let data = |index: usize| self.data.get(index).copied().unwrap_or_default();
+        match status & 0xf0 {
+            // A note on with a velocity of zero is how many devices send note off.
+            0x90 if data(2) > 0 => MidiEvent::NoteOn {
+                channel,
+                note: data(1),
+                velocity: data(2),
+            },
+            0x80 | 0x90 => MidiEvent::NoteOff {
+                channel,
+                note: data(1),
+                velocity: data(2),
+            },
+            0xb0 => MidiEvent::ControlChange {
+                channel,
+                controller: data(1),
+                value: data(2),
+            },
+            0xc0 => MidiEvent::ProgramChange {
+                channel,
+                program: data(1),
+            },
+            0xe0 => MidiEvent::PitchBend {
+                channel,
+                value: (data(1) as u16 | (data(2) as u16) << 7) as i16 - 0x2000,
+            },
+            _ => MidiEvent::Other,
+        }
+    }
+}
+
+/// A decoded [`MidiMessage`]. Channels are numbered from 0 to 15.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MidiEvent {
+    NoteOn {
+        channel: u8,
+        note: u8,
+        velocity: u8,
+    },
+    NoteOff {
+        channel: u8,
+        note: u8,
+        velocity: u8,
+    },
+    ControlChange {
+        channel: u8,
+        controller: u8,
+        value: u8,
+    },
+    ProgramChange {
+        channel: u8,
+        program: u8,
+    },
+    /// The bend from the center, from -8192 to 8191.
+    PitchBend {
+        channel: u8,
+        value: i16,
+    },
+    /// Any other message, like system exclusive messages.
+    Other,
+}
+
+/// The messages of an open port, and what keeps it open.
+pub(crate) type Messages = (UnboundedReceiver<MidiMessage>, platform::Connection);
+
+/// Start receiving the messages of a port. The port is closed once the connection is dropped.
+pub(crate) fn open(port: &MidiPort) -> Result<Messages, MidiError> {
+    platform::open(&port.id)
+}
+
+/// Describes errors that may occur when using MIDI devices.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MidiError {
+    /// The platform doesn't support MIDI.
+    Unsupported,
+    /// The user or the system denied access to MIDI devices.
+    PermissionDenied,
+    /// The port doesn't exist anymore, usually because the device was unplugged.
+    PortNotFound,
+    /// The platform reported an error.
+    Failed(String),
+}
+
+impl std::error::Error for MidiError {}
+impl fmt::Display for MidiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Unsupported => write!(f, "MIDI is not supported on this platform"),
+            Self::PermissionDenied => write!(f, "access to MIDI devices was denied"),
+            Self::PortNotFound => write!(f, "the MIDI port was not found"),
+            Self::Failed(reason) => write!(f, "MIDI error: {reason}"),
+        }
+    }
+}
+
+#[test]
+fn test_decode_events() {
+    let message = |data: &[u8]| MidiMessage {
+        data: data.to_vec(),
+        timestamp: Duration::ZERO,
+    };
+
+    assert_eq!(
+        message(&[0x91, 60, 100]).event(),
+        MidiEvent::NoteOn {
+            channel: 1,
+            note: 60,
+            velocity: 100
+        }
+    );
+    assert_eq!(
+        message(&[0x90, 60, 0]).event(),
+        MidiEvent::NoteOff {
+            channel: 0,
+            note: 60,
+            velocity: 0
+        }
+    );
+    assert_eq!(
+        message(&[0xe0, 0x00, 0x40]).event(),
+        MidiEvent::PitchBend {
+            channel: 0,
+            value: 0
+        }
+    );
+    assert_eq!(message(&[0xf8]).event(), MidiEvent::Other);
+}
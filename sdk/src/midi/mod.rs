@@ -0,0 +1,10 @@
+//! Receive messages from MIDI devices, like keyboards and controllers.
+//!
+//! Uses Web MIDI on the web and the system MIDI ports on desktop.
+
+mod core;
+mod platform;
+mod use_midi;
+
+pub use self::core::*;
+pub use self::use_midi::*;
@@ -0,0 +1,74 @@
+use futures::channel::mpsc;
+use midir::{MidiInput, MidiInputConnection};
+use std::time::Duration;
+
+use crate::midi::{Messages, MidiError, MidiMessage, MidiPort};
+
+/// How often the list of ports is checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The name the app uses when connecting to the system MIDI service.
+const CLIENT_NAME: &str = "dioxus-sdk";
+
+/// Closes the port when dropped.
+pub struct Connection {
+    _connection: MidiInputConnection<()>,
+}
+
+pub async fn inputs() -> Result<Vec<MidiPort>, MidiError> {
+    let input = client()?;
+    Ok(input
+        .ports()
+        .iter()
+        .filter_map(|port| {
+            Some(MidiPort {
+                id: port.id(),
+                name: input.port_name(port).ok()?,
+                manufacturer: None,
+            })
+        })
+        .collect())
+}
+
+/// Wait until the ports may have changed.
+///
+/// Not every platform reports when a device is plugged in, so the ports are checked periodically.
+pub async fn wait_for_change() {
+    tokio::time::sleep(POLL_INTERVAL).await;
+}
+
+pub fn open(id: &str) -> Result<Messages, MidiError> {
+    let mut input = client()?;
+    // Timing clock and active sensing messages are sent many times per second, and are rarely useful in apps.
+    input.ignore(midir::Ignore::TimeAndActiveSense);
+    let port = input
+        .find_port_by_id(id.to_string())
+        .ok_or(MidiError::PortNotFound)?;
+
+    let (tx, rx) = mpsc::unbounded();
+    let connection = input
+        .connect(
+            &port,
+            CLIENT_NAME,
+            move |timestamp, data, _| {
+                tx.unbounded_send(MidiMessage {
+                    data: data.to_vec(),
+                    timestamp: Duration::from_micros(timestamp),
+                })
+                .ok();
+            },
+            (),
+        )
+        .map_err(|e| MidiError::Failed(e.to_string()))?;
+
+    Ok((
+        rx,
+        Connection {
+            _connection: connection,
+        },
+    ))
+}
+
+fn client() -> Result<MidiInput, MidiError> {
+    MidiInput::new(CLIENT_NAME).map_err(|e| MidiError::Failed(e.to_string()))
+}
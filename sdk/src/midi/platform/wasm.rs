@@ -0,0 +1,151 @@
+use futures::channel::mpsc;
+use js_sys::{Array, Function, Promise, Reflect, Uint8Array};
+use std::{cell::RefCell, time::Duration};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+use crate::midi::{Messages, MidiError, MidiMessage, MidiPort};
+
+thread_local! {
+    /// The `MIDIAccess` object, once the user granted access.
+    static ACCESS: RefCell<Option<JsValue>> = const { RefCell::new(None) };
+}
+
+/// Stops listening to the port and closes it when dropped.
+pub struct Connection {
+    input: JsValue,
+    _listener: Closure<dyn FnMut(JsValue)>,
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        Reflect::set(&self.input, &"onmidimessage".into(), &JsValue::NULL).ok();
+        call(&self.input, "close", &[]);
+    }
+}
+
+pub async fn inputs() -> Result<Vec<MidiPort>, MidiError> {
+    let access = access().await?;
+    Ok(input_ports(&access)
+        .iter()
+        .filter_map(|input| {
+            let text = |key: &str| get(input, key).and_then(|value| value.as_string());
+            Some(MidiPort {
+                id: text("id")?,
+                name: text("name").unwrap_or_default(),
+                manufacturer: text("manufacturer").filter(|manufacturer| !manufacturer.is_empty()),
+            })
+        })
+        .collect())
+}
+
+/// Wait until a device is plugged in or unplugged.
+pub async fn wait_for_change() {
+    let Some(access) = ACCESS.with(|access| access.borrow().clone()) else {
+        // Nothing can change until access was granted, which happens when the ports are listed.
+        return std::future::pending().await;
+    };
+    let promise = Promise::new(&mut |resolve, _| {
+        Reflect::set(&access, &"onstatechange".into(), &resolve).ok();
+    });
+    JsFuture::from(promise).await.ok();
+}
+
+pub fn open(id: &str) -> Result<Messages, MidiError> {
+    // Ports are only listed once access was granted.
+    let access = ACCESS
+        .with(|access| access.borrow().clone())
+        .ok_or(MidiError::PermissionDenied)?;
+    let input = input_ports(&access)
+        .into_iter()
+        .find(|input| get(input, "id").and_then(|id| id.as_string()).as_deref() == Some(id))
+        .ok_or(MidiError::PortNotFound)?;
+
+    let (tx, rx) = mpsc::unbounded();
+    let listener = Closure::wrap(Box::new(move |event: JsValue| {
+        let Some(data) = get(&event, "data") else {
+            return;
+        };
+        let data = Uint8Array::new(&data).to_vec();
+        // Timing clock and active sensing messages are sent many times per second, and are rarely useful in apps.
+        if matches!(data.first(), Some(0xf8 | 0xfe)) {
+            return;
+        }
+        let timestamp = get(&event, "timeStamp")
+            .and_then(|timestamp| timestamp.as_f64())
+            .unwrap_or_default();
+        tx.unbounded_send(MidiMessage {
+            data,
+            timestamp: Duration::from_secs_f64(timestamp / 1000.0),
+        })
+        .ok();
+    }) as Box<dyn FnMut(JsValue)>);
+    // Setting the listener opens the port.
+    Reflect::set(&input, &"onmidimessage".into(), listener.as_ref())
+        .map_err(|_| MidiError::Failed("failed to listen to the port".to_string()))?;
+
+    Ok((
+        rx,
+        Connection {
+            input,
+            _listener: listener,
+        },
+    ))
+}
+
+/// Get the `MIDIAccess` object, asking the user for permission the first time.
+async fn access() -> Result<JsValue, MidiError> {
+    if let Some(access) = ACCESS.with(|access| access.borrow().clone()) {
+        return Ok(access);
+    }
+
+    let navigator = get(
+        &web_sys::window().ok_or(MidiError::Unsupported)?,
+        "navigator",
+    )
+    .ok_or(MidiError::Unsupported)?;
+    let promise: Promise = call(&navigator, "requestMIDIAccess", &[])
+        .ok_or(MidiError::Unsupported)?
+        .dyn_into()
+        .map_err(|_| MidiError::Unsupported)?;
+    let access = JsFuture::from(promise).await.map_err(|e| to_error(&e))?;
+
+    ACCESS.with(|cached| *cached.borrow_mut() = Some(access.clone()));
+    Ok(access)
+}
+
+/// Get the `MIDIInput`s of the `MIDIAccess` object.
+fn input_ports(access: &JsValue) -> Vec<JsValue> {
+    get(access, "inputs")
+        .and_then(|inputs| call(&inputs, "values", &[]))
+        .map(|values| Array::from(&values).to_vec())
+        .unwrap_or_default()
+}
+
+fn to_error(error: &JsValue) -> MidiError {
+    match get(error, "name")
+        .and_then(|name| name.as_string())
+        .as_deref()
+    {
+        Some("SecurityError") | Some("NotAllowedError") => MidiError::PermissionDenied,
+        Some("NotSupportedError") => MidiError::Unsupported,
+        _ => MidiError::Failed(
+            get(error, "message")
+                .and_then(|message| message.as_string())
+                .unwrap_or_else(|| format!("{error:?}")),
+        ),
+    }
+}
+
+/// Get a property, treating `undefined` and `null` as missing.
+fn get(target: &JsValue, key: &str) -> Option<JsValue> {
+    Reflect::get(target, &key.into())
+        .ok()
+        .filter(|value| !value.is_undefined() && !value.is_null())
+}
+
+/// Call a method, returning `None` if it doesn't exist or throws.
+fn call(target: &JsValue, method: &str, args: &[JsValue]) -> Option<JsValue> {
+    let function: Function = get(target, method)?.dyn_into().ok()?;
+    function.apply(target, &args.iter().collect::<Array>()).ok()
+}
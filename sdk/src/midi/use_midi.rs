@@ -0,0 +1,152 @@
+//! Provides the use_midi_inputs and use_midi_messages hooks.
+
+use dioxus::prelude::*;
+use futures::StreamExt;
+use std::{cell::RefCell, rc::Rc};
+
+use super::core::{midi_inputs, open, MidiError, MidiMessage, MidiPort};
+use super::platform;
+
+/// The interface for the MIDI input ports.
+///
+/// See [`use_midi_inputs`] for more information.
+#[derive(Clone, Copy, PartialEq)]
+pub struct UseMidiInputs {
+    ports: Signal<Vec<MidiPort>>,
+    error: Signal<Option<MidiError>>,
+}
+
+impl UseMidiInputs {
+    /// The input ports of the connected devices.
+    pub fn ports(&self) -> ReadOnlySignal<Vec<MidiPort>> {
+        ReadOnlySignal::new(self.ports)
+    }
+
+    /// The error from the last time the ports were listed, if any. On the web, this is
+    /// [`MidiError::PermissionDenied`] if the user denied access.
+    pub fn error(&self) -> ReadOnlySignal<Option<MidiError>> {
+        ReadOnlySignal::new(self.error)
+    }
+
+    /// List the ports again.
+    pub fn refresh(&self) {
+        let this = *self;
+        spawn(async move { this.update().await });
+    }
+
+    async fn update(mut self) {
+        match midi_inputs().await {
+            Ok(ports) => {
+                if *self.ports.peek() != ports {
+                    self.ports.set(ports);
+                }
+                self.error.set(None);
+            }
+            Err(e) => self.error.set(Some(e)),
+        }
+    }
+}
+
+/// A hook for the MIDI input ports of the connected devices.
+///
+/// The list is updated when devices are plugged in or unplugged. On the web, the user is asked for permission to use
+/// MIDI devices when the hook is first used.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::midi::use_midi_inputs;
+///
+/// fn App() -> Element {
+///     let midi = use_midi_inputs();
+///
+///     rsx! {
+///         if let Some(error) = midi.error()() {
+///             p { "{error}" }
+///         }
+///         for port in midi.ports().read().iter() {
+///             p { "{port.name}" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_midi_inputs() -> UseMidiInputs {
+    use_hook(|| {
+        let midi = UseMidiInputs {
+            ports: Signal::new(Vec::new()),
+            error: Signal::new(None),
+        };
+
+        spawn(async move {
+            loop {
+                midi.update().await;
+                platform::wait_for_change().await;
+            }
+        });
+
+        midi
+    })
+}
+
+/// A hook that calls `handler` with every message received from `port`.
+///
+/// The port is opened while the component is mounted, and reopened when `port` changes. Nothing is received while
+/// `port` is `None`. Returns the error from opening the port, if any.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::midi::{use_midi_inputs, use_midi_messages, MidiEvent};
+///
+/// fn Piano() -> Element {
+///     let midi = use_midi_inputs();
+///     let port = use_memo(move || midi.ports().read().first().cloned());
+///     let mut notes = use_signal(Vec::new);
+///     use_midi_messages(port, move |message| {
+///         if let MidiEvent::NoteOn { note, .. } = message.event() {
+///             notes.write().push(note);
+///         }
+///     });
+///
+///     rsx! { "{notes:?}" }
+/// }
+/// ```
+pub fn use_midi_messages(
+    port: impl Into<ReadOnlySignal<Option<MidiPort>>>,
+    handler: impl FnMut(MidiMessage) + 'static,
+) -> ReadOnlySignal<Option<MidiError>> {
+    let port = use_hook(|| port.into());
+    let handler = use_hook(|| Rc::new(RefCell::new(handler)));
+    let mut error = use_signal(|| None);
+    let mut task = use_hook(|| CopyValue::new(None::<Task>));
+
+    use_effect(move || {
+        let port = port();
+        if let Some(task) = task.write().take() {
+            task.cancel();
+        }
+        error.set(None);
+        let Some(port) = port else {
+            return;
+        };
+
+        let handler = handler.clone();
+        task.set(Some(spawn(async move {
+            // The connection closes the port once the task is cancelled.
+            let (mut messages, _connection) = match open(&port) {
+                Ok(opened) => opened,
+                Err(e) => {
+                    error.set(Some(e));
+                    return;
+                }
+            };
+            while let Some(message) = messages.next().await {
+                (handler.borrow_mut())(message);
+            }
+        })));
+    });
+
+    error.into()
+}
@@ -0,0 +1,318 @@
+//! Provides a notification abstraction to access the target system's notification feature.
+
+use std::fmt;
+
+use super::platform;
+
+/// Provides a builder API and contains relevant notification info.
+///
+/// # Examples
+///
+/// ```
+/// use dioxus_sdk::notification::Notification;
+///
+/// Notification::new()
+///     .app_name("dioxus test".to_string())
+///     .summary("hi, this is dioxus test".to_string())
+///     .body("lorem ipsum??".to_string())
+///     .show()
+///     .unwrap();
+///
+/// ```
+///
+/// New options are added over time, so build notifications with [`Notification::new`] and the setters instead of a
+/// struct literal.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct Notification {
+    pub app_name: String,
+    pub summary: String,
+    pub body: String,
+    pub icon_path: String,
+    pub timeout: NotificationTimeout,
+    pub id: Option<String>,
+    pub channel: Option<String>,
+    pub image: Option<String>,
+    pub hero_image: Option<String>,
+    pub respect_dnd: bool,
+    pub toast_fallback: bool,
+}
+
+/// What happened to a notification passed to [`Notification::show`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShowOutcome {
+    /// The notification was handed to the system.
+    Shown,
+    /// Do not disturb is on and the notification respects it, so it wasn't shown.
+    ///
+    /// Show it inside the app instead, like with a toast.
+    SuppressedByDnd,
+    /// The system couldn't show the notification, so it was shown in the app's toast layer instead.
+    ///
+    /// See [`Notification::toast_fallback`].
+    ShownAsToast,
+}
+
+/// Whether the user turned on do not disturb, so notifications are hidden or silenced.
+///
+/// This is Focus Assist on Windows, Focus on macOS, and the interruption filter on Android. On Linux, only GNOME
+/// reports it. The web guesses it from whether the page is hidden or in full screen. Platforms that don't expose it,
+/// like iOS, always return `false`.
+pub fn is_dnd_enabled() -> bool {
+    platform::is_dnd_enabled()
+}
+
+/// Represents the notification's timeout.
+#[derive(Debug, PartialEq, Clone)]
+pub enum NotificationTimeout {
+    /// Default depends on the target OS.
+    Default,
+    Never,
+    Milliseconds(u32),
+}
+
+impl Notification {
+    /// Creates a new notification with empty/default values.
+    pub fn new() -> Self {
+        Notification {
+            app_name: "".to_string(),
+            summary: "".to_string(),
+            body: "".to_string(),
+            icon_path: "".to_string(),
+            timeout: NotificationTimeout::Default,
+            id: None,
+            channel: None,
+            image: None,
+            hero_image: None,
+            respect_dnd: false,
+            toast_fallback: false,
+        }
+    }
+
+    /// Show the final notification.
+    ///
+    /// On mobile, the app needs permission to show notifications first. See [`Notification::request_permission`].
+    /// The web has no system notifications, so this fails there unless [`Notification::toast_fallback`] is set.
+    /// With the `storage` feature, the notification is also kept in the inbox once it is enabled, even if do not
+    /// disturb kept it from being shown. See `enable_notification_inbox`.
+    pub fn show(&self) -> Result<ShowOutcome, NotificationError> {
+        let shown = if self.respect_dnd && is_dnd_enabled() {
+            Ok(ShowOutcome::SuppressedByDnd)
+        } else if platform::is_blocked() {
+            Err(NotificationError::FailedToShowNotification(
+                "notifications are turned off for this app".to_string(),
+            ))
+        } else {
+            platform::show(self).map(|_| ShowOutcome::Shown)
+        };
+        let outcome = match shown {
+            Ok(ShowOutcome::Shown) => ShowOutcome::Shown,
+            _ if self.toast_fallback && super::toast::show_notification(self) => {
+                ShowOutcome::ShownAsToast
+            }
+            shown => shown?,
+        };
+        #[cfg(feature = "storage")]
+        super::inbox::record(self);
+        Ok(outcome)
+    }
+
+    /// Ask the user for permission to show notifications, returning whether it was granted.
+    ///
+    /// Android 13 and newer show a permission dialog, and iOS asks the first time this is called.
+    /// Desktop platforms don't require permission, so this always returns `true` there.
+    pub async fn request_permission() -> Result<bool, NotificationError> {
+        let granted = platform::request_permission().await;
+        // The web has no system notifications, so its answer says nothing about the browser permission.
+        #[cfg(all(feature = "permissions", not(target_family = "wasm")))]
+        if let Ok(granted) = granted {
+            use crate::permissions::{report_permission, Permission, PermissionStatus};
+
+            let status = match granted {
+                true => PermissionStatus::Granted,
+                false => PermissionStatus::Denied,
+            };
+            report_permission(Permission::Notifications, status);
+        }
+        granted
+    }
+
+    // Setters
+    /// Set the application's name for the notification.
+    pub fn app_name(&mut self, value: String) -> &mut Self {
+        self.app_name = value;
+        self
+    }
+
+    /// Set the summary content of the notification.
+    pub fn summary(&mut self, value: String) -> &mut Self {
+        self.summary = value;
+        self
+    }
+
+    /// Set the body content of the notification.
+    pub fn body(&mut self, value: String) -> &mut Self {
+        self.body = value;
+        self
+    }
+
+    /// Set full path to image.
+    /// Only works on Linux.
+    pub fn icon_path(&mut self, value: String) -> &mut Self {
+        self.icon_path = value;
+        self
+    }
+
+    /// Set an image shown in the notification.
+    ///
+    /// The image is an absolute path, an asset of the app like the path returned by `mg!(file("..."))`, or a
+    /// `data:` URI. Only works on Linux, Windows and macOS.
+    pub fn image(&mut self, asset: impl ToString) -> &mut Self {
+        self.image = Some(asset.to_string());
+        self
+    }
+
+    /// Set a large image shown prominently at the top of the notification.
+    ///
+    /// Accepts the same images as [`Notification::image`]. Desktop platforms show a single large image, so the hero
+    /// image is shown there. On Linux the image then takes the place of the icon, unless there is an icon path. On
+    /// Windows, and on macOS where the image is attached to the notification, the image is dropped. Only works on
+    /// Linux, Windows and macOS.
+    pub fn hero_image(&mut self, asset: impl ToString) -> &mut Self {
+        self.hero_image = Some(asset.to_string());
+        self
+    }
+
+    /// Set a timeout for when the notification should hide.
+    pub fn timeout(&mut self, value: NotificationTimeout) -> &mut Self {
+        self.timeout = value;
+        self
+    }
+
+    /// Set a stable id for the notification.
+    /// Showing a notification with the same id as a previous one replaces it instead of adding another,
+    /// even across app restarts. This is useful for progress updates.
+    /// Only works on Linux, Android and iOS. Windows and macOS ignore the id and add every notification, since the
+    /// libraries used there can't set the toast tag or the notification identifier.
+    pub fn id(&mut self, value: String) -> &mut Self {
+        self.id = Some(value);
+        self
+    }
+
+    /// Set whether the notification is skipped while do not disturb is on. See [`is_dnd_enabled`].
+    ///
+    /// The system usually hides notifications during do not disturb by itself. Respecting it lets the app know,
+    /// through [`ShowOutcome::SuppressedByDnd`], so it can fall back to showing the notification in the app.
+    pub fn respect_dnd(&mut self, value: bool) -> &mut Self {
+        self.respect_dnd = value;
+        self
+    }
+
+    /// Set whether the notification is shown in the app's toast layer when the system can't show it.
+    ///
+    /// This happens when showing it fails, when the user turned notifications off for the app on Android, and when
+    /// [`Notification::respect_dnd`] skips it. The toast layer is mounted with `use_toast_provider` or `ToastStack`.
+    /// If none is mounted, the notification isn't shown at all.
+    pub fn toast_fallback(&mut self, value: bool) -> &mut Self {
+        self.toast_fallback = value;
+        self
+    }
+
+    /// Set the channel the notification is posted to, which users can mute or configure in the system settings.
+    /// The channel is created the first time it is used. Notifications without a channel use a `default` channel.
+    /// Only works on Android.
+    pub fn channel(&mut self, value: String) -> &mut Self {
+        self.channel = Some(value);
+        self
+    }
+}
+
+/// The notification features supported by the current platform.
+///
+/// See [`Notification::capabilities`] for more information.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct NotificationCapabilities {
+    /// Buttons the user can click to respond to the notification.
+    pub actions: bool,
+    /// Images in the notification body.
+    pub images: bool,
+    /// Playing a sound when the notification is shown.
+    pub sounds: bool,
+    /// Markup like bold or italic text in the notification body.
+    pub markup: bool,
+    /// Replying to the notification with text.
+    pub inline_reply: bool,
+}
+
+impl Notification {
+    /// Get the notification features supported by the current platform.
+    ///
+    /// Use this to adapt the notification content, like leaving out markup the platform would show literally.
+    /// On Linux, this asks the notification server. Elsewhere, the supported features are fixed.
+    pub fn capabilities() -> Result<NotificationCapabilities, NotificationError> {
+        cfg_if::cfg_if! {
+            if #[cfg(all(unix, not(any(target_os = "macos", target_os = "android", target_os = "ios"))))] {
+                let capabilities = notify_rust::get_capabilities()
+                    .map_err(|e| NotificationError::FailedToGetCapabilities(e.to_string()))?;
+                let supports = |capability: &str| capabilities.iter().any(|c| c == capability);
+                Ok(NotificationCapabilities {
+                    actions: supports("actions"),
+                    images: supports("body-images"),
+                    sounds: supports("sound"),
+                    markup: supports("body-markup"),
+                    inline_reply: supports("inline-reply"),
+                })
+            } else if #[cfg(any(
+                target_os = "macos",
+                target_os = "windows",
+                target_os = "android",
+                target_os = "ios",
+            ))] {
+                Ok(NotificationCapabilities {
+                    sounds: true,
+                    ..Default::default()
+                })
+            } else {
+                Ok(NotificationCapabilities::default())
+            }
+        }
+    }
+}
+
+impl Default for Notification {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[test]
+fn test_notification() {
+    Notification::new()
+        .app_name("dioxus test".to_string())
+        .summary("hi, this is dioxus test".to_string())
+        .body("lorem ipsum??".to_string())
+        .show()
+        .unwrap();
+}
+
+/// Represents errors when utilizing the notification abstraction.
+#[derive(Debug)]
+pub enum NotificationError {
+    /// Failure to show a notification.
+    FailedToShowNotification(String),
+    /// Failure to get the notification capabilities of the platform.
+    FailedToGetCapabilities(String),
+    /// Failure to ask the user for permission to show notifications.
+    FailedToRequestPermission(String),
+}
+
+impl std::error::Error for NotificationError {}
+impl fmt::Display for NotificationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NotificationError::FailedToShowNotification(s) => write!(f, "{}", s),
+            NotificationError::FailedToGetCapabilities(s) => write!(f, "{}", s),
+            NotificationError::FailedToRequestPermission(s) => write!(f, "{}", s),
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! An in-app inbox that keeps the notifications the app has shown.
+
+use dioxus::prelude::*;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::watch::{channel, Sender};
+
+use super::Notification;
+use crate::storage::{LocalStorage, StorageBacking};
+
+/// The storage key the inbox is persisted under.
+const INBOX_KEY: &str = "dioxus-notification-inbox";
+/// How many notifications the inbox keeps before dropping the oldest ones.
+const MAX_ENTRIES: usize = 200;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+static INBOX: Lazy<Sender<Vec<InboxEntry>>> =
+    Lazy::new(|| channel(LocalStorage::get(&INBOX_KEY.to_string()).unwrap_or_default()).0);
+
+/// A notification kept in the inbox.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InboxEntry {
+    /// Identifies the entry in the inbox. This is unrelated to [`Notification::id`].
+    pub id: u64,
+    pub summary: String,
+    pub body: String,
+    /// When the notification was shown, in milliseconds since the Unix epoch.
+    pub shown_at: u64,
+    pub read: bool,
+}
+
+/// Start keeping every notification that is shown in the inbox.
+///
+/// Operating systems only keep a short notification history, if any, so the inbox lets users review the
+/// notifications they missed. Read it with [`use_notification_inbox`]. The inbox is persisted in [`LocalStorage`],
+/// so call [`set_dir!`](crate::set_dir) first.
+pub fn enable_notification_inbox() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Add a notification that was shown to the inbox, if the inbox is enabled.
+pub(crate) fn record(notification: &Notification) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    let shown_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |since| since.as_millis() as u64);
+    // `SystemTime::now` panics on the web, so ask the browser instead.
+    #[cfg(target_family = "wasm")]
+    let shown_at = js_sys::Date::now() as u64;
+    update(|entries| {
+        let id = entries.iter().map(|entry| entry.id + 1).max().unwrap_or(0);
+        entries.push(InboxEntry {
+            id,
+            summary: notification.summary.clone(),
+            body: notification.body.clone(),
+            shown_at,
+            read: false,
+        });
+        let overflow = entries.len().saturating_sub(MAX_ENTRIES);
+        entries.drain(..overflow);
+    });
+}
+
+/// Change the inbox, then persist it and notify every [`NotificationInbox`].
+fn update(f: impl FnOnce(&mut Vec<InboxEntry>)) {
+    INBOX.send_modify(|entries| {
+        f(entries);
+        LocalStorage::set(INBOX_KEY.to_string(), entries);
+    });
+}
+
+/// A handle to the notification inbox.
+///
+/// See [`use_notification_inbox`] for more information.
+#[derive(Clone, Copy, PartialEq)]
+pub struct NotificationInbox {
+    entries: Signal<Vec<InboxEntry>>,
+}
+
+impl NotificationInbox {
+    /// Get the notifications in the inbox, from oldest to newest.
+    pub fn entries(&self) -> ReadOnlySignal<Vec<InboxEntry>> {
+        self.entries.into()
+    }
+
+    /// Get how many notifications haven't been read yet.
+    pub fn unread_count(&self) -> usize {
+        self.entries
+            .read()
+            .iter()
+            .filter(|entry| !entry.read)
+            .count()
+    }
+
+    /// Mark a notification as read.
+    pub fn mark_read(&self, id: u64) {
+        update(|entries| {
+            if let Some(entry) = entries.iter_mut().find(|entry| entry.id == id) {
+                entry.read = true;
+            }
+        });
+    }
+
+    /// Mark every notification as read.
+    pub fn mark_all_read(&self) {
+        update(|entries| entries.iter_mut().for_each(|entry| entry.read = true));
+    }
+
+    /// Remove a notification from the inbox.
+    pub fn dismiss(&self, id: u64) {
+        update(|entries| entries.retain(|entry| entry.id != id));
+    }
+
+    /// Remove every notification from the inbox.
+    pub fn clear(&self) {
+        update(Vec::clear);
+    }
+}
+
+/// A hook to read the notification inbox.
+///
+/// The inbox keeps the notifications shown after [`enable_notification_inbox`] was called, including the ones shown
+/// in earlier runs of the app. It is persisted in [`LocalStorage`], so the `storage` feature is required.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::notification::use_notification_inbox;
+///
+/// fn Inbox() -> Element {
+///     let inbox = use_notification_inbox();
+///
+///     rsx! {
+///         p { "{inbox.unread_count()} unread" }
+///         for entry in inbox.entries()() {
+///             div {
+///                 key: "{entry.id}",
+///                 onclick: move |_| inbox.mark_read(entry.id),
+///                 b { "{entry.summary}" }
+///                 p { "{entry.body}" }
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_notification_inbox() -> NotificationInbox {
+    use_hook(|| {
+        let mut changes = INBOX.subscribe();
+        let mut entries = Signal::new(changes.borrow_and_update().clone());
+
+        spawn(async move {
+            while changes.changed().await.is_ok() {
+                let current = changes.borrow_and_update().clone();
+                entries.set(current);
+            }
+        });
+
+        NotificationInbox { entries }
+    })
+}
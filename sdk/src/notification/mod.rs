@@ -1,10 +1,20 @@
-//! Send desktop notifications.
+//! Send desktop and mobile notifications, and show them inside the app where the system can't.
+//!
+//! The web has no system notifications, so only the toast layer is available there. See [`use_toast_provider`].
 
-cfg_if::cfg_if! {
-    if #[cfg(not(target_family = "wasm"))] {
-        mod desktop;
-        pub use desktop::*;
-    } else {
-        compile_error!("the `notification` feature is only available on desktop targets");
-    }
-}
+mod core;
+mod platform;
+mod toast;
+pub use self::core::*;
+pub use self::toast::*;
+
+#[cfg(feature = "storage")]
+mod inbox;
+#[cfg(feature = "storage")]
+pub use inbox::*;
+
+#[cfg(all(
+    unix,
+    not(any(target_os = "macos", target_os = "android", target_os = "ios"))
+))]
+mod replace_ids;
@@ -0,0 +1,250 @@
+use jni::{
+    objects::{JObject, JValue},
+    JNIEnv,
+};
+use std::{
+    sync::atomic::{AtomicI32, Ordering},
+    time::{Duration, Instant},
+};
+
+use crate::android::with_activity;
+use crate::notification::{Notification, NotificationError, NotificationTimeout};
+
+/// The channel of notifications that don't set one.
+const DEFAULT_CHANNEL: &str = "default";
+
+/// `NotificationManager.IMPORTANCE_DEFAULT`
+const IMPORTANCE_DEFAULT: i32 = 3;
+
+/// `NotificationManager.INTERRUPTION_FILTER_ALL`, the filter when do not disturb is off.
+const INTERRUPTION_FILTER_ALL: i32 = 1;
+
+/// The runtime permission added in Android 13.
+const POST_NOTIFICATIONS: &str = "android.permission.POST_NOTIFICATIONS";
+
+/// How often the activity is checked while the permission dialog is open.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How long to wait for the permission dialog to open. The system doesn't show it again once the user denied it twice.
+const DIALOG_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The ids of notifications without a stable id. Notifications with one are told apart by their tag instead.
+static NEXT_ID: AtomicI32 = AtomicI32::new(1);
+
+pub fn show(info: &Notification) -> Result<(), NotificationError> {
+    with_activity(|env, activity| {
+        let manager = notification_manager(env, activity)?;
+        let channel_id = env.new_string(info.channel.as_deref().unwrap_or(DEFAULT_CHANNEL))?;
+
+        let builder = if sdk_version(env)? >= 26 {
+            // Creating a channel that already exists does nothing.
+            let channel = env.new_object(
+                "android/app/NotificationChannel",
+                "(Ljava/lang/String;Ljava/lang/CharSequence;I)V",
+                &[
+                    JValue::Object(&channel_id),
+                    JValue::Object(&channel_id),
+                    JValue::Int(IMPORTANCE_DEFAULT),
+                ],
+            )?;
+            env.call_method(
+                &manager,
+                "createNotificationChannel",
+                "(Landroid/app/NotificationChannel;)V",
+                &[JValue::Object(&channel)],
+            )?;
+            env.new_object(
+                "android/app/Notification$Builder",
+                "(Landroid/content/Context;Ljava/lang/String;)V",
+                &[JValue::Object(activity), JValue::Object(&channel_id)],
+            )?
+        } else {
+            env.new_object(
+                "android/app/Notification$Builder",
+                "(Landroid/content/Context;)V",
+                &[JValue::Object(activity)],
+            )?
+        };
+
+        let title = env.new_string(&info.summary)?;
+        let text = env.new_string(&info.body)?;
+        let app_info = env
+            .call_method(
+                activity,
+                "getApplicationInfo",
+                "()Landroid/content/pm/ApplicationInfo;",
+                &[],
+            )?
+            .l()?;
+        // Notifications must have an icon, so use the app icon.
+        let icon = env.get_field(&app_info, "icon", "I")?.i()?;
+
+        set(
+            env,
+            &builder,
+            "setContentTitle",
+            "(Ljava/lang/CharSequence;)Landroid/app/Notification$Builder;",
+            JValue::Object(&title),
+        )?;
+        set(
+            env,
+            &builder,
+            "setContentText",
+            "(Ljava/lang/CharSequence;)Landroid/app/Notification$Builder;",
+            JValue::Object(&text),
+        )?;
+        set(
+            env,
+            &builder,
+            "setSmallIcon",
+            "(I)Landroid/app/Notification$Builder;",
+            JValue::Int(icon),
+        )?;
+        set(
+            env,
+            &builder,
+            "setAutoCancel",
+            "(Z)Landroid/app/Notification$Builder;",
+            JValue::Bool(1),
+        )?;
+        if let NotificationTimeout::Milliseconds(ms) = info.timeout {
+            if sdk_version(env)? >= 26 {
+                set(
+                    env,
+                    &builder,
+                    "setTimeoutAfter",
+                    "(J)Landroid/app/Notification$Builder;",
+                    JValue::Long(ms.into()),
+                )?;
+            }
+        }
+
+        let notification = env
+            .call_method(&builder, "build", "()Landroid/app/Notification;", &[])?
+            .l()?;
+        let (tag, id) = match &info.id {
+            Some(id) => (JObject::from(env.new_string(id)?), 0),
+            None => (JObject::null(), NEXT_ID.fetch_add(1, Ordering::Relaxed)),
+        };
+        env.call_method(
+            &manager,
+            "notify",
+            "(Ljava/lang/String;ILandroid/app/Notification;)V",
+            &[
+                JValue::Object(&tag),
+                JValue::Int(id),
+                JValue::Object(&notification),
+            ],
+        )?;
+        Ok(())
+    })
+    .map_err(NotificationError::FailedToShowNotification)
+}
+
+pub async fn request_permission() -> Result<bool, NotificationError> {
+    let to_error = NotificationError::FailedToRequestPermission;
+    if notifications_enabled().map_err(to_error)? {
+        return Ok(true);
+    }
+
+    // Older versions don't have a runtime permission, so notifications were turned off in the settings.
+    let asked = with_activity(|env, activity| {
+        if sdk_version(env)? < 33 {
+            return Ok(false);
+        }
+        let permission = env.new_string(POST_NOTIFICATIONS)?;
+        let permissions = env.new_object_array(1, "java/lang/String", &permission)?;
+        env.call_method(
+            activity,
+            "requestPermissions",
+            "([Ljava/lang/String;I)V",
+            &[JValue::Object(&permissions), JValue::Int(0)],
+        )?;
+        Ok(true)
+    })
+    .map_err(to_error)?;
+    if !asked {
+        return Ok(false);
+    }
+
+    // The answer is only reported to the activity, so wait for the dialog to take the focus and give it back.
+    wait_for_focus(false, Some(DIALOG_TIMEOUT))
+        .await
+        .map_err(to_error)?;
+    wait_for_focus(true, None).await.map_err(to_error)?;
+    notifications_enabled().map_err(to_error)
+}
+
+/// Whether the user turned notifications off for the app, or didn't grant the permission on Android 13 and newer.
+pub fn is_blocked() -> bool {
+    notifications_enabled().is_ok_and(|enabled| !enabled)
+}
+
+/// Whether do not disturb filters out some notifications. The filter was added in Android 6.
+pub fn is_dnd_enabled() -> bool {
+    with_activity(|env, activity| {
+        if sdk_version(env)? < 23 {
+            return Ok(false);
+        }
+        let manager = notification_manager(env, activity)?;
+        let filter = env
+            .call_method(&manager, "getCurrentInterruptionFilter", "()I", &[])?
+            .i()?;
+        Ok(filter > INTERRUPTION_FILTER_ALL)
+    })
+    .unwrap_or(false)
+}
+
+/// Call a setter of a `Notification.Builder`.
+fn set(
+    env: &mut JNIEnv,
+    builder: &JObject,
+    method: &str,
+    signature: &str,
+    value: JValue,
+) -> jni::errors::Result<()> {
+    env.call_method(builder, method, signature, &[value])
+        .map(|_| ())
+}
+
+async fn wait_for_focus(focused: bool, timeout: Option<Duration>) -> Result<(), String> {
+    let start = Instant::now();
+    while has_focus()? != focused {
+        if timeout.is_some_and(|timeout| start.elapsed() > timeout) {
+            break;
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+    Ok(())
+}
+
+fn has_focus() -> Result<bool, String> {
+    with_activity(|env, activity| env.call_method(activity, "hasWindowFocus", "()Z", &[])?.z())
+}
+
+fn notifications_enabled() -> Result<bool, String> {
+    with_activity(|env, activity| {
+        let manager = notification_manager(env, activity)?;
+        env.call_method(&manager, "areNotificationsEnabled", "()Z", &[])?
+            .z()
+    })
+}
+
+fn notification_manager<'local>(
+    env: &mut JNIEnv<'local>,
+    activity: &JObject,
+) -> jni::errors::Result<JObject<'local>> {
+    let service = env.new_string("notification")?;
+    env.call_method(
+        activity,
+        "getSystemService",
+        "(Ljava/lang/String;)Ljava/lang/Object;",
+        &[JValue::Object(&service)],
+    )?
+    .l()
+}
+
+fn sdk_version(env: &mut JNIEnv) -> jni::errors::Result<i32> {
+    env.get_static_field("android/os/Build$VERSION", "SDK_INT", "I")?
+        .i()
+}
@@ -0,0 +1,77 @@
+//! Resolves the images of notifications to files, as desktop notification servers can only show files.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+use crate::notification::NotificationError;
+
+/// Get the file an image of a notification is stored in.
+///
+/// `asset` is an absolute path, a path relative to the assets of the app like the ones returned by `mg!`, or a
+/// `data:` URI, which is written to a temporary file.
+pub fn resolve(asset: &str) -> Result<PathBuf, NotificationError> {
+    if let Some(data) = asset.strip_prefix("data:") {
+        return write_data_uri(data);
+    }
+
+    let asset = asset.strip_prefix("file://").unwrap_or(asset);
+    let path = Path::new(asset);
+    if path.is_absolute() && path.exists() {
+        return Ok(path.to_path_buf());
+    }
+
+    // Bundled assets are next to the executable, or in the resources of a macOS app bundle.
+    // While developing, they are in the current directory.
+    let relative = asset.trim_start_matches('/');
+    let exe_dir = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf));
+    let candidates = [
+        exe_dir.as_ref().map(|dir| dir.join(relative)),
+        exe_dir
+            .as_ref()
+            .map(|dir| dir.join("../Resources").join(relative)),
+        std::env::current_dir().ok().map(|dir| dir.join(relative)),
+    ];
+
+    candidates
+        .into_iter()
+        .flatten()
+        .find(|candidate| candidate.exists())
+        .ok_or_else(|| {
+            NotificationError::FailedToShowNotification(format!("the image {asset} doesn't exist"))
+        })
+}
+
+/// Write the image in a `data:` URI, without the `data:` prefix, to a temporary file.
+fn write_data_uri(data: &str) -> Result<PathBuf, NotificationError> {
+    let invalid =
+        || NotificationError::FailedToShowNotification("the image isn't a base64 data URI".into());
+
+    let (header, encoded) = data.split_once(',').ok_or_else(invalid)?;
+    let mime = header.strip_suffix(";base64").ok_or_else(invalid)?;
+    let encoded: String = encoded.split_ascii_whitespace().collect();
+    let bytes = STANDARD.decode(encoded).map_err(|_| invalid())?;
+
+    let extension = match mime {
+        "image/jpeg" => "jpg",
+        "image/gif" => "gif",
+        "image/svg+xml" => "svg",
+        "image/webp" => "webp",
+        _ => "png",
+    };
+    // The same image is written to the same file, so showing it repeatedly doesn't fill the temporary directory.
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    let hash = hasher.finish();
+    let path = std::env::temp_dir().join(format!("dioxus-notification-{hash:016x}.{extension}"));
+
+    if !path.exists() {
+        std::fs::write(&path, bytes)
+            .map_err(|e| NotificationError::FailedToShowNotification(e.to_string()))?;
+    }
+    Ok(path)
+}
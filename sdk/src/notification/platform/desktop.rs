@@ -0,0 +1,173 @@
+use notify_rust::Timeout;
+
+use crate::notification::{Notification, NotificationError, NotificationTimeout};
+
+impl From<NotificationTimeout> for Timeout {
+    fn from(value: NotificationTimeout) -> Self {
+        match value {
+            NotificationTimeout::Default => Timeout::Default,
+            NotificationTimeout::Never => Timeout::Never,
+            NotificationTimeout::Milliseconds(ms) => Timeout::Milliseconds(ms),
+        }
+    }
+}
+
+pub fn show(info: &Notification) -> Result<(), NotificationError> {
+    // Notification servers show a single large image, so the hero image is used if there is one. The other image
+    // then takes the place of the icon.
+    let image = info
+        .hero_image
+        .as_deref()
+        .or(info.image.as_deref())
+        .map(super::asset::resolve)
+        .transpose()?;
+    #[cfg(not(target_os = "macos"))]
+    let small_image = match info.hero_image {
+        Some(_) => info
+            .image
+            .as_deref()
+            .map(super::asset::resolve)
+            .transpose()?,
+        None => None,
+    };
+
+    // notify-rust doesn't support images on macOS.
+    #[cfg(target_os = "macos")]
+    if let Some(image) = &image {
+        return show_with_attachment(info, image);
+    }
+
+    let mut notification = notify_rust::Notification::new();
+    notification
+        .appname(&info.app_name)
+        .summary(&info.summary)
+        .body(&info.body)
+        .icon(&info.icon_path)
+        .timeout(info.timeout.clone());
+
+    #[cfg(not(target_os = "macos"))]
+    if let Some(image) = &image {
+        notification.image_path(&image.to_string_lossy());
+    }
+    #[cfg(not(target_os = "macos"))]
+    if let (Some(small_image), true) = (&small_image, info.icon_path.is_empty()) {
+        notification.icon(&small_image.to_string_lossy());
+    }
+
+    // Replace the notification previously shown with the same id.
+    #[cfg(all(unix, not(target_os = "macos")))]
+    if let Some(id) = &info.id {
+        if let Some(server_id) = crate::notification::replace_ids::get(&info.app_name, id) {
+            notification.id(server_id);
+        }
+    }
+
+    let result = notification.show();
+
+    match result {
+        #[cfg(all(unix, not(target_os = "macos")))]
+        Ok(handle) => {
+            if let Some(id) = &info.id {
+                crate::notification::replace_ids::set(&info.app_name, id, handle.id());
+            }
+            Ok(())
+        }
+        #[cfg(not(all(unix, not(target_os = "macos"))))]
+        Ok(_) => Ok(()),
+        Err(e) => Err(NotificationError::FailedToShowNotification(e.to_string())),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn show_with_attachment(
+    info: &Notification,
+    image: &std::path::Path,
+) -> Result<(), NotificationError> {
+    let image = image.to_string_lossy();
+    mac_notification_sys::Notification::new()
+        .title(&info.summary)
+        .message(&info.body)
+        .content_image(&image)
+        .send()
+        .map(|_| ())
+        .map_err(|e| NotificationError::FailedToShowNotification(e.to_string()))
+}
+
+/// Desktop platforms don't let users turn notifications off in a way apps can read.
+pub fn is_blocked() -> bool {
+    false
+}
+
+/// Whether Focus Assist is on, or the user is presenting or playing a full screen game.
+#[cfg(windows)]
+pub fn is_dnd_enabled() -> bool {
+    use windows::Win32::UI::Shell::{
+        SHQueryUserNotificationState, QUNS_ACCEPTS_NOTIFICATIONS, QUNS_APP,
+    };
+
+    match unsafe { SHQueryUserNotificationState() } {
+        Ok(state) => state != QUNS_ACCEPTS_NOTIFICATIONS && state != QUNS_APP,
+        Err(_) => false,
+    }
+}
+
+/// Whether a Focus is on.
+///
+/// macOS has no public API for this, so the assertions the system keeps while a Focus is on are read instead.
+/// Without Full Disk Access they can't be read, and Focus is assumed to be off.
+#[cfg(target_os = "macos")]
+pub fn is_dnd_enabled() -> bool {
+    let Some(home) = std::env::var_os("HOME") else {
+        return false;
+    };
+    let assertions = std::path::Path::new(&home).join("Library/DoNotDisturb/DB/Assertions.json");
+    std::fs::read_to_string(assertions)
+        .is_ok_and(|assertions| assertions.contains("storeAssertionRecords"))
+}
+
+/// Whether GNOME hides notification banners. Other desktops don't expose their do not disturb state.
+///
+/// Reading the setting starts `gsettings`, so the state is cached and refreshed in the background once it is older
+/// than a few seconds. Only the first check waits for it.
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn is_dnd_enabled() -> bool {
+    use std::sync::Mutex;
+    use std::time::{Duration, Instant};
+
+    /// The last state read from gsettings, and when it was read.
+    static CACHED: Mutex<Option<(Instant, bool)>> = Mutex::new(None);
+    /// How long the cached state is used before it is read again.
+    const MAX_AGE: Duration = Duration::from_secs(5);
+
+    fn read() -> bool {
+        let enabled = std::process::Command::new("gsettings")
+            .args(["get", "org.gnome.desktop.notifications", "show-banners"])
+            .output()
+            .is_ok_and(|output| {
+                output.status.success() && String::from_utf8_lossy(&output.stdout).trim() == "false"
+            });
+        *CACHED.lock().unwrap() = Some((Instant::now(), enabled));
+        enabled
+    }
+
+    let mut cached = CACHED.lock().unwrap();
+    match *cached {
+        Some((read_at, enabled)) => {
+            if read_at.elapsed() > MAX_AGE {
+                // Mark the state as fresh so only one refresh runs at a time.
+                *cached = Some((Instant::now(), enabled));
+                std::thread::spawn(read);
+            }
+            enabled
+        }
+        None => {
+            drop(cached);
+            read()
+        }
+    }
+}
+
+/// Desktop apps can show notifications without asking.
+pub async fn request_permission() -> Result<bool, NotificationError> {
+    Ok(true)
+}
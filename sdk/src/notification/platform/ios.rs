@@ -0,0 +1,165 @@
+use block::ConcreteBlock;
+use futures::channel::oneshot;
+use objc::{
+    class, msg_send,
+    runtime::{Object, BOOL, NO},
+    sel, sel_impl,
+};
+use std::{
+    ffi::{c_void, CStr},
+    os::raw::c_char,
+    ptr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+};
+
+use crate::notification::{Notification, NotificationError};
+
+#[link(name = "UserNotifications", kind = "framework")]
+extern "C" {}
+
+/// `UNAuthorizationOptionBadge | UNAuthorizationOptionSound | UNAuthorizationOptionAlert`
+const AUTHORIZATION_OPTIONS: usize = 1 | 2 | 4;
+
+/// `NSUTF8StringEncoding`
+const UTF8_ENCODING: usize = 4;
+
+/// The ids of notifications without a stable id.
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Schedule the notification for delivery.
+///
+/// iOS doesn't show notifications while the app is in the foreground, so they only appear once the user leaves
+/// the app. The notification is delivered asynchronously, so delivery errors are only logged.
+pub fn show(info: &Notification) -> Result<(), NotificationError> {
+    let id = match &info.id {
+        Some(id) => id.clone(),
+        None => format!(
+            "dioxus-notification-{}",
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        ),
+    };
+    let title = NSString::new(&info.summary);
+    let body = NSString::new(&info.body);
+    let id = NSString::new(&id);
+
+    let completion = ConcreteBlock::new(|error: *mut Object| {
+        if !error.is_null() {
+            tracing::warn!("Failed to show notification: {}", unsafe {
+                error_description(error)
+            });
+        }
+    })
+    .copy();
+
+    unsafe {
+        let content: *mut Object = msg_send![class!(UNMutableNotificationContent), new];
+        if content.is_null() {
+            return Err(NotificationError::FailedToShowNotification(
+                "notifications are not supported".to_string(),
+            ));
+        }
+        let _: () = msg_send![content, setTitle: title.0];
+        let _: () = msg_send![content, setBody: body.0];
+        let sound: *mut Object = msg_send![class!(UNNotificationSound), defaultSound];
+        let _: () = msg_send![content, setSound: sound];
+
+        // Requests without a trigger are delivered right away, replacing the notification with the same id.
+        let request: *mut Object = msg_send![
+            class!(UNNotificationRequest),
+            requestWithIdentifier: id.0
+            content: content
+            trigger: ptr::null_mut::<Object>()
+        ];
+        let center = notification_center();
+        let _: () =
+            msg_send![center, addNotificationRequest: request withCompletionHandler: &*completion];
+        let _: () = msg_send![content, release];
+    }
+    Ok(())
+}
+
+pub async fn request_permission() -> Result<bool, NotificationError> {
+    let (tx, rx) = oneshot::channel();
+    // The block may be called from any thread, and only once.
+    let tx = Mutex::new(Some(tx));
+    let completion = ConcreteBlock::new(move |granted: BOOL, error: *mut Object| {
+        let result = if error.is_null() {
+            Ok(granted != NO)
+        } else {
+            Err(NotificationError::FailedToRequestPermission(unsafe {
+                error_description(error)
+            }))
+        };
+        if let Some(tx) = tx.lock().unwrap().take() {
+            tx.send(result).ok();
+        }
+    })
+    .copy();
+
+    unsafe {
+        let center = notification_center();
+        let _: () = msg_send![
+            center,
+            requestAuthorizationWithOptions: AUTHORIZATION_OPTIONS
+            completionHandler: &*completion
+        ];
+    }
+
+    rx.await.unwrap_or_else(|_| {
+        Err(NotificationError::FailedToRequestPermission(
+            "the request was cancelled".to_string(),
+        ))
+    })
+}
+
+unsafe fn notification_center() -> *mut Object {
+    msg_send![class!(UNUserNotificationCenter), currentNotificationCenter]
+}
+
+unsafe fn error_description(error: *mut Object) -> String {
+    let description: *mut Object = msg_send![error, localizedDescription];
+    let utf8: *const c_char = msg_send![description, UTF8String];
+    if utf8.is_null() {
+        return "unknown error".to_string();
+    }
+    CStr::from_ptr(utf8).to_string_lossy().into_owned()
+}
+
+/// An owned `NSString`, released when dropped.
+struct NSString(*mut Object);
+
+impl NSString {
+    fn new(value: &str) -> Self {
+        unsafe {
+            let string: *mut Object = msg_send![class!(NSString), alloc];
+            let string: *mut Object = msg_send![
+                string,
+                initWithBytes: value.as_ptr() as *const c_void
+                length: value.len()
+                encoding: UTF8_ENCODING
+            ];
+            Self(string)
+        }
+    }
+}
+
+impl Drop for NSString {
+    fn drop(&mut self) {
+        unsafe {
+            let _: () = msg_send![self.0, release];
+        }
+    }
+}
+
+/// The authorization status is only reported asynchronously, so notifications are assumed to be allowed.
+pub fn is_blocked() -> bool {
+    false
+}
+
+/// Reading the Focus status requires the Communication Notifications entitlement, so it is assumed to be off.
+pub fn is_dnd_enabled() -> bool {
+    false
+}
@@ -0,0 +1,16 @@
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "android")] {
+        mod android;
+        pub use android::*;
+    } else if #[cfg(target_os = "ios")] {
+        mod ios;
+        pub use ios::*;
+    } else if #[cfg(target_family = "wasm")] {
+        mod wasm;
+        pub use wasm::*;
+    } else {
+        mod asset;
+        mod desktop;
+        pub use desktop::*;
+    }
+}
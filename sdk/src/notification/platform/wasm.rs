@@ -0,0 +1,29 @@
+use crate::notification::{Notification, NotificationError};
+
+/// The web has no system notifications, so they can only be shown in the app's toast layer.
+pub fn show(_info: &Notification) -> Result<(), NotificationError> {
+    Err(NotificationError::FailedToShowNotification(
+        "system notifications are not supported on the web".to_string(),
+    ))
+}
+
+/// Nothing can be blocked, since no system notifications are shown.
+pub fn is_blocked() -> bool {
+    false
+}
+
+/// Browsers don't expose the do not disturb state, so it is guessed from the page.
+///
+/// Nothing the page shows is seen while it is hidden, and a page in full screen is treated like presenting, as on
+/// Windows.
+pub fn is_dnd_enabled() -> bool {
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return false;
+    };
+    document.hidden() || document.fullscreen_element().is_some()
+}
+
+/// No permission is needed, since no system notifications are shown.
+pub async fn request_permission() -> Result<bool, NotificationError> {
+    Ok(false)
+}
@@ -0,0 +1,62 @@
+//! Maps stable notification ids to the ids assigned by the notification server.
+//!
+//! The map is saved to the temporary directory so notifications can still be replaced after the app restarts.
+//! Notification servers forget their ids when the session ends, which is also when the temporary directory is usually cleared.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// The server ids of all notifications shown by this app, keyed by their stable id.
+static IDS: Mutex<Option<HashMap<String, u32>>> = Mutex::new(None);
+
+fn path(app_name: &str) -> PathBuf {
+    let app_name: String = app_name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    std::env::temp_dir().join(format!("dioxus-notification-ids-{}", app_name))
+}
+
+fn load(app_name: &str) -> HashMap<String, u32> {
+    let Ok(contents) = std::fs::read_to_string(path(app_name)) else {
+        return HashMap::new();
+    };
+
+    contents
+        .lines()
+        .filter_map(|line| {
+            let (id, server_id) = line.rsplit_once('\t')?;
+            Some((id.to_string(), server_id.parse().ok()?))
+        })
+        .collect()
+}
+
+fn save(app_name: &str, ids: &HashMap<String, u32>) {
+    let contents: String = ids
+        .iter()
+        .map(|(id, server_id)| format!("{}\t{}\n", id.replace(['\t', '\n'], " "), server_id))
+        .collect();
+
+    if let Err(e) = std::fs::write(path(app_name), contents) {
+        tracing::warn!("Failed to save notification ids: {}", e);
+    }
+}
+
+/// Get the server id of the notification previously shown with this stable id.
+pub(crate) fn get(app_name: &str, id: &str) -> Option<u32> {
+    let mut ids = IDS.lock().ok()?;
+    ids.get_or_insert_with(|| load(app_name)).get(id).copied()
+}
+
+/// Remember the server id of the notification shown with this stable id.
+pub(crate) fn set(app_name: &str, id: &str, server_id: u32) {
+    let Ok(mut ids) = IDS.lock() else {
+        return;
+    };
+
+    let ids = ids.get_or_insert_with(|| load(app_name));
+    if ids.insert(id.to_string(), server_id) != Some(server_id) {
+        save(app_name, ids);
+    }
+}
@@ -0,0 +1,236 @@
+//! An in-app toast layer notifications fall back to when the system can't show them.
+
+use dioxus::prelude::*;
+use futures::{channel::mpsc, StreamExt};
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+use super::{Notification, NotificationTimeout};
+use crate::utils::timing::clock;
+
+/// How long a toast stays when the notification has the default timeout.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+/// How many toasts are stacked before the oldest ones are dropped.
+const MAX_TOASTS: usize = 5;
+
+/// The style of the toasts of [`ToastStack`] when no class is given.
+const TOAST_STYLE: &str = "background: #222; color: #fff; padding: 12px 16px; border-radius: 8px; \
+    box-shadow: 0 4px 12px rgba(0, 0, 0, 0.3); min-width: 240px; max-width: 360px;";
+
+/// The mounted toast layer, which notifications can be sent to from any thread.
+static LAYER: Mutex<Option<mpsc::UnboundedSender<Toast>>> = Mutex::new(None);
+
+/// A notification shown inside the app.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toast {
+    /// Identifies the toast in the layer. This is unrelated to [`Notification::id`].
+    pub id: u64,
+    pub summary: String,
+    pub body: String,
+    /// How long the toast stays, or `None` to keep it until it is dismissed.
+    pub timeout: Option<Duration>,
+}
+
+/// Show a toast in the layer of [`use_toast_provider`], returning whether a layer was mounted to show it.
+///
+/// [`Notification::toast_fallback`] shows notifications the system couldn't show this way. Call this directly for
+/// messages that should always stay inside the app.
+pub fn show_toast(
+    summary: impl ToString,
+    body: impl ToString,
+    timeout: NotificationTimeout,
+) -> bool {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+    let timeout = match timeout {
+        NotificationTimeout::Default => Some(DEFAULT_TIMEOUT),
+        NotificationTimeout::Never => None,
+        NotificationTimeout::Milliseconds(ms) => Some(Duration::from_millis(ms.into())),
+    };
+    let toast = Toast {
+        id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+        summary: summary.to_string(),
+        body: body.to_string(),
+        timeout,
+    };
+
+    match &*LAYER.lock().unwrap() {
+        Some(layer) => layer.unbounded_send(toast).is_ok(),
+        None => false,
+    }
+}
+
+/// Show a notification the system couldn't show as a toast.
+pub(crate) fn show_notification(notification: &Notification) -> bool {
+    show_toast(
+        &notification.summary,
+        &notification.body,
+        notification.timeout.clone(),
+    )
+}
+
+/// A handle to the toast layer.
+///
+/// See [`use_toast_provider`] for more information.
+#[derive(Clone, Copy, PartialEq)]
+pub struct ToastLayer {
+    toasts: Signal<Vec<Toast>>,
+}
+
+impl ToastLayer {
+    /// Get the toasts that are showing, from oldest to newest.
+    pub fn toasts(&self) -> ReadOnlySignal<Vec<Toast>> {
+        self.toasts.into()
+    }
+
+    /// Hide a toast before its timeout.
+    pub fn dismiss(&self, id: u64) {
+        let mut toasts = self.toasts;
+        toasts.write().retain(|toast| toast.id != id);
+    }
+
+    /// Hide every toast.
+    pub fn clear(&self) {
+        let mut toasts = self.toasts;
+        toasts.write().clear();
+    }
+}
+
+/// A hook that mounts the toast layer notifications fall back to, for rendering the toasts yourself.
+///
+/// Call this once, near the root of the app. While it is mounted, notifications with
+/// [`Notification::toast_fallback`] that the system can't show are added to the layer, and [`show_toast`] can add
+/// toasts directly. Every toast is removed after its timeout. Use [`ToastStack`] for a ready-made layer.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::notification::use_toast_provider;
+///
+/// fn App() -> Element {
+///     let layer = use_toast_provider();
+///
+///     rsx! {
+///         div { class: "toasts",
+///             for toast in layer.toasts()() {
+///                 div {
+///                     key: "{toast.id}",
+///                     onclick: move |_| layer.dismiss(toast.id),
+///                     b { "{toast.summary}" }
+///                     p { "{toast.body}" }
+///                 }
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_toast_provider() -> ToastLayer {
+    let (layer, sender) = use_hook(|| {
+        let (sender, mut receiver) = mpsc::unbounded::<Toast>();
+        *LAYER.lock().unwrap() = Some(sender.clone());
+
+        let layer = ToastLayer {
+            toasts: Signal::new(Vec::new()),
+        };
+        spawn(async move {
+            while let Some(toast) = receiver.next().await {
+                let mut toasts = layer.toasts;
+                let id = toast.id;
+                let timeout = toast.timeout;
+                {
+                    let mut toasts = toasts.write();
+                    toasts.push(toast);
+                    let overflow = toasts.len().saturating_sub(MAX_TOASTS);
+                    toasts.drain(..overflow);
+                }
+
+                if let Some(timeout) = timeout {
+                    spawn(async move {
+                        clock::sleep(timeout).await;
+                        layer.dismiss(id);
+                    });
+                }
+            }
+        });
+
+        (layer, sender)
+    });
+
+    // Stop routing notifications here once the layer is gone, unless another layer took over.
+    use_drop(move || {
+        let mut current = LAYER.lock().unwrap();
+        if current
+            .as_ref()
+            .is_some_and(|current| current.same_receiver(&sender))
+        {
+            *current = None;
+        }
+    });
+
+    layer
+}
+
+/// A ready-made toast layer that stacks toasts in the bottom right corner of the window.
+///
+/// Every toast has a button to dismiss it. Use [`use_toast_provider`] to render the toasts yourself.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::notification::{Notification, ToastStack};
+///
+/// fn App() -> Element {
+///     rsx! {
+///         button {
+///             onclick: move |_| {
+///                 Notification::new()
+///                     .summary("Saved".to_string())
+///                     .toast_fallback(true)
+///                     .show()
+///                     .ok();
+///             },
+///             "Save"
+///         }
+///         ToastStack {}
+///     }
+/// }
+/// ```
+#[component]
+pub fn ToastStack(
+    /// The class of every toast. The stack has no styles for toasts once this is set.
+    #[props(default)]
+    class: String,
+) -> Element {
+    let layer = use_toast_provider();
+    let toast_style = if class.is_empty() { TOAST_STYLE } else { "" };
+
+    rsx! {
+        div {
+            style: "position: fixed; right: 16px; bottom: 16px; z-index: 2147483647; \
+                display: flex; flex-direction: column; gap: 8px;",
+            for toast in layer.toasts()() {
+                div {
+                    key: "{toast.id}",
+                    class: class.clone(),
+                    style: toast_style,
+                    role: "status",
+                    button {
+                        style: "float: right; background: none; border: none; color: inherit; cursor: pointer;",
+                        aria_label: "Dismiss",
+                        onclick: move |_| layer.dismiss(toast.id),
+                        "×"
+                    }
+                    b { "{toast.summary}" }
+                    if !toast.body.is_empty() {
+                        p { style: "margin: 4px 0 0;", "{toast.body}" }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! The permission center shared by all capabilities.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use tokio::sync::watch::{channel, Receiver, Sender};
+
+use super::platform;
+
+/// A capability that the user may have to grant the app access to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Permission {
+    Geolocation,
+    Notifications,
+    Camera,
+    Microphone,
+    /// Reading the clipboard. Writing to the clipboard doesn't require a permission.
+    ClipboardRead,
+    Bluetooth,
+}
+
+impl Permission {
+    /// The name of the permission in the web Permissions API.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Geolocation => "geolocation",
+            Self::Notifications => "notifications",
+            Self::Camera => "camera",
+            Self::Microphone => "microphone",
+            Self::ClipboardRead => "clipboard-read",
+            Self::Bluetooth => "bluetooth",
+        }
+    }
+}
+
+/// Describes whether the app has access to a [`Permission`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PermissionStatus {
+    /// The app has access.
+    Granted,
+    /// The user or the system denied access.
+    Denied,
+    /// The user will be asked when the app requests access.
+    #[default]
+    Prompt,
+    /// The permission isn't available on this platform.
+    Unsupported,
+}
+
+type Statuses = HashMap<Permission, PermissionStatus>;
+
+/// The last known status of every permission.
+fn statuses() -> &'static Sender<Statuses> {
+    static STATUSES: OnceLock<Sender<Statuses>> = OnceLock::new();
+    STATUSES.get_or_init(|| channel(HashMap::new()).0)
+}
+
+/// Subscribe to changes of the last known permission statuses.
+pub(crate) fn subscribe() -> Receiver<Statuses> {
+    statuses().subscribe()
+}
+
+/// Get the last known status of a permission without asking the platform.
+pub fn last_known_status(permission: Permission) -> Option<PermissionStatus> {
+    statuses().borrow().get(&permission).copied()
+}
+
+/// Record the status of a permission and notify every [`use_permission`](super::use_permission) hook.
+///
+/// SDK modules that find out about a permission while using it, like geolocation being denied, report it here
+/// so the whole app sees a consistent status. Apps can do the same for capabilities they access themselves.
+pub fn report_permission(permission: Permission, status: PermissionStatus) {
+    statuses().send_if_modified(|statuses| statuses.insert(permission, status) != Some(status));
+}
+
+/// Ask the platform for the status of a permission without prompting the user.
+pub async fn query_permission(permission: Permission) -> PermissionStatus {
+    let status = platform::query(permission).await;
+    report_permission(permission, status);
+    status
+}
+
+/// Ask the user to grant a permission if they haven't decided yet, and return the resulting status.
+///
+/// On the web this must be called in response to a user interaction, like a click, or browsers may ignore it.
+pub async fn request_permission(permission: Permission) -> PermissionStatus {
+    let status = platform::request(permission).await;
+    report_permission(permission, status);
+    status
+}
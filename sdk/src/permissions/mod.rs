@@ -0,0 +1,8 @@
+//! A single place to check and request the permissions of every capability.
+
+mod core;
+mod platform;
+mod use_permission;
+
+pub use self::core::*;
+pub use self::use_permission::*;
@@ -0,0 +1,47 @@
+use crate::permissions::{Permission, PermissionStatus};
+
+/// Get the status of a permission.
+pub async fn query(permission: Permission) -> PermissionStatus {
+    status(permission).await
+}
+
+/// Request a permission.
+///
+/// Desktop platforms don't prompt for these permissions at runtime, so this is the same as [`query`].
+pub async fn request(permission: Permission) -> PermissionStatus {
+    status(permission).await
+}
+
+async fn status(permission: Permission) -> PermissionStatus {
+    match permission {
+        // Desktop apps can show notifications and read the clipboard without asking.
+        Permission::Notifications | Permission::ClipboardRead => PermissionStatus::Granted,
+        Permission::Geolocation => geolocation_status().await,
+        Permission::Camera | Permission::Microphone => PermissionStatus::Unsupported,
+        // macOS asks for access the first time the Bluetooth adapter is used. Other platforms don't ask.
+        Permission::Bluetooth if cfg!(target_os = "macos") => PermissionStatus::Prompt,
+        Permission::Bluetooth => PermissionStatus::Granted,
+    }
+}
+
+#[cfg(windows)]
+async fn geolocation_status() -> PermissionStatus {
+    use windows::Devices::Geolocation::{GeolocationAccessStatus, Geolocator};
+
+    // Desktop apps don't get a prompt. Access is controlled in the privacy settings.
+    // Waiting for the answer blocks, so it happens off the UI thread.
+    let access = tokio::task::spawn_blocking(|| {
+        Geolocator::RequestAccessAsync().and_then(|access| access.get())
+    });
+    match access.await {
+        Ok(Ok(GeolocationAccessStatus::Allowed)) => PermissionStatus::Granted,
+        Ok(Ok(GeolocationAccessStatus::Denied)) => PermissionStatus::Denied,
+        Ok(Ok(_)) => PermissionStatus::Prompt,
+        _ => PermissionStatus::Unsupported,
+    }
+}
+
+#[cfg(not(windows))]
+async fn geolocation_status() -> PermissionStatus {
+    PermissionStatus::Unsupported
+}
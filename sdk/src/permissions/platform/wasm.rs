@@ -0,0 +1,225 @@
+use std::{cell::RefCell, collections::HashSet};
+
+use js_sys::{Array, Function, Object, Promise, Reflect};
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+use crate::permissions::{report_permission, Permission, PermissionStatus};
+
+thread_local! {
+    /// The permissions that are already being watched for changes.
+    static WATCHED: RefCell<HashSet<Permission>> = RefCell::new(HashSet::new());
+}
+
+/// Get the status of a permission without prompting the user.
+pub async fn query(permission: Permission) -> PermissionStatus {
+    match query_permissions_api(permission).await {
+        Some(status) => status,
+        None => fallback_status(permission),
+    }
+}
+
+/// Prompt the user for a permission by using the API it guards.
+pub async fn request(permission: Permission) -> PermissionStatus {
+    let Some(navigator) = navigator() else {
+        return PermissionStatus::Unsupported;
+    };
+
+    let status = match permission {
+        Permission::Geolocation => request_position(&navigator).await,
+        Permission::Notifications => request_notifications().await,
+        Permission::Camera => request_media(&navigator, "video").await,
+        Permission::Microphone => request_media(&navigator, "audio").await,
+        Permission::ClipboardRead => request_clipboard(&navigator).await,
+        Permission::Bluetooth => request_bluetooth(&navigator).await,
+    };
+    status.unwrap_or(PermissionStatus::Unsupported)
+}
+
+/// Query the Permissions API and watch the permission for changes made in the browser settings.
+///
+/// Returns `None` if the browser doesn't support the Permissions API or doesn't know the permission.
+async fn query_permissions_api(permission: Permission) -> Option<PermissionStatus> {
+    let permissions = get(&navigator()?, "permissions")?;
+    let descriptor = Object::new();
+    Reflect::set(&descriptor, &"name".into(), &permission.name().into()).ok()?;
+    let result = call_async(&permissions, "query", &[descriptor.into()])
+        .await?
+        .ok()?;
+
+    watch_changes(permission, &result);
+    status_from_state(&result)
+}
+
+fn watch_changes(permission: Permission, result: &JsValue) {
+    let is_new = WATCHED.with(|watched| watched.borrow_mut().insert(permission));
+    if !is_new {
+        return;
+    }
+
+    let target = result.clone();
+    let onchange = Closure::wrap(Box::new(move |_: JsValue| {
+        if let Some(status) = status_from_state(&target) {
+            report_permission(permission, status);
+        }
+    }) as Box<dyn FnMut(JsValue)>);
+    Reflect::set(result, &"onchange".into(), onchange.as_ref()).ok();
+    // The status object lives as long as the page.
+    onchange.forget();
+}
+
+/// The status of a permission when the Permissions API can't tell.
+fn fallback_status(permission: Permission) -> PermissionStatus {
+    let Some(navigator) = navigator() else {
+        return PermissionStatus::Unsupported;
+    };
+
+    let api = match permission {
+        Permission::Notifications => {
+            return notification_api()
+                .and_then(|notification| get(&notification, "permission"))
+                .and_then(|state| state.as_string())
+                .map(|state| state_to_status(&state))
+                .unwrap_or(PermissionStatus::Unsupported);
+        }
+        Permission::Geolocation => get(&navigator, "geolocation"),
+        Permission::Camera | Permission::Microphone => get(&navigator, "mediaDevices"),
+        Permission::ClipboardRead => get(&navigator, "clipboard"),
+        Permission::Bluetooth => get(&navigator, "bluetooth"),
+    };
+
+    match api {
+        Some(_) => PermissionStatus::Prompt,
+        None => PermissionStatus::Unsupported,
+    }
+}
+
+async fn request_position(navigator: &JsValue) -> Option<PermissionStatus> {
+    let geolocation = get(navigator, "geolocation")?;
+    let promise = Promise::new(&mut |resolve, reject| {
+        call(
+            &geolocation,
+            "getCurrentPosition",
+            &[resolve.into(), reject.into()],
+        );
+    });
+
+    Some(match JsFuture::from(promise).await {
+        Ok(_) => PermissionStatus::Granted,
+        // Error code 1 is PERMISSION_DENIED. Other errors mean access was granted but the position is unavailable.
+        Err(e) if get(&e, "code").and_then(|code| code.as_f64()) == Some(1.0) => {
+            PermissionStatus::Denied
+        }
+        Err(_) => PermissionStatus::Granted,
+    })
+}
+
+async fn request_notifications() -> Option<PermissionStatus> {
+    let state = call_async(&notification_api()?, "requestPermission", &[])
+        .await?
+        .ok()?
+        .as_string()?;
+    Some(state_to_status(&state))
+}
+
+async fn request_media(navigator: &JsValue, kind: &str) -> Option<PermissionStatus> {
+    let media_devices = get(navigator, "mediaDevices")?;
+    let constraints = Object::new();
+    Reflect::set(&constraints, &kind.into(), &JsValue::TRUE).ok()?;
+
+    Some(
+        match call_async(&media_devices, "getUserMedia", &[constraints.into()]).await? {
+            Ok(stream) => {
+                // Only the permission is needed, so release the device right away.
+                if let Some(tracks) = call(&stream, "getTracks", &[]) {
+                    for track in Array::from(&tracks).iter() {
+                        call(&track, "stop", &[]);
+                    }
+                }
+                PermissionStatus::Granted
+            }
+            // There is no device to grant access to.
+            Err(e)
+                if get(&e, "name").and_then(|name| name.as_string()).as_deref()
+                    == Some("NotFoundError") =>
+            {
+                PermissionStatus::Unsupported
+            }
+            Err(_) => PermissionStatus::Denied,
+        },
+    )
+}
+
+async fn request_clipboard(navigator: &JsValue) -> Option<PermissionStatus> {
+    let clipboard = get(navigator, "clipboard")?;
+    Some(match call_async(&clipboard, "readText", &[]).await? {
+        Ok(_) => PermissionStatus::Granted,
+        Err(_) => PermissionStatus::Denied,
+    })
+}
+
+/// Access is granted per device on the web, so this asks the user to pick any device.
+async fn request_bluetooth(navigator: &JsValue) -> Option<PermissionStatus> {
+    let bluetooth = get(navigator, "bluetooth")?;
+    let options = Object::new();
+    Reflect::set(&options, &"acceptAllDevices".into(), &JsValue::TRUE).ok()?;
+
+    Some(
+        match call_async(&bluetooth, "requestDevice", &[options.into()]).await? {
+            Ok(_) => PermissionStatus::Granted,
+            // The user closed the chooser without deciding.
+            Err(e)
+                if get(&e, "name").and_then(|name| name.as_string()).as_deref()
+                    == Some("NotFoundError") =>
+            {
+                PermissionStatus::Prompt
+            }
+            Err(_) => PermissionStatus::Denied,
+        },
+    )
+}
+
+fn status_from_state(status: &JsValue) -> Option<PermissionStatus> {
+    let state = get(status, "state")?.as_string()?;
+    Some(state_to_status(&state))
+}
+
+fn state_to_status(state: &str) -> PermissionStatus {
+    match state {
+        "granted" => PermissionStatus::Granted,
+        "denied" => PermissionStatus::Denied,
+        // The Notification API calls this "default".
+        _ => PermissionStatus::Prompt,
+    }
+}
+
+fn navigator() -> Option<JsValue> {
+    get(&web_sys::window()?, "navigator")
+}
+
+fn notification_api() -> Option<JsValue> {
+    get(&web_sys::window()?, "Notification")
+}
+
+/// Get a property, treating `undefined` and `null` as missing.
+fn get(target: &JsValue, key: &str) -> Option<JsValue> {
+    Reflect::get(target, &key.into())
+        .ok()
+        .filter(|value| !value.is_undefined() && !value.is_null())
+}
+
+/// Call a method, returning `None` if it doesn't exist or throws.
+fn call(target: &JsValue, method: &str, args: &[JsValue]) -> Option<JsValue> {
+    let function: Function = get(target, method)?.dyn_into().ok()?;
+    function.apply(target, &args.iter().collect::<Array>()).ok()
+}
+
+/// Call a method that returns a promise and wait for it, returning `None` if the method doesn't exist.
+async fn call_async(
+    target: &JsValue,
+    method: &str,
+    args: &[JsValue],
+) -> Option<Result<JsValue, JsValue>> {
+    let promise: Promise = call(target, method, args)?.dyn_into().ok()?;
+    Some(JsFuture::from(promise).await)
+}
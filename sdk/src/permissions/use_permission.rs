@@ -0,0 +1,89 @@
+//! Provides the use_permission hook.
+
+use dioxus::prelude::*;
+
+use super::core::{
+    last_known_status, query_permission, request_permission, subscribe, Permission,
+    PermissionStatus,
+};
+
+/// The interface for a permission.
+///
+/// See [`use_permission`] for more information.
+#[derive(Clone, Copy, PartialEq)]
+pub struct UsePermission {
+    permission: Permission,
+    status: Signal<PermissionStatus>,
+}
+
+impl UsePermission {
+    /// The permission this hook tracks.
+    pub fn permission(&self) -> Permission {
+        self.permission
+    }
+
+    /// Get the current status of the permission.
+    pub fn status(&self) -> ReadOnlySignal<PermissionStatus> {
+        ReadOnlySignal::new(self.status)
+    }
+
+    /// Ask the user to grant the permission. The status is updated once they decide.
+    ///
+    /// On the web this must be called in response to a user interaction, like a click, or browsers may ignore it.
+    pub fn request(&self) {
+        let permission = self.permission;
+        spawn(async move {
+            request_permission(permission).await;
+        });
+    }
+}
+
+/// A hook for the status of a [`Permission`].
+///
+/// The status is queried from the platform when the hook is created and kept in sync with every request
+/// and every status reported by other parts of the SDK, like geolocation being denied.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::permissions::{use_permission, Permission, PermissionStatus};
+///
+/// fn App() -> Element {
+///     let camera = use_permission(Permission::Camera);
+///
+///     match camera.status()() {
+///         PermissionStatus::Granted => rsx! { "Camera ready" },
+///         PermissionStatus::Denied => rsx! { "Enable the camera in your settings" },
+///         PermissionStatus::Prompt => rsx! {
+///             button { onclick: move |_| camera.request(), "Allow camera" }
+///         },
+///         PermissionStatus::Unsupported => rsx! { "No camera available" },
+///     }
+/// }
+/// ```
+pub fn use_permission(permission: Permission) -> UsePermission {
+    use_hook(|| {
+        let mut status = Signal::new(last_known_status(permission).unwrap_or_default());
+
+        spawn(async move {
+            let mut statuses = subscribe();
+            query_permission(permission).await;
+
+            loop {
+                let latest = statuses.borrow_and_update().get(&permission).copied();
+                if let Some(latest) = latest {
+                    if *status.peek() != latest {
+                        status.set(latest);
+                    }
+                }
+
+                if statuses.changed().await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        UsePermission { permission, status }
+    })
+}
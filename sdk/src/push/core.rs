@@ -0,0 +1,219 @@
+//! The push types shared by every platform.
+
+use futures::{
+    channel::mpsc::{self, UnboundedReceiver, UnboundedSender},
+    Stream, StreamExt,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    fmt,
+    pin::Pin,
+    sync::Mutex,
+    task::{Context, Poll},
+};
+
+use super::platform;
+
+/// A service worker script that forwards push messages to the app.
+///
+/// Serve it from your app, for example at `/push-worker.js`, and register it with [`register_service_worker`].
+/// Messages arriving while no page of the app is visible are also shown as a notification. If the payload is a
+/// JSON object, its `title` and `body` fields are used for the notification.
+pub const PUSH_SERVICE_WORKER: &str = r#"self.addEventListener("push", (event) => {
+    const payload = event.data ? event.data.text() : "";
+    event.waitUntil((async () => {
+        const windows = await self.clients.matchAll({ type: "window", includeUncontrolled: true });
+        for (const client of windows) {
+            client.postMessage({ dioxusPush: payload });
+        }
+        if (!windows.some((client) => client.visibilityState === "visible")) {
+            let notification = { title: "", body: payload };
+            try {
+                notification = { ...notification, ...JSON.parse(payload) };
+            } catch {}
+            await self.registration.showNotification(notification.title, { body: notification.body });
+        }
+    })());
+});
+"#;
+
+/// Register the service worker at `url`, which should serve [`PUSH_SERVICE_WORKER`] or a script that forwards
+/// messages the same way.
+///
+/// This is only supported on the web.
+pub async fn register_service_worker(url: &str) -> Result<(), PushError> {
+    platform::register(url).await
+}
+
+/// Subscribe to Web Push with the public VAPID key of your server, encoded as URL-safe base64.
+///
+/// Send the returned subscription to your server so it can push messages to this browser. The browser asks the
+/// user for permission to show notifications if it wasn't granted yet. A service worker must be registered with
+/// [`register_service_worker`] first.
+///
+/// This is only supported on the web. On mobile, send the [`push_token`] to your server instead.
+pub async fn subscribe_push(vapid_key: &str) -> Result<PushSubscription, PushError> {
+    let subscription = platform::subscribe(vapid_key).await;
+    #[cfg(feature = "permissions")]
+    {
+        use crate::permissions::{report_permission, Permission, PermissionStatus};
+
+        match &subscription {
+            Ok(_) => report_permission(Permission::Notifications, PermissionStatus::Granted),
+            Err(PushError::PermissionDenied) => {
+                report_permission(Permission::Notifications, PermissionStatus::Denied)
+            }
+            Err(_) => {}
+        }
+    }
+    subscription
+}
+
+/// Remove the current Web Push subscription, if any.
+pub async fn unsubscribe_push() -> Result<(), PushError> {
+    platform::unsubscribe().await
+}
+
+/// A Web Push subscription, in the JSON format browsers and push libraries use.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PushSubscription {
+    /// The URL of the push service your server sends messages to.
+    pub endpoint: String,
+    /// When the subscription expires, in milliseconds since the Unix epoch.
+    pub expiration_time: Option<f64>,
+    /// The keys your server encrypts messages with.
+    pub keys: PushKeys,
+}
+
+impl PushSubscription {
+    /// Serialize the subscription as JSON, the same as `JSON.stringify` does in the browser.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("a subscription can always be serialized")
+    }
+}
+
+/// The encryption keys of a [`PushSubscription`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PushKeys {
+    pub p256dh: String,
+    pub auth: String,
+}
+
+/// The push service a [`PushToken`] belongs to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PushService {
+    /// Apple Push Notification service, used on iOS.
+    Apns,
+    /// Firebase Cloud Messaging, used on Android.
+    Fcm,
+}
+
+/// The token your server uses to push messages to this device.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PushToken {
+    pub service: PushService,
+    pub token: String,
+}
+
+/// A message pushed to the app.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PushMessage {
+    /// The payload of the message, as sent by your server.
+    pub payload: String,
+}
+
+impl PushMessage {
+    /// Deserialize the payload from JSON.
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        serde_json::from_str(&self.payload)
+    }
+}
+
+/// The device token, set by the native app code.
+static TOKEN: Mutex<Option<PushToken>> = Mutex::new(None);
+
+/// The streams waiting for messages and tokens. Closed streams are removed the next time something is sent.
+static MESSAGE_LISTENERS: Mutex<Vec<UnboundedSender<PushMessage>>> = Mutex::new(Vec::new());
+static TOKEN_LISTENERS: Mutex<Vec<UnboundedSender<PushToken>>> = Mutex::new(Vec::new());
+
+/// Hand the device token over to the app.
+///
+/// Dioxus doesn't own the app delegate on iOS or the messaging service on Android, so the native app code has to
+/// call this once it receives the token, and again whenever the token changes. This can be called from any thread.
+pub fn set_push_token(token: PushToken) {
+    *TOKEN.lock().unwrap() = Some(token.clone());
+    TOKEN_LISTENERS
+        .lock()
+        .unwrap()
+        .retain(|listener| listener.unbounded_send(token.clone()).is_ok());
+}
+
+/// Get the device token set with [`set_push_token`], if any.
+pub fn push_token() -> Option<PushToken> {
+    TOKEN.lock().unwrap().clone()
+}
+
+/// Hand a received message over to the app.
+///
+/// On the web the service worker does this. On mobile, the native app code has to call this for every message it
+/// receives. This can be called from any thread.
+pub fn deliver_push_message(payload: impl Into<String>) {
+    let message = PushMessage {
+        payload: payload.into(),
+    };
+    MESSAGE_LISTENERS
+        .lock()
+        .unwrap()
+        .retain(|listener| listener.unbounded_send(message.clone()).is_ok());
+}
+
+/// Listen for the messages pushed to the app from now on.
+pub fn push_messages() -> PushMessages {
+    platform::listen();
+    let (sender, receiver) = mpsc::unbounded();
+    MESSAGE_LISTENERS.lock().unwrap().push(sender);
+    PushMessages { receiver }
+}
+
+/// Listen for changes to the device token.
+pub(crate) fn push_token_updates() -> UnboundedReceiver<PushToken> {
+    let (sender, receiver) = mpsc::unbounded();
+    TOKEN_LISTENERS.lock().unwrap().push(sender);
+    receiver
+}
+
+/// The messages pushed to the app. See [`push_messages`].
+pub struct PushMessages {
+    receiver: UnboundedReceiver<PushMessage>,
+}
+
+impl Stream for PushMessages {
+    type Item = PushMessage;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<PushMessage>> {
+        self.get_mut().receiver.poll_next_unpin(cx)
+    }
+}
+
+/// Describes errors that may occur when subscribing to push messages.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PushError {
+    /// The platform doesn't support Web Push.
+    Unsupported,
+    /// The user denied the permission to show notifications.
+    PermissionDenied,
+    /// The platform reported an error.
+    Failed(String),
+}
+
+impl std::error::Error for PushError {}
+impl fmt::Display for PushError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Unsupported => write!(f, "push messages are not supported on this platform"),
+            Self::PermissionDenied => write!(f, "permission to show notifications was denied"),
+            Self::Failed(reason) => write!(f, "push error: {reason}"),
+        }
+    }
+}
@@ -0,0 +1,11 @@
+//! Receive push messages, the client half of a push pipeline.
+//!
+//! On the web, a service worker subscribes to Web Push and forwards the messages to the app. On mobile, the native
+//! app code hands the device token and the received messages over with [`set_push_token`] and [`deliver_push_message`].
+
+mod core;
+mod platform;
+mod use_push_messages;
+
+pub use self::core::*;
+pub use self::use_push_messages::*;
@@ -0,0 +1,17 @@
+use crate::push::{PushError, PushSubscription};
+
+/// Web Push is only available in browsers. Mobile apps use the device token instead.
+pub async fn register(_url: &str) -> Result<(), PushError> {
+    Err(PushError::Unsupported)
+}
+
+pub async fn subscribe(_vapid_key: &str) -> Result<PushSubscription, PushError> {
+    Err(PushError::Unsupported)
+}
+
+pub async fn unsubscribe() -> Result<(), PushError> {
+    Err(PushError::Unsupported)
+}
+
+/// Messages are delivered by the native app code, so there is nothing to listen to.
+pub fn listen() {}
@@ -0,0 +1,139 @@
+use js_sys::{Array, Function, Object, Promise, Reflect, JSON};
+use std::cell::Cell;
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+use crate::push::{deliver_push_message, PushError, PushSubscription};
+
+thread_local! {
+    /// Whether messages from the service worker are already being forwarded.
+    static LISTENING: Cell<bool> = const { Cell::new(false) };
+}
+
+pub async fn register(url: &str) -> Result<(), PushError> {
+    let container = service_worker().ok_or(PushError::Unsupported)?;
+    call_async(&container, "register", &[url.into()])
+        .await
+        .ok_or(PushError::Unsupported)?
+        .map_err(|e| to_error(&e))?;
+    listen();
+    Ok(())
+}
+
+pub async fn subscribe(vapid_key: &str) -> Result<PushSubscription, PushError> {
+    let push_manager = push_manager().await?;
+    let options = Object::new();
+    Reflect::set(&options, &"userVisibleOnly".into(), &JsValue::TRUE).ok();
+    Reflect::set(&options, &"applicationServerKey".into(), &vapid_key.into()).ok();
+
+    let subscription = call_async(&push_manager, "subscribe", &[options.into()])
+        .await
+        .ok_or(PushError::Unsupported)?
+        .map_err(|e| to_error(&e))?;
+
+    // The subscription serializes itself to the JSON format servers expect.
+    let json = JSON::stringify(&subscription)
+        .ok()
+        .and_then(|json| json.as_string())
+        .ok_or_else(|| PushError::Failed("the subscription can't be serialized".to_string()))?;
+    serde_json::from_str(&json).map_err(|e| PushError::Failed(e.to_string()))
+}
+
+pub async fn unsubscribe() -> Result<(), PushError> {
+    let push_manager = push_manager().await?;
+    let subscription = call_async(&push_manager, "getSubscription", &[])
+        .await
+        .ok_or(PushError::Unsupported)?
+        .map_err(|e| to_error(&e))?;
+    if subscription.is_null() {
+        return Ok(());
+    }
+
+    call_async(&subscription, "unsubscribe", &[])
+        .await
+        .ok_or(PushError::Unsupported)?
+        .map(|_| ())
+        .map_err(|e| to_error(&e))
+}
+
+/// Forward the messages the service worker posts to the app.
+pub fn listen() {
+    if LISTENING.with(|listening| listening.replace(true)) {
+        return;
+    }
+    let Some(container) = service_worker() else {
+        return;
+    };
+
+    let listener = Closure::wrap(Box::new(|event: JsValue| {
+        let payload = get(&event, "data")
+            .and_then(|data| get(&data, "dioxusPush"))
+            .and_then(|payload| payload.as_string());
+        if let Some(payload) = payload {
+            deliver_push_message(payload);
+        }
+    }) as Box<dyn FnMut(JsValue)>);
+    call(
+        &container,
+        "addEventListener",
+        &["message".into(), listener.as_ref().clone()],
+    );
+    // Messages are queued until the page starts them, which only happens implicitly for `onmessage`.
+    call(&container, "startMessages", &[]);
+    // The listener stays for the lifetime of the page.
+    listener.forget();
+}
+
+/// Get the push manager of the active service worker.
+///
+/// This waits until a service worker is active, so it never finishes if none was registered.
+async fn push_manager() -> Result<JsValue, PushError> {
+    let container = service_worker().ok_or(PushError::Unsupported)?;
+    let ready: Promise = get(&container, "ready")
+        .and_then(|ready| ready.dyn_into().ok())
+        .ok_or(PushError::Unsupported)?;
+    let registration = JsFuture::from(ready).await.map_err(|e| to_error(&e))?;
+    get(&registration, "pushManager").ok_or(PushError::Unsupported)
+}
+
+fn service_worker() -> Option<JsValue> {
+    get(&get(&web_sys::window()?, "navigator")?, "serviceWorker")
+}
+
+fn to_error(error: &JsValue) -> PushError {
+    match get(error, "name")
+        .and_then(|name| name.as_string())
+        .as_deref()
+    {
+        Some("NotAllowedError") | Some("SecurityError") => PushError::PermissionDenied,
+        Some("NotSupportedError") => PushError::Unsupported,
+        _ => PushError::Failed(
+            get(error, "message")
+                .and_then(|message| message.as_string())
+                .unwrap_or_else(|| format!("{error:?}")),
+        ),
+    }
+}
+
+/// Get a property, treating `undefined` and `null` as missing.
+fn get(target: &JsValue, key: &str) -> Option<JsValue> {
+    Reflect::get(target, &key.into())
+        .ok()
+        .filter(|value| !value.is_undefined() && !value.is_null())
+}
+
+/// Call a method, returning `None` if it doesn't exist or throws.
+fn call(target: &JsValue, method: &str, args: &[JsValue]) -> Option<JsValue> {
+    let function: Function = get(target, method)?.dyn_into().ok()?;
+    function.apply(target, &args.iter().collect::<Array>()).ok()
+}
+
+/// Call a method that returns a promise and wait for it, returning `None` if the method doesn't exist.
+async fn call_async(
+    target: &JsValue,
+    method: &str,
+    args: &[JsValue],
+) -> Option<Result<JsValue, JsValue>> {
+    let promise: Promise = call(target, method, args)?.dyn_into().ok()?;
+    Some(JsFuture::from(promise).await)
+}
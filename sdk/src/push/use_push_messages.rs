@@ -0,0 +1,61 @@
+//! Provides the use_push_messages and use_push_token hooks.
+
+use dioxus::prelude::*;
+use futures::StreamExt;
+
+use super::core::{push_messages, push_token, push_token_updates, PushMessage, PushToken};
+
+/// A hook for the latest message pushed to the app.
+///
+/// On the web, register a service worker with [`register_service_worker`](super::register_service_worker) so
+/// messages reach the app. To handle every message instead of only the latest one, use
+/// [`push_messages`](super::push_messages).
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::push::use_push_messages;
+///
+/// fn App() -> Element {
+///     let message = use_push_messages();
+///
+///     rsx! {
+///         if let Some(message) = message() {
+///             p { "New message: {message.payload}" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_push_messages() -> ReadOnlySignal<Option<PushMessage>> {
+    let message = use_hook(|| {
+        let mut message = Signal::new(None);
+        let mut messages = push_messages();
+        spawn(async move {
+            while let Some(next) = messages.next().await {
+                message.set(Some(next));
+            }
+        });
+        message
+    });
+
+    ReadOnlySignal::new(message)
+}
+
+/// A hook for the device token on mobile, once the native app code set it with
+/// [`set_push_token`](super::set_push_token).
+pub fn use_push_token() -> ReadOnlySignal<Option<PushToken>> {
+    let token = use_hook(|| {
+        // Listen first so a token set in between isn't missed.
+        let mut updates = push_token_updates();
+        let mut token = Signal::new(push_token());
+        spawn(async move {
+            while let Some(next) = updates.next().await {
+                token.set(Some(next));
+            }
+        });
+        token
+    });
+
+    ReadOnlySignal::new(token)
+}
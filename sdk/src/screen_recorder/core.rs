@@ -0,0 +1,92 @@
+//! The screen recorder types shared by every platform.
+
+use std::{fmt, path::Path};
+
+use super::platform;
+
+/// The state of a screen recorder.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum RecorderState {
+    /// Nothing is being recorded.
+    #[default]
+    Idle,
+    /// The user is choosing what to record.
+    Starting,
+    Recording,
+    Paused,
+    /// The recording is being finished.
+    Stopping,
+}
+
+/// Options for a screen recording.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RecordingOptions {
+    /// The container and codecs of the video, like `video/webm;codecs=vp9` or `video/mp4`.
+    ///
+    /// If the browser doesn't support the format, it picks one itself. Most browsers record to WebM.
+    pub mime_type: Option<String>,
+    /// Whether to record the audio of the tab or system too, if the user allows it.
+    pub audio: bool,
+    /// The bits per second of the video, or `None` to let the browser choose.
+    pub bits_per_second: Option<u32>,
+}
+
+/// A finished screen recording.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recording {
+    /// The format of the video, like `video/webm`.
+    pub mime_type: String,
+    /// The encoded video.
+    pub data: Vec<u8>,
+}
+
+impl Recording {
+    /// The file extension matching the format of the video, without a dot.
+    pub fn extension(&self) -> &'static str {
+        match self.mime_type.split(';').next().map(str::trim) {
+            Some("video/mp4") => "mp4",
+            Some("video/x-matroska") => "mkv",
+            _ => "webm",
+        }
+    }
+
+    /// Let the user download the video as `file_name`.
+    ///
+    /// This is only supported on the web. Use [`Recording::save`] on other platforms.
+    pub fn download(&self, file_name: &str) -> Result<(), RecorderError> {
+        platform::download(self, file_name)
+    }
+
+    /// Write the video to a file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), RecorderError> {
+        std::fs::write(path, &self.data).map_err(|e| RecorderError::Failed(e.to_string()))
+    }
+}
+
+/// Describes errors that may occur when recording the screen.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecorderError {
+    /// The platform can't record the screen.
+    Unsupported,
+    /// The user didn't allow recording, or closed the picker without choosing anything.
+    PermissionDenied,
+    /// A recording is already running.
+    AlreadyRecording,
+    /// Nothing is being recorded.
+    NotRecording,
+    /// The platform reported an error.
+    Failed(String),
+}
+
+impl std::error::Error for RecorderError {}
+impl fmt::Display for RecorderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Unsupported => write!(f, "screen recording is not supported on this platform"),
+            Self::PermissionDenied => write!(f, "permission to record the screen was denied"),
+            Self::AlreadyRecording => write!(f, "a recording is already running"),
+            Self::NotRecording => write!(f, "nothing is being recorded"),
+            Self::Failed(reason) => write!(f, "screen recording error: {reason}"),
+        }
+    }
+}
@@ -0,0 +1,11 @@
+//! Record the screen, a window, or a browser tab to a video.
+//!
+//! Recording uses the screen capture and `MediaRecorder` APIs of the browser, so it is only available on the web.
+//! Native capture on desktop isn't implemented yet.
+
+mod core;
+mod platform;
+mod use_screen_recorder;
+
+pub use self::core::*;
+pub use self::use_screen_recorder::*;
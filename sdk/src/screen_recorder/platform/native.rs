@@ -0,0 +1,26 @@
+use crate::screen_recorder::{RecorderError, Recording, RecordingOptions};
+
+/// Native screen capture isn't implemented yet.
+pub struct Recorder;
+
+impl Recorder {
+    pub async fn start(
+        _options: &RecordingOptions,
+        _on_data: impl FnMut(u64) + 'static,
+        _on_end: impl FnMut() + 'static,
+    ) -> Result<Self, RecorderError> {
+        Err(RecorderError::Unsupported)
+    }
+
+    pub fn pause(&self) {}
+
+    pub fn resume(&self) {}
+
+    pub async fn stop(self) -> Result<Recording, RecorderError> {
+        Err(RecorderError::Unsupported)
+    }
+}
+
+pub fn download(_recording: &Recording, _file_name: &str) -> Result<(), RecorderError> {
+    Err(RecorderError::Unsupported)
+}
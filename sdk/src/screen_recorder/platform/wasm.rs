@@ -0,0 +1,243 @@
+use futures::channel::oneshot;
+use js_sys::{Array, Function, Object, Promise, Reflect, Uint8Array};
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+use crate::screen_recorder::{RecorderError, Recording, RecordingOptions};
+
+/// How often the recorder hands over the video recorded so far, in milliseconds.
+const TIMESLICE: u32 = 1000;
+
+/// A running `MediaRecorder` and the stream it records.
+pub struct Recorder {
+    recorder: JsValue,
+    stream: JsValue,
+    chunks: Rc<RefCell<Vec<JsValue>>>,
+    stopped: Rc<RefCell<Option<oneshot::Sender<()>>>>,
+    // The listeners are kept alive for as long as the recorder.
+    _listeners: Vec<Closure<dyn FnMut(JsValue)>>,
+}
+
+impl Recorder {
+    pub async fn start(
+        options: &RecordingOptions,
+        mut on_data: impl FnMut(u64) + 'static,
+        mut on_end: impl FnMut() + 'static,
+    ) -> Result<Self, RecorderError> {
+        let window = web_sys::window().ok_or(RecorderError::Unsupported)?;
+        let media_devices = get(&window, "navigator")
+            .and_then(|navigator| get(&navigator, "mediaDevices"))
+            .ok_or(RecorderError::Unsupported)?;
+        let constructor: Function = get(&window, "MediaRecorder")
+            .and_then(|constructor| constructor.dyn_into().ok())
+            .ok_or(RecorderError::Unsupported)?;
+
+        let constraints = Object::new();
+        Reflect::set(&constraints, &"video".into(), &JsValue::TRUE).ok();
+        Reflect::set(&constraints, &"audio".into(), &options.audio.into()).ok();
+        let stream = call_async(&media_devices, "getDisplayMedia", &[constraints.into()])
+            .await
+            .ok_or(RecorderError::Unsupported)?
+            .map_err(|e| to_error(&e))?;
+
+        let recorder_options = Object::new();
+        let supported = |mime_type: &str| {
+            get(&constructor, "isTypeSupported")
+                .and_then(|function| function.dyn_into::<Function>().ok())
+                .and_then(|function| function.call1(&constructor, &mime_type.into()).ok())
+                .and_then(|supported| supported.as_bool())
+                .unwrap_or(false)
+        };
+        if let Some(mime_type) = options.mime_type.as_deref().filter(|m| supported(m)) {
+            Reflect::set(&recorder_options, &"mimeType".into(), &mime_type.into()).ok();
+        }
+        if let Some(bits_per_second) = options.bits_per_second {
+            Reflect::set(
+                &recorder_options,
+                &"bitsPerSecond".into(),
+                &bits_per_second.into(),
+            )
+            .ok();
+        }
+
+        let recorder =
+            Reflect::construct(&constructor, &Array::of2(&stream, &recorder_options.into()))
+                .map_err(|e| {
+                    stop_tracks(&stream);
+                    to_error(&e)
+                })?;
+
+        let chunks = Rc::new(RefCell::new(Vec::new()));
+        let stopped = Rc::new(RefCell::new(None));
+        let mut listeners = Vec::new();
+
+        let on_data_available = Closure::wrap(Box::new({
+            let chunks = chunks.clone();
+            let mut size = 0.0;
+            move |event: JsValue| {
+                if let Some(data) = get(&event, "data") {
+                    size += get(&data, "size").and_then(|s| s.as_f64()).unwrap_or(0.0);
+                    chunks.borrow_mut().push(data);
+                    on_data(size as u64);
+                }
+            }
+        }) as Box<dyn FnMut(JsValue)>);
+        listen(&recorder, "dataavailable", &on_data_available);
+        listeners.push(on_data_available);
+
+        let on_stop = Closure::wrap(Box::new({
+            let stopped = stopped.clone();
+            move |_: JsValue| {
+                if let Some(stopped) = stopped.borrow_mut().take() {
+                    stopped.send(()).ok();
+                }
+            }
+        }) as Box<dyn FnMut(JsValue)>);
+        listen(&recorder, "stop", &on_stop);
+        listeners.push(on_stop);
+
+        // The user can stop sharing from the browser, which ends the video track.
+        let on_track_ended =
+            Closure::wrap(Box::new(move |_: JsValue| on_end()) as Box<dyn FnMut(JsValue)>);
+        if let Some(tracks) = call(&stream, "getVideoTracks", &[]) {
+            for track in Array::from(&tracks).iter() {
+                listen(&track, "ended", &on_track_ended);
+            }
+        }
+        listeners.push(on_track_ended);
+
+        call(&recorder, "start", &[TIMESLICE.into()]).ok_or_else(|| {
+            stop_tracks(&stream);
+            RecorderError::Failed("the recorder couldn't be started".to_string())
+        })?;
+
+        Ok(Self {
+            recorder,
+            stream,
+            chunks,
+            stopped,
+            _listeners: listeners,
+        })
+    }
+
+    pub fn pause(&self) {
+        call(&self.recorder, "pause", &[]);
+    }
+
+    pub fn resume(&self) {
+        call(&self.recorder, "resume", &[]);
+    }
+
+    pub async fn stop(self) -> Result<Recording, RecorderError> {
+        // The recorder stops by itself once the user stops sharing.
+        let inactive = get(&self.recorder, "state")
+            .and_then(|state| state.as_string())
+            .map_or(true, |state| state == "inactive");
+        if !inactive {
+            // The last chunk is handed over before the stop event.
+            let (tx, rx) = oneshot::channel();
+            *self.stopped.borrow_mut() = Some(tx);
+            call(&self.recorder, "stop", &[]);
+            rx.await.ok();
+        }
+        stop_tracks(&self.stream);
+
+        let mime_type = get(&self.recorder, "mimeType")
+            .and_then(|mime_type| mime_type.as_string())
+            .filter(|mime_type| !mime_type.is_empty())
+            .unwrap_or_else(|| "video/webm".to_string());
+        let chunks: Array = self.chunks.borrow().iter().collect();
+        let blob = new_blob(&chunks, &mime_type)?;
+        let buffer = call_async(&blob, "arrayBuffer", &[])
+            .await
+            .ok_or(RecorderError::Unsupported)?
+            .map_err(|e| to_error(&e))?;
+
+        Ok(Recording {
+            mime_type,
+            data: Uint8Array::new(&buffer).to_vec(),
+        })
+    }
+}
+
+pub fn download(recording: &Recording, file_name: &str) -> Result<(), RecorderError> {
+    let window = web_sys::window().ok_or(RecorderError::Unsupported)?;
+    let document = get(&window, "document").ok_or(RecorderError::Unsupported)?;
+    let url_api = get(&window, "URL").ok_or(RecorderError::Unsupported)?;
+
+    let data = Uint8Array::from(recording.data.as_slice());
+    let blob = new_blob(&Array::of1(&data), &recording.mime_type)?;
+    let url = call(&url_api, "createObjectURL", &[blob]).ok_or(RecorderError::Unsupported)?;
+
+    let link = call(&document, "createElement", &["a".into()]).ok_or(RecorderError::Unsupported)?;
+    Reflect::set(&link, &"href".into(), &url).ok();
+    Reflect::set(&link, &"download".into(), &file_name.into()).ok();
+    call(&link, "click", &[]);
+    call(&url_api, "revokeObjectURL", &[url]);
+    Ok(())
+}
+
+fn new_blob(parts: &Array, mime_type: &str) -> Result<JsValue, RecorderError> {
+    let constructor: Function = web_sys::window()
+        .and_then(|window| get(&window, "Blob"))
+        .and_then(|constructor| constructor.dyn_into().ok())
+        .ok_or(RecorderError::Unsupported)?;
+    let options = Object::new();
+    Reflect::set(&options, &"type".into(), &mime_type.into()).ok();
+    Reflect::construct(&constructor, &Array::of2(parts, &options)).map_err(|e| to_error(&e))
+}
+
+fn stop_tracks(stream: &JsValue) {
+    if let Some(tracks) = call(stream, "getTracks", &[]) {
+        for track in Array::from(&tracks).iter() {
+            call(&track, "stop", &[]);
+        }
+    }
+}
+
+fn listen(target: &JsValue, event: &str, listener: &Closure<dyn FnMut(JsValue)>) {
+    call(
+        target,
+        "addEventListener",
+        &[event.into(), listener.as_ref().clone()],
+    );
+}
+
+fn to_error(error: &JsValue) -> RecorderError {
+    match get(error, "name")
+        .and_then(|name| name.as_string())
+        .as_deref()
+    {
+        Some("NotAllowedError") | Some("AbortError") => RecorderError::PermissionDenied,
+        Some("NotSupportedError") => RecorderError::Unsupported,
+        _ => RecorderError::Failed(
+            get(error, "message")
+                .and_then(|message| message.as_string())
+                .unwrap_or_else(|| format!("{error:?}")),
+        ),
+    }
+}
+
+/// Get a property, treating `undefined` and `null` as missing.
+fn get(target: &JsValue, key: &str) -> Option<JsValue> {
+    Reflect::get(target, &key.into())
+        .ok()
+        .filter(|value| !value.is_undefined() && !value.is_null())
+}
+
+/// Call a method, returning `None` if it doesn't exist or throws.
+fn call(target: &JsValue, method: &str, args: &[JsValue]) -> Option<JsValue> {
+    let function: Function = get(target, method)?.dyn_into().ok()?;
+    function.apply(target, &args.iter().collect::<Array>()).ok()
+}
+
+/// Call a method that returns a promise and wait for it, returning `None` if the method doesn't exist.
+async fn call_async(
+    target: &JsValue,
+    method: &str,
+    args: &[JsValue],
+) -> Option<Result<JsValue, JsValue>> {
+    let promise: Promise = call(target, method, args)?.dyn_into().ok()?;
+    Some(JsFuture::from(promise).await)
+}
@@ -0,0 +1,174 @@
+//! Provides the use_screen_recorder hook.
+
+use dioxus::prelude::*;
+use futures::{channel::mpsc, StreamExt};
+
+use super::core::{RecorderError, RecorderState, Recording, RecordingOptions};
+use super::platform::Recorder;
+
+/// A handle to record the screen, created with [`use_screen_recorder`].
+#[derive(Clone, Copy, PartialEq)]
+pub struct UseScreenRecorder {
+    recorder: Signal<Option<Recorder>>,
+    state: Signal<RecorderState>,
+    size: Signal<u64>,
+    recording: Signal<Option<Recording>>,
+    error: Signal<Option<RecorderError>>,
+}
+
+impl UseScreenRecorder {
+    /// Ask the user what to record and start recording it.
+    ///
+    /// Browsers only show the picker in response to a user action, so call this from an event handler like `onclick`.
+    /// Starting a new recording discards the previous one.
+    pub fn start(&self, options: RecordingOptions) {
+        let mut this = *self;
+        if *this.state.peek() != RecorderState::Idle {
+            this.error.set(Some(RecorderError::AlreadyRecording));
+            return;
+        }
+
+        this.state.set(RecorderState::Starting);
+        this.size.set(0);
+        this.recording.set(None);
+        this.error.set(None);
+        spawn(async move {
+            // The browser ends the recording from an event listener, outside of the runtime, so the recording is
+            // stopped from this task instead.
+            let (ended_tx, mut ended) = mpsc::unbounded();
+            let on_data = move |size| this.size.set(size);
+            let on_end = move || {
+                ended_tx.unbounded_send(()).ok();
+            };
+            match Recorder::start(&options, on_data, on_end).await {
+                Ok(recorder) => {
+                    this.recorder.set(Some(recorder));
+                    this.state.set(RecorderState::Recording);
+                    // The channel closes without a message once the recording is stopped another way.
+                    if ended.next().await.is_some() {
+                        this.stop();
+                    }
+                }
+                Err(e) => {
+                    this.error.set(Some(e));
+                    this.state.set(RecorderState::Idle);
+                }
+            }
+        });
+    }
+
+    /// Pause the recording. Nothing is recorded until it is resumed.
+    pub fn pause(&self) {
+        let mut state = self.state;
+        if let (Some(recorder), RecorderState::Recording) = (&*self.recorder.peek(), *state.peek())
+        {
+            recorder.pause();
+            state.set(RecorderState::Paused);
+        }
+    }
+
+    /// Resume a paused recording.
+    pub fn resume(&self) {
+        let mut state = self.state;
+        if let (Some(recorder), RecorderState::Paused) = (&*self.recorder.peek(), *state.peek()) {
+            recorder.resume();
+            state.set(RecorderState::Recording);
+        }
+    }
+
+    /// Stop recording. The video is available from [`UseScreenRecorder::recording`] once it is finished.
+    pub fn stop(&self) {
+        let mut this = *self;
+        let Some(recorder) = this.recorder.write().take() else {
+            return;
+        };
+
+        this.state.set(RecorderState::Stopping);
+        spawn(async move {
+            match recorder.stop().await {
+                Ok(recording) => this.recording.set(Some(recording)),
+                Err(e) => this.error.set(Some(e)),
+            }
+            this.state.set(RecorderState::Idle);
+        });
+    }
+
+    /// Get the state of the recorder.
+    pub fn state(&self) -> ReadOnlySignal<RecorderState> {
+        self.state.into()
+    }
+
+    /// Get the size of the video recorded so far, in bytes.
+    pub fn size(&self) -> ReadOnlySignal<u64> {
+        self.size.into()
+    }
+
+    /// Get the last finished recording.
+    pub fn recording(&self) -> ReadOnlySignal<Option<Recording>> {
+        self.recording.into()
+    }
+
+    /// Get the error of the last action, if it failed.
+    pub fn error(&self) -> ReadOnlySignal<Option<RecorderError>> {
+        self.error.into()
+    }
+}
+
+/// A hook for recording the screen, a window, or a browser tab to a video.
+///
+/// The recording stops when [`UseScreenRecorder::stop`] is called or the user stops sharing from the browser.
+/// Screen recording is only supported on the web. Native capture on desktop isn't implemented, so desktop and mobile
+/// report [`RecorderError::Unsupported`].
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+use futures::{channel::mpsc, StreamExt};
+/// use dioxus_sdk::screen_recorder::{use_screen_recorder, RecorderState, RecordingOptions};
+///
+/// fn App() -> Element {
+///     let recorder = use_screen_recorder();
+///     let state = recorder.state();
+///     let size = recorder.size();
+///
+///     rsx! {
+///         if state() == RecorderState::Idle {
+///             button { onclick: move |_| recorder.start(RecordingOptions::default()), "Record" }
+///         } else {
+///             button { onclick: move |_| recorder.stop(), "Stop" }
+///             p { "Recorded {size() / 1024} KiB" }
+///         }
+///         if let Some(recording) = recorder.recording()() {
+///             button {
+///                 onclick: move |_| {
+///                     let file_name = format!("feedback.{}", recording.extension());
+///                     recording.download(&file_name).ok();
+///                 },
+///                 "Download"
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_screen_recorder() -> UseScreenRecorder {
+    let recorder = use_hook(|| UseScreenRecorder {
+        recorder: Signal::new(None),
+        state: Signal::new(RecorderState::Idle),
+        size: Signal::new(0),
+        recording: Signal::new(None),
+        error: Signal::new(None),
+    });
+
+    // Stop capturing the screen if the component is dropped while recording.
+    use_drop(move || {
+        let mut signal = recorder.recorder;
+        if let Some(recorder) = signal.try_write().ok().and_then(|mut r| r.take()) {
+            spawn_forever(async move {
+                recorder.stop().await.ok();
+            });
+        }
+    });
+
+    recorder
+}
@@ -0,0 +1,177 @@
+//! The serial port types shared by every platform.
+
+use futures::{channel::mpsc::UnboundedReceiver, Stream, StreamExt};
+use std::{
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use super::platform;
+
+/// A serial port that can be opened with [`SerialConnection::open`].
+#[derive(Clone)]
+pub struct SerialPortInfo {
+    /// The name of the port, like `COM3` or `/dev/ttyUSB0`.
+    ///
+    /// Browsers don't expose port names, so on the web this is built from the USB ids when they are known.
+    pub name: String,
+    /// Information about the USB device behind the port, if it is one.
+    pub usb: Option<UsbInfo>,
+    pub(crate) port: platform::Port,
+}
+
+impl PartialEq for SerialPortInfo {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.usb == other.usb
+    }
+}
+
+impl fmt::Debug for SerialPortInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SerialPortInfo")
+            .field("name", &self.name)
+            .field("usb", &self.usb)
+            .finish()
+    }
+}
+
+/// Describes the USB device behind a serial port.
+///
+/// Browsers only report the vendor and product ids.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsbInfo {
+    pub vendor_id: u16,
+    pub product_id: u16,
+    pub manufacturer: Option<String>,
+    pub product: Option<String>,
+    pub serial_number: Option<String>,
+}
+
+/// List the serial ports the app can open.
+///
+/// On the web this only includes the ports the user granted access to with [`request_port`].
+pub async fn available_ports() -> Result<Vec<SerialPortInfo>, SerialError> {
+    platform::ports().await
+}
+
+/// Ask the user to pick a serial port for the app to access.
+///
+/// Browsers show a chooser, which must be opened in response to a user interaction like a click. Returns `None`
+/// if the user closed it without picking a port. Desktop apps can access every port, so this always returns `None`
+/// there and the ports are listed by [`available_ports`] instead.
+pub async fn request_port() -> Result<Option<SerialPortInfo>, SerialError> {
+    platform::request().await
+}
+
+/// An open serial port.
+///
+/// Read the incoming bytes with [`SerialConnection::read`] and send bytes with [`SerialConnection::write`].
+/// To read and write from different tasks, [`split`](SerialConnection::split) the connection.
+/// The port is closed once the connection, or both of its halves, are dropped.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use dioxus_sdk::serial::{SerialConnection, SerialPortInfo};
+///
+/// async fn ping(port: &SerialPortInfo) -> Result<(), dioxus_sdk::serial::SerialError> {
+///     let mut connection = SerialConnection::open(port, 115_200).await?;
+///     connection.write(b"ping\n").await?;
+///     while let Some(chunk) = connection.read().await {
+///         println!("{}", String::from_utf8_lossy(&chunk?));
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct SerialConnection {
+    reader: SerialReader,
+    writer: SerialWriter,
+}
+
+impl SerialConnection {
+    /// Open a port with the given baud rate, using 8 data bits, no parity, and one stop bit.
+    pub async fn open(port: &SerialPortInfo, baud_rate: u32) -> Result<Self, SerialError> {
+        let (chunks, guard, writer) = platform::open(&port.port, baud_rate).await?;
+        Ok(Self {
+            reader: SerialReader {
+                chunks,
+                _guard: guard,
+            },
+            writer: SerialWriter { inner: writer },
+        })
+    }
+
+    /// Wait for the next bytes from the port. Returns `None` once the port is closed.
+    pub async fn read(&mut self) -> Option<Result<Vec<u8>, SerialError>> {
+        self.reader.next().await
+    }
+
+    /// Send bytes to the port.
+    pub async fn write(&self, data: &[u8]) -> Result<(), SerialError> {
+        self.writer.write(data).await
+    }
+
+    /// Split the connection into a stream of incoming bytes and a writer.
+    pub fn split(self) -> (SerialReader, SerialWriter) {
+        (self.reader, self.writer)
+    }
+}
+
+/// The bytes read from a port by the platform.
+pub(crate) type Chunks = UnboundedReceiver<Result<Vec<u8>, SerialError>>;
+
+/// The incoming bytes of a [`SerialConnection`], in the chunks they arrived in.
+pub struct SerialReader {
+    chunks: Chunks,
+    _guard: platform::ReadGuard,
+}
+
+impl Stream for SerialReader {
+    type Item = Result<Vec<u8>, SerialError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().chunks.poll_next_unpin(cx)
+    }
+}
+
+/// Sends bytes to a [`SerialConnection`].
+#[derive(Clone)]
+pub struct SerialWriter {
+    inner: platform::Writer,
+}
+
+impl SerialWriter {
+    /// Send bytes to the port.
+    pub async fn write(&self, data: &[u8]) -> Result<(), SerialError> {
+        self.inner.write(data).await
+    }
+}
+
+/// Describes errors that may occur when using serial ports.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SerialError {
+    /// The platform doesn't support serial ports.
+    Unsupported,
+    /// The user or the system denied access to the port.
+    PermissionDenied,
+    /// The port doesn't exist anymore, usually because the device was unplugged.
+    PortNotFound,
+    /// The port is already open.
+    Busy,
+    /// The platform reported an error.
+    Failed(String),
+}
+
+impl std::error::Error for SerialError {}
+impl fmt::Display for SerialError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Unsupported => write!(f, "serial ports are not supported on this platform"),
+            Self::PermissionDenied => write!(f, "access to the serial port was denied"),
+            Self::PortNotFound => write!(f, "the serial port was not found"),
+            Self::Busy => write!(f, "the serial port is already open"),
+            Self::Failed(reason) => write!(f, "serial port error: {reason}"),
+        }
+    }
+}
@@ -0,0 +1,10 @@
+//! Talk to serial devices, like microcontrollers connected over USB.
+//!
+//! Uses Web Serial on the web and the system serial ports on desktop.
+
+mod core;
+mod platform;
+mod use_serial_ports;
+
+pub use self::core::*;
+pub use self::use_serial_ports::*;
@@ -0,0 +1,127 @@
+use futures::channel::mpsc;
+use serialport::{ErrorKind, SerialPort, SerialPortType};
+use std::{
+    io::{self, Read, Write},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use crate::serial::{Chunks, SerialError, SerialPortInfo, UsbInfo};
+
+/// How often the list of ports is checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How long a read waits for bytes before checking whether the connection was dropped.
+const READ_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// The path of a serial port.
+pub type Port = String;
+
+/// The reader thread stops by itself once the receiver is dropped.
+pub struct ReadGuard;
+
+pub async fn ports() -> Result<Vec<SerialPortInfo>, SerialError> {
+    let ports = tokio::task::spawn_blocking(serialport::available_ports)
+        .await
+        .map_err(|e| SerialError::Failed(e.to_string()))?
+        .map_err(to_error)?;
+
+    Ok(ports
+        .into_iter()
+        .map(|port| SerialPortInfo {
+            usb: match port.port_type {
+                SerialPortType::UsbPort(usb) => Some(UsbInfo {
+                    vendor_id: usb.vid,
+                    product_id: usb.pid,
+                    manufacturer: usb.manufacturer,
+                    product: usb.product,
+                    serial_number: usb.serial_number,
+                }),
+                _ => None,
+            },
+            name: port.port_name.clone(),
+            port: port.port_name,
+        })
+        .collect())
+}
+
+/// Wait until the ports may have changed.
+///
+/// Desktop platforms don't report when a port is plugged in, so the ports are checked periodically.
+pub async fn wait_for_change() {
+    tokio::time::sleep(POLL_INTERVAL).await;
+}
+
+/// Desktop apps can open every port without asking, so there is nothing to request.
+pub async fn request() -> Result<Option<SerialPortInfo>, SerialError> {
+    Ok(None)
+}
+
+pub async fn open(port: &Port, baud_rate: u32) -> Result<(Chunks, ReadGuard, Writer), SerialError> {
+    let port = serialport::new(port, baud_rate)
+        .timeout(READ_TIMEOUT)
+        .open()
+        .map_err(to_error)?;
+    let mut reader = port.try_clone().map_err(to_error)?;
+
+    let (tx, rx) = mpsc::unbounded();
+    std::thread::Builder::new()
+        .name("dioxus-serial-reader".to_string())
+        .spawn(move || {
+            let mut buffer = [0; 1024];
+            while !tx.is_closed() {
+                match reader.read(&mut buffer) {
+                    Ok(0) => break,
+                    Ok(len) => {
+                        tx.unbounded_send(Ok(buffer[..len].to_vec())).ok();
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::TimedOut => {}
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                    Err(e) => {
+                        tx.unbounded_send(Err(SerialError::Failed(e.to_string())))
+                            .ok();
+                        break;
+                    }
+                }
+            }
+        })
+        .map_err(|e| SerialError::Failed(e.to_string()))?;
+
+    Ok((
+        rx,
+        ReadGuard,
+        Writer {
+            port: Arc::new(Mutex::new(port)),
+        },
+    ))
+}
+
+#[derive(Clone)]
+pub struct Writer {
+    port: Arc<Mutex<Box<dyn SerialPort>>>,
+}
+
+impl Writer {
+    pub async fn write(&self, data: &[u8]) -> Result<(), SerialError> {
+        let port = self.port.clone();
+        let data = data.to_vec();
+        // Writes block until the bytes are sent, which can take a while at low baud rates.
+        tokio::task::spawn_blocking(move || {
+            let mut port = port.lock().unwrap();
+            port.write_all(&data)?;
+            port.flush()
+        })
+        .await
+        .map_err(|e| SerialError::Failed(e.to_string()))?
+        .map_err(|e| SerialError::Failed(e.to_string()))
+    }
+}
+
+fn to_error(error: serialport::Error) -> SerialError {
+    match error.kind() {
+        ErrorKind::NoDevice => SerialError::PortNotFound,
+        ErrorKind::Io(io::ErrorKind::NotFound) => SerialError::PortNotFound,
+        ErrorKind::Io(io::ErrorKind::PermissionDenied) => SerialError::PermissionDenied,
+        _ => SerialError::Failed(error.to_string()),
+    }
+}
@@ -0,0 +1,216 @@
+use futures::channel::mpsc;
+use js_sys::{Array, Function, Object, Promise, Reflect, Uint8Array};
+use std::rc::Rc;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+
+use crate::serial::{Chunks, SerialError, SerialPortInfo, UsbInfo};
+
+/// A Web Serial `SerialPort`.
+pub type Port = JsValue;
+
+/// Keeps the port open while the reader is alive.
+pub struct ReadGuard {
+    _port: Rc<OpenPort>,
+}
+
+pub async fn ports() -> Result<Vec<SerialPortInfo>, SerialError> {
+    let serial = serial().ok_or(SerialError::Unsupported)?;
+    let ports = call_async(&serial, "getPorts", &[])
+        .await
+        .ok_or(SerialError::Unsupported)?
+        .map_err(|e| to_error(&e))?;
+
+    Ok(Array::from(&ports)
+        .iter()
+        .enumerate()
+        .map(|(index, port)| port_info(port, index))
+        .collect())
+}
+
+pub async fn request() -> Result<Option<SerialPortInfo>, SerialError> {
+    let serial = serial().ok_or(SerialError::Unsupported)?;
+    match call_async(&serial, "requestPort", &[]).await {
+        Some(Ok(port)) => Ok(Some(port_info(port, 0))),
+        // The user closed the chooser without picking a port.
+        Some(Err(e)) if error_name(&e).as_deref() == Some("NotFoundError") => Ok(None),
+        Some(Err(e)) => Err(to_error(&e)),
+        None => Err(SerialError::Unsupported),
+    }
+}
+
+/// Wait until a port is plugged in or unplugged.
+pub async fn wait_for_change() {
+    let Some(serial) = serial() else {
+        return std::future::pending().await;
+    };
+    let promise = Promise::new(&mut |resolve, _| {
+        Reflect::set(&serial, &"onconnect".into(), &resolve).ok();
+        Reflect::set(&serial, &"ondisconnect".into(), &resolve).ok();
+    });
+    JsFuture::from(promise).await.ok();
+}
+
+fn port_info(port: JsValue, index: usize) -> SerialPortInfo {
+    let info = call(&port, "getInfo", &[]).unwrap_or_else(|| Object::new().into());
+    let id = |key: &str| {
+        get(&info, key)
+            .and_then(|id| id.as_f64())
+            .map(|id| id as u16)
+    };
+
+    let usb = match (id("usbVendorId"), id("usbProductId")) {
+        (Some(vendor_id), Some(product_id)) => Some(UsbInfo {
+            vendor_id,
+            product_id,
+            manufacturer: None,
+            product: None,
+            serial_number: None,
+        }),
+        _ => None,
+    };
+    let name = match &usb {
+        Some(usb) => format!("USB {:04x}:{:04x}", usb.vendor_id, usb.product_id),
+        None => format!("Serial port {}", index + 1),
+    };
+
+    SerialPortInfo { name, usb, port }
+}
+
+pub async fn open(port: &Port, baud_rate: u32) -> Result<(Chunks, ReadGuard, Writer), SerialError> {
+    let options = Object::new();
+    Reflect::set(&options, &"baudRate".into(), &baud_rate.into()).ok();
+    call_async(port, "open", &[options.into()])
+        .await
+        .ok_or(SerialError::Unsupported)?
+        .map_err(|e| to_error(&e))?;
+
+    let reader = get(port, "readable").and_then(|readable| call(&readable, "getReader", &[]));
+    let writer = get(port, "writable").and_then(|writable| call(&writable, "getWriter", &[]));
+    let (Some(reader), Some(writer)) = (reader, writer) else {
+        call(port, "close", &[]);
+        return Err(SerialError::Failed("the port has no streams".to_string()));
+    };
+
+    let open = Rc::new(OpenPort {
+        port: port.clone(),
+        reader: reader.clone(),
+        writer: writer.clone(),
+    });
+
+    let (tx, rx) = mpsc::unbounded();
+    spawn_local(async move {
+        loop {
+            let result = match call_async(&reader, "read", &[]).await {
+                Some(Ok(result)) => result,
+                Some(Err(e)) => {
+                    tx.unbounded_send(Err(to_error(&e))).ok();
+                    break;
+                }
+                None => break,
+            };
+            // Reading is done once the reader is cancelled or the device is unplugged.
+            if get(&result, "done").is_some_and(|done| done.is_truthy()) {
+                break;
+            }
+            if let Some(value) = get(&result, "value") {
+                if tx
+                    .unbounded_send(Ok(Uint8Array::new(&value).to_vec()))
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        }
+    });
+
+    Ok((
+        rx,
+        ReadGuard {
+            _port: open.clone(),
+        },
+        Writer { port: open },
+    ))
+}
+
+#[derive(Clone)]
+pub struct Writer {
+    port: Rc<OpenPort>,
+}
+
+impl Writer {
+    pub async fn write(&self, data: &[u8]) -> Result<(), SerialError> {
+        let data: JsValue = Uint8Array::from(data).into();
+        call_async(&self.port.writer, "write", &[data])
+            .await
+            .ok_or(SerialError::Unsupported)?
+            .map(|_| ())
+            .map_err(|e| to_error(&e))
+    }
+}
+
+/// An open port with its locked streams. Closes the port when dropped.
+struct OpenPort {
+    port: JsValue,
+    reader: JsValue,
+    writer: JsValue,
+}
+
+impl Drop for OpenPort {
+    fn drop(&mut self) {
+        let port = self.port.clone();
+        let reader = self.reader.clone();
+        let writer = self.writer.clone();
+        spawn_local(async move {
+            // The streams have to be unlocked before the port can be closed.
+            call_async(&reader, "cancel", &[]).await;
+            call(&reader, "releaseLock", &[]);
+            call(&writer, "releaseLock", &[]);
+            call_async(&port, "close", &[]).await;
+        });
+    }
+}
+
+fn serial() -> Option<JsValue> {
+    get(&get(&web_sys::window()?, "navigator")?, "serial")
+}
+
+fn error_name(error: &JsValue) -> Option<String> {
+    get(error, "name")?.as_string()
+}
+
+fn to_error(error: &JsValue) -> SerialError {
+    match error_name(error).as_deref() {
+        Some("SecurityError") | Some("NotAllowedError") => SerialError::PermissionDenied,
+        Some("NotFoundError") => SerialError::PortNotFound,
+        Some("InvalidStateError") => SerialError::Busy,
+        _ => SerialError::Failed(
+            get(error, "message")
+                .and_then(|message| message.as_string())
+                .unwrap_or_else(|| format!("{error:?}")),
+        ),
+    }
+}
+
+/// Get a property, treating `undefined` and `null` as missing.
+fn get(target: &JsValue, key: &str) -> Option<JsValue> {
+    Reflect::get(target, &key.into())
+        .ok()
+        .filter(|value| !value.is_undefined() && !value.is_null())
+}
+
+/// Call a method, returning `None` if it doesn't exist or throws.
+fn call(target: &JsValue, method: &str, args: &[JsValue]) -> Option<JsValue> {
+    let function: Function = get(target, method)?.dyn_into().ok()?;
+    function.apply(target, &args.iter().collect::<Array>()).ok()
+}
+
+/// Call a method that returns a promise and wait for it, returning `None` if the method doesn't exist.
+async fn call_async(
+    target: &JsValue,
+    method: &str,
+    args: &[JsValue],
+) -> Option<Result<JsValue, JsValue>> {
+    let promise: Promise = call(target, method, args)?.dyn_into().ok()?;
+    Some(JsFuture::from(promise).await)
+}
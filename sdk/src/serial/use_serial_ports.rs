@@ -0,0 +1,102 @@
+//! Provides the use_serial_ports hook.
+
+use dioxus::prelude::*;
+
+use super::core::{available_ports, request_port, SerialError, SerialPortInfo};
+use super::platform;
+
+/// The interface for the serial ports the app can open.
+///
+/// See [`use_serial_ports`] for more information.
+#[derive(Clone, Copy, PartialEq)]
+pub struct UseSerialPorts {
+    ports: Signal<Vec<SerialPortInfo>>,
+    error: Signal<Option<SerialError>>,
+}
+
+impl UseSerialPorts {
+    /// The ports the app can open.
+    pub fn ports(&self) -> ReadOnlySignal<Vec<SerialPortInfo>> {
+        ReadOnlySignal::new(self.ports)
+    }
+
+    /// The error from the last time the ports were listed, if any.
+    pub fn error(&self) -> ReadOnlySignal<Option<SerialError>> {
+        ReadOnlySignal::new(self.error)
+    }
+
+    /// List the ports again.
+    pub fn refresh(&self) {
+        let this = *self;
+        spawn(async move { this.update().await });
+    }
+
+    /// Ask the user to pick a port, and add it to the list once they do.
+    ///
+    /// On the web this must be called in response to a user interaction, like a click. On desktop every port
+    /// is listed already, so this only refreshes the list.
+    pub fn request(&self) {
+        let mut this = *self;
+        spawn(async move {
+            if let Err(e) = request_port().await {
+                this.error.set(Some(e));
+                return;
+            }
+            this.update().await;
+        });
+    }
+
+    async fn update(mut self) {
+        match available_ports().await {
+            Ok(ports) => {
+                if *self.ports.peek() != ports {
+                    self.ports.set(ports);
+                }
+                self.error.set(None);
+            }
+            Err(e) => self.error.set(Some(e)),
+        }
+    }
+}
+
+/// A hook for the serial ports the app can open with [`SerialConnection::open`](super::SerialConnection::open).
+///
+/// The list is updated when devices are plugged in or unplugged.
+///
+/// - On desktop, every port of the system is listed.
+/// - On the web, only ports the user granted access to are listed. Use [`UseSerialPorts::request`] to ask for one.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::serial::use_serial_ports;
+///
+/// fn App() -> Element {
+///     let serial = use_serial_ports();
+///
+///     rsx! {
+///         button { onclick: move |_| serial.request(), "Add a device" }
+///         for port in serial.ports().read().iter() {
+///             p { "{port.name}" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_serial_ports() -> UseSerialPorts {
+    use_hook(|| {
+        let serial = UseSerialPorts {
+            ports: Signal::new(Vec::new()),
+            error: Signal::new(None),
+        };
+
+        spawn(async move {
+            loop {
+                serial.update().await;
+                platform::wait_for_change().await;
+            }
+        });
+
+        serial
+    })
+}
@@ -0,0 +1,260 @@
+//! A storage backing that keeps a bounded number of entries and evicts the least recently used ones.
+
+use once_cell::sync::Lazy;
+use serde::{de::DeserializeOwned, Serialize};
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::Mutex;
+
+use super::StorageBacking;
+
+/// The prefix of the keys cache entries are stored under in the inner backing.
+const ENTRY_PREFIX: &str = "dioxus-cache:";
+/// The key the least recently used order is stored under in the inner backing.
+const INDEX_KEY: &str = "dioxus-cache-index";
+
+/// A storage backing for caches, such as cached responses, that must not grow without bounds.
+///
+/// Entries are stored in the inner backing `S`, and the least recently used entries are removed once there are more
+/// than [`CacheConfig::max_entries`] entries or they take more than [`CacheConfig::max_bytes`]. The order of the
+/// entries is stored as well, so the limits still apply after the app restarts. Reads only update the stored order
+/// the next time an entry is written.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::storage::{cache_stats, set_cache_config, use_storage, CacheConfig, CacheStorage, LocalStorage};
+///
+/// fn App() -> Element {
+///     use_hook(|| {
+///         set_cache_config::<LocalStorage>(CacheConfig {
+///             max_entries: 50,
+///             max_bytes: 1024 * 1024,
+///         })
+///     });
+///     let response = use_storage::<CacheStorage<LocalStorage>, Option<String>>(
+///         "https://example.com/feed".to_string(),
+///         || None,
+///     );
+///     let stats = cache_stats::<LocalStorage>();
+///
+///     rsx! {
+///         p { "{response:?}" }
+///         p { "{stats.hits} hits, {stats.misses} misses" }
+///     }
+/// }
+/// ```
+pub struct CacheStorage<S = super::LocalStorage>(PhantomData<fn() -> S>);
+
+// Manually implement Clone as #[derive] thinks that S needs to implement it (it doesn't).
+impl<S> Clone for CacheStorage<S> {
+    fn clone(&self) -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// The limits of a [`CacheStorage`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CacheConfig {
+    /// The maximum number of entries.
+    pub max_entries: usize,
+    /// The maximum size of all entries together, in bytes of their serialized form.
+    pub max_bytes: usize,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: 100,
+            max_bytes: 5 * 1024 * 1024,
+        }
+    }
+}
+
+/// The statistics of a [`CacheStorage`] since the app started.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CacheStats {
+    /// How many reads found an entry.
+    pub hits: u64,
+    /// How many reads found no entry.
+    pub misses: u64,
+    /// How many entries were removed to stay within the limits.
+    pub evictions: u64,
+    /// The number of entries.
+    pub entries: usize,
+    /// The size of all entries together, in bytes.
+    pub bytes: usize,
+}
+
+/// Set the limits of the [`CacheStorage`] stored in `S`, evicting entries right away if needed.
+pub fn set_cache_config<S: StorageBacking<Key = String>>(config: CacheConfig) {
+    with_cache::<S, _>(|cache| {
+        cache.config = config;
+        cache.evict::<S>();
+    })
+}
+
+/// Remove every entry of the [`CacheStorage`] stored in `S`.
+pub fn clear_cache<S: StorageBacking<Key = String>>() {
+    with_cache::<S, _>(|cache| {
+        for (key, _) in cache.entries.drain(..) {
+            S::remove(&entry_key(&key));
+        }
+        cache.save::<S>();
+    })
+}
+
+/// Get the statistics of the [`CacheStorage`] stored in `S`.
+pub fn cache_stats<S: StorageBacking<Key = String>>() -> CacheStats {
+    with_cache::<S, _>(|cache| CacheStats {
+        entries: cache.entries.len(),
+        bytes: cache.bytes(),
+        ..cache.stats
+    })
+}
+
+/// The state of the cache stored in one inner backing.
+struct Cache {
+    config: CacheConfig,
+    /// The keys and sizes of the entries, least recently used first.
+    entries: Vec<(String, usize)>,
+    stats: CacheStats,
+}
+
+impl Cache {
+    fn bytes(&self) -> usize {
+        self.entries.iter().map(|(_, size)| size).sum()
+    }
+
+    fn position(&self, key: &str) -> Option<usize> {
+        self.entries.iter().position(|(entry, _)| entry == key)
+    }
+
+    /// Remove the least recently used entries until the cache is within its limits.
+    fn evict<S: StorageBacking<Key = String>>(&mut self) {
+        let mut evicted = false;
+        while !self.entries.is_empty()
+            && (self.entries.len() > self.config.max_entries
+                || self.bytes() > self.config.max_bytes)
+        {
+            let (key, _) = self.entries.remove(0);
+            S::remove(&entry_key(&key));
+            self.stats.evictions += 1;
+            evicted = true;
+        }
+        if evicted {
+            self.save::<S>();
+        }
+    }
+
+    fn save<S: StorageBacking<Key = String>>(&self) {
+        S::set(INDEX_KEY.to_string(), &self.entries);
+    }
+}
+
+static CACHES: Lazy<Mutex<HashMap<TypeId, Cache>>> = Lazy::new(Default::default);
+
+fn with_cache<S: StorageBacking<Key = String>, R>(f: impl FnOnce(&mut Cache) -> R) -> R {
+    let mut caches = CACHES.lock().unwrap();
+    let cache = caches.entry(TypeId::of::<S>()).or_insert_with(|| Cache {
+        config: CacheConfig::default(),
+        entries: S::get(&INDEX_KEY.to_string()).unwrap_or_default(),
+        stats: CacheStats::default(),
+    });
+    f(cache)
+}
+
+fn entry_key(key: &str) -> String {
+    format!("{ENTRY_PREFIX}{key}")
+}
+
+impl<S: StorageBacking<Key = String>> StorageBacking for CacheStorage<S> {
+    type Key = String;
+
+    fn get<T: DeserializeOwned + Clone + 'static>(key: &String) -> Option<T> {
+        with_cache::<S, _>(|cache| {
+            let value = cache
+                .position(key)
+                .and_then(|index| Some((index, S::get::<T>(&entry_key(key))?)));
+            match value {
+                Some((index, value)) => {
+                    let entry = cache.entries.remove(index);
+                    cache.entries.push(entry);
+                    cache.stats.hits += 1;
+                    Some(value)
+                }
+                None => {
+                    cache.stats.misses += 1;
+                    None
+                }
+            }
+        })
+    }
+
+    fn set<T: Serialize + Send + Sync + Clone + 'static>(key: String, value: &T) {
+        let size = postcard::to_allocvec(value).map_or(0, |bytes| bytes.len());
+        with_cache::<S, _>(|cache| {
+            if let Some(index) = cache.position(&key) {
+                cache.entries.remove(index);
+            }
+            cache.entries.push((key.clone(), size));
+            cache.evict::<S>();
+
+            // The entry itself may be larger than the whole cache.
+            if cache.position(&key).is_some() {
+                S::set(entry_key(&key), value);
+                cache.save::<S>();
+            }
+        })
+    }
+
+    fn remove(key: &String) {
+        with_cache::<S, _>(|cache| {
+            if let Some(index) = cache.position(key) {
+                cache.entries.remove(index);
+                cache.save::<S>();
+            }
+            S::remove(&entry_key(key));
+        })
+    }
+
+    fn raw_key(key: &String) -> Option<String> {
+        S::raw_key(&entry_key(key))
+    }
+
+    fn keys() -> Vec<String> {
+        with_cache::<S, _>(|cache| cache.entries.iter().map(|(key, _)| key.clone()).collect())
+    }
+
+    fn stored_size(key: &String) -> Option<usize> {
+        S::stored_size(&entry_key(key))
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn test_lru_eviction() {
+    use super::LocalStorage;
+
+    super::client_storage::set_test_directory();
+    clear_cache::<LocalStorage>();
+    set_cache_config::<LocalStorage>(CacheConfig {
+        max_entries: 2,
+        max_bytes: usize::MAX,
+    });
+    type Cached = CacheStorage<LocalStorage>;
+
+    Cached::set("cache-a".to_string(), &1u32);
+    Cached::set("cache-b".to_string(), &2u32);
+    // Reading "a" makes "b" the least recently used entry.
+    assert_eq!(Cached::get::<u32>(&"cache-a".to_string()), Some(1));
+    Cached::set("cache-c".to_string(), &3u32);
+
+    assert_eq!(Cached::get::<u32>(&"cache-b".to_string()), None);
+    assert_eq!(Cached::get::<u32>(&"cache-a".to_string()), Some(1));
+    assert_eq!(Cached::get::<u32>(&"cache-c".to_string()), Some(3));
+
+    let stats = cache_stats::<LocalStorage>();
+    assert_eq!((stats.hits, stats.misses), (3, 1));
+    assert_eq!((stats.evictions, stats.entries), (1, 2));
+}
@@ -2,11 +2,19 @@ use crate::storage::{StorageChannelPayload, StorageSubscription};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use std::collections::HashMap;
-use std::io::Write;
-use std::sync::{OnceLock, RwLock};
+use std::future::Future;
+use std::io::{Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Mutex, OnceLock, RwLock};
+use std::time::{Duration, Instant};
 use tokio::sync::watch::{channel, Receiver};
 
-use crate::storage::{serde_to_string, try_serde_from_string, StorageBacking, StorageSubscriber};
+use crate::storage::error::report_storage_error;
+use crate::storage::keys::notify_key_change;
+use crate::storage::{
+    serde_to_string, set_if_unchanged, try_serde_from_string, StorageBacking, StorageError,
+    StorageSubscriber,
+};
 
 #[doc(hidden)]
 /// Sets the directory where the storage files are located.
@@ -27,26 +35,280 @@ pub fn set_dir_name(name: &str) {
 /// The location where the storage files are located.
 static LOCATION: OnceLock<std::path::PathBuf> = OnceLock::new();
 
+/// Get the configured storage location.
+fn location() -> &'static PathBuf {
+    LOCATION
+        .get()
+        .expect("Call the set_dir macro before accessing persistant data")
+}
+
+/// Store values in a temporary directory for tests.
+#[cfg(test)]
+pub(crate) fn set_test_directory() {
+    LOCATION.get_or_init(|| std::env::temp_dir().join("dioxus-sdk-storage-test"));
+}
+
+/// The file the previous good value of a key is kept in.
+fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".bak");
+    PathBuf::from(name)
+}
+
+/// The file that holds the version of a key. It is locked while the key is compared and set, so other processes
+/// using the same directory wait for each other.
+fn lock_path(key: &str) -> PathBuf {
+    location().join(format!("{key}.lock"))
+}
+
+/// Compare and set a value while holding the lock on the version file of the key.
+fn compare_and_set_locked<T>(
+    key: String,
+    version: u64,
+    expected: Option<T>,
+    value: T,
+) -> std::io::Result<bool>
+where
+    T: Serialize + DeserializeOwned + Send + Sync + Clone + 'static,
+{
+    std::fs::create_dir_all(location())?;
+    let mut file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(lock_path(&key))?;
+    // The lock is released when the file is closed.
+    file.lock()?;
+
+    let mut current = String::new();
+    file.read_to_string(&mut current)?;
+    if current.trim().parse().unwrap_or(0) != version
+        || !set_if_unchanged::<LocalStorage, T>(key, expected.as_ref(), &value)
+    {
+        return Ok(false);
+    }
+    // Other processes only see what is on disk, so the value can't wait for the background writer.
+    flush();
+
+    file.set_len(0)?;
+    file.rewind()?;
+    write!(file, "{}", version + 1)?;
+    Ok(true)
+}
+
+/// A change that has been queued but not written to disk yet.
+#[derive(Clone, PartialEq)]
+enum PendingWrite {
+    Set(String),
+    Remove,
+}
+
+/// The changes waiting for the background writer, by key. Reads check this before the disk.
+static PENDING: Mutex<Option<HashMap<String, PendingWrite>>> = Mutex::new(None);
+
+/// Held while queued changes are written, so the writer thread and [`flush`] don't interleave.
+static WRITING: Mutex<()> = Mutex::new(());
+
+/// Wakes the background writer. Only set once background writes are enabled.
+static WRITER: OnceLock<Mutex<mpsc::Sender<()>>> = OnceLock::new();
+
+/// How long the background writer waits for more changes, so a burst of changes is written at once.
+const WRITE_DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// The longest the background writer waits while changes keep coming.
+const MAX_WRITE_DELAY: Duration = Duration::from_secs(1);
+
+/// Write storage values to disk on a dedicated thread instead of the thread that sets them.
+///
+/// Large values can take a while to write, which causes jank when it happens on the UI thread.
+/// Once enabled, values are kept in an in-memory queue until the writer thread persists them, once no value changed
+/// for 100 milliseconds, or after a second while values keep changing. Reads and storage subscribers see queued
+/// values immediately.
+///
+/// Queued values are lost if the process is killed before they are written, so call [`flush`]
+/// before exiting, or use [`use_background_writes`] in the root component to do it automatically.
+pub fn enable_background_writes() {
+    WRITER.get_or_init(|| {
+        let (tx, rx) = mpsc::channel::<()>();
+        std::thread::Builder::new()
+            .name("dioxus-storage-writer".to_string())
+            .spawn(move || {
+                while rx.recv().is_ok() {
+                    let deadline = Instant::now() + MAX_WRITE_DELAY;
+                    loop {
+                        let remaining = deadline.saturating_duration_since(Instant::now());
+                        if remaining.is_zero() {
+                            break;
+                        }
+                        // Another change restarts the wait.
+                        if rx.recv_timeout(WRITE_DEBOUNCE.min(remaining)).is_err() {
+                            break;
+                        }
+                    }
+                    flush();
+                }
+            })
+            .expect("Failed to spawn the storage writer thread");
+        Mutex::new(tx)
+    });
+}
+
+/// Enables [background writes](enable_background_writes) and flushes the queue when the component is dropped.
+///
+/// Call this in the root component so queued values are written when the app exits.
+pub fn use_background_writes() {
+    dioxus::prelude::use_hook(enable_background_writes);
+    dioxus::prelude::use_drop(flush);
+}
+
+/// Write every queued value to disk, blocking until they are written.
+///
+/// This stores the saves waiting for the end of the current tick, and the queue of the
+/// [background writer](enable_background_writes) if it is enabled.
+pub fn flush() {
+    crate::storage::coalesce::flush_writes();
+    let _writing = WRITING.lock().unwrap();
+    let queued: Vec<(String, PendingWrite)> = match &*PENDING.lock().unwrap() {
+        Some(pending) => pending
+            .iter()
+            .map(|(key, write)| (key.clone(), write.clone()))
+            .collect(),
+        None => return,
+    };
+
+    for (key, write) in queued {
+        match &write {
+            PendingWrite::Set(contents) => write_value(key.clone(), contents),
+            PendingWrite::Remove => remove_value(&key),
+        }
+
+        // Keep the entry if it changed while it was being written, so the newer value is written next.
+        let mut pending = PENDING.lock().unwrap();
+        if let Some(pending) = pending.as_mut() {
+            if pending.get(&key) == Some(&write) {
+                pending.remove(&key);
+            }
+        }
+    }
+}
+
+/// Whether changes are queued for the background writer instead of written right away.
+fn background_writes_enabled() -> bool {
+    WRITER.get().is_some()
+}
+
+/// Queue a change for the background writer.
+fn queue(key: String, write: PendingWrite) {
+    PENDING
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(key, write);
+    if let Some(writer) = WRITER.get() {
+        writer.lock().unwrap().send(()).ok();
+    }
+}
+
+/// Get the queued change for a key, if there is one.
+fn queued(key: &str) -> Option<PendingWrite> {
+    PENDING.lock().unwrap().as_ref()?.get(key).cloned()
+}
+
 /// Set a value in the configured storage location using the key as the file name.
 fn set<T: Serialize>(key: String, value: &T) {
     let as_str = serde_to_string(value);
-    let path = LOCATION
-        .get()
-        .expect("Call the set_dir macro before accessing persistant data");
-    std::fs::create_dir_all(path).unwrap();
-    let file_path = path.join(key);
-    let mut file = std::fs::File::create(file_path).unwrap();
-    file.write_all(as_str.as_bytes()).unwrap();
+    match background_writes_enabled() {
+        true => queue(key, PendingWrite::Set(as_str)),
+        false => write_value(key, &as_str),
+    }
+}
+
+/// Write an encoded value to disk.
+///
+/// The value is written to a temporary file that then replaces the old file, so a crash mid-write
+/// never leaves a partially written value behind. The old file is kept as a backup.
+fn write_value(key: String, contents: &str) {
+    if let Err(err) = write_atomic(location(), &key, contents.as_bytes()) {
+        report_storage_error(StorageError::WriteFailed {
+            key,
+            reason: err.to_string(),
+        });
+    }
+}
+
+fn write_atomic(dir: &Path, key: &str, contents: &[u8]) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let file_path = dir.join(key);
+    let mut temp_name = file_path.as_os_str().to_os_string();
+    temp_name.push(".tmp");
+    let temp_path = PathBuf::from(temp_name);
+
+    let mut file = std::fs::File::create(&temp_path)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+
+    if file_path.exists() {
+        std::fs::rename(&file_path, backup_path(&file_path))?;
+    }
+    std::fs::rename(&temp_path, &file_path)
+}
+
+/// Remove a value from the configured storage location using the key as the file name.
+fn remove(key: &str) {
+    match background_writes_enabled() {
+        true => queue(key.to_string(), PendingWrite::Remove),
+        false => remove_value(key),
+    }
+}
+
+/// Remove a value and its backup from disk.
+fn remove_value(key: &str) {
+    let path = location().join(key);
+    std::fs::remove_file(backup_path(&path)).ok();
+    std::fs::remove_file(path).ok();
 }
 
 /// Get a value from the configured storage location using the key as the file name.
+///
+/// If the value can't be decoded, the backup of the previous value is used instead.
 fn get<T: DeserializeOwned>(key: &str) -> Option<T> {
-    let path = LOCATION
-        .get()
-        .expect("Call the set_dir macro before accessing persistant data")
-        .join(key);
-    let s = std::fs::read_to_string(path).ok()?;
-    try_serde_from_string(&s)
+    // Changes that haven't been written yet are newer than the disk.
+    match queued(key) {
+        Some(PendingWrite::Set(contents)) => return try_serde_from_string(&contents),
+        Some(PendingWrite::Remove) => return None,
+        None => {}
+    }
+
+    let path = location().join(key);
+    let reason = match std::fs::read_to_string(&path) {
+        Ok(s) => match try_serde_from_string(&s) {
+            Some(value) => return Some(value),
+            None => "the value could not be decoded".to_string(),
+        },
+        // If the file is missing, the value was either never written or a crash happened between
+        // moving it to the backup and replacing it.
+        Err(err) => err.to_string(),
+    };
+
+    let backup = std::fs::read_to_string(backup_path(&path)).ok();
+    match backup.as_deref().and_then(try_serde_from_string) {
+        Some(value) => {
+            report_storage_error(StorageError::Recovered {
+                key: key.to_string(),
+                reason,
+            });
+            Some(value)
+        }
+        None => {
+            if path.exists() {
+                report_storage_error(StorageError::Corrupted {
+                    key: key.to_string(),
+                });
+            }
+            None
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -59,6 +321,7 @@ impl StorageBacking for LocalStorage {
         let key_clone = key.clone();
         let value_clone = (*value).clone();
         set(key, value);
+        notify_key_change();
 
         // If the subscriptions map is not initialized, we don't need to notify any subscribers.
         if let Some(subscriptions) = SUBSCRIPTIONS.get() {
@@ -75,6 +338,146 @@ impl StorageBacking for LocalStorage {
     fn get<T: DeserializeOwned>(key: &String) -> Option<T> {
         get(key)
     }
+
+    fn remove(key: &String) {
+        remove(key);
+        notify_key_change();
+
+        // Let the windows that are subscribed to the key start over instead of writing the old value back.
+        if let Some(subscriptions) = SUBSCRIPTIONS.get() {
+            let read_binding = subscriptions.read().unwrap();
+            if let Some(subscription) = read_binding.get(key) {
+                subscription.tx.send(StorageChannelPayload::removed()).ok();
+            }
+        }
+    }
+
+    fn raw_key(key: &String) -> Option<String> {
+        Some(key.clone())
+    }
+
+    fn keys() -> Vec<String> {
+        let mut keys = Vec::new();
+        if let Ok(dir) = std::fs::read_dir(location()) {
+            for file in dir.flatten() {
+                match file.file_name().into_string() {
+                    Ok(key) if !is_internal_file(&key) => keys.push(key),
+                    _ => {}
+                }
+            }
+        }
+
+        if let Some(pending) = PENDING.lock().unwrap().as_ref() {
+            for (key, write) in pending {
+                match write {
+                    PendingWrite::Set(_) if !keys.contains(key) => keys.push(key.clone()),
+                    PendingWrite::Remove => keys.retain(|k| k != key),
+                    _ => {}
+                }
+            }
+        }
+        keys
+    }
+
+    fn stored_size(key: &String) -> Option<usize> {
+        match queued(key) {
+            Some(PendingWrite::Set(contents)) => Some(contents.len()),
+            Some(PendingWrite::Remove) => None,
+            None => std::fs::metadata(location().join(key))
+                .ok()
+                .map(|metadata| metadata.len() as usize),
+        }
+    }
+
+    fn version(key: &String) -> u64 {
+        std::fs::read_to_string(lock_path(key))
+            .ok()
+            .and_then(|version| version.trim().parse().ok())
+            .unwrap_or(0)
+    }
+
+    fn compare_and_set<T>(
+        key: String,
+        version: u64,
+        expected: Option<T>,
+        value: T,
+    ) -> impl Future<Output = bool>
+    where
+        T: Serialize + DeserializeOwned + Send + Sync + Clone + 'static,
+    {
+        let set = match compare_and_set_locked(key.clone(), version, expected, value) {
+            Ok(set) => set,
+            Err(err) => {
+                report_storage_error(StorageError::WriteFailed {
+                    key,
+                    reason: err.to_string(),
+                });
+                false
+            }
+        };
+        std::future::ready(set)
+    }
+}
+
+/// Whether a file in the storage directory is a backup, a value that is being written, or a version, not a value.
+fn is_internal_file(name: &str) -> bool {
+    name.ends_with(".bak") || name.ends_with(".tmp") || name.ends_with(".lock")
+}
+
+/// The backings [`dump_storage`](crate::storage::dump_storage) reads. The in-memory session storage holds
+/// live values instead of encoded ones, so only the local storage is included.
+pub(crate) const DUMPED_BACKINGS: &[&str] = &["local"];
+
+/// Get the encoded value of every key in a backing, including changes that haven't been written yet.
+pub(crate) fn raw_entries(backing: &str) -> Vec<(String, String)> {
+    if backing != "local" {
+        return Vec::new();
+    }
+
+    let mut entries = HashMap::new();
+    if let Ok(dir) = std::fs::read_dir(location()) {
+        for file in dir.flatten() {
+            let Ok(key) = file.file_name().into_string() else {
+                continue;
+            };
+            if is_internal_file(&key) {
+                continue;
+            }
+            if let Ok(contents) = std::fs::read_to_string(file.path()) {
+                entries.insert(key, contents);
+            }
+        }
+    }
+
+    if let Some(pending) = PENDING.lock().unwrap().as_ref() {
+        for (key, write) in pending {
+            match write {
+                PendingWrite::Set(contents) => entries.insert(key.clone(), contents.clone()),
+                PendingWrite::Remove => entries.remove(key),
+            };
+        }
+    }
+
+    entries.into_iter().collect()
+}
+
+/// Set the encoded value of a key in a backing and notify its subscribers.
+pub(crate) fn set_raw(backing: &str, key: String, contents: String) {
+    if backing != "local" {
+        return;
+    }
+
+    match background_writes_enabled() {
+        true => queue(key.clone(), PendingWrite::Set(contents)),
+        false => write_value(key.clone(), &contents),
+    }
+    notify_key_change();
+
+    if let Some(subscriptions) = SUBSCRIPTIONS.get() {
+        if let Some(subscription) = subscriptions.read().unwrap().get(&key) {
+            subscription.get_and_send().ok();
+        }
+    }
 }
 
 // Note that this module contains an optimization that differs from the web version. Dioxus Desktop runs all windows in
@@ -126,3 +529,73 @@ impl StorageSubscriber<LocalStorage> for LocalStorage {
 /// A map of all the channels that are currently subscribed to and the getters for the corresponding storage entry.
 /// This gets initialized lazily.
 static SUBSCRIPTIONS: OnceLock<RwLock<HashMap<String, StorageSubscription>>> = OnceLock::new();
+
+#[test]
+fn test_synced_windows_ignore_their_own_writes() {
+    use crate::storage::StorageOrigin;
+
+    set_test_directory();
+    let key = "ping-pong".to_string();
+
+    // Two windows subscribed to the same key.
+    let mut window_a = LocalStorage::subscribe::<i32>(&key);
+    let mut window_b = LocalStorage::subscribe::<i32>(&key);
+    let origin_a = StorageOrigin::new();
+    let origin_b = StorageOrigin::new();
+
+    // Window A saves. Both windows are notified, but only window B should apply the value.
+    origin_a.scope(|| LocalStorage::set(key.clone(), &1));
+    assert_eq!(window_a.borrow_and_update().origin(), Some(origin_a));
+    let payload = window_b.borrow_and_update().clone();
+    assert_eq!(payload.origin(), Some(origin_a));
+    assert_eq!(payload.data::<i32>(), Some(&1));
+
+    // Window B answers with its own save. This time only window A should apply it.
+    origin_b.scope(|| LocalStorage::set(key.clone(), &2));
+    let payload = window_a.borrow_and_update().clone();
+    assert_eq!(payload.origin(), Some(origin_b));
+    assert_eq!(payload.data::<i32>(), Some(&2));
+    assert_eq!(window_b.borrow_and_update().origin(), Some(origin_b));
+
+    // Neither window writes the value back, so the ping-pong stops.
+    assert!(!window_a.has_changed().unwrap());
+    assert!(!window_b.has_changed().unwrap());
+
+    // Writes from outside of a synced entry aren't tagged.
+    LocalStorage::set(key.clone(), &3);
+    assert_eq!(window_a.borrow_and_update().origin(), None);
+
+    LocalStorage::unsubscribe(&key);
+    LocalStorage::remove(&key);
+}
+
+#[test]
+fn test_compare_and_set() {
+    use futures_util::FutureExt;
+
+    set_test_directory();
+    let key = "compare-and-set".to_string();
+    LocalStorage::remove(&key);
+    std::fs::remove_file(lock_path(&key)).ok();
+    // Writes to the disk are done before the future is returned.
+    let compare_and_set = |version, expected: Option<f64>, value: f64| {
+        LocalStorage::compare_and_set(key.clone(), version, expected, value).now_or_never()
+            == Some(true)
+    };
+
+    assert!(compare_and_set(0, None, 1.0));
+    assert_eq!(LocalStorage::version(&key), 1);
+    // Another window saved 2 after this one read 1, so saving on top of 1 fails.
+    LocalStorage::set(key.clone(), &2.0);
+    assert!(!compare_and_set(1, Some(1.0), 3.0));
+    assert_eq!(LocalStorage::get::<f64>(&key), Some(2.0));
+    // Another window modified the value after this one read it, so an outdated version fails too.
+    assert!(!compare_and_set(0, Some(2.0), 3.0));
+    assert!(compare_and_set(1, Some(2.0), f64::NAN));
+    // Values that aren't equal to themselves still match what was read.
+    assert!(compare_and_set(2, Some(f64::NAN), 3.0));
+    assert_eq!(LocalStorage::get::<f64>(&key), Some(3.0));
+
+    LocalStorage::remove(&key);
+    std::fs::remove_file(lock_path(&key)).ok();
+}
@@ -5,6 +5,7 @@ use std::ops::{Deref, DerefMut};
 use std::rc::Rc;
 use std::sync::Arc;
 
+use crate::storage::keys::notify_key_change;
 use crate::storage::StorageBacking;
 
 #[derive(Clone)]
@@ -16,6 +17,7 @@ impl StorageBacking for SessionStorage {
     fn set<T: Clone + 'static>(key: String, value: &T) {
         let session = SessionStore::get_current_session();
         session.borrow_mut().insert(key, Arc::new(value.clone()));
+        notify_key_change();
     }
 
     fn get<T: Clone + 'static>(key: &String) -> Option<T> {
@@ -24,6 +26,18 @@ impl StorageBacking for SessionStorage {
         let value_any = read_binding.get(key)?;
         value_any.downcast_ref::<T>().cloned()
     }
+
+    fn remove(key: &String) {
+        let session = SessionStore::get_current_session();
+        session.borrow_mut().remove(key);
+        notify_key_change();
+    }
+
+    fn keys() -> Vec<String> {
+        let session = SessionStore::get_current_session();
+        let keys = session.borrow().keys().cloned().collect();
+        keys
+    }
 }
 
 /// An in-memory session store that is tied to the current Dioxus root context.
@@ -34,6 +34,8 @@ cfg_if::cfg_if! {
     if #[cfg(target_family = "wasm")] {
         pub mod web;
         pub use web::*;
+        pub mod opfs;
+        pub use opfs::{opfs_loaded, OpfsStorage};
     } else {
         pub mod fs;
         pub use fs::*;
@@ -0,0 +1,386 @@
+//! A storage backing in the Origin Private File System, for values too large for local storage.
+
+use js_sys::{Array, Function, Object, Promise, Reflect};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+};
+use tokio::sync::watch::{channel, Receiver, Sender};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+
+use crate::storage::keys::notify_key_change;
+use crate::storage::{
+    is_seeding, serde_to_string, try_serde_from_string, StorageBacking, StorageChannelPayload,
+    StorageSubscriber, StorageSubscription,
+};
+
+/// The directory the values are stored in, in the root of the origin private file system.
+const DIRECTORY: &str = "dioxus-storage";
+
+/// How long to collect writes before writing them to files.
+const FLUSH_DELAY_MS: i32 = 100;
+
+/// A storage backing that stores each value in a file of the [Origin Private File System](https://developer.mozilla.org/en-US/docs/Web/API/File_System_API/Origin_private_file_system).
+///
+/// Local storage is limited to a few megabytes and blocks the page while it is written. The origin private file
+/// system can store much larger values, and is written asynchronously.
+///
+/// Files can only be read asynchronously, so every value is loaded into memory in the background the first time
+/// the backing is used. Until then values read as missing, and synced storage picks up the stored values once they
+/// are loaded. Use [`opfs_loaded`] to wait for them. The initial value of a key that reads as missing is only
+/// written once the files are loaded and turn out to have no value for it, so it never replaces a stored value.
+/// Writes are kept in memory and written to the files shortly after, so the latest writes can be lost if the page is
+/// closed right away.
+///
+/// Changes are not synced between tabs. If the browser doesn't support the origin private file system, values are
+/// only kept in memory.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::storage::{use_synced_storage, OpfsStorage};
+///
+/// fn App() -> Element {
+///     let drawing = use_synced_storage::<OpfsStorage, Vec<(f32, f32)>>("drawing".to_string(), Vec::new);
+///
+///     rsx! { "{drawing.read().len()} points" }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct OpfsStorage;
+
+impl StorageBacking for OpfsStorage {
+    type Key = String;
+
+    fn set<T: Serialize + Send + Sync + Clone + 'static>(key: String, value: &T) {
+        let contents = serde_to_string(value);
+        CACHE.with(|cache| cache.borrow_mut().insert(key.clone(), contents.clone()));
+        if is_seeding() && !is_loaded() {
+            // The key may only read as missing because the files aren't loaded yet.
+            SEEDED.with(|seeded| seeded.borrow_mut().insert(key));
+        } else {
+            SEEDED.with(|seeded| seeded.borrow_mut().remove(&key));
+            queue_write(key, Some(contents));
+        }
+        notify_key_change();
+    }
+
+    fn get<T: DeserializeOwned + Clone + 'static>(key: &String) -> Option<T> {
+        start_loading();
+        let contents = CACHE.with(|cache| cache.borrow().get(key).cloned())?;
+        try_serde_from_string(&contents)
+    }
+
+    fn remove(key: &String) {
+        CACHE.with(|cache| cache.borrow_mut().remove(key));
+        SEEDED.with(|seeded| seeded.borrow_mut().remove(key));
+        queue_write(key.clone(), None);
+        notify_key_change();
+
+        // Let the entries that are subscribed to the key start over instead of writing the old value back.
+        SUBSCRIPTIONS.with(|subscriptions| {
+            if let Some(subscription) = subscriptions.borrow().get(key) {
+                subscription.tx.send(StorageChannelPayload::removed()).ok();
+            }
+        });
+    }
+
+    fn raw_key(key: &String) -> Option<String> {
+        Some(key.clone())
+    }
+
+    fn keys() -> Vec<String> {
+        start_loading();
+        CACHE.with(|cache| cache.borrow().keys().cloned().collect())
+    }
+
+    fn stored_size(key: &String) -> Option<usize> {
+        start_loading();
+        CACHE.with(|cache| cache.borrow().get(key).map(String::len))
+    }
+}
+
+impl StorageSubscriber<OpfsStorage> for OpfsStorage {
+    fn subscribe<T: DeserializeOwned + Send + Sync + Clone + 'static>(
+        key: &String,
+    ) -> Receiver<StorageChannelPayload> {
+        SUBSCRIPTIONS.with(|subscriptions| {
+            let mut subscriptions = subscriptions.borrow_mut();
+            match subscriptions.get(key) {
+                Some(subscription) => subscription.tx.subscribe(),
+                None => {
+                    let (tx, rx) =
+                        channel::<StorageChannelPayload>(StorageChannelPayload::default());
+                    let subscription = StorageSubscription::new::<OpfsStorage, T>(tx, key.clone());
+                    subscriptions.insert(key.clone(), subscription);
+                    rx
+                }
+            }
+        })
+    }
+
+    fn unsubscribe(key: &String) {
+        SUBSCRIPTIONS.with(|subscriptions| {
+            let mut subscriptions = subscriptions.borrow_mut();
+            if subscriptions
+                .get(key)
+                .is_some_and(|entry| entry.tx.is_closed())
+            {
+                subscriptions.remove(key);
+            }
+        });
+    }
+}
+
+/// Wait until the stored values are loaded into memory.
+///
+/// Starts loading them if the backing wasn't used yet.
+pub async fn opfs_loaded() {
+    start_loading();
+    let mut loaded = LOADED.with(|loaded| loaded.subscribe());
+    loaded.wait_for(|loaded| *loaded).await.ok();
+}
+
+thread_local! {
+    /// The serialized value of every key, loaded from the files and updated by every write.
+    static CACHE: RefCell<HashMap<String, String>> = RefCell::new(HashMap::new());
+
+    /// The writes that weren't written to files yet. `None` removes the file.
+    static PENDING_WRITES: RefCell<HashMap<String, Option<String>>> = RefCell::new(HashMap::new());
+
+    /// The keys whose initial value was set before the files were loaded. The value is only written if the key has
+    /// no file.
+    static SEEDED: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
+
+    /// Whether a task is writing the pending writes to files.
+    static WRITING: Cell<bool> = const { Cell::new(false) };
+
+    /// The subscriptions of synced storage, which are notified once the values are loaded.
+    static SUBSCRIPTIONS: RefCell<HashMap<String, StorageSubscription>> = RefCell::new(HashMap::new());
+
+    static LOADING_STARTED: Cell<bool> = const { Cell::new(false) };
+
+    static LOADED: Sender<bool> = channel(false).0;
+}
+
+/// Load the stored values in the background, the first time this is called.
+fn start_loading() {
+    if LOADING_STARTED.with(|started| started.replace(true)) {
+        return;
+    }
+
+    spawn_local(async {
+        if let Err(e) = load().await {
+            tracing::warn!("Failed to load the origin private file system storage: {e:?}");
+        }
+        LOADED.with(|loaded| loaded.send_replace(true));
+        notify_key_change();
+
+        // The initial values of keys that really were missing can be written now.
+        let seeded = SEEDED.with(|seeded| std::mem::take(&mut *seeded.borrow_mut()));
+        for key in seeded {
+            if let Some(contents) = CACHE.with(|cache| cache.borrow().get(&key).cloned()) {
+                queue_write(key, Some(contents));
+            }
+        }
+
+        // Synced storage read the values before they were loaded.
+        SUBSCRIPTIONS.with(|subscriptions| {
+            for (key, subscription) in subscriptions.borrow().iter() {
+                if CACHE.with(|cache| cache.borrow().contains_key(key)) {
+                    subscription.get_and_send().ok();
+                }
+            }
+        });
+    });
+}
+
+async fn load() -> Result<(), JsValue> {
+    let directory = directory().await?;
+    let entries = call(&directory, "values", &[]).ok_or("the directory can't be listed")?;
+
+    loop {
+        let next = call_async(&entries, "next", &[])
+            .await
+            .ok_or("the directory can't be listed")??;
+        if get(&next, "done").and_then(|done| done.as_bool()) == Some(true) {
+            break;
+        }
+        let Some(handle) = get(&next, "value") else {
+            continue;
+        };
+        if get(&handle, "kind")
+            .and_then(|kind| kind.as_string())
+            .as_deref()
+            != Some("file")
+        {
+            continue;
+        }
+        let Some(key) = get(&handle, "name")
+            .and_then(|name| name.as_string())
+            .and_then(|name| key_from_file_name(&name))
+        else {
+            continue;
+        };
+
+        let file = call_async(&handle, "getFile", &[])
+            .await
+            .ok_or("the file can't be read")??;
+        let contents = call_async(&file, "text", &[])
+            .await
+            .ok_or("the file can't be read")??
+            .as_string()
+            .unwrap_or_default();
+
+        // Values written while loading are newer than the files, but initial values are not.
+        if PENDING_WRITES.with(|pending| !pending.borrow().contains_key(&key)) {
+            SEEDED.with(|seeded| seeded.borrow_mut().remove(&key));
+            CACHE.with(|cache| cache.borrow_mut().insert(key, contents));
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether the stored values were loaded into memory.
+fn is_loaded() -> bool {
+    LOADED.with(|loaded| *loaded.borrow())
+}
+
+/// Write a value to its file shortly, together with the other writes until then.
+fn queue_write(key: String, contents: Option<String>) {
+    PENDING_WRITES.with(|pending| pending.borrow_mut().insert(key, contents));
+    if WRITING.with(|writing| writing.replace(true)) {
+        return;
+    }
+
+    // Only one task writes at a time, so files are written in the order their values were set.
+    spawn_local(async {
+        loop {
+            sleep_ms(FLUSH_DELAY_MS).await;
+            let writes = PENDING_WRITES.with(|pending| std::mem::take(&mut *pending.borrow_mut()));
+            if writes.is_empty() {
+                break;
+            }
+            if let Err(e) = write_files(writes).await {
+                tracing::error!("Failed to write to the origin private file system: {e:?}");
+            }
+        }
+        WRITING.with(|writing| writing.set(false));
+    });
+}
+
+async fn write_files(writes: HashMap<String, Option<String>>) -> Result<(), JsValue> {
+    let directory = directory().await?;
+
+    for (key, contents) in writes {
+        let name = JsValue::from(file_name(&key));
+        let Some(contents) = contents else {
+            // Removing a file that was never written fails, which is fine.
+            call_async(&directory, "removeEntry", &[name]).await;
+            continue;
+        };
+
+        let options = Object::new();
+        Reflect::set(&options, &"create".into(), &true.into())?;
+        let handle = call_async(&directory, "getFileHandle", &[name, options.into()])
+            .await
+            .ok_or("the file can't be opened")??;
+        let writable = call_async(&handle, "createWritable", &[])
+            .await
+            .ok_or("the file can't be written")??;
+        call_async(&writable, "write", &[contents.into()])
+            .await
+            .ok_or("the file can't be written")??;
+        call_async(&writable, "close", &[])
+            .await
+            .ok_or("the file can't be written")??;
+    }
+
+    Ok(())
+}
+
+/// Get the directory the values are stored in, creating it if needed.
+async fn directory() -> Result<JsValue, JsValue> {
+    let storage = web_sys::window()
+        .and_then(|window| get(&window, "navigator"))
+        .and_then(|navigator| get(&navigator, "storage"))
+        .ok_or("the storage manager is not supported")?;
+    let root = call_async(&storage, "getDirectory", &[])
+        .await
+        .ok_or("the origin private file system is not supported")??;
+
+    let options = Object::new();
+    Reflect::set(&options, &"create".into(), &true.into())?;
+    call_async(
+        &root,
+        "getDirectoryHandle",
+        &[DIRECTORY.into(), options.into()],
+    )
+    .await
+    .ok_or("the origin private file system is not supported")?
+}
+
+/// Wait for `ms` milliseconds.
+async fn sleep_ms(ms: i32) {
+    let promise = Promise::new(&mut |resolve, _| {
+        if let Some(window) = web_sys::window() {
+            window
+                .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+                .ok();
+        }
+    });
+    JsFuture::from(promise).await.ok();
+}
+
+/// Escape a key into a valid file name.
+///
+/// Keys can contain characters like `/` which are not allowed in file names, so everything but letters, digits, `-`
+/// and `_` is percent encoded.
+fn file_name(key: &str) -> String {
+    key.bytes()
+        .map(|byte| match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' => (byte as char).to_string(),
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+/// Get the key of a file written by [`file_name`], or `None` for other files.
+fn key_from_file_name(name: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(name.len());
+    let mut chars = name.bytes();
+    while let Some(byte) = chars.next() {
+        if byte == b'%' {
+            let hex = [chars.next()?, chars.next()?];
+            bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// Get a property, treating `undefined` and `null` as missing.
+fn get(target: &JsValue, key: &str) -> Option<JsValue> {
+    Reflect::get(target, &key.into())
+        .ok()
+        .filter(|value| !value.is_undefined() && !value.is_null())
+}
+
+/// Call a method, returning `None` if it doesn't exist or throws.
+fn call(target: &JsValue, method: &str, args: &[JsValue]) -> Option<JsValue> {
+    let function: Function = get(target, method)?.dyn_into().ok()?;
+    function.apply(target, &args.iter().collect::<Array>()).ok()
+}
+
+/// Call a method that returns a promise and wait for it, returning `None` if the method doesn't exist.
+async fn call_async(
+    target: &JsValue,
+    method: &str,
+    args: &[JsValue],
+) -> Option<Result<JsValue, JsValue>> {
+    let promise: Promise = call(target, method, args)?.dyn_into().ok()?;
+    Some(JsFuture::from(promise).await)
+}
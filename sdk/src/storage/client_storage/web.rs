@@ -1,5 +1,8 @@
 use std::{
+    cell::RefCell,
     collections::HashMap,
+    future::Future,
+    rc::Rc,
     sync::{Arc, RwLock},
 };
 
@@ -10,9 +13,10 @@ use wasm_bindgen::prelude::Closure;
 use wasm_bindgen::JsCast;
 use web_sys::{window, Storage};
 
+use crate::storage::keys::notify_key_change;
 use crate::storage::{
-    serde_to_string, try_serde_from_string, StorageBacking, StorageChannelPayload,
-    StorageSubscriber, StorageSubscription,
+    serde_to_string, set_if_unchanged, try_serde_from_string, StorageBacking,
+    StorageChannelPayload, StorageSubscriber, StorageSubscription,
 };
 
 #[derive(Clone)]
@@ -28,6 +32,102 @@ impl StorageBacking for LocalStorage {
     fn get<T: DeserializeOwned>(key: &String) -> Option<T> {
         get(key, WebStorageType::Local)
     }
+
+    fn remove(key: &String) {
+        remove(key, WebStorageType::Local)
+    }
+
+    fn raw_key(key: &String) -> Option<String> {
+        Some(key.clone())
+    }
+
+    fn keys() -> Vec<String> {
+        keys(WebStorageType::Local)
+    }
+
+    fn stored_size(key: &String) -> Option<usize> {
+        stored_size(key, WebStorageType::Local)
+    }
+
+    fn version(key: &String) -> u64 {
+        get_storage_by_type(WebStorageType::Local)
+            .and_then(|storage| storage.get_item(&version_key(key)).ok()?)
+            .and_then(|version| version.parse().ok())
+            .unwrap_or(0)
+    }
+
+    fn compare_and_set<T>(
+        key: String,
+        version: u64,
+        expected: Option<T>,
+        value: T,
+    ) -> impl Future<Output = bool>
+    where
+        T: Serialize + DeserializeOwned + Send + Sync + Clone + 'static,
+    {
+        with_lock(key.clone(), move || {
+            if Self::version(&key) != version
+                || !set_if_unchanged::<Self, T>(key.clone(), expected.as_ref(), &value)
+            {
+                return false;
+            }
+            if let Some(storage) = get_storage_by_type(WebStorageType::Local) {
+                storage
+                    .set_item(&version_key(&key), &(version + 1).to_string())
+                    .ok();
+            }
+            true
+        })
+    }
+}
+
+/// The prefix of the keys the versions of local storage values are stored under.
+const VERSION_PREFIX: &str = "__dioxus_sdk_version:";
+
+/// The key the version of a local storage value is stored under.
+fn version_key(key: &str) -> String {
+    format!("{VERSION_PREFIX}{key}")
+}
+
+/// Run `f` while holding the Web Lock of a key, so other tabs can't compare and set the key in between.
+///
+/// Browsers without the Web Locks API run `f` right away, which is only atomic within the tab.
+async fn with_lock<R: 'static>(key: String, f: impl FnOnce() -> R + 'static) -> R {
+    use js_sys::{Function, Promise, Reflect};
+    use wasm_bindgen::JsValue;
+    use wasm_bindgen_futures::JsFuture;
+
+    let locks = window()
+        .and_then(|window| Reflect::get(&window.navigator(), &"locks".into()).ok())
+        .filter(|locks| !locks.is_undefined());
+    let request = locks
+        .as_ref()
+        .and_then(|locks| Reflect::get(locks, &"request".into()).ok())
+        .and_then(|request| request.dyn_into::<Function>().ok());
+
+    let f = Rc::new(RefCell::new(Some(f)));
+    let result = Rc::new(RefCell::new(None));
+    if let (Some(locks), Some(request)) = (locks, request) {
+        // The lock is held until the callback returns.
+        let callback = {
+            let (f, result) = (f.clone(), result.clone());
+            Closure::once_into_js(move |_lock: JsValue| {
+                if let Some(f) = f.borrow_mut().take() {
+                    *result.borrow_mut() = Some(f());
+                }
+            })
+        };
+        let name = JsValue::from_str(&version_key(&key));
+        if let Ok(promise) = request.call2(&locks, &name, &callback) {
+            JsFuture::from(Promise::from(promise)).await.ok();
+        }
+    }
+
+    let result = result.borrow_mut().take();
+    result.unwrap_or_else(|| {
+        let f = f.borrow_mut().take();
+        f.expect("the lock callback only runs once")()
+    })
 }
 
 impl StorageSubscriber<LocalStorage> for LocalStorage {
@@ -66,14 +166,85 @@ static SUBSCRIPTIONS: Lazy<Arc<RwLock<HashMap<String, StorageSubscription>>>> =
     // Create a closure that will be called when a storage event occurs.
     let closure = Closure::wrap(Box::new(move |e: web_sys::StorageEvent| {
         tracing::trace!("Storage event: {:?}", e);
-        let key: String = e.key().unwrap();
+        // Events without a key are caused by clearing the storage.
+        let Some(key) = e.key() else {
+            notify_key_change();
+            return;
+        };
+        if key.starts_with(VERSION_PREFIX) {
+            return;
+        }
+        // Only keep the latest value for each key until the next flush.
+        let is_first = PENDING_EVENTS.with(|pending| {
+            let mut pending = pending.borrow_mut();
+            let is_first = pending.is_empty();
+            pending.insert(key, e.new_value());
+            is_first
+        });
+        if is_first {
+            schedule_flush();
+        }
+    }) as Box<dyn FnMut(web_sys::StorageEvent)>);
+    // Register the closure to be called when a storage event occurs.
+    window()
+        .unwrap()
+        .add_event_listener_with_callback("storage", closure.as_ref().unchecked_ref())
+        .unwrap();
+    // Relinquish ownership of the closure to the JS runtime so that it can be called later.
+    closure.forget();
+    Arc::new(RwLock::new(HashMap::new()))
+});
+
+/// How long to collect storage events before delivering them, roughly one animation frame.
+const FLUSH_DELAY_MS: i32 = 16;
+
+thread_local! {
+    /// The latest serialized value of each key that changed since the last flush.
+    static PENDING_EVENTS: RefCell<HashMap<String, Option<String>>> = RefCell::new(HashMap::new());
+
+    /// The last serialized value of each key this tab has seen, either from a storage event or its own writes.
+    static KNOWN_VALUES: RefCell<HashMap<String, Option<String>>> = RefCell::new(HashMap::new());
+}
+
+/// Deliver the pending storage events after a short delay.
+///
+/// Other tabs can write many times in quick succession. Batching the events means each key is
+/// only decoded once per flush, no matter how many events it received.
+fn schedule_flush() {
+    let flush = Closure::once_into_js(flush_storage_events);
+    window()
+        .unwrap()
+        .set_timeout_with_callback_and_timeout_and_arguments_0(
+            flush.unchecked_ref(),
+            FLUSH_DELAY_MS,
+        )
+        .unwrap();
+}
+
+fn flush_storage_events() {
+    let pending = PENDING_EVENTS.with(|pending| std::mem::take(&mut *pending.borrow_mut()));
+    if !pending.is_empty() {
+        notify_key_change();
+    }
+    for (key, value) in pending {
+        // Skip the decode if the value ended up the same as the one this tab already has.
+        let unchanged = KNOWN_VALUES.with(|known| {
+            let previous = known.borrow_mut().insert(key.clone(), value.clone());
+            previous == Some(value)
+        });
+        if unchanged {
+            tracing::trace!("Skipping unchanged storage event for \"{}\"", key);
+            continue;
+        }
+
         let read_binding = SUBSCRIPTIONS.read().unwrap();
         if let Some(subscription) = read_binding.get(&key) {
             if subscription.tx.is_closed() {
                 tracing::trace!("Channel is closed, removing subscription for \"{}\"", key);
                 drop(read_binding);
                 SUBSCRIPTIONS.write().unwrap().remove(&key);
-                return;
+                KNOWN_VALUES.with(|known| known.borrow_mut().remove(&key));
+                continue;
             }
             // Call the getter for the given entry and send the value to said entry's channel.
             match subscription.get_and_send() {
@@ -81,16 +252,21 @@ static SUBSCRIPTIONS: Lazy<Arc<RwLock<HashMap<String, StorageSubscription>>>> =
                 Err(err) => tracing::error!("Error sending storage event: {:?}", err.to_string()),
             }
         }
-    }) as Box<dyn FnMut(web_sys::StorageEvent)>);
-    // Register the closure to be called when a storage event occurs.
-    window()
-        .unwrap()
-        .add_event_listener_with_callback("storage", closure.as_ref().unchecked_ref())
-        .unwrap();
-    // Relinquish ownership of the closure to the JS runtime so that it can be called later.
-    closure.forget();
-    Arc::new(RwLock::new(HashMap::new()))
-});
+    }
+}
+
+/// Web storage is written synchronously by the browser, so there is nothing to move off of the main thread.
+///
+/// This only exists so the same code can enable background writes on every platform.
+pub fn enable_background_writes() {}
+
+/// Does nothing on the web. See [`enable_background_writes`].
+pub fn use_background_writes() {}
+
+/// Store the saves waiting for the end of the current tick. Everything else is already written on the web.
+pub fn flush() {
+    crate::storage::coalesce::flush_writes();
+}
 
 #[derive(Clone)]
 pub struct SessionStorage;
@@ -105,6 +281,22 @@ impl StorageBacking for SessionStorage {
     fn get<T: DeserializeOwned>(key: &String) -> Option<T> {
         get(key, WebStorageType::Session)
     }
+
+    fn remove(key: &String) {
+        remove(key, WebStorageType::Session)
+    }
+
+    fn raw_key(key: &String) -> Option<String> {
+        Some(key.clone())
+    }
+
+    fn keys() -> Vec<String> {
+        keys(WebStorageType::Session)
+    }
+
+    fn stored_size(key: &String) -> Option<usize> {
+        stored_size(key, WebStorageType::Session)
+    }
 }
 
 fn set<T: Serialize>(key: String, value: &T, storage_type: WebStorageType) {
@@ -113,6 +305,10 @@ fn set<T: Serialize>(key: String, value: &T, storage_type: WebStorageType) {
         .unwrap()
         .set_item(&key, &as_str)
         .unwrap();
+    if let WebStorageType::Local = storage_type {
+        KNOWN_VALUES.with(|known| known.borrow_mut().insert(key, Some(as_str)));
+    }
+    notify_key_change();
 }
 
 fn get<T: DeserializeOwned>(key: &str, storage_type: WebStorageType) -> Option<T> {
@@ -120,6 +316,91 @@ fn get<T: DeserializeOwned>(key: &str, storage_type: WebStorageType) -> Option<T
     try_serde_from_string(&s)
 }
 
+fn stored_size(key: &str, storage_type: WebStorageType) -> Option<usize> {
+    let s: String = get_storage_by_type(storage_type)?.get_item(key).ok()??;
+    Some(s.len())
+}
+
+fn remove(key: &str, storage_type: WebStorageType) {
+    if let WebStorageType::Local = storage_type {
+        KNOWN_VALUES.with(|known| known.borrow_mut().insert(key.to_string(), None));
+    }
+    if let Some(storage) = get_storage_by_type(storage_type) {
+        storage.remove_item(key).ok();
+    }
+    notify_key_change();
+
+    // The browser only sends storage events to other tabs, so the entries in this tab are told here.
+    if let WebStorageType::Local = storage_type {
+        if let Some(subscription) = SUBSCRIPTIONS.read().unwrap().get(key) {
+            subscription.tx.send(StorageChannelPayload::removed()).ok();
+        }
+    }
+}
+
+fn keys(storage_type: WebStorageType) -> Vec<String> {
+    let Some(storage) = get_storage_by_type(storage_type) else {
+        return Vec::new();
+    };
+    let len = storage.length().unwrap_or(0);
+    (0..len)
+        .filter_map(|index| storage.key(index).ok()?)
+        .filter(|key| !key.starts_with(VERSION_PREFIX))
+        .collect()
+}
+
+/// The backings [`dump_storage`](crate::storage::dump_storage) reads.
+pub(crate) const DUMPED_BACKINGS: &[&str] = &["local", "session"];
+
+fn storage_type_by_name(backing: &str) -> Option<WebStorageType> {
+    match backing {
+        "local" => Some(WebStorageType::Local),
+        "session" => Some(WebStorageType::Session),
+        _ => None,
+    }
+}
+
+/// Get the encoded value of every key in a backing.
+///
+/// Web storage is shared with the rest of the page, so this includes values that weren't written by this crate.
+pub(crate) fn raw_entries(backing: &str) -> Vec<(String, String)> {
+    let Some(storage) = storage_type_by_name(backing).and_then(get_storage_by_type) else {
+        return Vec::new();
+    };
+
+    let len = storage.length().unwrap_or(0);
+    (0..len)
+        .filter_map(|index| {
+            let key = storage.key(index).ok()??;
+            if key.starts_with(VERSION_PREFIX) {
+                return None;
+            }
+            let value = storage.get_item(&key).ok()??;
+            Some((key, value))
+        })
+        .collect()
+}
+
+/// Set the encoded value of a key in a backing and notify its subscribers in this tab.
+pub(crate) fn set_raw(backing: &str, key: String, contents: String) {
+    let Some(storage_type) = storage_type_by_name(backing) else {
+        return;
+    };
+    let Some(storage) = get_storage_by_type(storage_type) else {
+        return;
+    };
+    storage.set_item(&key, &contents).ok();
+    notify_key_change();
+
+    // The browser only sends storage events to other tabs.
+    if let WebStorageType::Local = storage_type {
+        KNOWN_VALUES.with(|known| known.borrow_mut().insert(key.clone(), Some(contents)));
+        if let Some(subscription) = SUBSCRIPTIONS.read().unwrap().get(&key) {
+            subscription.get_and_send().ok();
+        }
+    }
+}
+
 fn get_storage_by_type(storage_type: WebStorageType) -> Option<Storage> {
     window().map_or_else(
         || None,
@@ -130,6 +411,7 @@ fn get_storage_by_type(storage_type: WebStorageType) -> Option<Storage> {
     )
 }
 
+#[derive(Clone, Copy)]
 enum WebStorageType {
     Local,
     Session,
@@ -0,0 +1,168 @@
+//! Coalesces writes to the same key within one tick.
+//!
+//! Several signals can save the same key in one frame, like a persistent projection and a storage entry of the same
+//! key, or a signal written many times by one event handler. Saves are queued with [`queue_write`] instead of stored
+//! right away, and once the current render is done only the last value queued for each key is encoded and stored.
+//! Anything that reads the backing directly, like listing or exporting keys, calls [`flush_writes`] first.
+
+use dioxus::prelude::spawn_forever;
+use serde::Serialize;
+use std::any::TypeId;
+use std::cell::RefCell;
+
+use super::{limits, metrics, StorageBacking, StorageOperation, StorageOrigin};
+
+/// A write waiting for the end of the tick.
+struct PendingWrite {
+    /// The backing and the debug name of the key, which identify the stored value.
+    id: (TypeId, String),
+    write: Box<dyn FnOnce()>,
+}
+
+thread_local! {
+    /// The writes queued in the current tick, in the order their keys were first written.
+    static PENDING: RefCell<Vec<PendingWrite>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Store a value at the end of the current tick, replacing any write to the same key queued before it.
+///
+/// Outside of a running app there are no ticks, so the value is stored right away.
+pub(crate) fn queue_write<S, T>(key: S::Key, value: T)
+where
+    S: StorageBacking,
+    T: Serialize + Send + Sync + Clone + 'static,
+{
+    let id = (TypeId::of::<S>(), format!("{key:?}"));
+    let metrics_key = key.clone();
+    // Keep the origin of the write, so synced entries still recognize their own saves.
+    let origin = StorageOrigin::current();
+    let write = move || match origin {
+        Some(origin) => origin.scope(|| store::<S, T>(key, &value)),
+        None => store::<S, T>(key, &value),
+    };
+
+    let (replaced, is_first) = PENDING.with(|pending| {
+        let mut pending = pending.borrow_mut();
+        let write = Box::new(write);
+        match pending.iter_mut().find(|pending| pending.id == id) {
+            Some(pending) => {
+                pending.write = write;
+                (true, false)
+            }
+            None => {
+                pending.push(PendingWrite { id, write });
+                (false, pending.len() == 1)
+            }
+        }
+    });
+    if replaced {
+        metrics::record_coalesced::<S>(&metrics_key);
+    }
+    // Tasks are only polled once the current render is finished. The writes are also stored if the task is dropped
+    // before it runs, like when the app exits, or if there is no runtime to spawn it in.
+    if is_first {
+        let flush = FlushOnDrop;
+        spawn_forever(async move { drop(flush) });
+    }
+}
+
+/// Stores the queued writes when dropped.
+struct FlushOnDrop;
+
+impl Drop for FlushOnDrop {
+    fn drop(&mut self) {
+        flush_writes();
+    }
+}
+
+/// Store a value right away.
+pub(crate) fn store<S, T>(key: S::Key, value: &T)
+where
+    S: StorageBacking,
+    T: Serialize + Send + Sync + Clone + 'static,
+{
+    {
+        let _operation = metrics::track::<S>(StorageOperation::Store, &key);
+        S::set(key, value);
+    }
+    limits::check_limits::<S>();
+}
+
+/// Store every write queued in the current tick.
+pub(crate) fn flush_writes() {
+    let pending = PENDING.with(|pending| std::mem::take(&mut *pending.borrow_mut()));
+    for pending in pending {
+        (pending.write)();
+    }
+}
+
+/// Store the write queued for a key in the current tick, if any, so reading the key gets the latest value.
+pub(crate) fn flush_key<S: StorageBacking>(key: &S::Key) {
+    let id = (TypeId::of::<S>(), format!("{key:?}"));
+    let pending = PENDING.with(|pending| {
+        let mut pending = pending.borrow_mut();
+        let index = pending.iter().position(|pending| pending.id == id)?;
+        Some(pending.remove(index))
+    });
+    if let Some(pending) = pending {
+        (pending.write)();
+    }
+}
+
+#[cfg(all(test, feature = "storage-metrics", not(target_family = "wasm")))]
+#[test]
+fn test_coalesce_writes() {
+    use super::{storage_metrics, LocalStorage};
+    use dioxus::prelude::*;
+
+    fn app() -> Element {
+        use_hook(|| {
+            for value in 1..=3 {
+                queue_write::<LocalStorage, i32>("coalesce-test".to_string(), value);
+            }
+        });
+        rsx! {}
+    }
+
+    super::client_storage::set_test_directory();
+    let key = "coalesce-test".to_string();
+    LocalStorage::remove(&key);
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    // Nothing is stored until the render is done.
+    assert_eq!(LocalStorage::get::<i32>(&key), None);
+    dom.process_events();
+
+    // Only the last value is stored, and the two writes it replaced are counted.
+    assert_eq!(LocalStorage::get::<i32>(&key), Some(3));
+    let metrics = &storage_metrics().keys["LocalStorage:\"coalesce-test\""];
+    assert_eq!(metrics.stores, 1);
+    assert_eq!(metrics.coalesced, 2);
+
+    LocalStorage::remove(&key);
+}
+
+#[cfg(all(test, not(target_family = "wasm")))]
+#[test]
+fn test_coalesced_writes_stored_on_exit() {
+    use super::LocalStorage;
+    use dioxus::prelude::*;
+
+    fn app() -> Element {
+        use_hook(|| queue_write::<LocalStorage, i32>("coalesce-exit-test".to_string(), 1));
+        rsx! {}
+    }
+
+    super::client_storage::set_test_directory();
+    let key = "coalesce-exit-test".to_string();
+    LocalStorage::remove(&key);
+
+    // The app exits before the task that stores the write is polled.
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    drop(dom);
+    assert_eq!(LocalStorage::get::<i32>(&key), Some(1));
+
+    LocalStorage::remove(&key);
+}
@@ -0,0 +1,147 @@
+//! Conflict resolution for synced storage entries.
+
+use serde::{Deserialize, Serialize};
+use std::{cell::RefCell, rc::Rc};
+
+/// The values involved in a conflict between two app sessions writing to the same synced storage entry.
+#[derive(Debug)]
+pub struct Conflict<'a, T> {
+    /// The last value both sessions agreed on.
+    pub base: &'a T,
+    /// The local value that has not been written to storage yet.
+    pub local: &'a T,
+    /// The value that was written to storage by another session.
+    pub remote: &'a T,
+}
+
+/// A strategy for resolving conflicts in synced storage entries.
+///
+/// The resolver is invoked by [`SyncedStorageEntry::subscribe_to_storage`](super::SyncedStorageEntry::subscribe_to_storage)
+/// when a value arrives from another session while the local value holds changes that have not been synced yet.
+/// Any closure with the signature `Fn(Conflict<T>) -> T` can be used as a resolver.
+pub trait ConflictResolver<T>: 'static {
+    /// Returns the value that should be kept.
+    fn resolve(&self, conflict: Conflict<'_, T>) -> T;
+}
+
+impl<T, F> ConflictResolver<T> for F
+where
+    F: Fn(Conflict<'_, T>) -> T + 'static,
+{
+    fn resolve(&self, conflict: Conflict<'_, T>) -> T {
+        self(conflict)
+    }
+}
+
+/// The last value a synced storage entry knows to be in sync with storage, which is the base of its conflicts.
+///
+/// It is shared by the clones of the entry, so saves and updates from storage both move it forward.
+#[derive(Clone)]
+pub(crate) struct SyncedValue<T>(Rc<RefCell<T>>);
+
+impl<T: Clone + PartialEq> SyncedValue<T> {
+    pub(crate) fn new(value: T) -> Self {
+        Self(Rc::new(RefCell::new(value)))
+    }
+
+    /// Remember a value that was saved to storage or came from it.
+    pub(crate) fn set(&self, value: T) {
+        *self.0.borrow_mut() = value;
+    }
+
+    /// Pick the value to keep when `remote` arrives from storage while the entry holds `local`.
+    ///
+    /// The resolver is only called if the local value changed since it was last in sync.
+    pub(crate) fn receive(&self, local: T, remote: T, resolver: &dyn ConflictResolver<T>) -> T {
+        let synced = self.0.borrow().clone();
+        let value = if local != synced && local != remote {
+            tracing::trace!("Resolving storage conflict");
+            resolver.resolve(Conflict {
+                base: &synced,
+                local: &local,
+                remote: &remote,
+            })
+        } else {
+            remote
+        };
+        self.set(value.clone());
+        value
+    }
+}
+
+/// Always keeps the value from storage. This is the default behavior for synced storage.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RemoteWins;
+
+impl<T: Clone> ConflictResolver<T> for RemoteWins {
+    fn resolve(&self, conflict: Conflict<'_, T>) -> T {
+        conflict.remote.clone()
+    }
+}
+
+/// Always keeps the local value, overwriting the value from storage.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalWins;
+
+impl<T: Clone> ConflictResolver<T> for LocalWins {
+    fn resolve(&self, conflict: Conflict<'_, T>) -> T {
+        conflict.local.clone()
+    }
+}
+
+/// A value tagged with a version that is bumped on every change.
+///
+/// Use with [`HighestVersionWins`] to keep whichever session made the most changes since they last agreed.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Versioned<T> {
+    /// The number of changes made to the value.
+    pub version: u64,
+    /// The versioned value.
+    pub value: T,
+}
+
+impl<T> Versioned<T> {
+    /// Creates a new versioned value at version zero.
+    pub fn new(value: T) -> Self {
+        Self { version: 0, value }
+    }
+
+    /// Replaces the value and bumps the version.
+    pub fn set(&mut self, value: T) {
+        self.value = value;
+        self.version += 1;
+    }
+
+    /// Modifies the value in place and bumps the version.
+    pub fn update(&mut self, f: impl FnOnce(&mut T)) {
+        f(&mut self.value);
+        self.version += 1;
+    }
+}
+
+/// Keeps the value with the highest version, preferring the value from storage on a tie.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HighestVersionWins;
+
+impl<T: Clone> ConflictResolver<Versioned<T>> for HighestVersionWins {
+    fn resolve(&self, conflict: Conflict<'_, Versioned<T>>) -> Versioned<T> {
+        if conflict.local.version > conflict.remote.version {
+            conflict.local.clone()
+        } else {
+            conflict.remote.clone()
+        }
+    }
+}
+
+#[test]
+fn test_local_save_is_not_a_conflict() {
+    let synced = SyncedValue::new(0);
+
+    // The local value was saved, so a later update from another session is applied as is.
+    synced.set(1);
+    assert_eq!(synced.receive(1, 2, &LocalWins), 2);
+
+    // A change that wasn't saved yet still conflicts with the update.
+    assert_eq!(synced.receive(3, 4, &LocalWins), 3);
+    assert_eq!(synced.receive(3, 5, &RemoteWins), 5);
+}
@@ -0,0 +1,104 @@
+//! Errors reported by storage backings.
+
+use dioxus::prelude::*;
+use once_cell::sync::Lazy;
+use std::fmt::Display;
+use tokio::sync::watch::Sender;
+
+use super::{StorageLimits, StorageUsage};
+
+/// The most recent storage error. A watch channel keeps the latest value, so hooks created after
+/// an error was reported still see it.
+static LAST_ERROR: Lazy<Sender<Option<StorageError>>> =
+    Lazy::new(|| tokio::sync::watch::channel(None).0);
+
+/// A problem a storage backing ran into while reading or writing a value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StorageError {
+    /// The stored value could not be decoded, so the previous good value was restored from its backup.
+    Recovered { key: String, reason: String },
+    /// The stored value and its backup could not be decoded, so the initial value was used instead.
+    Corrupted { key: String },
+    /// The value could not be written.
+    WriteFailed { key: String, reason: String },
+    /// [`StorageEntry::modify`](super::StorageEntry::modify) gave up because other writes kept changing the value.
+    Contended { key: String },
+    /// A backing holds more than [`StorageLimits::warn_ratio`] of its [`StorageLimits`].
+    NearingLimit {
+        backing: String,
+        usage: StorageUsage,
+        limits: StorageLimits,
+    },
+    /// A backing holds more than its [`StorageLimits`] allow.
+    LimitExceeded {
+        backing: String,
+        usage: StorageUsage,
+        limits: StorageLimits,
+    },
+}
+
+impl std::error::Error for StorageError {}
+impl Display for StorageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Recovered { key, reason } => {
+                write!(f, "recovered \"{key}\" from its backup: {reason}")
+            }
+            Self::Corrupted { key } => write!(f, "\"{key}\" is corrupted and has no usable backup"),
+            Self::WriteFailed { key, reason } => write!(f, "failed to write \"{key}\": {reason}"),
+            Self::Contended { key } => write!(f, "{key} kept changing while it was modified"),
+            Self::NearingLimit { backing, usage, .. } => write!(
+                f,
+                "{backing} is nearing its limits with {} keys and {} bytes",
+                usage.keys, usage.bytes
+            ),
+            Self::LimitExceeded { backing, usage, .. } => write!(
+                f,
+                "{backing} exceeds its limits with {} keys and {} bytes",
+                usage.keys, usage.bytes
+            ),
+        }
+    }
+}
+
+/// Reports an error to every [`use_storage_error`] hook.
+pub(crate) fn report_storage_error(error: StorageError) {
+    tracing::warn!("Storage error: {}", error);
+    LAST_ERROR.send_replace(Some(error));
+}
+
+/// A hook for the most recent error reported by a storage backing.
+///
+/// Storage hooks fall back to a backup or their initial value instead of failing, so this is the
+/// place to surface those recoveries to the user.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::storage::use_storage_error;
+///
+/// fn App() -> Element {
+///     let error = use_storage_error();
+///
+///     rsx! {
+///         if let Some(error) = error() {
+///             p { "Some of your settings could not be loaded: {error}" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_storage_error() -> ReadOnlySignal<Option<StorageError>> {
+    let error = use_hook(|| {
+        let mut receiver = LAST_ERROR.subscribe();
+        let mut error = Signal::new(receiver.borrow_and_update().clone());
+
+        spawn(async move {
+            while receiver.changed().await.is_ok() {
+                error.set(receiver.borrow_and_update().clone());
+            }
+        });
+
+        error
+    });
+
+    ReadOnlySignal::new(error)
+}
@@ -0,0 +1,239 @@
+//! Untyped access to everything in storage, for exporting user data and debugging.
+
+use once_cell::sync::Lazy;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::RwLock;
+
+use super::client_storage::{raw_entries, set_raw, DUMPED_BACKINGS};
+use super::{coalesce, serde_to_string, try_serde_from_string};
+
+/// Converts the stored values of one key between their encoded form and JSON.
+#[derive(Clone, Copy)]
+struct Codec {
+    decode: fn(&str) -> Option<Value>,
+    encode: fn(&Value) -> Result<String, String>,
+}
+
+/// The codec of every key that a storage entry has been created for in this process.
+///
+/// Stored values don't describe their own type, so only keys whose type is known can be decoded.
+static CODECS: Lazy<RwLock<HashMap<String, Codec>>> = Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Remember the type of the value stored under a key, so [`dump_storage`] can decode it.
+pub(crate) fn register_type<T: Serialize + DeserializeOwned + 'static>(raw_key: String) {
+    CODECS.write().unwrap().insert(
+        raw_key,
+        Codec {
+            decode: decode::<T>,
+            encode: encode::<T>,
+        },
+    );
+}
+
+fn decode<T: Serialize + DeserializeOwned>(encoded: &str) -> Option<Value> {
+    serde_json::to_value(try_serde_from_string::<T>(encoded)?).ok()
+}
+
+fn encode<T: Serialize + DeserializeOwned>(value: &Value) -> Result<String, String> {
+    let value: T = serde_json::from_value(value.clone()).map_err(|err| err.to_string())?;
+    Ok(serde_to_string(&value))
+}
+
+fn codec(raw_key: &str) -> Option<Codec> {
+    CODECS.read().unwrap().get(raw_key).copied()
+}
+
+/// A problem with the data passed to [`import_storage`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImportStorageError {
+    /// The data doesn't have the shape [`dump_storage`] produces.
+    InvalidFormat { reason: String },
+    /// The data contains a backing that doesn't exist on this platform.
+    UnknownBacking { backing: String },
+    /// A decoded value was given for a key whose type isn't known, so it can't be encoded.
+    UnknownType { key: String },
+    /// A value doesn't match the type of its key.
+    InvalidValue { key: String, reason: String },
+}
+
+impl std::error::Error for ImportStorageError {}
+impl Display for ImportStorageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::InvalidFormat { reason } => write!(f, "invalid storage data: {reason}"),
+            Self::UnknownBacking { backing } => write!(f, "unknown storage backing \"{backing}\""),
+            Self::UnknownType { key } => {
+                write!(
+                    f,
+                    "the type of \"{key}\" is unknown, so it must be imported raw"
+                )
+            }
+            Self::InvalidValue { key, reason } => {
+                write!(f, "invalid value for \"{key}\": {reason}")
+            }
+        }
+    }
+}
+
+/// Export every key in storage as JSON.
+///
+/// The result maps each backing (`"local"` and, on the web, `"session"`) to its keys. Each key maps to
+/// `{ "value": ... }` if its value could be decoded, or `{ "raw": "..." }` with the encoded value otherwise.
+/// Stored values don't describe their own type, so only keys that a storage hook has used since the app
+/// started can be decoded.
+///
+/// ```json
+/// {
+///     "local": {
+///         "count": { "value": 3 },
+///         "settings": { "raw": "78da63..." }
+///     }
+/// }
+/// ```
+///
+/// The in-memory session storage on desktop isn't included.
+pub fn dump_storage() -> Value {
+    // Include the saves waiting for the end of the current tick.
+    coalesce::flush_writes();
+    let mut backings = Map::new();
+    for backing in DUMPED_BACKINGS {
+        let mut entries = Map::new();
+        for (key, encoded) in raw_entries(backing) {
+            let decoded = codec(&key).and_then(|codec| (codec.decode)(&encoded));
+            let entry = match decoded {
+                Some(value) => [("value".to_string(), value)],
+                None => [("raw".to_string(), Value::String(encoded))],
+            };
+            entries.insert(key, Value::Object(entry.into_iter().collect()));
+        }
+        backings.insert(backing.to_string(), Value::Object(entries));
+    }
+    Value::Object(backings)
+}
+
+/// Import keys exported with [`dump_storage`], overwriting the values that are already stored.
+///
+/// Keys that are missing from `data` are left alone. Storage hooks that are synced with storage pick
+/// up the imported values right away, other hooks see them the next time they are created.
+///
+/// Nothing is written if any of the entries is invalid.
+pub fn import_storage(data: Value) -> Result<(), ImportStorageError> {
+    let Value::Object(backings) = data else {
+        return Err(invalid_format("expected an object of backings"));
+    };
+
+    let mut writes = Vec::new();
+    for (backing, entries) in backings {
+        if !DUMPED_BACKINGS.contains(&backing.as_str()) {
+            return Err(ImportStorageError::UnknownBacking { backing });
+        }
+        let Value::Object(entries) = entries else {
+            return Err(invalid_format(format!(
+                "expected an object of keys for \"{backing}\""
+            )));
+        };
+
+        for (key, entry) in entries {
+            let encoded = encode_entry(&key, entry)?;
+            writes.push((backing.clone(), key, encoded));
+        }
+    }
+
+    for (backing, key, encoded) in writes {
+        set_raw(&backing, key, encoded);
+    }
+    Ok(())
+}
+
+/// Get the encoded form of one entry of [`import_storage`].
+fn encode_entry(key: &str, entry: Value) -> Result<String, ImportStorageError> {
+    let codec = codec(key);
+    match entry {
+        Value::Object(mut entry) if entry.len() == 1 => {
+            if let Some(value) = entry.remove("value") {
+                let codec = codec.ok_or_else(|| ImportStorageError::UnknownType {
+                    key: key.to_string(),
+                })?;
+                return (codec.encode)(&value).map_err(|reason| ImportStorageError::InvalidValue {
+                    key: key.to_string(),
+                    reason,
+                });
+            }
+
+            if let Some(Value::String(raw)) = entry.remove("raw") {
+                // Subscribers of known keys decode the new value right away, so it has to be valid.
+                if codec.is_some_and(|codec| (codec.decode)(&raw).is_none()) {
+                    return Err(ImportStorageError::InvalidValue {
+                        key: key.to_string(),
+                        reason: "the raw value could not be decoded".to_string(),
+                    });
+                }
+                return Ok(raw);
+            }
+
+            Err(invalid_entry(key))
+        }
+        _ => Err(invalid_entry(key)),
+    }
+}
+
+fn invalid_format(reason: impl Into<String>) -> ImportStorageError {
+    ImportStorageError::InvalidFormat {
+        reason: reason.into(),
+    }
+}
+
+fn invalid_entry(key: &str) -> ImportStorageError {
+    invalid_format(format!(
+        "expected {{ \"value\": ... }} or {{ \"raw\": \"...\" }} for \"{key}\""
+    ))
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn test_dump_and_import_storage() {
+    use super::{LocalStorage, StorageBacking};
+
+    super::client_storage::set_test_directory();
+    let known = "inspect-known".to_string();
+    let unknown = "inspect-unknown".to_string();
+    register_type::<Vec<u32>>(known.clone());
+    LocalStorage::set(known.clone(), &vec![1u32, 2, 3]);
+    LocalStorage::set(unknown.clone(), &"hidden".to_string());
+
+    let dump = dump_storage();
+    assert_eq!(
+        dump["local"][&known],
+        serde_json::json!({ "value": [1, 2, 3] })
+    );
+    let raw = dump["local"][&unknown]["raw"].clone();
+    assert!(raw.is_string());
+
+    import_storage(serde_json::json!({
+        "local": { &known: { "value": [4, 5] }, &unknown: { "raw": raw } }
+    }))
+    .unwrap();
+    assert_eq!(LocalStorage::get::<Vec<u32>>(&known), Some(vec![4, 5]));
+    assert_eq!(
+        LocalStorage::get::<String>(&unknown),
+        Some("hidden".to_string())
+    );
+
+    // A decoded value can't be imported without knowing its type, and nothing is written then.
+    let error = import_storage(serde_json::json!({
+        "local": { &known: { "value": [6] }, &unknown: { "value": "shown" } }
+    }));
+    assert_eq!(
+        error,
+        Err(ImportStorageError::UnknownType {
+            key: unknown.clone()
+        })
+    );
+    assert_eq!(LocalStorage::get::<Vec<u32>>(&known), Some(vec![4, 5]));
+
+    LocalStorage::remove(&known);
+    LocalStorage::remove(&unknown);
+}
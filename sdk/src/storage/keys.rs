@@ -0,0 +1,104 @@
+//! Listing the keys in a storage backing and watching them change.
+
+use dioxus::prelude::*;
+use once_cell::sync::Lazy;
+use tokio::sync::watch::{channel, Sender};
+
+use super::{coalesce, current_scope, StorageBacking};
+
+/// Notified whenever any backing sets or removes a key, so listings can be refreshed.
+static KEY_CHANGES: Lazy<Sender<()>> = Lazy::new(|| channel(()).0);
+
+/// Let the key listings know that a key was set or removed.
+pub(crate) fn notify_key_change() {
+    KEY_CHANGES.send_replace(());
+}
+
+/// Get the sorted keys of the backing `S` that start with `prefix`.
+///
+/// If a storage scope is active, only the keys in that scope are listed, without the scope prefix, so they can be
+/// passed straight back to the storage hooks.
+pub fn storage_keys<S: StorageBacking<Key = String>>(prefix: &str) -> Vec<String> {
+    let scope = current_scope().map(|scope| format!("{scope}."));
+    list_keys::<S>(scope.as_deref(), prefix)
+}
+
+fn list_keys<S: StorageBacking<Key = String>>(scope: Option<&str>, prefix: &str) -> Vec<String> {
+    // Keys that were first written in this tick only exist once their writes are stored.
+    coalesce::flush_writes();
+    let mut keys: Vec<String> = S::keys()
+        .into_iter()
+        .filter_map(|key| match scope {
+            Some(scope) => key.strip_prefix(scope).map(str::to_string),
+            None => Some(key),
+        })
+        // The index of a scope is an implementation detail.
+        .filter(|key| key.starts_with(prefix) && !(scope.is_some() && key == "__keys"))
+        .collect();
+    keys.sort();
+    keys
+}
+
+/// A hook that lists the keys of the backing `S` that start with `prefix`, and updates when keys are added or removed.
+///
+/// Like [`storage_keys`], the keys are listed in the storage scope that is active when the hook is first called.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::storage::{use_storage_keys, LocalStorage};
+///
+/// fn Drafts() -> Element {
+///     let drafts = use_storage_keys::<LocalStorage>("draft:");
+///
+///     rsx! {
+///         for key in drafts() {
+///             p { "{key}" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_storage_keys<S: StorageBacking<Key = String>>(
+    prefix: impl ToString,
+) -> ReadOnlySignal<Vec<String>> {
+    use_hook(|| {
+        let scope = current_scope().map(|scope| format!("{scope}."));
+        let prefix = prefix.to_string();
+        let mut keys = Signal::new(list_keys::<S>(scope.as_deref(), &prefix));
+
+        let mut changes = KEY_CHANGES.subscribe();
+        spawn(async move {
+            while changes.changed().await.is_ok() {
+                let current = list_keys::<S>(scope.as_deref(), &prefix);
+                // Most writes only change values, so avoid rerendering when the keys stay the same.
+                if *keys.peek() != current {
+                    keys.set(current);
+                }
+            }
+        });
+
+        keys.into()
+    })
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn test_list_keys_with_prefix() {
+    use super::LocalStorage;
+
+    super::client_storage::set_test_directory();
+    LocalStorage::set("keys-test.b".to_string(), &1);
+    LocalStorage::set("keys-test.a".to_string(), &2);
+    LocalStorage::set("keys-test.__keys".to_string(), &vec!["a", "b"]);
+
+    assert_eq!(
+        list_keys::<LocalStorage>(Some("keys-test."), ""),
+        vec!["a".to_string(), "b".to_string()]
+    );
+    LocalStorage::remove(&"keys-test.a".to_string());
+    assert_eq!(
+        list_keys::<LocalStorage>(None, "keys-test.b"),
+        vec!["keys-test.b".to_string()]
+    );
+}
@@ -0,0 +1,192 @@
+//! Guardrails that report when a storage backing grows past the limits the app expects.
+
+use once_cell::sync::Lazy;
+use std::any::{type_name, TypeId};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use super::error::report_storage_error;
+use super::{StorageBacking, StorageError};
+
+/// The limits of a storage backing, set with [`set_storage_limits`].
+///
+/// Exceeding a limit doesn't stop writes. It is reported to [`use_storage_error`](super::use_storage_error), so the app
+/// can clean up before the platform runs out of space, like the browser quota of local storage.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::storage::{set_storage_limits, storage_usage, LocalStorage, StorageLimits};
+///
+/// fn App() -> Element {
+///     use_hook(|| {
+///         set_storage_limits::<LocalStorage>(StorageLimits {
+///             max_keys: Some(500),
+///             max_bytes: Some(4 * 1024 * 1024),
+///             ..Default::default()
+///         })
+///     });
+///     let usage = storage_usage::<LocalStorage>();
+///
+///     rsx! { p { "{usage.keys} keys, {usage.bytes} bytes" } }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StorageLimits {
+    /// The maximum number of keys.
+    pub max_keys: Option<usize>,
+    /// The maximum size of all values together, in bytes of their encoded form.
+    pub max_bytes: Option<usize>,
+    /// The fraction of a limit after which [`StorageError::NearingLimit`] is reported.
+    pub warn_ratio: f64,
+}
+
+impl Default for StorageLimits {
+    fn default() -> Self {
+        Self {
+            max_keys: None,
+            max_bytes: None,
+            warn_ratio: 0.8,
+        }
+    }
+}
+
+/// How much a storage backing holds.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StorageUsage {
+    /// The number of keys.
+    pub keys: usize,
+    /// The size of all values together, in bytes of their encoded form. Backings that keep live values in memory
+    /// count as zero bytes.
+    pub bytes: usize,
+}
+
+/// How close a backing is to its limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Level {
+    Within,
+    Nearing,
+    Exceeded,
+}
+
+impl Level {
+    fn of(usage: StorageUsage, limits: StorageLimits) -> Self {
+        let amounts = [
+            (usage.keys, limits.max_keys),
+            (usage.bytes, limits.max_bytes),
+        ];
+        amounts
+            .into_iter()
+            .filter_map(|(amount, limit)| Some((amount, limit?)))
+            .map(|(amount, limit)| {
+                if amount > limit {
+                    Level::Exceeded
+                } else if amount as f64 > limit as f64 * limits.warn_ratio {
+                    Level::Nearing
+                } else {
+                    Level::Within
+                }
+            })
+            .max()
+            .unwrap_or(Level::Within)
+    }
+}
+
+struct Guardrail {
+    limits: StorageLimits,
+    /// The level that was reported last, so each level is only reported once until usage drops again.
+    reported: Level,
+}
+
+static GUARDRAILS: Lazy<Mutex<HashMap<TypeId, Guardrail>>> = Lazy::new(Default::default);
+
+/// Set the limits of the backing `S` and check them right away.
+///
+/// Once set, the limits are checked every time a storage hook writes to `S`, which lists every key of `S`. Keep this
+/// in mind for backings with many keys that are written often.
+pub fn set_storage_limits<S: StorageBacking>(limits: StorageLimits) {
+    GUARDRAILS.lock().unwrap().insert(
+        TypeId::of::<S>(),
+        Guardrail {
+            limits,
+            reported: Level::Within,
+        },
+    );
+    check_limits::<S>();
+}
+
+/// Stop checking the limits of the backing `S`.
+pub fn clear_storage_limits<S: StorageBacking>() {
+    GUARDRAILS.lock().unwrap().remove(&TypeId::of::<S>());
+}
+
+/// Get how much the backing `S` currently holds.
+pub fn storage_usage<S: StorageBacking>() -> StorageUsage {
+    let keys = S::keys();
+    StorageUsage {
+        bytes: keys.iter().filter_map(S::stored_size).sum(),
+        keys: keys.len(),
+    }
+}
+
+/// Report to [`use_storage_error`](super::use_storage_error) if the backing `S` grew past its limits.
+pub(crate) fn check_limits<S: StorageBacking>() {
+    let Some(limits) = GUARDRAILS
+        .lock()
+        .unwrap()
+        .get(&TypeId::of::<S>())
+        .map(|guardrail| guardrail.limits)
+    else {
+        return;
+    };
+
+    // Listing the keys can be slow, so the lock isn't held meanwhile.
+    let usage = storage_usage::<S>();
+    let level = Level::of(usage, limits);
+
+    let mut guardrails = GUARDRAILS.lock().unwrap();
+    let Some(guardrail) = guardrails.get_mut(&TypeId::of::<S>()) else {
+        return;
+    };
+    let previous = std::mem::replace(&mut guardrail.reported, level);
+    drop(guardrails);
+
+    if level <= previous {
+        return;
+    }
+    let backing = type_name::<S>().to_string();
+    report_storage_error(match level {
+        Level::Exceeded => StorageError::LimitExceeded {
+            backing,
+            usage,
+            limits,
+        },
+        _ => StorageError::NearingLimit {
+            backing,
+            usage,
+            limits,
+        },
+    });
+}
+
+#[test]
+fn test_limit_levels() {
+    let limits = StorageLimits {
+        max_keys: Some(10),
+        max_bytes: Some(1000),
+        warn_ratio: 0.8,
+    };
+    let usage = |keys, bytes| StorageUsage { keys, bytes };
+
+    assert_eq!(Level::of(usage(8, 800), limits), Level::Within);
+    assert_eq!(Level::of(usage(9, 0), limits), Level::Nearing);
+    assert_eq!(Level::of(usage(0, 801), limits), Level::Nearing);
+    assert_eq!(Level::of(usage(11, 900), limits), Level::Exceeded);
+    assert_eq!(Level::of(usage(1, 1001), limits), Level::Exceeded);
+
+    // Limits that aren't set are never reached.
+    let no_limits = StorageLimits::default();
+    assert_eq!(
+        Level::of(usage(usize::MAX, usize::MAX), no_limits),
+        Level::Within
+    );
+}
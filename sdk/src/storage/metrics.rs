@@ -0,0 +1,296 @@
+//! Tracing and counters for storage loads, stores, and subscriptions.
+//!
+//! Storage entries wrap every operation in [`track`]. The spans and counters are only kept with the `storage-metrics`
+//! feature, otherwise tracking compiles to nothing.
+
+use super::StorageBacking;
+
+/// What a storage entry did.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StorageOperation {
+    /// A value was read from storage.
+    Load,
+    /// A value was written to storage.
+    Store,
+    /// An entry started listening for changes to its key.
+    Subscribe,
+}
+
+/// Keeps an operation tracked until it is dropped.
+pub(crate) struct OperationGuard {
+    #[cfg(feature = "storage-metrics")]
+    _operation: tracking::Operation,
+}
+
+/// Track a storage operation until the returned guard is dropped.
+#[cfg(feature = "storage-metrics")]
+pub(crate) fn track<S: StorageBacking>(
+    operation: StorageOperation,
+    key: &S::Key,
+) -> OperationGuard {
+    OperationGuard {
+        _operation: tracking::Operation::start::<S>(operation, key),
+    }
+}
+
+/// Track a storage operation until the returned guard is dropped.
+#[cfg(not(feature = "storage-metrics"))]
+pub(crate) fn track<S: StorageBacking>(
+    _operation: StorageOperation,
+    _key: &S::Key,
+) -> OperationGuard {
+    OperationGuard {}
+}
+
+/// Count a write to a key that was replaced by a later write before it was stored.
+#[cfg(feature = "storage-metrics")]
+pub(crate) fn record_coalesced<S: StorageBacking>(key: &S::Key) {
+    tracking::record_coalesced::<S>(key);
+}
+
+/// Count a write to a key that was replaced by a later write before it was stored.
+#[cfg(not(feature = "storage-metrics"))]
+pub(crate) fn record_coalesced<S: StorageBacking>(_key: &S::Key) {}
+
+/// Count the bytes an encoded value takes towards the operation in progress, if any.
+#[cfg(feature = "storage-metrics")]
+pub(crate) fn record_bytes(bytes: usize) {
+    tracking::record_bytes(bytes);
+}
+
+/// Count the bytes an encoded value takes towards the operation in progress, if any.
+#[cfg(not(feature = "storage-metrics"))]
+pub(crate) fn record_bytes(_bytes: usize) {}
+
+#[cfg(feature = "storage-metrics")]
+pub use tracking::{reset_storage_metrics, storage_metrics, KeyMetrics, StorageMetrics};
+
+#[cfg(feature = "storage-metrics")]
+mod tracking {
+    use super::{StorageBacking, StorageOperation};
+    use once_cell::sync::Lazy;
+    use std::{
+        any::type_name,
+        cell::Cell,
+        collections::{BTreeMap, VecDeque},
+        sync::Mutex,
+        time::Duration,
+    };
+
+    /// How many writes to one key within [`BURST_WINDOW`] are reported as a burst.
+    const BURST_WRITES: usize = 10;
+    const BURST_WINDOW: Duration = Duration::from_secs(1);
+
+    thread_local! {
+        /// The bytes counted towards the operation in progress on this thread.
+        static BYTES: Cell<Option<usize>> = const { Cell::new(None) };
+    }
+
+    static METRICS: Lazy<Mutex<State>> = Lazy::new(Default::default);
+
+    #[derive(Default)]
+    struct State {
+        metrics: StorageMetrics,
+        /// When the latest writes to each key happened, for spotting bursts.
+        recent_writes: BTreeMap<String, VecDeque<Duration>>,
+    }
+
+    /// The counters of one storage key.
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct KeyMetrics {
+        pub loads: u64,
+        pub stores: u64,
+        pub subscriptions: u64,
+        /// The encoded size of every value read, in bytes.
+        pub bytes_loaded: u64,
+        /// The encoded size of every value written, in bytes.
+        pub bytes_stored: u64,
+        /// Writes that were replaced by a later write to the key in the same tick, so they were never encoded.
+        pub coalesced: u64,
+    }
+
+    /// The counters of every storage key, collected with the `storage-metrics` feature.
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct StorageMetrics {
+        /// The counters of each key, by backing and key.
+        pub keys: BTreeMap<String, KeyMetrics>,
+    }
+
+    impl StorageMetrics {
+        /// The counters of all keys added up.
+        pub fn total(&self) -> KeyMetrics {
+            self.keys
+                .values()
+                .fold(KeyMetrics::default(), |total, key| KeyMetrics {
+                    loads: total.loads + key.loads,
+                    stores: total.stores + key.stores,
+                    subscriptions: total.subscriptions + key.subscriptions,
+                    bytes_loaded: total.bytes_loaded + key.bytes_loaded,
+                    bytes_stored: total.bytes_stored + key.bytes_stored,
+                    coalesced: total.coalesced + key.coalesced,
+                })
+        }
+    }
+
+    /// Get the storage counters collected so far.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use dioxus_sdk::storage::storage_metrics;
+    ///
+    /// for (key, metrics) in storage_metrics().keys {
+    ///     println!("{key}: {} writes, {} bytes", metrics.stores, metrics.bytes_stored);
+    /// }
+    /// ```
+    pub fn storage_metrics() -> StorageMetrics {
+        METRICS.lock().unwrap().metrics.clone()
+    }
+
+    /// Set every storage counter back to zero.
+    pub fn reset_storage_metrics() {
+        *METRICS.lock().unwrap() = State::default();
+    }
+
+    pub(crate) fn record_bytes(bytes: usize) {
+        BYTES.with(|current| {
+            if let Some(total) = current.get() {
+                current.set(Some(total + bytes));
+            }
+        });
+    }
+
+    pub(crate) fn record_coalesced<S: StorageBacking>(key: &S::Key) {
+        let (backing, key) = names::<S>(key);
+        tracing::trace!(key = %key, backing = %backing, "storage write coalesced");
+        let mut state = METRICS.lock().unwrap();
+        state
+            .metrics
+            .keys
+            .entry(format!("{backing}:{key}"))
+            .or_default()
+            .coalesced += 1;
+    }
+
+    /// The names of a backing and a key, like `ScopedStorage<LocalStorage>` and `"theme"`.
+    fn names<S: StorageBacking>(key: &S::Key) -> (String, String) {
+        // Keep only the names of the backing and its type parameters.
+        let backing = type_name::<S>()
+            .split_inclusive(|c| matches!(c, '<' | '>' | ','))
+            .map(|part| part.rsplit("::").next().unwrap_or_default())
+            .collect();
+        (backing, format!("{key:?}"))
+    }
+
+    pub(crate) struct Operation {
+        operation: StorageOperation,
+        name: String,
+        span: tracing::span::EnteredSpan,
+        started: Duration,
+        previous_bytes: Option<usize>,
+    }
+
+    impl Operation {
+        pub(crate) fn start<S: StorageBacking>(operation: StorageOperation, key: &S::Key) -> Self {
+            let (backing, key) = names::<S>(key);
+            let span = tracing::debug_span!(
+                "storage",
+                ?operation,
+                key = %key,
+                backing = %backing,
+                bytes = tracing::field::Empty,
+                duration_us = tracing::field::Empty,
+            )
+            .entered();
+
+            Self {
+                operation,
+                name: format!("{backing}:{key}"),
+                span,
+                started: now(),
+                previous_bytes: BYTES.with(|current| current.replace(Some(0))),
+            }
+        }
+    }
+
+    impl Drop for Operation {
+        fn drop(&mut self) {
+            let bytes = BYTES
+                .with(|current| current.replace(self.previous_bytes))
+                .unwrap_or_default();
+            let duration = now().saturating_sub(self.started);
+            self.span.record("bytes", bytes);
+            self.span.record("duration_us", duration.as_micros() as u64);
+            tracing::trace!(parent: &*self.span, "storage {:?} finished", self.operation);
+
+            let mut state = METRICS.lock().unwrap();
+            let metrics = state.metrics.keys.entry(self.name.clone()).or_default();
+            match self.operation {
+                StorageOperation::Load => {
+                    metrics.loads += 1;
+                    metrics.bytes_loaded += bytes as u64;
+                }
+                StorageOperation::Store => {
+                    metrics.stores += 1;
+                    metrics.bytes_stored += bytes as u64;
+                }
+                StorageOperation::Subscribe => metrics.subscriptions += 1,
+            }
+
+            if self.operation == StorageOperation::Store {
+                let writes = state.recent_writes.entry(self.name.clone()).or_default();
+                let now = now();
+                writes.push_back(now);
+                while writes
+                    .front()
+                    .is_some_and(|first| now.saturating_sub(*first) > BURST_WINDOW)
+                {
+                    writes.pop_front();
+                }
+                // Only warn once per burst.
+                if writes.len() == BURST_WRITES {
+                    tracing::warn!(
+                        parent: &*self.span,
+                        "\"{}\" was written to storage {BURST_WRITES} times within {BURST_WINDOW:?}. \
+                         If it is saved on every keystroke, consider debouncing the signal.",
+                        self.name
+                    );
+                }
+            }
+        }
+    }
+
+    /// The time since an arbitrary point, for measuring durations.
+    #[cfg(not(target_family = "wasm"))]
+    fn now() -> Duration {
+        static START: Lazy<std::time::Instant> = Lazy::new(std::time::Instant::now);
+        START.elapsed()
+    }
+
+    /// The time since an arbitrary point, for measuring durations.
+    #[cfg(target_family = "wasm")]
+    fn now() -> Duration {
+        // `Instant::now` panics on the web, so ask the browser instead.
+        Duration::from_secs_f64(js_sys::Date::now() / 1000.0)
+    }
+}
+
+#[cfg(all(test, feature = "storage-metrics"))]
+mod tests {
+    use super::*;
+    use crate::storage::SessionStorage;
+
+    #[test]
+    fn test_store_counters() {
+        let key = "metrics-counters".to_string();
+        for bytes in [3, 5] {
+            let _operation = track::<SessionStorage>(StorageOperation::Store, &key);
+            record_bytes(bytes);
+        }
+
+        let metrics = storage_metrics();
+        let key = &metrics.keys["SessionStorage:\"metrics-counters\""];
+        assert_eq!(key.stores, 2);
+        assert_eq!(key.bytes_stored, 8);
+    }
+}
@@ -25,26 +25,79 @@
 //! }
 //! ```
 
+mod cache;
 mod client_storage;
+mod coalesce;
+mod conflict;
+mod error;
+mod inspect;
+mod keys;
+mod limits;
+mod metrics;
 mod persistence;
+#[cfg(feature = "storage-cloud-sync")]
+mod remote;
+mod schema;
+mod scope;
+mod ssr;
 
+pub use cache::{
+    cache_stats, clear_cache, set_cache_config, CacheConfig, CacheStats, CacheStorage,
+};
 pub use client_storage::{LocalStorage, SessionStorage};
+use conflict::SyncedValue;
+pub use conflict::{
+    Conflict, ConflictResolver, HighestVersionWins, LocalWins, RemoteWins, Versioned,
+};
+pub use error::{use_storage_error, StorageError};
 use futures_util::stream::StreamExt;
+pub use inspect::{dump_storage, import_storage, ImportStorageError};
+pub use keys::{storage_keys, use_storage_keys};
+pub use limits::{
+    clear_storage_limits, set_storage_limits, storage_usage, StorageLimits, StorageUsage,
+};
+#[cfg(feature = "storage-metrics")]
+pub use metrics::{
+    reset_storage_metrics, storage_metrics, KeyMetrics, StorageMetrics, StorageOperation,
+};
 pub use persistence::{
-    new_persistent, new_singleton_persistent, use_persistent, use_singleton_persistent,
+    new_persistent, new_persistent_projection, new_persistent_with_options,
+    new_singleton_persistent, use_persistent, use_persistent_projection,
+    use_persistent_with_options, use_singleton_persistent, PersistOptions, ASYNC_LOAD_TIMEOUT,
 };
+#[cfg(feature = "storage-cloud-sync")]
+pub use remote::{
+    use_cloud_synced_storage, use_cloud_synced_storage_with_options, CloudSyncOptions,
+    CloudSyncStatus, CloudSyncedStorage, RemoteSyncAdapter, RemoteSyncError, RemoteValue,
+};
+pub use scope::{
+    clear_scope, current_scope, use_storage_scope, with_scope, ScopedKey, ScopedStorage,
+    StorageScope,
+};
+pub use ssr::{provide_storage_request_context, use_prefetched_storage, StorageRequestContext};
 
 use dioxus::prelude::*;
+#[cfg(not(feature = "storage-metrics"))]
+use metrics::StorageOperation;
+use once_cell::sync::Lazy;
 use postcard::to_allocvec;
 use serde::{de::DeserializeOwned, Serialize};
-use std::any::Any;
+use std::any::{Any, TypeId};
+use std::cell::Cell;
+use std::collections::HashMap;
 use std::fmt::{Debug, Display};
+use std::future::Future;
 use std::ops::{Deref, DerefMut};
-use std::sync::Arc;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use tokio::sync::watch::error::SendError;
 use tokio::sync::watch::{Receiver, Sender};
 
 pub use client_storage::set_dir;
+pub use client_storage::{enable_background_writes, flush, use_background_writes};
+#[cfg(target_family = "wasm")]
+pub use client_storage::{opfs_loaded, OpfsStorage};
 #[cfg(not(target_family = "wasm"))]
 pub use client_storage::{set_dir_name, set_directory};
 
@@ -64,13 +117,15 @@ pub use client_storage::{set_dir_name, set_directory};
 ///     use_storage::<S, _>("user-id", || 123)
 /// }
 /// ```
+#[track_caller]
 pub fn use_storage<S, T>(key: S::Key, init: impl FnOnce() -> T) -> Signal<T>
 where
     S: StorageBacking,
     T: Serialize + DeserializeOwned + Clone + Send + Sync + PartialEq + 'static,
     S::Key: Clone,
 {
-    use_hook(|| new_storage::<S, T>(key, init))
+    let caller = std::panic::Location::caller();
+    use_hook(|| schema::called_from(caller, || new_storage::<S, T>(key, init)))
 }
 
 /// Creates a Signal that can be used to store data that will persist across application reloads.
@@ -89,6 +144,7 @@ where
 ///     new_storage::<S, _>("user-id", || 123)
 /// }
 /// ```
+#[track_caller]
 pub fn new_storage<S, T>(key: S::Key, init: impl FnOnce() -> T) -> Signal<T>
 where
     S: StorageBacking,
@@ -98,22 +154,27 @@ where
     let mut init = Some(init);
 
     if cfg!(feature = "ssr") {
-        // SSR does not support storage on the backend. We will just use a normal Signal to represent the initial state.
+        // SSR does not support storage on the backend. We will just use a normal Signal to represent the initial state,
+        // prefetched from the StorageRequestContext if the app provided one.
         // The client will hydrate this with a correct StorageEntry and maintain state.
-        Signal::new(init.take().unwrap()())
-    } else if cfg!(feature = "hydrate") {
-        let key_clone = key.clone();
-        let mut storage_entry = new_storage_entry::<S, T>(key, init.take().unwrap());
-        if generation() == 0 {
-            // The first generation is rendered on the server side and so must be hydrated.
-            needs_update();
-        }
-        if generation() == 1 {
-            // The first time the vdom is hydrated, we set the correct value from storage and set up the subscription to storage events.
-            storage_entry.set(get_from_storage::<S, T>(key_clone, init.take().unwrap()));
+        Signal::new(
+            ssr::get_from_request_context::<S, T>(&key).unwrap_or_else(|| init.take().unwrap()()),
+        )
+    } else if ssr::must_render_server_value::<S>() {
+        // The first render hydrates the markup the server rendered with the initial value, so it must render the
+        // initial value as well. The stored value is swapped in right after.
+        let initial = init.take().unwrap()();
+        let storage_entry = StorageEntry::<S, T>::new(key.clone(), initial.clone());
+        let mut data = storage_entry.data;
+        spawn(async move {
+            ssr::finish_hydration();
+            let stored = get_from_storage::<S, T>(key, || initial);
+            if *data.peek() != stored {
+                data.set(stored);
+            }
             storage_entry.save_to_storage_on_change();
-        }
-        storage_entry.data
+        });
+        data
     } else {
         // The client is rendered normally, so we can just use the storage entry.
         let storage_entry = new_storage_entry::<S, T>(key, init.take().unwrap());
@@ -126,20 +187,60 @@ where
 ///
 /// This hook returns a Signal that can be used to read and modify the state.
 /// The changes to the state will be persisted to storage and all other app sessions will be notified of the change to update their local state.
+#[track_caller]
 pub fn use_synced_storage<S, T>(key: S::Key, init: impl FnOnce() -> T) -> Signal<T>
 where
     S: StorageBacking + StorageSubscriber<S>,
     T: Serialize + DeserializeOwned + Clone + Send + Sync + PartialEq + 'static,
     S::Key: Clone,
 {
-    use_hook(|| new_synced_storage::<S, T>(key, init))
+    let caller = std::panic::Location::caller();
+    use_hook(|| schema::called_from(caller, || new_synced_storage::<S, T>(key, init)))
+}
+
+/// A storage hook like [`use_synced_storage`] that resolves conflicting writes from other app sessions with the given [`ConflictResolver`].
+///
+/// The resolver is called when a new value arrives from storage while the local state holds changes that have not been synced yet.
+#[track_caller]
+pub fn use_synced_storage_with_resolver<S, T>(
+    key: S::Key,
+    init: impl FnOnce() -> T,
+    resolver: impl ConflictResolver<T>,
+) -> Signal<T>
+where
+    S: StorageBacking + StorageSubscriber<S>,
+    T: Serialize + DeserializeOwned + Clone + Send + Sync + PartialEq + 'static,
+    S::Key: Clone,
+{
+    let caller = std::panic::Location::caller();
+    use_hook(|| {
+        schema::called_from(caller, || {
+            new_synced_storage_with_resolver::<S, T>(key, init, resolver)
+        })
+    })
 }
 
 /// Create a signal that can be used to store data that will persist across application reloads and be synced across all app sessions for a given installation or browser.
 ///
 /// This hook returns a Signal that can be used to read and modify the state.
 /// The changes to the state will be persisted to storage and all other app sessions will be notified of the change to update their local state.
+#[track_caller]
 pub fn new_synced_storage<S, T>(key: S::Key, init: impl FnOnce() -> T) -> Signal<T>
+where
+    S: StorageBacking + StorageSubscriber<S>,
+    T: Serialize + DeserializeOwned + Clone + Send + Sync + PartialEq + 'static,
+    S::Key: Clone,
+{
+    new_synced_storage_with_resolver::<S, T>(key, init, RemoteWins)
+}
+
+/// Create a signal like [`new_synced_storage`] that resolves conflicting writes from other app sessions with the given [`ConflictResolver`].
+#[track_caller]
+pub fn new_synced_storage_with_resolver<S, T>(
+    key: S::Key,
+    init: impl FnOnce() -> T,
+    resolver: impl ConflictResolver<T>,
+) -> Signal<T>
 where
     S: StorageBacking + StorageSubscriber<S>,
     T: Serialize + DeserializeOwned + Clone + Send + Sync + PartialEq + 'static,
@@ -148,28 +249,35 @@ where
     let mut init = Some(init);
     let signal = {
         if cfg!(feature = "ssr") {
-            // SSR does not support synced storage on the backend. We will just use a normal Signal to represent the initial state.
+            // SSR does not support synced storage on the backend. We will just use a normal Signal to represent the initial state,
+            // prefetched from the StorageRequestContext if the app provided one.
             // The client will hydrate this with a correct SyncedStorageEntry and maintain state.
-            Signal::new(init.take().unwrap()())
-        } else if cfg!(feature = "hydrate") {
-            let key_clone = key.clone();
-            let mut storage_entry = new_synced_storage_entry::<S, T>(key, init.take().unwrap());
-            if generation() == 0 {
-                // The first generation is rendered on the server side and so must be hydrated.
-                needs_update();
-            }
-            if generation() == 1 {
-                // The first time the vdom is hydrated, we set the correct value from storage and set up the subscription to storage events.
-                storage_entry
-                    .entry
-                    .set(get_from_storage::<S, T>(key_clone, init.take().unwrap()));
+            Signal::new(
+                ssr::get_from_request_context::<S, T>(&key)
+                    .unwrap_or_else(|| init.take().unwrap()()),
+            )
+        } else if ssr::must_render_server_value::<S>() {
+            // The first render hydrates the markup the server rendered with the initial value, so it must render the
+            // initial value as well. The stored value is swapped in right after, before listening for changes.
+            let initial = init.take().unwrap()();
+            let storage_entry = SyncedStorageEntry::<S, T>::new(key.clone(), initial.clone())
+                .with_conflict_resolver(resolver);
+            let mut data = *storage_entry.data();
+            spawn(async move {
+                ssr::finish_hydration();
+                let stored = get_from_storage::<S, T>(key, || initial);
+                storage_entry.synced.set(stored.clone());
+                if *data.peek() != stored {
+                    data.set(stored);
+                }
                 storage_entry.save_to_storage_on_change();
                 storage_entry.subscribe_to_storage();
-            }
-            *storage_entry.data()
+            });
+            data
         } else {
             // The client is rendered normally, so we can just use the synced storage entry.
-            let storage_entry = new_synced_storage_entry::<S, T>(key, init.take().unwrap());
+            let storage_entry = new_synced_storage_entry::<S, T>(key, init.take().unwrap())
+                .with_conflict_resolver(resolver);
             storage_entry.save_to_storage_on_change();
             storage_entry.subscribe_to_storage();
             *storage_entry.data()
@@ -179,16 +287,19 @@ where
 }
 
 /// A hook that creates a StorageEntry with the latest value from storage or the init value if it doesn't exist.
+#[track_caller]
 pub fn use_storage_entry<S, T>(key: S::Key, init: impl FnOnce() -> T) -> StorageEntry<S, T>
 where
     S: StorageBacking,
     T: Serialize + DeserializeOwned + Clone + Send + Sync + PartialEq + 'static,
     S::Key: Clone,
 {
-    use_hook(|| new_storage_entry::<S, T>(key, init))
+    let caller = std::panic::Location::caller();
+    use_hook(|| schema::called_from(caller, || new_storage_entry::<S, T>(key, init)))
 }
 
 /// A hook that creates a StorageEntry with the latest value from storage or the init value if it doesn't exist, and provides a channel to subscribe to updates to the underlying storage.
+#[track_caller]
 pub fn use_synced_storage_entry<S, T>(
     key: S::Key,
     init: impl FnOnce() -> T,
@@ -198,10 +309,12 @@ where
     T: Serialize + DeserializeOwned + Clone + Send + Sync + PartialEq + 'static,
     S::Key: Clone,
 {
-    use_hook(|| new_synced_storage_entry::<S, T>(key, init))
+    let caller = std::panic::Location::caller();
+    use_hook(|| schema::called_from(caller, || new_synced_storage_entry::<S, T>(key, init)))
 }
 
 /// Returns a StorageEntry with the latest value from storage or the init value if it doesn't exist.
+#[track_caller]
 pub fn new_storage_entry<S, T>(key: S::Key, init: impl FnOnce() -> T) -> StorageEntry<S, T>
 where
     S: StorageBacking,
@@ -215,6 +328,7 @@ where
 /// Returns a synced StorageEntry with the latest value from storage or the init value if it doesn't exist.
 ///
 /// This differs from `storage_entry` in that this one will return a channel to subscribe to updates to the underlying storage.
+#[track_caller]
 pub fn new_synced_storage_entry<S, T>(
     key: S::Key,
     init: impl FnOnce() -> T,
@@ -224,11 +338,13 @@ where
     T: Serialize + DeserializeOwned + Clone + PartialEq + Send + Sync + 'static,
     S::Key: Clone,
 {
-    let data = get_from_storage::<S, T>(key.clone(), init);
-    SyncedStorageEntry::new(key, data)
+    let initial = init();
+    let data = get_from_storage::<S, T>(key.clone(), || initial.clone());
+    SyncedStorageEntry::new(key, data).with_initial_value(initial)
 }
 
 /// Returns a value from storage or the init value if it doesn't exist.
+#[track_caller]
 pub fn get_from_storage<
     S: StorageBacking,
     T: Serialize + DeserializeOwned + Send + Sync + Clone + 'static,
@@ -236,13 +352,42 @@ pub fn get_from_storage<
     key: S::Key,
     init: impl FnOnce() -> T,
 ) -> T {
-    S::get(&key).unwrap_or_else(|| {
+    schema::check_type::<S, T>(&key);
+    if let Some(raw_key) = S::raw_key(&key) {
+        inspect::register_type::<T>(raw_key);
+    }
+    coalesce::flush_key::<S>(&key);
+    let stored = {
+        let _operation = metrics::track::<S>(StorageOperation::Load, &key);
+        S::get(&key)
+    };
+    stored.unwrap_or_else(|| {
         let data = init();
-        S::set(key, &data);
+        {
+            let _operation = metrics::track::<S>(StorageOperation::Store, &key);
+            SEEDING.with(|seeding| seeding.set(true));
+            S::set(key, &data);
+            SEEDING.with(|seeding| seeding.set(false));
+        }
+        limits::check_limits::<S>();
         data
     })
 }
 
+thread_local! {
+    /// Set while the initial value of a key that wasn't in storage is stored.
+    static SEEDING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Whether the value being set is the initial value of a key that wasn't in storage, rather than a change.
+///
+/// Backings that load their values in the background use this to keep an initial value from overwriting a stored
+/// value that just wasn't loaded yet.
+#[cfg_attr(not(target_family = "wasm"), allow(dead_code))]
+pub(crate) fn is_seeding() -> bool {
+    SEEDING.with(|seeding| seeding.get())
+}
+
 /// A trait for common functionality between StorageEntry and SyncedStorageEntry
 pub trait StorageEntryTrait<S: StorageBacking, T: PartialEq + Clone + 'static>:
     Clone + 'static
@@ -295,6 +440,14 @@ pub struct SyncedStorageEntry<
     pub(crate) entry: StorageEntry<S, T>,
     /// The channel to subscribe to updates to the underlying storage
     pub(crate) channel: Receiver<StorageChannelPayload>,
+    /// The strategy used to resolve conflicts between local changes and updates from storage
+    pub(crate) resolver: Rc<dyn ConflictResolver<T>>,
+    /// Tags the writes of this entry so it can ignore them when they come back through the channel
+    pub(crate) origin: StorageOrigin,
+    /// The last value that is known to be in sync with storage
+    pub(crate) synced: SyncedValue<T>,
+    /// The value the entry starts over from when its key is removed from storage
+    pub(crate) initial: Rc<T>,
 }
 
 impl<S, T> SyncedStorageEntry<S, T>
@@ -303,10 +456,46 @@ where
     T: Serialize + DeserializeOwned + Clone + Send + Sync + PartialEq + 'static,
 {
     pub fn new(key: S::Key, data: T) -> Self {
-        let channel = S::subscribe::<T>(&key);
+        let channel = {
+            let _operation = metrics::track::<S>(StorageOperation::Subscribe, &key);
+            S::subscribe::<T>(&key)
+        };
         Self {
+            synced: SyncedValue::new(data.clone()),
+            initial: Rc::new(data.clone()),
             entry: StorageEntry::new(key, data),
             channel,
+            resolver: Rc::new(RemoteWins),
+            origin: StorageOrigin::new(),
+        }
+    }
+
+    /// Sets the value the entry starts over from when its key is removed from storage, which defaults to the value
+    /// the entry was created with
+    pub fn with_initial_value(mut self, initial: T) -> Self {
+        self.initial = Rc::new(initial);
+        self
+    }
+
+    /// Sets the strategy used to resolve conflicts between local changes and updates from storage
+    pub fn with_conflict_resolver(mut self, resolver: impl ConflictResolver<T>) -> Self {
+        self.resolver = Rc::new(resolver);
+        self
+    }
+
+    /// Modifies the latest stored value with `f`, saves it, and returns it.
+    ///
+    /// See [`StorageEntry::modify`] for more information.
+    pub fn modify(
+        &mut self,
+        f: impl FnMut(&mut T),
+    ) -> impl Future<Output = Result<T, StorageError>> {
+        let modify = self.origin.scope(|| self.entry.modify(f));
+        let synced = self.synced.clone();
+        async move {
+            let value = modify.await?;
+            synced.set(value.clone());
+            Ok(value)
         }
     }
 
@@ -319,18 +508,43 @@ where
     pub fn subscribe_to_storage(&self) {
         let storage_entry_signal = *self.data();
         let channel = self.channel.clone();
+        let resolver = self.resolver.clone();
+        let origin = self.origin;
+        let synced = self.synced.clone();
+        let initial = self.initial.clone();
         spawn(async move {
             to_owned![channel, storage_entry_signal];
             loop {
                 // Wait for an update to the channel
                 if channel.changed().await.is_ok() {
-                    // Retrieve the latest value from the channel, mark it as read, and update the state
-                    let payload = channel.borrow_and_update();
-                    *storage_entry_signal.write() = payload
-                        .data
-                        .downcast_ref::<T>()
+                    // Retrieve the latest value from the channel and mark it as read
+                    let payload = channel.borrow_and_update().clone();
+
+                    // Keeping the old value would write it back on the next change, like a logged out user's data
+                    if payload.is_removed() {
+                        tracing::trace!(
+                            "Storage value was removed, starting over from the initial value"
+                        );
+                        synced.set((*initial).clone());
+                        storage_entry_signal.set((*initial).clone());
+                        continue;
+                    }
+
+                    let remote = payload
+                        .data::<T>()
                         .expect("Type mismatch with storage entry")
                         .clone();
+
+                    // Writing our own save back into the signal would only cause a redundant render and save
+                    if payload.origin() == Some(origin) {
+                        tracing::trace!("Ignoring storage update from this entry");
+                        synced.set(remote);
+                        continue;
+                    }
+
+                    // If the local state changed since the last sync and hasn't been saved yet, we have a conflict
+                    let local = storage_entry_signal.peek().clone();
+                    *storage_entry_signal.write() = synced.receive(local, remote, &*resolver);
                 }
             }
         });
@@ -346,12 +560,21 @@ where
         //  We want to save in the following conditions
         //      - The value from the channel is different from the current value
         //      - The value from the channel could not be determined, likely because it hasn't been set yet
-        if let Some(payload) = self.channel.borrow().data.downcast_ref::<T>() {
-            if *self.entry.data.read() == *payload {
+        let value = self.entry.data.read().clone();
+        // The saved value is the new base of conflicts, even where storage doesn't echo saves back to the entry
+        self.synced.set(value.clone());
+        let payload = self.channel.borrow();
+        if let Some(payload) = payload.data::<T>() {
+            if value == *payload {
                 return;
             }
         }
-        self.entry.save();
+        // Starting over after the key was removed shouldn't store it again
+        if payload.is_removed() && value == *self.initial {
+            return;
+        }
+        drop(payload);
+        self.origin.scope(|| self.entry.save());
     }
 
     fn update(&mut self) {
@@ -397,16 +620,92 @@ where
     }
 }
 
+impl<S, T> StorageEntry<S, T>
+where
+    S: StorageBacking,
+    T: Serialize + DeserializeOwned + Clone + PartialEq + Send + Sync + 'static,
+{
+    /// Modifies the latest stored value with `f`, saves it, and returns it.
+    ///
+    /// Writing the signal and saving it starts from the value this entry last saw, so a change another window saved
+    /// in the meantime is lost. This reloads the value from storage first, and only saves if storage still holds that
+    /// value at the same version. If another write came in between, `f` runs again on the newer value, so `f`
+    /// shouldn't have side effects. If other writes keep changing the value, this gives up with
+    /// [`StorageError::Contended`] after a few tries.
+    ///
+    /// The returned future doesn't borrow the entry, so it can be spawned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use dioxus::prelude::*;
+    /// use dioxus_sdk::storage::{use_storage_entry, LocalStorage};
+    ///
+    /// fn Visits() -> Element {
+    ///     let mut visits = use_storage_entry::<LocalStorage, u32>("visits".to_string(), || 0);
+    ///     use_hook(|| {
+    ///         let modify = visits.modify(|count| *count += 1);
+    ///         spawn(async move {
+    ///             modify.await.ok();
+    ///         })
+    ///     });
+    ///
+    ///     rsx! { "Visit number {visits}" }
+    /// }
+    /// ```
+    pub fn modify(
+        &mut self,
+        mut f: impl FnMut(&mut T),
+    ) -> impl Future<Output = Result<T, StorageError>> {
+        // Synced entries tag their writes while this is called, which is over before the future runs.
+        let origin = StorageOrigin::current();
+        let mut this = self.clone();
+        async move {
+            coalesce::flush_key::<S>(&this.key);
+            for _ in 0..MODIFY_ATTEMPTS {
+                let version = S::version(&this.key);
+                let stored = {
+                    let _operation = metrics::track::<S>(StorageOperation::Load, &this.key);
+                    S::get::<T>(&this.key)
+                };
+                let mut value = stored.clone().unwrap_or_else(|| this.data.peek().clone());
+                f(&mut value);
+
+                let key = this.key.clone();
+                let compare_and_set = || S::compare_and_set(key, version, stored, value.clone());
+                let set = match origin {
+                    Some(origin) => origin.scope_future(compare_and_set).await,
+                    None => compare_and_set().await,
+                };
+                if set {
+                    this.data.set(value.clone());
+                    return Ok(value);
+                }
+                tracing::trace!("Storage changed while modifying {:?}, retrying", this.key);
+            }
+            Err(StorageError::Contended {
+                key: format!("{:?}", this.key),
+            })
+        }
+    }
+}
+
+/// How many times [`StorageEntry::modify`] tries to save before it gives up, when other writes keep changing the value.
+const MODIFY_ATTEMPTS: usize = 16;
+
 impl<S, T> StorageEntryTrait<S, T> for StorageEntry<S, T>
 where
     S: StorageBacking,
     T: Serialize + DeserializeOwned + Clone + PartialEq + Send + Sync + 'static,
 {
     fn save(&self) {
-        S::set(self.key.clone(), &*self.data.read());
+        // Other signals may save the same key in this tick, so only the last value is encoded.
+        coalesce::queue_write::<S, T>(self.key.clone(), self.data.read().clone());
     }
 
     fn update(&mut self) {
+        coalesce::flush_key::<S>(&self.key);
+        let _operation = metrics::track::<S>(StorageOperation::Load, &self.key);
         self.data = S::get(&self.key).unwrap_or(self.data);
     }
 
@@ -461,6 +760,84 @@ pub trait StorageBacking: Clone + 'static {
     fn get<T: DeserializeOwned + Clone + 'static>(key: &Self::Key) -> Option<T>;
     /// Sets a value in storage for the given key
     fn set<T: Serialize + Send + Sync + Clone + 'static>(key: Self::Key, value: &T);
+    /// Removes the value in storage for the given key
+    ///
+    /// The default implementation only logs a warning, so backings written before this method existed keep
+    /// compiling. Backings that can delete values should override it.
+    fn remove(key: &Self::Key) {
+        tracing::warn!("{} can't remove {:?}", std::any::type_name::<Self>(), key);
+    }
+    /// Gets the key the encoded value is stored under, if the backing stores values that [`dump_storage`] can read
+    fn raw_key(_key: &Self::Key) -> Option<String> {
+        None
+    }
+    /// Lists every key in storage, if the backing can enumerate its keys
+    fn keys() -> Vec<Self::Key> {
+        Vec::new()
+    }
+    /// Gets the size of the encoded value stored for the given key in bytes, if the backing stores encoded values
+    fn stored_size(_key: &Self::Key) -> Option<usize> {
+        None
+    }
+    /// Gets the version of the value stored for the given key, which [`StorageBacking::compare_and_set`] bumps
+    ///
+    /// The default implementation keeps the versions in memory, so they start over when the app restarts.
+    fn version(key: &Self::Key) -> u64 {
+        let versions = VERSIONS.lock().unwrap_or_else(|e| e.into_inner());
+        let id = (TypeId::of::<Self>(), format!("{key:?}"));
+        versions.get(&id).copied().unwrap_or(0)
+    }
+    /// Sets a value in storage for the given key if the stored value is still `expected` at `version`, bumps the
+    /// version, and returns whether it was set
+    ///
+    /// The default implementation is atomic within the app. Backings shared with other processes override it to
+    /// keep the version next to the value, and to check and set both under a lock the other processes respect.
+    fn compare_and_set<T>(
+        key: Self::Key,
+        version: u64,
+        expected: Option<T>,
+        value: T,
+    ) -> impl Future<Output = bool>
+    where
+        T: Serialize + DeserializeOwned + Send + Sync + Clone + 'static,
+    {
+        let mut versions = VERSIONS.lock().unwrap_or_else(|e| e.into_inner());
+        let id = (TypeId::of::<Self>(), format!("{key:?}"));
+        let set = versions.get(&id).copied().unwrap_or(0) == version
+            && set_if_unchanged::<Self, T>(key, expected.as_ref(), &value);
+        if set {
+            versions.insert(id, version + 1);
+        }
+        std::future::ready(set)
+    }
+}
+
+/// The versions of the backings that keep them in memory. Also held while [`StorageBacking::compare_and_set`]
+/// checks and sets a value, so no other write can come in between.
+static VERSIONS: Lazy<Mutex<HashMap<(TypeId, String), u64>>> = Lazy::new(Default::default);
+
+/// Sets a value if the stored value is still `expected`, for [`StorageBacking::compare_and_set`] implementations
+/// that already hold their lock and checked the version.
+///
+/// The encoded values are compared, so values that aren't equal to themselves, like `NaN`, still match.
+pub(crate) fn set_if_unchanged<S, T>(key: S::Key, expected: Option<&T>, value: &T) -> bool
+where
+    S: StorageBacking,
+    T: Serialize + DeserializeOwned + Send + Sync + Clone + 'static,
+{
+    let current = {
+        let _operation = metrics::track::<S>(StorageOperation::Load, &key);
+        S::get::<T>(&key)
+    };
+    if current.as_ref().map(serde_to_string) != expected.map(serde_to_string) {
+        return false;
+    }
+    {
+        let _operation = metrics::track::<S>(StorageOperation::Store, &key);
+        S::set(key, value);
+    }
+    limits::check_limits::<S>();
+    true
 }
 
 /// A trait for a subscriber to events from a storage backing
@@ -490,9 +867,9 @@ impl StorageSubscription {
         tx: Sender<StorageChannelPayload>,
         key: S::Key,
     ) -> Self {
-        let getter = move || {
-            let data = S::get::<T>(&key).unwrap();
-            StorageChannelPayload::new(data)
+        let getter = move || match S::get::<T>(&key) {
+            Some(data) => StorageChannelPayload::new(data),
+            None => StorageChannelPayload::removed(),
         };
         Self {
             getter: Box::new(getter),
@@ -507,29 +884,107 @@ impl StorageSubscription {
     }
 }
 
+/// Identifies the synced storage entry that wrote a value.
+///
+/// Every [`SyncedStorageEntry`] has its own origin, so an app session can tell its own writes apart from writes
+/// made by other sessions, like another window, when they come back through the storage channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StorageOrigin(u64);
+
+thread_local! {
+    /// The origin of the storage write that is currently in progress.
+    static CURRENT_ORIGIN: Cell<Option<StorageOrigin>> = const { Cell::new(None) };
+}
+
+impl StorageOrigin {
+    /// Creates a new unique origin.
+    pub(crate) fn new() -> Self {
+        static NEXT_ORIGIN: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT_ORIGIN.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// Runs `f` with every [`StorageChannelPayload`] created inside of it tagged with this origin.
+    pub(crate) fn scope<R>(self, f: impl FnOnce() -> R) -> R {
+        struct RestoreOrigin(Option<StorageOrigin>);
+
+        impl Drop for RestoreOrigin {
+            fn drop(&mut self) {
+                CURRENT_ORIGIN.with(|current| current.set(self.0));
+            }
+        }
+
+        let _guard = RestoreOrigin(CURRENT_ORIGIN.with(|current| current.replace(Some(self))));
+        f()
+    }
+
+    /// Creates a future with `f` and polls it with every [`StorageChannelPayload`] created inside of it tagged with
+    /// this origin.
+    pub(crate) async fn scope_future<F: Future>(self, f: impl FnOnce() -> F) -> F::Output {
+        let mut future = std::pin::pin!(self.scope(f));
+        std::future::poll_fn(|cx| self.scope(|| future.as_mut().poll(cx))).await
+    }
+
+    /// Gets the origin of the storage write that is currently in progress, if any.
+    pub fn current() -> Option<Self> {
+        CURRENT_ORIGIN.with(|current| current.get())
+    }
+}
+
 /// A payload for a storage channel that contains the latest value from storage.
 #[derive(Clone, Debug)]
 pub struct StorageChannelPayload {
     data: Arc<dyn Any + Send + Sync>,
+    origin: Option<StorageOrigin>,
 }
 
 impl StorageChannelPayload {
     /// Creates a new StorageChannelPayload
+    ///
+    /// The payload is tagged with the origin of the storage write that is currently in progress, if any.
+    /// Storage backings that notify subscribers while the value is being set get this for free.
     pub fn new<T: Send + Sync + 'static>(data: T) -> Self {
         Self {
             data: Arc::new(data),
+            origin: StorageOrigin::current(),
+        }
+    }
+
+    /// Creates a payload telling subscribers that the key was removed from storage
+    pub fn removed() -> Self {
+        Self {
+            data: Arc::new(Removed),
+            origin: StorageOrigin::current(),
         }
     }
 
+    /// Whether the key was removed from storage, like when its [scope](crate::storage::clear_scope) was cleared
+    pub fn is_removed(&self) -> bool {
+        self.data.is::<Removed>()
+    }
+
     /// Gets the data from the payload
     pub fn data<T: 'static>(&self) -> Option<&T> {
         self.data.downcast_ref::<T>()
     }
+
+    /// Gets the origin of the write that produced this payload.
+    ///
+    /// This is `None` for values that were written by other processes, like another browser tab.
+    pub fn origin(&self) -> Option<StorageOrigin> {
+        self.origin
+    }
 }
 
+/// The data of a [`StorageChannelPayload`] for a key that was removed.
+#[derive(Debug)]
+struct Removed;
+
 impl Default for StorageChannelPayload {
     fn default() -> Self {
-        Self { data: Arc::new(()) }
+        Self {
+            data: Arc::new(()),
+            origin: None,
+        }
     }
 }
 
@@ -544,6 +999,7 @@ pub(crate) fn serde_to_string<T: Serialize>(value: &T) -> String {
         yazi::CompressionLevel::BestSize,
     )
     .unwrap();
+    metrics::record_bytes(compressed.len());
     let as_str: String = compressed
         .iter()
         .flat_map(|u| {
@@ -565,6 +1021,7 @@ pub(crate) fn serde_from_string<T: DeserializeOwned>(value: &str) -> T {
 
 /// Deserializes and decompresses a value from a string and returns None if there is an error.
 pub(crate) fn try_serde_from_string<T: DeserializeOwned>(value: &str) -> Option<T> {
+    metrics::record_bytes(value.len() / 2);
     let mut bytes: Vec<u8> = Vec::new();
     let mut chars = value.chars();
     while let Some(c) = chars.next() {
@@ -1,38 +1,180 @@
+use crate::storage::get_from_storage;
 use crate::storage::new_storage_entry;
+use crate::storage::scope::scoped_key;
 use crate::storage::SessionStorage;
+use crate::storage::StorageBacking;
+use crate::utils::timing::wait_for_idle;
 use dioxus::prelude::*;
 use dioxus_signals::Signal;
+use futures_util::stream::StreamExt;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::panic::Location;
+use std::rc::Rc;
+use std::time::Duration;
 
-use super::StorageEntryTrait;
+use super::{coalesce, schema, StorageEntry, StorageEntryTrait};
 
 /// A persistent storage hook that can be used to store data across application reloads.
 ///
-/// Depending on the platform this uses either local storage or a file storage
+/// Depending on the platform this uses either local storage or a file storage.
+/// The key is prefixed with the current [storage scope](crate::storage::use_storage_scope), if any.
 #[allow(clippy::needless_return)]
+#[track_caller]
 pub fn use_persistent<
     T: Serialize + DeserializeOwned + Default + Clone + Send + Sync + PartialEq + 'static,
 >(
     key: impl ToString,
     init: impl FnOnce() -> T,
 ) -> Signal<T> {
-    use_hook(|| new_persistent(key, init))
+    let caller = Location::caller();
+    use_hook(|| schema::called_from(caller, || new_persistent(key, init)))
+}
+
+/// A persistent storage hook like [`use_persistent`] that can be configured with [`PersistOptions`].
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::storage::{use_persistent_with_options, PersistOptions};
+///
+/// fn App() -> Element {
+///     // Render the empty list right away and show the saved notes once they are loaded.
+///     let notes = use_persistent_with_options(
+///         "notes",
+///         Vec::<String>::new,
+///         PersistOptions { async_load: true },
+///     );
+///
+///     rsx! {
+///         for note in notes() {
+///             p { "{note}" }
+///         }
+///     }
+/// }
+/// ```
+#[allow(clippy::needless_return)]
+#[track_caller]
+pub fn use_persistent_with_options<
+    T: Serialize + DeserializeOwned + Default + Clone + Send + Sync + PartialEq + 'static,
+>(
+    key: impl ToString,
+    init: impl FnOnce() -> T,
+    options: PersistOptions,
+) -> Signal<T> {
+    let caller = Location::caller();
+    use_hook(|| schema::called_from(caller, || new_persistent_with_options(key, init, options)))
+}
+
+/// Options for [`use_persistent_with_options`] and [`new_persistent_with_options`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PersistOptions {
+    /// Start with the init value and load the stored value once the app is idle after the first render,
+    /// instead of reading storage before the hook returns.
+    ///
+    /// This keeps large stored values from delaying the first render. The value is loaded after at most
+    /// [`ASYNC_LOAD_TIMEOUT`] if the app doesn't become idle. If the signal is changed before the stored value is
+    /// loaded, the change wins and overwrites the stored value.
+    pub async_load: bool,
 }
 
+/// The longest an [`async_load`](PersistOptions::async_load) signal waits for the app to be idle before it loads the
+/// stored value anyway.
+pub const ASYNC_LOAD_TIMEOUT: Duration = Duration::from_secs(1);
+
 /// Creates a persistent storage signal that can be used to store data across application reloads.
 ///
-/// Depending on the platform this uses either local storage or a file storage
+/// Depending on the platform this uses either local storage or a file storage.
+/// Every call with the same key and type in the app shares the same signal, so changes are immediately visible to all of them.
 #[allow(clippy::needless_return)]
+#[track_caller]
 pub fn new_persistent<
     T: Serialize + DeserializeOwned + Default + Clone + Send + Sync + PartialEq + 'static,
 >(
     key: impl ToString,
     init: impl FnOnce() -> T,
 ) -> Signal<T> {
-    let storage_entry = new_storage_entry::<SessionStorage, T>(key.to_string(), init);
-    storage_entry.save_to_storage_on_change();
-    storage_entry.data
+    new_persistent_with_options(key, init, PersistOptions::default())
+}
+
+/// Creates a persistent storage signal like [`new_persistent`] that can be configured with [`PersistOptions`].
+#[allow(clippy::needless_return)]
+#[track_caller]
+pub fn new_persistent_with_options<
+    T: Serialize + DeserializeOwned + Default + Clone + Send + Sync + PartialEq + 'static,
+>(
+    key: impl ToString,
+    init: impl FnOnce() -> T,
+    options: PersistOptions,
+) -> Signal<T> {
+    let caller = Location::caller();
+    let key = scoped_key::<SessionStorage>(key.to_string());
+    let registry = match try_consume_context::<PersistentRegistry>() {
+        Some(registry) => registry,
+        None => provide_root_context(PersistentRegistry::default()),
+    };
+
+    let id = (key.clone(), TypeId::of::<T>());
+    if let Some(signal) = registry
+        .signals
+        .borrow()
+        .get(&id)
+        .and_then(|signal| signal.downcast_ref::<Signal<T>>())
+    {
+        return *signal;
+    }
+
+    // The signal is owned by the root scope so it outlives the component that happened to create it.
+    let signal = ScopeId::ROOT.in_runtime(|| {
+        if options.async_load {
+            return load_in_background(key, init(), caller);
+        }
+        let storage_entry =
+            schema::called_from(caller, || new_storage_entry::<SessionStorage, T>(key, init));
+        storage_entry.save_to_storage_on_change();
+        storage_entry.data
+    });
+    registry.signals.borrow_mut().insert(id, Box::new(signal));
+    signal
+}
+
+/// Create a storage entry holding `initial` and replace it with the stored value once the app is idle.
+fn load_in_background<
+    T: Serialize + DeserializeOwned + Clone + Send + Sync + PartialEq + 'static,
+>(
+    key: String,
+    initial: T,
+    caller: &'static Location<'static>,
+) -> Signal<T> {
+    let storage_entry = StorageEntry::<SessionStorage, T>::new(key.clone(), initial.clone());
+    let mut data = storage_entry.data;
+    spawn(async move {
+        // Tasks are polled right after the render that spawned them, before it is painted, so reading storage here
+        // would still hold up the first frame.
+        wait_for_idle(ASYNC_LOAD_TIMEOUT).await;
+        if *data.peek() == initial {
+            let stored = schema::called_from(caller, || {
+                get_from_storage::<SessionStorage, T>(key, || initial.clone())
+            });
+            if stored != initial {
+                data.set(stored);
+            }
+        } else {
+            storage_entry.save();
+        }
+        storage_entry.save_to_storage_on_change();
+    });
+    data
+}
+
+/// The signals created by [`new_persistent`], keyed by their storage key and type.
+#[derive(Clone, Default)]
+struct PersistentRegistry {
+    signals: Rc<RefCell<HashMap<(String, TypeId), Box<dyn Any>>>>,
 }
 
 /// A persistent storage hook that can be used to store data across application reloads.
@@ -46,7 +188,8 @@ pub fn use_singleton_persistent<
 >(
     init: impl FnOnce() -> T,
 ) -> Signal<T> {
-    use_hook(|| new_singleton_persistent(init))
+    let caller = Location::caller();
+    use_hook(|| schema::called_from(caller, || new_persistent(hook_singleton_key(), init)))
 }
 
 /// Create a persistent storage signal that can be used to store data across application reloads.
@@ -60,7 +203,84 @@ pub fn new_singleton_persistent<
 >(
     init: impl FnOnce() -> T,
 ) -> Signal<T> {
-    let caller = std::panic::Location::caller();
+    let caller = Location::caller();
     let key = format!("{}:{}", caller.file(), caller.line());
     new_persistent(key, init)
 }
+
+/// The key [`use_singleton_persistent`] stores its value under.
+///
+/// The hook used to call [`new_singleton_persistent`] from line 49 of this file, which is where the key came from.
+/// The key is kept as it was so stored values survive changes to this file.
+fn hook_singleton_key() -> String {
+    format!("{}:49", file!())
+}
+
+/// A persistent storage hook that only persists a projection of the state.
+///
+/// This is useful for large state structs where only some fields should survive a reload, while
+/// transient fields like caches or UI state are kept out of storage. `project` extracts the part of
+/// the state that should be saved and `merge` applies a loaded projection back onto the initial state.
+///
+/// Depending on the platform this uses either local storage or a file storage
+#[track_caller]
+pub fn use_persistent_projection<T, P>(
+    key: impl ToString,
+    init: impl FnOnce() -> T,
+    project: fn(&T) -> P,
+    merge: fn(&mut T, P),
+) -> Signal<T>
+where
+    T: 'static,
+    P: Serialize + DeserializeOwned + Clone + Send + Sync + PartialEq + 'static,
+{
+    let caller = Location::caller();
+    use_hook(|| {
+        schema::called_from(caller, || {
+            new_persistent_projection(key, init, project, merge)
+        })
+    })
+}
+
+/// Creates a persistent storage signal that only persists a projection of the state.
+///
+/// See [`use_persistent_projection`] for more information.
+#[track_caller]
+pub fn new_persistent_projection<T, P>(
+    key: impl ToString,
+    init: impl FnOnce() -> T,
+    project: fn(&T) -> P,
+    merge: fn(&mut T, P),
+) -> Signal<T>
+where
+    T: 'static,
+    P: Serialize + DeserializeOwned + Clone + Send + Sync + PartialEq + 'static,
+{
+    let key = scoped_key::<SessionStorage>(key.to_string());
+    schema::check_type::<SessionStorage, P>(&key);
+    let mut value = init();
+    match SessionStorage::get::<P>(&key) {
+        Some(saved) => merge(&mut value, saved),
+        None => SessionStorage::set(key.clone(), &project(&value)),
+    }
+
+    let data = Signal::new(value);
+    let mut last = project(&data.peek());
+    spawn(async move {
+        loop {
+            let (rc, mut reactive_context) = ReactiveContext::new();
+            rc.run_in(|| {
+                let projected = project(&data.read());
+                if projected != last {
+                    tracing::trace!("Saving projection to storage");
+                    coalesce::queue_write::<SessionStorage, P>(key.clone(), projected.clone());
+                    last = projected;
+                }
+            });
+            if reactive_context.next().await.is_none() {
+                break;
+            }
+        }
+    });
+    data
+}
@@ -0,0 +1,571 @@
+//! Sync storage entries across a user's devices through a remote server.
+
+use dioxus::prelude::*;
+use futures::{
+    channel::mpsc::{self, UnboundedReceiver, UnboundedSender},
+    future::{self, Either, LocalBoxFuture},
+    stream::{self, LocalBoxStream},
+    StreamExt,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{fmt::Display, time::Duration};
+
+use super::{new_storage, Conflict, ConflictResolver, LocalStorage, RemoteWins};
+use crate::utils::timing::clock;
+
+/// How many times a push is retried after it conflicted with a newer value on the server.
+const MAX_CONFLICT_RETRIES: usize = 3;
+
+/// A value stored on the server, along with its version.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteValue {
+    /// The encoded value.
+    pub bytes: Vec<u8>,
+    /// The version of the value, which is bumped on every push.
+    pub version: u64,
+}
+
+/// An error returned by a [`RemoteSyncAdapter`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum RemoteSyncError {
+    /// The server holds a newer version than the push was based on. The newer value is merged and pushed again.
+    Conflict(RemoteValue),
+    /// The server can't be reached right now. Changes stay queued until it can.
+    Offline,
+    /// The request failed for another reason.
+    Failed(String),
+}
+
+impl std::error::Error for RemoteSyncError {}
+impl Display for RemoteSyncError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Conflict(remote) => write!(f, "the server holds version {}", remote.version),
+            Self::Offline => write!(f, "the server can't be reached"),
+            Self::Failed(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+
+/// A connection to a server that stores values for [`use_cloud_synced_storage`], like a REST API or a websocket.
+///
+/// Values are pushed with the version they will have on the server. The server should only accept a push if its
+/// version is newer than the one it holds, and otherwise reject it with [`RemoteSyncError::Conflict`] and the value
+/// it holds, so the two values can be merged.
+pub trait RemoteSyncAdapter: 'static {
+    /// Store the encoded value of `key` on the server as `version`.
+    fn push(
+        &self,
+        key: &str,
+        bytes: Vec<u8>,
+        version: u64,
+    ) -> LocalBoxFuture<'_, Result<(), RemoteSyncError>>;
+
+    /// Get the value of `key` from the server, or `None` if it was never pushed.
+    fn pull(&self, key: &str) -> LocalBoxFuture<'_, Result<Option<RemoteValue>, RemoteSyncError>>;
+
+    /// Get a stream of the values other devices push for `key`.
+    ///
+    /// Adapters that can't be notified of changes, like a plain REST API, can keep the default empty stream.
+    /// The value is then pulled when the hook is created and when [`CloudSyncedStorage::sync`] is called.
+    fn subscribe(&self, _key: &str) -> LocalBoxStream<'static, RemoteValue> {
+        stream::pending().boxed_local()
+    }
+}
+
+/// Whether the local value of a [`use_cloud_synced_storage`] hook is on the server.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CloudSyncStatus {
+    /// The local value is on the server.
+    Synced,
+    /// Local changes are queued, for example because the device is offline.
+    Pending,
+    /// The last sync failed. It is retried later.
+    Failed(String),
+}
+
+/// The options for [`use_cloud_synced_storage_with_options`].
+pub struct CloudSyncOptions<T> {
+    resolver: Box<dyn ConflictResolver<T>>,
+    retry: Duration,
+}
+
+impl<T: Clone + 'static> Default for CloudSyncOptions<T> {
+    fn default() -> Self {
+        Self {
+            resolver: Box::new(RemoteWins),
+            retry: Duration::from_secs(30),
+        }
+    }
+}
+
+impl<T: Clone + 'static> CloudSyncOptions<T> {
+    /// Create the default options, which keep the value from the server on a conflict and retry every 30 seconds.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolve conflicts between local changes and changes from other devices with the given [`ConflictResolver`].
+    pub fn resolver(mut self, resolver: impl ConflictResolver<T>) -> Self {
+        self.resolver = Box::new(resolver);
+        self
+    }
+
+    /// How long to wait before retrying a failed sync.
+    pub fn retry(mut self, retry: Duration) -> Self {
+        self.retry = retry;
+        self
+    }
+}
+
+/// The local state of a cloud synced entry, persisted so queued changes survive restarts.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+struct CloudRecord<T> {
+    /// The current local value.
+    value: T,
+    /// The value of `version` on the server.
+    base: T,
+    /// The latest version this device has seen on the server.
+    version: u64,
+}
+
+impl<T: PartialEq + Clone> CloudRecord<T> {
+    /// Whether the local value has changes that aren't on the server.
+    fn is_dirty(&self) -> bool {
+        self.value != self.base
+    }
+
+    /// The record after a newer value from the server, keeping the value the resolver picks over local changes.
+    fn merge(&self, remote: T, version: u64, resolver: &dyn ConflictResolver<T>) -> Self {
+        let value = if self.is_dirty() && self.value != remote {
+            tracing::trace!("Resolving cloud sync conflict");
+            resolver.resolve(Conflict {
+                base: &self.base,
+                local: &self.value,
+                remote: &remote,
+            })
+        } else {
+            remote.clone()
+        };
+
+        CloudRecord {
+            value,
+            base: remote,
+            version,
+        }
+    }
+}
+
+enum Wake {
+    Changed,
+    Sync,
+}
+
+enum Event {
+    Remote(RemoteValue),
+    Wake(Wake),
+}
+
+/// The interface for a cloud synced storage entry.
+///
+/// See [`use_cloud_synced_storage`] for more information.
+pub struct CloudSyncedStorage<T: 'static> {
+    data: Signal<T>,
+    record: Signal<CloudRecord<T>>,
+    status: Signal<CloudSyncStatus>,
+    wake: Signal<UnboundedSender<Wake>>,
+}
+
+impl<T> CloudSyncedStorage<T> {
+    /// Get the signal that holds the value. Writing to it saves the value locally and pushes it to the server.
+    pub fn data(&self) -> Signal<T> {
+        self.data
+    }
+
+    /// Get whether the local value is on the server.
+    pub fn status(&self) -> ReadOnlySignal<CloudSyncStatus> {
+        self.status.into()
+    }
+
+    /// Pull the value from the server and push queued changes right away, for example once the device is back online.
+    pub fn sync(&self) {
+        self.wake.peek().unbounded_send(Wake::Sync).ok();
+    }
+}
+
+// Manually implement Clone, Copy, and PartialEq as #[derive] thinks that T needs to implement these (it doesn't).
+
+impl<T> Clone for CloudSyncedStorage<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for CloudSyncedStorage<T> {}
+
+impl<T> PartialEq for CloudSyncedStorage<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+/// A storage hook that persists a value locally and syncs it across the user's devices through a [`RemoteSyncAdapter`].
+///
+/// Changes are saved to [`LocalStorage`] right away and pushed to the server in the background. While the server
+/// can't be reached, changes stay queued in storage, even across restarts, and are retried later. When another device
+/// changed the value in the meantime, the conflict is resolved by keeping the value from the server.
+/// See [`use_cloud_synced_storage_with_options`] to use another [`ConflictResolver`].
+///
+/// Values are sent to the adapter encoded with `postcard`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::storage::{use_cloud_synced_storage, CloudSyncStatus, RemoteSyncAdapter};
+///
+/// fn Settings() -> Element {
+///     let settings = use_cloud_synced_storage("settings", || false, SettingsApi::new("https://example.com"));
+///     let mut dark_mode = settings.data();
+///
+///     rsx! {
+///         input {
+///             r#type: "checkbox",
+///             checked: dark_mode(),
+///             oninput: move |e| dark_mode.set(e.checked()),
+///         }
+///         if settings.status()() == CloudSyncStatus::Pending {
+///             p { "Your changes will be synced once you are online." }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_cloud_synced_storage<T>(
+    key: impl ToString,
+    init: impl FnOnce() -> T,
+    adapter: impl RemoteSyncAdapter,
+) -> CloudSyncedStorage<T>
+where
+    T: Serialize + DeserializeOwned + Clone + Send + Sync + PartialEq + 'static,
+{
+    use_cloud_synced_storage_with_options(key, init, adapter, CloudSyncOptions::new())
+}
+
+/// A storage hook like [`use_cloud_synced_storage`] configured with [`CloudSyncOptions`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::storage::{use_cloud_synced_storage_with_options, CloudSyncOptions, Conflict};
+/// use std::time::Duration;
+///
+/// fn Bookmarks() -> Element {
+///     let bookmarks = use_cloud_synced_storage_with_options(
+///         "bookmarks",
+///         Vec::<String>::new,
+///         BookmarksApi::new(),
+///         CloudSyncOptions::new()
+///             // Keep the bookmarks added on either device.
+///             .resolver(|conflict: Conflict<Vec<String>>| {
+///                 let mut merged = conflict.remote.clone();
+///                 merged.extend(conflict.local.iter().filter(|b| !conflict.remote.contains(b)).cloned());
+///                 merged
+///             })
+///             .retry(Duration::from_secs(10)),
+///     );
+///
+///     rsx! {
+///         for bookmark in bookmarks.data()() {
+///             p { "{bookmark}" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_cloud_synced_storage_with_options<T>(
+    key: impl ToString,
+    init: impl FnOnce() -> T,
+    adapter: impl RemoteSyncAdapter,
+    options: CloudSyncOptions<T>,
+) -> CloudSyncedStorage<T>
+where
+    T: Serialize + DeserializeOwned + Clone + Send + Sync + PartialEq + 'static,
+{
+    let storage = use_hook(|| {
+        let key = key.to_string();
+        let record = new_storage::<LocalStorage, CloudRecord<T>>(key.clone(), || {
+            let value = init();
+            CloudRecord {
+                base: value.clone(),
+                value,
+                version: 0,
+            }
+        });
+
+        let (sender, receiver) = mpsc::unbounded();
+        let storage = CloudSyncedStorage {
+            data: Signal::new(record.peek().value.clone()),
+            record,
+            status: Signal::new(if record.peek().is_dirty() {
+                CloudSyncStatus::Pending
+            } else {
+                CloudSyncStatus::Synced
+            }),
+            wake: Signal::new(sender),
+        };
+
+        let sync = CloudSync {
+            key,
+            adapter,
+            options,
+            storage,
+        };
+        spawn(sync.run(receiver));
+        storage
+    });
+
+    // Queue local changes.
+    use_effect(move || {
+        let value = storage.data.read().clone();
+        let mut record = storage.record;
+        if record.peek().value != value {
+            record.write().value = value;
+            storage.wake.peek().unbounded_send(Wake::Changed).ok();
+        }
+    });
+
+    storage
+}
+
+/// The background task that keeps an entry in sync with the server.
+struct CloudSync<T: 'static, A> {
+    key: String,
+    adapter: A,
+    options: CloudSyncOptions<T>,
+    storage: CloudSyncedStorage<T>,
+}
+
+impl<T, A> CloudSync<T, A>
+where
+    T: Serialize + DeserializeOwned + Clone + Send + Sync + PartialEq + 'static,
+    A: RemoteSyncAdapter,
+{
+    async fn run(mut self, wake: UnboundedReceiver<Wake>) {
+        let remote = self.adapter.subscribe(&self.key).map(Event::Remote);
+        let mut events = stream::select(remote, wake.map(Event::Wake));
+        let mut pull = true;
+
+        loop {
+            let result = self.sync(pull).await;
+            let status = match &result {
+                Ok(()) => CloudSyncStatus::Synced,
+                Err(RemoteSyncError::Offline) => CloudSyncStatus::Pending,
+                Err(e) => CloudSyncStatus::Failed(e.to_string()),
+            };
+            if *self.storage.status.peek() != status {
+                self.storage.status.set(status);
+            }
+
+            // Wait for a change, or retry a failed sync after a while.
+            let retry = match result {
+                Ok(()) => Either::Left(future::pending::<()>()),
+                Err(_) => Either::Right(clock::sleep(self.options.retry)),
+            };
+            let next = events.next();
+            let event = match future::select(std::pin::pin!(retry), next).await {
+                Either::Left(_) => {
+                    pull = true;
+                    continue;
+                }
+                Either::Right((event, _)) => event,
+            };
+
+            pull = match event {
+                Some(Event::Remote(remote)) => {
+                    self.merge(remote);
+                    false
+                }
+                Some(Event::Wake(Wake::Changed)) => false,
+                Some(Event::Wake(Wake::Sync)) => true,
+                None => return,
+            };
+        }
+    }
+
+    /// Pull the value from the server if asked to, then push the local changes.
+    async fn sync(&mut self, pull: bool) -> Result<(), RemoteSyncError> {
+        if pull {
+            if let Some(remote) = self.adapter.pull(&self.key).await? {
+                self.merge(remote);
+            }
+        }
+
+        for _ in 0..MAX_CONFLICT_RETRIES {
+            let record = self.storage.record.peek().clone();
+            if !record.is_dirty() {
+                return Ok(());
+            }
+
+            let bytes = postcard::to_allocvec(&record.value)
+                .map_err(|e| RemoteSyncError::Failed(e.to_string()))?;
+            let version = record.version + 1;
+            match self.adapter.push(&self.key, bytes, version).await {
+                // The value may have changed again during the push, which is picked up by the next iteration.
+                Ok(()) => {
+                    let mut current = self.storage.record.write();
+                    current.base = record.value;
+                    current.version = version;
+                }
+                Err(RemoteSyncError::Conflict(remote)) if remote.version > record.version => {
+                    self.merge(remote)
+                }
+                // The server rejected the push without sending a newer value, so ask for the value it holds.
+                Err(RemoteSyncError::Conflict(_)) => {
+                    if let Some(remote) = self.adapter.pull(&self.key).await? {
+                        self.merge(remote);
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(RemoteSyncError::Failed(format!(
+            "\"{}\" kept conflicting with other devices",
+            self.key
+        )))
+    }
+
+    /// Merge a value from the server into the local value.
+    fn merge(&mut self, remote: RemoteValue) {
+        let record = self.storage.record.peek().clone();
+        if remote.version <= record.version {
+            return;
+        }
+        let remote_value = match postcard::from_bytes::<T>(&remote.bytes) {
+            Ok(value) => value,
+            Err(e) => {
+                tracing::warn!("Failed to decode \"{}\" from the server: {}", self.key, e);
+                return;
+            }
+        };
+
+        let record = record.merge(remote_value, remote.version, &*self.options.resolver);
+        let value = record.value.clone();
+        self.storage.record.set(record);
+        if *self.storage.data.peek() != value {
+            self.storage.data.set(value);
+        }
+    }
+}
+
+#[test]
+fn test_merge_record() {
+    use super::LocalWins;
+
+    // Without local changes the value from the server is taken.
+    let clean = CloudRecord {
+        value: 1,
+        base: 1,
+        version: 1,
+    };
+    let merged = clean.merge(2, 2, &LocalWins);
+    assert_eq!((merged.value, merged.base, merged.version), (2, 2, 2));
+
+    // Local changes go through the resolver, and stay dirty so they are pushed on top of the new version.
+    let dirty = CloudRecord {
+        value: 3,
+        base: 1,
+        version: 1,
+    };
+    let merged = dirty.merge(2, 2, &LocalWins);
+    assert_eq!((merged.value, merged.base, merged.version), (3, 2, 2));
+    assert!(merged.is_dirty());
+    let merged = dirty.merge(2, 2, &RemoteWins);
+    assert!(!merged.is_dirty());
+}
+
+#[cfg(all(test, not(target_family = "wasm")))]
+#[test]
+fn test_stale_conflict_pulls_newer_version() {
+    use super::{LocalWins, StorageBacking};
+    use std::{cell::RefCell, rc::Rc};
+
+    thread_local! {
+        static STORAGE: RefCell<Option<CloudSyncedStorage<i32>>> = const { RefCell::new(None) };
+    }
+
+    /// A server that rejects pushes with a stale copy of its value, and only returns its value from the second pull.
+    #[derive(Clone, Default)]
+    struct StaleServer {
+        pulls: Rc<RefCell<usize>>,
+        pushes: Rc<RefCell<Vec<u64>>>,
+    }
+
+    impl RemoteSyncAdapter for StaleServer {
+        fn push(
+            &self,
+            _key: &str,
+            _bytes: Vec<u8>,
+            version: u64,
+        ) -> LocalBoxFuture<'_, Result<(), RemoteSyncError>> {
+            self.pushes.borrow_mut().push(version);
+            let result = match version > 4 {
+                true => Ok(()),
+                false => Err(RemoteSyncError::Conflict(RemoteValue {
+                    bytes: postcard::to_allocvec(&0).unwrap(),
+                    version: 0,
+                })),
+            };
+            Box::pin(future::ready(result))
+        }
+
+        fn pull(
+            &self,
+            _key: &str,
+        ) -> LocalBoxFuture<'_, Result<Option<RemoteValue>, RemoteSyncError>> {
+            *self.pulls.borrow_mut() += 1;
+            let value = (*self.pulls.borrow() > 1).then(|| RemoteValue {
+                bytes: postcard::to_allocvec(&2).unwrap(),
+                version: 4,
+            });
+            Box::pin(future::ready(Ok(value)))
+        }
+    }
+
+    fn app(server: StaleServer) -> Element {
+        let storage = use_cloud_synced_storage_with_options(
+            "cloud-stale-conflict-test",
+            || 0,
+            server,
+            CloudSyncOptions::new().resolver(LocalWins),
+        );
+        STORAGE.with(|cell| *cell.borrow_mut() = Some(storage));
+        rsx! {}
+    }
+
+    super::client_storage::set_test_directory();
+    let key = "cloud-stale-conflict-test".to_string();
+    LocalStorage::set(
+        key.clone(),
+        &CloudRecord {
+            value: 1,
+            base: 0,
+            version: 0,
+        },
+    );
+
+    let server = StaleServer::default();
+    let mut dom = VirtualDom::new_with_props(app, server.clone());
+    dom.rebuild_in_place();
+    dom.process_events();
+
+    // The stale conflict makes the entry pull version 4, and the local change is pushed on top of it.
+    assert_eq!(*server.pushes.borrow(), vec![1, 5]);
+    let storage = STORAGE.with(|cell| cell.borrow_mut().take()).unwrap();
+    dom.in_runtime(|| {
+        assert_eq!(storage.record.peek().version, 5);
+        assert_eq!(*storage.status.peek(), CloudSyncStatus::Synced);
+    });
+
+    LocalStorage::remove(&key);
+}
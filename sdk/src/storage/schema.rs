@@ -0,0 +1,103 @@
+//! Catches storage keys that are used with more than one type.
+//!
+//! Values are stored without their type, so two entries that share a key but not a type silently fail to decode
+//! each other's values. In debug builds every entry records the type of its key and where it was created, and
+//! creating an entry for the same key with a different type panics with both locations.
+
+use std::panic::Location;
+
+use super::StorageBacking;
+
+#[cfg(debug_assertions)]
+use once_cell::sync::Lazy;
+#[cfg(debug_assertions)]
+use std::{
+    any::{type_name, TypeId},
+    cell::Cell,
+    collections::HashMap,
+    sync::Mutex,
+};
+
+/// The type a key was first used with.
+#[cfg(debug_assertions)]
+struct KeySchema {
+    type_id: TypeId,
+    type_name: &'static str,
+    location: &'static Location<'static>,
+}
+
+/// The schema of every key, by backing and key.
+#[cfg(debug_assertions)]
+static SCHEMAS: Lazy<Mutex<HashMap<(TypeId, String), KeySchema>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[cfg(debug_assertions)]
+thread_local! {
+    /// The location of the hook that is creating an entry, when a closure hides it from `#[track_caller]`.
+    static CALLER: Cell<Option<&'static Location<'static>>> = const { Cell::new(None) };
+}
+
+/// Record that `key` holds a `T`, panicking in debug builds if it was used with another type before.
+#[cfg(debug_assertions)]
+#[track_caller]
+pub(crate) fn check_type<S: StorageBacking, T: 'static>(key: &S::Key) {
+    let location = CALLER
+        .with(|caller| caller.get())
+        .unwrap_or_else(Location::caller);
+
+    let mut schemas = SCHEMAS.lock().unwrap();
+    let schema = schemas
+        .entry((TypeId::of::<S>(), format!("{key:?}")))
+        .or_insert_with(|| KeySchema {
+            type_id: TypeId::of::<T>(),
+            type_name: type_name::<T>(),
+            location,
+        });
+    if schema.type_id == TypeId::of::<T>() {
+        return;
+    }
+
+    let (first_type, first_location) = (schema.type_name, schema.location);
+    // Release the lock first so the registry stays usable if the panic is caught.
+    drop(schemas);
+    panic!(
+        "the storage key {key:?} is used with two types: `{first_type}` at {first_location} and `{}` at {location}",
+        type_name::<T>()
+    );
+}
+
+#[cfg(not(debug_assertions))]
+pub(crate) fn check_type<S: StorageBacking, T: 'static>(_key: &S::Key) {}
+
+/// Run `f`, attributing the entries it creates to `caller`.
+///
+/// Hooks wrap the closure passed to `use_hook` in this, since the closure would otherwise hide the component that
+/// called the hook.
+#[cfg(debug_assertions)]
+pub(crate) fn called_from<R>(caller: &'static Location<'static>, f: impl FnOnce() -> R) -> R {
+    // The outermost hook is the one the app called, so nested hooks keep its location.
+    let previous = CALLER.with(|current| current.replace(current.get().or(Some(caller))));
+    let result = f();
+    CALLER.with(|current| current.set(previous));
+    result
+}
+
+#[cfg(not(debug_assertions))]
+pub(crate) fn called_from<R>(_caller: &'static Location<'static>, f: impl FnOnce() -> R) -> R {
+    f()
+}
+
+#[cfg(all(test, debug_assertions))]
+mod tests {
+    use super::check_type;
+    use crate::storage::SessionStorage;
+
+    #[test]
+    #[should_panic(expected = "is used with two types")]
+    fn test_key_type_collision() {
+        let key = "schema-collision".to_string();
+        check_type::<SessionStorage, u32>(&key);
+        check_type::<SessionStorage, u32>(&key);
+        check_type::<SessionStorage, String>(&key);
+    }
+}
@@ -0,0 +1,260 @@
+//! Namespaced storage scopes.
+//!
+//! Scopes isolate the persisted state of different users or profiles by prefixing every key created inside of them.
+
+use std::any::TypeId;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::marker::PhantomData;
+
+use dioxus::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::sync::watch::Receiver;
+
+use super::{StorageBacking, StorageChannelPayload, StorageSubscriber};
+
+thread_local! {
+    /// The scopes entered with [`with_scope`], innermost last.
+    static SCOPE_STACK: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+
+    /// The keys that have already been added to their scope's index, per storage backing.
+    static REGISTERED: RefCell<HashSet<(TypeId, String)>> = RefCell::new(HashSet::new());
+}
+
+/// The name of a storage scope provided to child components with [`use_storage_scope`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct StorageScope(pub String);
+
+/// Provides a storage scope to all child components.
+///
+/// Storage hooks in child components will prefix their keys with the scope name,
+/// so their state is isolated from other scopes and can be purged with [`clear_scope`].
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::storage::{use_persistent, use_storage_scope};
+///
+/// #[component]
+/// fn Account(user_id: String) -> Element {
+///     use_storage_scope(format!("user-{user_id}"));
+///     rsx! { Settings {} }
+/// }
+///
+/// #[component]
+/// fn Settings() -> Element {
+///     // Stored under "user-<id>.volume"
+///     let volume = use_persistent("volume", || 50);
+///     rsx! { "{volume}" }
+/// }
+/// ```
+pub fn use_storage_scope(name: impl ToString) -> StorageScope {
+    use_hook(|| provide_context(StorageScope(name.to_string())))
+}
+
+/// Runs `f` inside the given storage scope.
+///
+/// Storage hooks and keys created inside of `f` will be prefixed with the scope name.
+/// This takes priority over a scope provided by [`use_storage_scope`].
+pub fn with_scope<R>(name: impl ToString, f: impl FnOnce() -> R) -> R {
+    struct PopScope;
+
+    impl Drop for PopScope {
+        fn drop(&mut self) {
+            SCOPE_STACK.with(|stack| stack.borrow_mut().pop());
+        }
+    }
+
+    SCOPE_STACK.with(|stack| stack.borrow_mut().push(name.to_string()));
+    let _guard = PopScope;
+    f()
+}
+
+/// Gets the name of the storage scope that is currently active, if any.
+pub fn current_scope() -> Option<String> {
+    SCOPE_STACK
+        .with(|stack| stack.borrow().last().cloned())
+        .or_else(|| try_consume_context::<StorageScope>().map(|scope| scope.0))
+}
+
+/// Removes all values stored in the given scope with the storage backing `S`.
+///
+/// This is useful for purging a user's persisted state when they log out.
+pub fn clear_scope<S: StorageBacking<Key = String>>(name: &str) {
+    let index = index_key(name);
+    for key in S::get::<Vec<String>>(&index).unwrap_or_default() {
+        S::remove(&prefixed(name, &key));
+    }
+    S::remove(&index);
+
+    let prefix = format!("{}.", name);
+    REGISTERED.with(|registered| {
+        registered
+            .borrow_mut()
+            .retain(|(backing, key)| *backing != TypeId::of::<S>() || !key.starts_with(&prefix))
+    });
+}
+
+/// Prefixes the key with the current scope and records it in the scope's index so it can be cleared later.
+pub(crate) fn scoped_key<S: StorageBacking<Key = String>>(key: String) -> String {
+    match current_scope() {
+        Some(scope) => {
+            register::<S>(&scope, &key);
+            prefixed(&scope, &key)
+        }
+        None => key,
+    }
+}
+
+fn prefixed(scope: &str, key: &str) -> String {
+    format!("{}.{}", scope, key)
+}
+
+fn index_key(scope: &str) -> String {
+    prefixed(scope, "__keys")
+}
+
+fn register<S: StorageBacking<Key = String>>(scope: &str, key: &str) {
+    let entry = (TypeId::of::<S>(), prefixed(scope, key));
+    let is_new = REGISTERED.with(|registered| registered.borrow_mut().insert(entry));
+    if !is_new {
+        return;
+    }
+
+    let index = index_key(scope);
+    let mut keys = S::get::<Vec<String>>(&index).unwrap_or_default();
+    if !keys.iter().any(|k| k == key) {
+        keys.push(key.to_string());
+        S::set(index, &keys);
+    }
+}
+
+/// A key for [`ScopedStorage`] that captures the storage scope that was active when it was created.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScopedKey {
+    scope: Option<String>,
+    key: String,
+}
+
+impl ScopedKey {
+    /// Creates a key in the current storage scope.
+    pub fn new(key: impl ToString) -> Self {
+        Self {
+            scope: current_scope(),
+            key: key.to_string(),
+        }
+    }
+
+    /// Gets the key including the scope prefix.
+    pub fn full_key(&self) -> String {
+        match &self.scope {
+            Some(scope) => prefixed(scope, &self.key),
+            None => self.key.clone(),
+        }
+    }
+}
+
+impl From<&str> for ScopedKey {
+    fn from(key: &str) -> Self {
+        Self::new(key)
+    }
+}
+
+impl From<String> for ScopedKey {
+    fn from(key: String) -> Self {
+        Self::new(key)
+    }
+}
+
+/// A storage backing that prefixes all keys of the inner backing `S` with the storage scope they were created in.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::storage::{use_synced_storage, LocalStorage, ScopedStorage};
+///
+/// fn use_theme() -> Signal<String> {
+///     use_synced_storage::<ScopedStorage<LocalStorage>, _>("theme".into(), || "dark".to_string())
+/// }
+/// ```
+pub struct ScopedStorage<S>(PhantomData<S>);
+
+impl<S> Clone for ScopedStorage<S> {
+    fn clone(&self) -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<S: StorageBacking<Key = String>> StorageBacking for ScopedStorage<S> {
+    type Key = ScopedKey;
+
+    fn get<T: DeserializeOwned + Clone + 'static>(key: &ScopedKey) -> Option<T> {
+        S::get(&key.full_key())
+    }
+
+    fn set<T: Serialize + Send + Sync + Clone + 'static>(key: ScopedKey, value: &T) {
+        if let Some(scope) = &key.scope {
+            register::<S>(scope, &key.key);
+        }
+        S::set(key.full_key(), value)
+    }
+
+    fn remove(key: &ScopedKey) {
+        S::remove(&key.full_key())
+    }
+
+    fn raw_key(key: &ScopedKey) -> Option<String> {
+        S::raw_key(&key.full_key())
+    }
+}
+
+impl<S> StorageSubscriber<ScopedStorage<S>> for ScopedStorage<S>
+where
+    S: StorageBacking<Key = String> + StorageSubscriber<S>,
+{
+    fn subscribe<T: DeserializeOwned + Send + Sync + Clone + 'static>(
+        key: &ScopedKey,
+    ) -> Receiver<StorageChannelPayload> {
+        S::subscribe::<T>(&key.full_key())
+    }
+
+    fn unsubscribe(key: &ScopedKey) {
+        S::unsubscribe(&key.full_key())
+    }
+}
+
+#[cfg(all(test, not(target_family = "wasm")))]
+#[test]
+fn test_clear_scope_resets_synced_entries() {
+    use super::{use_synced_storage, LocalStorage};
+
+    thread_local! {
+        static VOLUME: std::cell::Cell<Option<Signal<i32>>> = const { std::cell::Cell::new(None) };
+    }
+
+    fn app() -> Element {
+        let key = with_scope("clear-scope-test", || ScopedKey::new("volume"));
+        let volume = use_synced_storage::<ScopedStorage<LocalStorage>, i32>(key, || 50);
+        VOLUME.with(|cell| cell.set(Some(volume)));
+        rsx! {}
+    }
+
+    super::client_storage::set_test_directory();
+    let key = "clear-scope-test.volume".to_string();
+    with_scope("clear-scope-test", || {
+        ScopedStorage::<LocalStorage>::set(ScopedKey::new("volume"), &80)
+    });
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    let volume = VOLUME.with(|cell| cell.get()).unwrap();
+    assert_eq!(dom.in_runtime(|| *volume.peek()), 80);
+
+    // Logging out clears the scope while the entry is still subscribed to it.
+    clear_scope::<LocalStorage>("clear-scope-test");
+    dom.process_events();
+
+    // The entry starts over instead of keeping the old value, and doesn't store it again.
+    assert_eq!(dom.in_runtime(|| *volume.peek()), 50);
+    assert_eq!(LocalStorage::get::<i32>(&key), None);
+
+    LocalStorage::unsubscribe(&key);
+}
@@ -0,0 +1,221 @@
+//! Server side prefetching of storage values.
+
+use std::{cell::Cell, marker::PhantomData, rc::Rc};
+
+use dioxus::prelude::*;
+use serde::de::DeserializeOwned;
+
+use super::{try_serde_from_string, StorageBacking};
+
+/// Supplies the initial values of storage entries while rendering on the server.
+///
+/// Storage is read-only during server side rendering. By default, every storage hook renders its
+/// initial value on the server and the client swaps in the persisted value during hydration. If the
+/// persisted values are available to the server (for example, because the client sends them in a cookie
+/// or header), a `StorageRequestContext` lets the server render them directly instead of flashing the default.
+/// Call [`use_prefetched_storage`] on the client so it hydrates with the persisted values right away.
+///
+/// Values are expected in the same encoded form the storage backing writes.
+pub struct StorageRequestContext<S: StorageBacking> {
+    provider: Rc<dyn Fn(&S::Key) -> Option<String>>,
+}
+
+impl<S: StorageBacking> Clone for StorageRequestContext<S> {
+    fn clone(&self) -> Self {
+        Self {
+            provider: self.provider.clone(),
+        }
+    }
+}
+
+impl<S: StorageBacking> StorageRequestContext<S> {
+    /// Creates a new request context from a function that looks up the encoded value for a key.
+    pub fn new(provider: impl Fn(&S::Key) -> Option<String> + 'static) -> Self {
+        Self {
+            provider: Rc::new(provider),
+        }
+    }
+
+    /// Gets and decodes the value for the given key, if the provider has one.
+    pub fn get<T: DeserializeOwned>(&self, key: &S::Key) -> Option<T> {
+        let encoded = (self.provider)(key)?;
+        let value = try_serde_from_string(&encoded);
+        if value.is_none() {
+            tracing::warn!("Failed to decode prefetched storage value for {:?}", key);
+        }
+        value
+    }
+}
+
+/// Provides a [`StorageRequestContext`] for the storage backing `S` to all storage hooks below the current component.
+///
+/// This should be called near the root of the app while rendering on the server.
+///
+/// ```rust,ignore
+/// use dioxus_sdk::storage::{provide_storage_request_context, LocalStorage};
+///
+/// fn app() -> Element {
+///     let cookies = use_context::<RequestCookies>();
+///     provide_storage_request_context::<LocalStorage>(move |key| cookies.get(key));
+///     rsx! { /* ... */ }
+/// }
+/// ```
+pub fn provide_storage_request_context<S: StorageBacking>(
+    provider: impl Fn(&S::Key) -> Option<String> + 'static,
+) -> StorageRequestContext<S> {
+    use_hook(|| provide_context(StorageRequestContext::<S>::new(provider)))
+}
+
+/// Gets the prefetched value for a key from the current [`StorageRequestContext`], if one was provided.
+pub(crate) fn get_from_request_context<S: StorageBacking, T: DeserializeOwned>(
+    key: &S::Key,
+) -> Option<T> {
+    try_consume_context::<StorageRequestContext<S>>()?.get(key)
+}
+
+/// Marks that the server rendered the persisted values of the storage backing `S`.
+struct Prefetched<S>(PhantomData<S>);
+
+impl<S> Clone for Prefetched<S> {
+    fn clone(&self) -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// Tells the storage hooks below the current component that the server rendered the persisted values of the storage
+/// backing `S` with a [`StorageRequestContext`].
+///
+/// By default, the client first renders the initial values to match the markup from the server, then swaps in the
+/// persisted values, which briefly shows the initial values. With this, the client hydrates with the persisted values
+/// right away. Only use it if the server has every value the client has, otherwise the markup won't match.
+///
+/// ```rust,ignore
+/// use dioxus_sdk::storage::{provide_storage_request_context, use_prefetched_storage, LocalStorage};
+///
+/// fn app() -> Element {
+///     #[cfg(feature = "server")]
+///     provide_storage_request_context::<LocalStorage>(|key| cookies().get(key));
+///     #[cfg(feature = "web")]
+///     use_prefetched_storage::<LocalStorage>();
+///     rsx! { /* ... */ }
+/// }
+/// ```
+pub fn use_prefetched_storage<S: StorageBacking>() {
+    use_hook(|| provide_context(Prefetched::<S>(PhantomData)));
+}
+
+/// Whether the markup from the server was hydrated.
+///
+/// It is kept in the root context, so every app on the thread hydrates on its own.
+#[derive(Clone, Default)]
+struct Hydrated(Rc<Cell<bool>>);
+
+fn hydrated() -> Rc<Cell<bool>> {
+    match try_consume_context::<Hydrated>() {
+        Some(hydrated) => hydrated.0,
+        None => provide_root_context(Hydrated::default()).0,
+    }
+}
+
+/// Whether a storage hook has to render the initial value first, because it is part of the markup the server
+/// rendered without the persisted value.
+///
+/// The hydration render happens before any task runs, so the first storage task marks it as done.
+pub(crate) fn must_render_server_value<S: StorageBacking>() -> bool {
+    cfg!(feature = "hydrate")
+        && !hydrated().get()
+        && try_consume_context::<Prefetched<S>>().is_none()
+}
+
+/// Marks the markup from the server as hydrated, so storage hooks created later read the persisted value right away.
+pub(crate) fn finish_hydration() {
+    hydrated().set(true);
+}
+
+#[cfg(all(test, feature = "hydrate", not(target_family = "wasm")))]
+#[test]
+fn test_hydration_renders_initial_value_first() {
+    use super::{use_storage, LocalStorage};
+    use std::cell::RefCell;
+
+    thread_local! {
+        static RENDERED: RefCell<Vec<i32>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn app() -> Element {
+        let value = use_storage::<LocalStorage, _>("hydrate-test".to_string(), || 1);
+        RENDERED.with(|rendered| rendered.borrow_mut().push(value()));
+        rsx! {}
+    }
+
+    super::client_storage::set_test_directory();
+    LocalStorage::set("hydrate-test".to_string(), &2);
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    dom.process_events();
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+
+    // The stored value is only rendered after hydration, and the initial value isn't written over it.
+    RENDERED.with(|rendered| assert_eq!(*rendered.borrow(), vec![1, 2]));
+    assert_eq!(
+        LocalStorage::get::<i32>(&"hydrate-test".to_string()),
+        Some(2)
+    );
+}
+
+#[cfg(all(test, feature = "hydrate", not(target_family = "wasm")))]
+#[test]
+fn test_every_app_hydrates() {
+    use super::{use_storage, LocalStorage};
+    use std::cell::RefCell;
+
+    thread_local! {
+        static FIRST_RENDER: RefCell<Vec<i32>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn app() -> Element {
+        let value = use_storage::<LocalStorage, _>("hydrate-apps-test".to_string(), || 1);
+        use_hook(|| FIRST_RENDER.with(|rendered| rendered.borrow_mut().push(value())));
+        rsx! {}
+    }
+
+    super::client_storage::set_test_directory();
+    LocalStorage::set("hydrate-apps-test".to_string(), &2);
+
+    // A second app on the same thread also hydrates markup from the server, even though the first one finished.
+    for _ in 0..2 {
+        let mut dom = VirtualDom::new(app);
+        dom.rebuild_in_place();
+        dom.process_events();
+    }
+    FIRST_RENDER.with(|rendered| assert_eq!(*rendered.borrow(), vec![1, 1]));
+}
+
+#[cfg(all(test, feature = "hydrate", not(target_family = "wasm")))]
+#[test]
+fn test_prefetched_storage_hydrates_with_stored_value() {
+    use super::{use_storage, LocalStorage};
+    use std::cell::RefCell;
+
+    thread_local! {
+        static RENDERED: RefCell<Vec<i32>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn app() -> Element {
+        use_prefetched_storage::<LocalStorage>();
+        let value = use_storage::<LocalStorage, _>("hydrate-prefetched-test".to_string(), || 1);
+        RENDERED.with(|rendered| rendered.borrow_mut().push(value()));
+        rsx! {}
+    }
+
+    super::client_storage::set_test_directory();
+    LocalStorage::set("hydrate-prefetched-test".to_string(), &2);
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    dom.process_events();
+
+    // The server rendered the stored value too, so it is rendered right away without the initial value.
+    RENDERED.with(|rendered| assert_eq!(*rendered.borrow(), vec![2]));
+}
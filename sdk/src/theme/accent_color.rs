@@ -0,0 +1,203 @@
+//! Utilities to get and subscribe to the system accent color.
+
+use dioxus::prelude::*;
+use std::{error::Error, fmt::Display};
+
+use crate::utils::color::Color;
+
+/// Represents an error with system accent color utilities.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AccentColorError {
+    /// The accent color is not supported on this platform.
+    NotSupported,
+    /// Failed to get the accent color.
+    CheckFailed,
+}
+
+impl Error for AccentColorError {}
+impl Display for AccentColorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::NotSupported => write!(f, "the current platform is not supported"),
+            Self::CheckFailed => write!(
+                f,
+                "the system returned an error while checking the accent color"
+            ),
+        }
+    }
+}
+
+type AccentColorResult = Result<Color, AccentColorError>;
+
+/// A hook for receiving the accent color the user picked for the system.
+///
+/// The initial color will be returned and updated if the accent color changes. The color is checked again whenever
+/// the app gains focus, which is when a change made in the system settings shows up. On the web, only browsers that
+/// support the CSS `AccentColor` system color report it, and the color may be a browser default rather than the
+/// system accent color.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::theme::{use_accent_color, Color};
+///
+/// fn App() -> Element {
+///     let accent = use_accent_color();
+///     // Fall back to the brand color where the accent color isn't known.
+///     let accent = accent().unwrap_or(Color::rgb(0, 90, 158));
+///
+///     rsx! {
+///         button {
+///             style: "background: {accent}; color: {accent.contrasting_text()};",
+///             "Continue"
+///         }
+///     }
+/// }
+/// ```
+pub fn use_accent_color() -> ReadOnlySignal<AccentColorResult> {
+    let accent_color = match try_use_context::<Signal<AccentColorResult>>() {
+        Some(s) => s,
+        // This should only run once.
+        None => {
+            let signal = Signal::new_in_scope(get_accent_color(), ScopeId::ROOT);
+            let accent_color = provide_root_context(signal);
+            listen(accent_color);
+            accent_color
+        }
+    };
+
+    use_hook(|| ReadOnlySignal::new(accent_color))
+}
+
+/// Get the accent color the user picked for the system.
+///
+/// See [`use_accent_color`] for the limitations on the web.
+pub fn get_accent_color() -> AccentColorResult {
+    get_accent_color_platform()
+}
+
+/// Update the accent color if it changed.
+fn refresh(mut accent_color: Signal<AccentColorResult>) {
+    let latest = get_accent_color();
+    if *accent_color.peek() != latest {
+        accent_color.set(latest);
+    }
+}
+
+/// The listener implementation for wasm targets.
+/// This should only be called once.
+#[cfg(target_family = "wasm")]
+fn listen(accent_color: Signal<AccentColorResult>) {
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+
+    let listener = Closure::wrap(Box::new(move || refresh(accent_color)) as Box<dyn FnMut()>);
+    window
+        .add_event_listener_with_callback("focus", listener.as_ref().unchecked_ref())
+        .ok();
+    listener.forget();
+}
+
+/// The listener implementation for desktop targets. (not linux)
+/// This should only be called once.
+#[cfg(not(target_family = "wasm"))]
+fn listen(accent_color: Signal<AccentColorResult>) {
+    use dioxus_desktop::{tao::event::Event, window, WindowEvent};
+
+    let window = window();
+
+    // The accent color usually changes along with the theme, or while the app is in the background.
+    window.create_wry_event_handler(move |event, _| {
+        if let Event::WindowEvent {
+            event: WindowEvent::Focused(true) | WindowEvent::ThemeChanged(_),
+            ..
+        } = event
+        {
+            refresh(accent_color);
+        }
+    });
+}
+
+/// The wasm implementation to get the accent color, from the CSS `AccentColor` system color.
+#[cfg(target_family = "wasm")]
+fn get_accent_color_platform() -> AccentColorResult {
+    use wasm_bindgen::JsCast;
+
+    if !web_sys::css::supports_with_value("color", "AccentColor").unwrap_or(false) {
+        return Err(AccentColorError::NotSupported);
+    }
+    let window = web_sys::window().ok_or(AccentColorError::NotSupported)?;
+    let document = window.document().ok_or(AccentColorError::NotSupported)?;
+    let body = document.body().ok_or(AccentColorError::NotSupported)?;
+
+    // System colors are only resolved to a real color in the computed style of an element on the page.
+    let element = document
+        .create_element("span")
+        .ok()
+        .and_then(|element| element.dyn_into::<web_sys::HtmlElement>().ok())
+        .ok_or(AccentColorError::CheckFailed)?;
+    element
+        .style()
+        .set_property("color", "AccentColor")
+        .or(Err(AccentColorError::CheckFailed))?;
+    body.append_child(&element)
+        .or(Err(AccentColorError::CheckFailed))?;
+    let color = window
+        .get_computed_style(&element)
+        .ok()
+        .flatten()
+        .and_then(|style| style.get_property_value("color").ok());
+    element.remove();
+
+    color
+        .and_then(|color| color.parse().ok())
+        .ok_or(AccentColorError::CheckFailed)
+}
+
+/// The Windows implementation to get the accent color.
+#[cfg(windows)]
+fn get_accent_color_platform() -> AccentColorResult {
+    use windows::UI::ViewManagement::{UIColorType, UISettings};
+
+    let color = UISettings::new()
+        .and_then(|settings| settings.GetColorValue(UIColorType::Accent))
+        .or(Err(AccentColorError::CheckFailed))?;
+    Ok(Color::rgba(color.R, color.G, color.B, color.A))
+}
+
+/// The macOS implementation to get the accent color.
+#[cfg(target_os = "macos")]
+fn get_accent_color_platform() -> AccentColorResult {
+    use objc::{
+        class, msg_send,
+        runtime::{Object, BOOL, NO},
+        sel, sel_impl,
+    };
+
+    unsafe {
+        // `controlAccentColor` is only available since macOS 10.14.
+        let responds: BOOL =
+            msg_send![class!(NSColor), respondsToSelector: sel!(controlAccentColor)];
+        if responds == NO {
+            return Err(AccentColorError::NotSupported);
+        }
+        let color: *mut Object = msg_send![class!(NSColor), controlAccentColor];
+
+        // The components can only be read from a color in an RGB color space.
+        let srgb: *mut Object = msg_send![class!(NSColorSpace), sRGBColorSpace];
+        let color: *mut Object = msg_send![color, colorUsingColorSpace: srgb];
+        if color.is_null() {
+            return Err(AccentColorError::CheckFailed);
+        }
+        let channel = |value: f64| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+        let r: f64 = msg_send![color, redComponent];
+        let g: f64 = msg_send![color, greenComponent];
+        let b: f64 = msg_send![color, blueComponent];
+        let a: f64 = msg_send![color, alphaComponent];
+        Ok(Color::rgba(channel(r), channel(g), channel(b), channel(a)))
+    }
+}
@@ -0,0 +1,98 @@
+//! An app theme that combines the system theme with a persisted user override.
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{use_system_theme, SystemTheme};
+use crate::storage::{LocalStorage, StorageBacking};
+
+/// The storage key the theme override is persisted under.
+const OVERRIDE_KEY: &str = "dioxus-sdk-theme-override";
+
+/// The theme the user picked for the app.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum ThemeOverride {
+    /// Always use the light theme.
+    Light,
+    /// Always use the dark theme.
+    Dark,
+    /// Follow the system theme.
+    #[default]
+    System,
+}
+
+/// The interface for reading and overriding the app theme.
+///
+/// See [`use_theme`] for more information.
+#[derive(Clone, Copy, PartialEq)]
+pub struct UseTheme {
+    system_theme: ReadOnlySignal<Result<SystemTheme, super::SystemThemeError>>,
+    theme_override: Signal<ThemeOverride>,
+}
+
+impl UseTheme {
+    /// Get the theme the app should use.
+    ///
+    /// This is the override if one is set, otherwise the system theme.
+    /// Falls back to [`SystemTheme::Light`] if the system theme can't be determined.
+    pub fn theme(&self) -> SystemTheme {
+        match *self.theme_override.read() {
+            ThemeOverride::Light => SystemTheme::Light,
+            ThemeOverride::Dark => SystemTheme::Dark,
+            ThemeOverride::System => self.system_theme.read().unwrap_or(SystemTheme::Light),
+        }
+    }
+
+    /// Get the theme override the user picked.
+    pub fn theme_override(&self) -> ThemeOverride {
+        *self.theme_override.read()
+    }
+
+    /// Override the system theme and persist the choice.
+    pub fn set_override(&mut self, theme_override: ThemeOverride) {
+        self.theme_override.set(theme_override);
+        LocalStorage::set(OVERRIDE_KEY.to_string(), &theme_override);
+    }
+}
+
+/// A hook for the app theme, combining the system theme with an override that is persisted across reloads.
+///
+/// Every call to this hook shares the same override, so changing it in one component updates the whole app.
+/// This requires the `storage` feature. On desktop, the storage directory must be set with [`set_dir!`](crate::storage::set_dir) first.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::theme::{use_theme, ThemeOverride};
+///
+/// fn App() -> Element {
+///     let mut theme = use_theme();
+///
+///     rsx! {
+///         div {
+///             class: "{theme.theme()}",
+///             button { onclick: move |_| theme.set_override(ThemeOverride::Light), "Light" }
+///             button { onclick: move |_| theme.set_override(ThemeOverride::Dark), "Dark" }
+///             button { onclick: move |_| theme.set_override(ThemeOverride::System), "System" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_theme() -> UseTheme {
+    let system_theme = use_system_theme();
+    let theme_override = match try_use_context::<Signal<ThemeOverride>>() {
+        Some(s) => s,
+        // This should only run once.
+        None => {
+            let stored = LocalStorage::get::<ThemeOverride>(&OVERRIDE_KEY.to_string());
+            let signal = Signal::new_in_scope(stored.unwrap_or_default(), ScopeId::ROOT);
+            provide_root_context(signal)
+        }
+    };
+
+    use_hook(|| UseTheme {
+        system_theme,
+        theme_override,
+    })
+}
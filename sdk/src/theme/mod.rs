@@ -2,9 +2,24 @@
 
 cfg_if::cfg_if! {
     if #[cfg(any(target_family = "wasm", target_os = "windows", target_os = "macos"))] {
+        #[cfg(feature = "system_theme")]
         mod system_theme;
+        #[cfg(feature = "system_theme")]
         pub use system_theme::*;
+
+        #[cfg(all(feature = "system_theme", feature = "storage"))]
+        mod app_theme;
+        #[cfg(all(feature = "system_theme", feature = "storage"))]
+        pub use app_theme::*;
+
+        #[cfg(feature = "accent_color")]
+        mod accent_color;
+        #[cfg(feature = "accent_color")]
+        pub use accent_color::*;
     } else {
         compile_error!("the `color_scheme` feature is only available on wasm, windows, and macos targets");
     }
 }
+
+#[cfg(feature = "accent_color")]
+pub use crate::utils::color::*;
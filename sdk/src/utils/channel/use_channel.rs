@@ -1,5 +1,6 @@
 use async_broadcast::{broadcast, InactiveReceiver, Receiver, SendError, Sender, TrySendError};
 use dioxus::prelude::*;
+use std::collections::VecDeque;
 use uuid::Uuid;
 
 /// Send and listen for messages between multiple components.
@@ -8,6 +9,8 @@ pub struct UseChannel<MessageType: Clone + 'static> {
     id: Uuid,
     sender: Signal<Sender<MessageType>>,
     inactive_receiver: Signal<InactiveReceiver<MessageType>>,
+    history: Signal<VecDeque<MessageType>>,
+    replay: usize,
 }
 
 impl<T: Clone> PartialEq for UseChannel<T> {
@@ -18,13 +21,48 @@ impl<T: Clone> PartialEq for UseChannel<T> {
 
 impl<MessageType: Clone + 'static> UseChannel<MessageType> {
     /// Tries to send a message to all listeners of the channel.
+    ///
+    /// If the channel keeps a replay history, the message is kept once it is delivered, or right away if nobody is listening yet.
     pub fn try_send(&self, msg: impl Into<MessageType>) -> Result<(), TrySendError<MessageType>> {
-        self.sender.peek().try_broadcast(msg.into()).map(|_| ())
+        let msg = msg.into();
+        // Without listeners, the message is only kept for the ones that subscribe later.
+        if self.replay > 0 && self.sender.peek().receiver_count() == 0 {
+            self.record(msg);
+            return Ok(());
+        }
+        // Only keep messages that were delivered, so a retry after an error isn't replayed twice.
+        let kept = (self.replay > 0).then(|| msg.clone());
+        self.sender.peek().try_broadcast(msg)?;
+        if let Some(msg) = kept {
+            self.record(msg);
+        }
+        Ok(())
     }
 
     /// Sends a message to all listeners of the channel.
+    ///
+    /// If the channel keeps a replay history, the message is kept even if nobody is listening yet.
     pub async fn send(&self, msg: impl Into<MessageType>) -> Result<(), SendError<MessageType>> {
-        self.sender.peek().broadcast(msg.into()).await.map(|_| ())
+        let msg = msg.into();
+        // Without listeners, broadcasting would wait until one subscribes, which then gets the message twice.
+        if self.replay > 0 && self.sender.peek().receiver_count() == 0 {
+            self.record(msg);
+            return Ok(());
+        }
+        let kept = (self.replay > 0).then(|| msg.clone());
+        let sender = self.sender.peek().clone();
+        sender.broadcast(msg).await?;
+        if let Some(msg) = kept {
+            self.record(msg);
+        }
+        Ok(())
+    }
+
+    /// Get the last messages sent through the channel, oldest first.
+    ///
+    /// This is always empty unless the channel was created with [`use_channel_with_replay`].
+    pub fn replay(&self) -> Vec<MessageType> {
+        self.history.peek().iter().cloned().collect()
     }
 
     /// Create a receiver for the channel.
@@ -32,10 +70,31 @@ impl<MessageType: Clone + 'static> UseChannel<MessageType> {
     pub fn receiver(&mut self) -> Receiver<MessageType> {
         self.inactive_receiver.peek().clone().activate()
     }
+
+    /// Add a message to the replay history.
+    fn record(&self, msg: MessageType) {
+        let mut history_signal = self.history;
+        let mut history = history_signal.write();
+        if history.len() == self.replay {
+            history.pop_front();
+        }
+        history.push_back(msg);
+    }
 }
 
 /// Send and listen for messages between multiple components.
 pub fn use_channel<MessageType: Clone + 'static>(size: usize) -> UseChannel<MessageType> {
+    use_channel_with_replay(size, 0)
+}
+
+/// Send and listen for messages between multiple components, retaining the last `replay` messages.
+///
+/// Listeners created with [`super::use_listen_channel()`] will receive the retained messages
+/// when they subscribe, so messages sent before a listener was mounted are not lost.
+pub fn use_channel_with_replay<MessageType: Clone + 'static>(
+    size: usize,
+    replay: usize,
+) -> UseChannel<MessageType> {
     use_hook(|| {
         let id = Uuid::new_v4();
         let (sender, receiver) = broadcast::<MessageType>(size);
@@ -43,6 +102,54 @@ pub fn use_channel<MessageType: Clone + 'static>(size: usize) -> UseChannel<Mess
             id,
             sender: Signal::new(sender),
             inactive_receiver: Signal::new(receiver.deactivate()),
+            history: Signal::new(VecDeque::with_capacity(replay)),
+            replay,
         }
     })
 }
+
+#[test]
+fn test_replay_messages_sent_before_subscribing() {
+    fn app() -> Element {
+        let mut channel = use_channel_with_replay::<i32>(4, 2);
+        use_hook(|| {
+            // Nobody is listening yet, so the messages are only kept for the replay.
+            for message in 1..=3 {
+                channel.try_send(message).unwrap();
+            }
+            assert_eq!(channel.replay(), vec![2, 3]);
+
+            // Messages sent once someone listens are delivered and kept.
+            let mut receiver = channel.receiver();
+            channel.try_send(4).unwrap();
+            assert_eq!(receiver.try_recv().ok(), Some(4));
+            assert_eq!(channel.replay(), vec![3, 4]);
+        });
+        rsx! {}
+    }
+
+    VirtualDom::new(app).rebuild_in_place();
+}
+
+#[test]
+fn test_replay_skips_messages_that_were_not_delivered() {
+    fn app() -> Element {
+        let mut channel = use_channel_with_replay::<i32>(1, 4);
+        use_hook(|| {
+            let mut receiver = channel.receiver();
+            channel.try_send(1).unwrap();
+
+            // The channel is full, so the message isn't delivered or kept.
+            assert!(matches!(channel.try_send(2), Err(TrySendError::Full(2))));
+            assert_eq!(channel.replay(), vec![1]);
+
+            // Retrying once there is room keeps it only once.
+            assert_eq!(receiver.try_recv().ok(), Some(1));
+            channel.try_send(2).unwrap();
+            assert_eq!(channel.replay(), vec![1, 2]);
+        });
+        rsx! {}
+    }
+
+    VirtualDom::new(app).rebuild_in_place();
+}
@@ -8,6 +8,8 @@ use super::UseChannel;
 pub type UseListenChannelError = RecvError;
 
 /// Create a messages listener for the given channel.
+///
+/// If the channel retains a replay history, the retained messages are delivered first.
 pub fn use_listen_channel<MessageType: Clone + 'static, Handler>(
     channel: &UseChannel<MessageType>,
     action: impl Fn(Result<MessageType, UseListenChannelError>) -> Handler + 'static,
@@ -19,6 +21,9 @@ pub fn use_listen_channel<MessageType: Clone + 'static, Handler>(
         to_owned![action];
         spawn(async move {
             let mut receiver = channel.receiver();
+            for message in channel.replay() {
+                action(Ok(message)).await;
+            }
             loop {
                 let message = receiver.recv().await;
                 let message_err = message.clone().err();
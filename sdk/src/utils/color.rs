@@ -0,0 +1,276 @@
+//! A color type with conversions between the formats apps and platforms use.
+
+use std::{error::Error, fmt::Display, str::FromStr};
+
+/// A color in the sRGB color space, with an alpha channel.
+///
+/// Colors can be parsed from CSS hex colors like `#0078d4` and from `rgb()` and `rgba()` functions, and are
+/// displayed as hex colors, so they can be used in styles directly.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus_sdk::utils::color::Color;
+///
+/// let accent: Color = "#005a9e".parse().unwrap();
+/// let hover = accent.lighten(0.1);
+/// let text = accent.contrasting_text();
+/// assert_eq!(text, Color::WHITE);
+/// println!("background: {hover}; color: {text};");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    /// The opacity, from `0` (transparent) to `255` (opaque).
+    pub a: u8,
+}
+
+impl Color {
+    pub const BLACK: Self = Self::rgb(0, 0, 0);
+    pub const WHITE: Self = Self::rgb(255, 255, 255);
+
+    /// Create an opaque color from its red, green, and blue channels.
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b, a: 255 }
+    }
+
+    /// Create a color from its red, green, blue, and alpha channels.
+    pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// Create an opaque color from its hue in degrees, and its saturation and lightness from `0.0` to `1.0`.
+    pub fn from_hsl(hue: f64, saturation: f64, lightness: f64) -> Self {
+        let hue = hue.rem_euclid(360.0) / 60.0;
+        let saturation = saturation.clamp(0.0, 1.0);
+        let lightness = lightness.clamp(0.0, 1.0);
+
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+        let (r, g, b) = match hue as u8 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = lightness - chroma / 2.0;
+        let channel = |value: f64| ((value + m) * 255.0).round() as u8;
+        Self::rgb(channel(r), channel(g), channel(b))
+    }
+
+    /// Get the hue in degrees, and the saturation and lightness from `0.0` to `1.0`.
+    pub fn to_hsl(&self) -> (f64, f64, f64) {
+        let [r, g, b] = [self.r, self.g, self.b].map(|channel| channel as f64 / 255.0);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let chroma = max - min;
+        let lightness = (max + min) / 2.0;
+        if chroma == 0.0 {
+            return (0.0, 0.0, lightness);
+        }
+
+        let saturation = chroma / (1.0 - (2.0 * lightness - 1.0).abs());
+        let hue = if max == r {
+            ((g - b) / chroma).rem_euclid(6.0)
+        } else if max == g {
+            (b - r) / chroma + 2.0
+        } else {
+            (r - g) / chroma + 4.0
+        };
+        (hue * 60.0, saturation, lightness)
+    }
+
+    /// Make the color lighter by an amount of lightness from `0.0` to `1.0`, keeping its hue and opacity.
+    pub fn lighten(&self, amount: f64) -> Self {
+        let (hue, saturation, lightness) = self.to_hsl();
+        Self {
+            a: self.a,
+            ..Self::from_hsl(hue, saturation, lightness + amount)
+        }
+    }
+
+    /// Make the color darker by an amount of lightness from `0.0` to `1.0`, keeping its hue and opacity.
+    pub fn darken(&self, amount: f64) -> Self {
+        self.lighten(-amount)
+    }
+
+    /// Change the opacity, from `0.0` (transparent) to `1.0` (opaque).
+    pub fn with_alpha(&self, alpha: f64) -> Self {
+        Self {
+            a: (alpha.clamp(0.0, 1.0) * 255.0).round() as u8,
+            ..*self
+        }
+    }
+
+    /// Mix the color with another. A `weight` of `0.0` gives this color, and `1.0` gives the other.
+    pub fn mix(&self, other: Color, weight: f64) -> Self {
+        let weight = weight.clamp(0.0, 1.0);
+        let channel =
+            |from: u8, to: u8| (from as f64 + (to as f64 - from as f64) * weight).round() as u8;
+        Self {
+            r: channel(self.r, other.r),
+            g: channel(self.g, other.g),
+            b: channel(self.b, other.b),
+            a: channel(self.a, other.a),
+        }
+    }
+
+    /// The relative luminance as defined by WCAG, from `0.0` for black to `1.0` for white.
+    pub fn luminance(&self) -> f64 {
+        let linear = |channel: u8| {
+            let channel = channel as f64 / 255.0;
+            if channel <= 0.04045 {
+                channel / 12.92
+            } else {
+                ((channel + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        0.2126 * linear(self.r) + 0.7152 * linear(self.g) + 0.0722 * linear(self.b)
+    }
+
+    /// The WCAG contrast ratio with another color, from `1.0` to `21.0`.
+    ///
+    /// Text should have a ratio of at least `4.5` with its background to be readable.
+    pub fn contrast_ratio(&self, other: Color) -> f64 {
+        let (lighter, darker) = match self.luminance() > other.luminance() {
+            true => (self.luminance(), other.luminance()),
+            false => (other.luminance(), self.luminance()),
+        };
+        (lighter + 0.05) / (darker + 0.05)
+    }
+
+    /// Black or white, whichever is more readable on top of this color.
+    pub fn contrasting_text(&self) -> Self {
+        match self.contrast_ratio(Self::BLACK) > self.contrast_ratio(Self::WHITE) {
+            true => Self::BLACK,
+            false => Self::WHITE,
+        }
+    }
+
+    /// The color as a CSS hex color, like `#0078d4`. The alpha channel is only included if the color isn't opaque.
+    pub fn to_hex(&self) -> String {
+        match self.a {
+            255 => format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b),
+            a => format!("#{:02x}{:02x}{:02x}{a:02x}", self.r, self.g, self.b),
+        }
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.to_hex())
+    }
+}
+
+impl FromStr for Color {
+    type Err = ColorParseError;
+
+    /// Parse a CSS hex color with 3, 4, 6, or 8 digits, or an `rgb()` or `rgba()` function.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(hex) = s.strip_prefix('#') {
+            return parse_hex(hex).ok_or(ColorParseError);
+        }
+
+        s.strip_prefix("rgba(")
+            .or_else(|| s.strip_prefix("rgb("))
+            .and_then(|s| s.strip_suffix(')'))
+            .and_then(parse_rgb)
+            .ok_or(ColorParseError)
+    }
+}
+
+/// Parse the arguments of an `rgb()` or `rgba()` function.
+fn parse_rgb(arguments: &str) -> Option<Color> {
+    // Both the legacy `rgb(1, 2, 3)` and the modern `rgb(1 2 3 / 0.5)` syntax are accepted.
+    let values: Vec<&str> = arguments
+        .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|value| !value.is_empty())
+        .collect();
+    let channel = |value: &str| -> Option<u8> {
+        Some(value.parse::<f64>().ok()?.round().clamp(0.0, 255.0) as u8)
+    };
+    let alpha = |value: &str| -> Option<u8> {
+        let alpha = match value.strip_suffix('%') {
+            Some(percent) => percent.parse::<f64>().ok()? / 100.0,
+            None => value.parse::<f64>().ok()?,
+        };
+        Some((alpha.clamp(0.0, 1.0) * 255.0).round() as u8)
+    };
+    match values[..] {
+        [r, g, b] => Some(Color::rgb(channel(r)?, channel(g)?, channel(b)?)),
+        [r, g, b, a] => Some(Color::rgba(
+            channel(r)?,
+            channel(g)?,
+            channel(b)?,
+            alpha(a)?,
+        )),
+        _ => None,
+    }
+}
+
+/// Parse the digits of a hex color.
+fn parse_hex(hex: &str) -> Option<Color> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok().map(|d| d * 17);
+    let pair = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    match hex.len() {
+        3 => Some(Color::rgb(digit(0)?, digit(1)?, digit(2)?)),
+        4 => Some(Color::rgba(digit(0)?, digit(1)?, digit(2)?, digit(3)?)),
+        6 => Some(Color::rgb(pair(0)?, pair(2)?, pair(4)?)),
+        8 => Some(Color::rgba(pair(0)?, pair(2)?, pair(4)?, pair(6)?)),
+        _ => None,
+    }
+}
+
+/// The error returned when a string isn't a color [`Color`] can parse.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorParseError;
+
+impl Error for ColorParseError {}
+impl Display for ColorParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "expected a hex color or an rgb() color")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_conversions() {
+        let accent = Color::rgb(0, 120, 212);
+        assert_eq!("#0078d4".parse(), Ok(accent));
+        assert_eq!("rgb(0, 120, 212)".parse(), Ok(accent));
+        assert_eq!("rgb(0 120 212 / 50%)".parse(), Ok(accent.with_alpha(0.5)));
+        assert_eq!("#fff".parse(), Ok(Color::WHITE));
+        assert_eq!("#0078d480".parse(), Ok(Color::rgba(0, 120, 212, 128)));
+        assert!("accent".parse::<Color>().is_err());
+        assert!("#12345".parse::<Color>().is_err());
+
+        assert_eq!(accent.to_string(), "#0078d4");
+        assert_eq!(Color::rgba(0, 120, 212, 128).to_hex(), "#0078d480");
+
+        // Converting to HSL and back gives the same color.
+        let (hue, saturation, lightness) = accent.to_hsl();
+        assert_eq!(Color::from_hsl(hue, saturation, lightness), accent);
+        assert_eq!(Color::rgb(255, 0, 0).to_hsl(), (0.0, 1.0, 0.5));
+        assert_eq!(accent.lighten(1.0), Color::WHITE);
+        assert_eq!(accent.darken(1.0), Color::BLACK);
+
+        assert_eq!(
+            Color::BLACK.mix(Color::WHITE, 0.5),
+            Color::rgb(128, 128, 128)
+        );
+        assert!((Color::BLACK.contrast_ratio(Color::WHITE) - 21.0).abs() < 1e-9);
+        assert_eq!(Color::rgb(0, 90, 158).contrasting_text(), Color::WHITE);
+        assert_eq!(Color::rgb(255, 200, 0).contrasting_text(), Color::BLACK);
+    }
+}
@@ -0,0 +1,70 @@
+use futures::{Sink, Stream};
+use std::{fmt, future::Future, pin::Pin};
+
+/// Represents errors with a connection.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConnectionError {
+    /// The connection could not be opened.
+    Connect(String),
+    /// The connection failed after it was opened.
+    Closed(String),
+    /// The connection can only receive messages.
+    ReceiveOnly,
+    /// A message could not be serialized or deserialized.
+    Serde(String),
+}
+
+impl std::error::Error for ConnectionError {}
+impl fmt::Display for ConnectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConnectionError::Connect(e) => write!(f, "failed to connect: {}", e),
+            ConnectionError::Closed(e) => write!(f, "the connection was closed: {}", e),
+            ConnectionError::ReceiveOnly => write!(f, "the connection can only receive messages"),
+            ConnectionError::Serde(e) => write!(f, "failed to encode or decode a message: {}", e),
+        }
+    }
+}
+
+/// The request used to open a connection. Middleware can modify it before every connection attempt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectRequest {
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+}
+
+impl ConnectRequest {
+    /// Creates a request for the given url without any headers.
+    pub fn new(url: impl ToString) -> Self {
+        Self {
+            url: url.to_string(),
+            headers: Vec::new(),
+        }
+    }
+
+    /// Adds a query parameter to the url.
+    pub fn query(&mut self, name: &str, value: &str) {
+        let separator = match self.url.contains('?') {
+            true => '&',
+            false => '?',
+        };
+        self.url = format!("{}{}{}={}", self.url, separator, name, value);
+    }
+}
+
+/// Sends messages over an open connection.
+pub type MessageSink = Pin<Box<dyn Sink<String, Error = ConnectionError>>>;
+
+/// Receives messages from an open connection. The stream ends when the connection is closed.
+pub type MessageStream = Pin<Box<dyn Stream<Item = Result<String, ConnectionError>>>>;
+
+/// A transport that [`use_connection`](super::use_connection) can keep open.
+///
+/// See [`WebSocket`](super::WebSocket) and [`EventSource`](super::EventSource) for the built-in transports.
+pub trait Connection: 'static {
+    /// Open a new connection.
+    fn open(
+        &self,
+        request: ConnectRequest,
+    ) -> impl Future<Output = Result<(MessageSink, MessageStream), ConnectionError>>;
+}
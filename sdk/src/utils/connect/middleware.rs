@@ -0,0 +1,114 @@
+use std::{rc::Rc, time::Duration};
+
+use super::ConnectRequest;
+
+/// Hooks into every connection made by [`use_connection`](super::use_connection).
+///
+/// All methods have default implementations that do nothing, so middleware only implements what it needs.
+pub trait Middleware: 'static {
+    /// Modify the request before every connection attempt.
+    fn on_connect(&self, _request: &mut ConnectRequest) {}
+
+    /// Messages to send as soon as the connection opens, like an authentication handshake.
+    fn on_open(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// A message to send periodically while the connection is open, and how often to send it.
+    fn heartbeat(&self) -> Option<(Duration, String)> {
+        None
+    }
+
+    /// Inspect an incoming message. Return `None` to stop it from reaching subscribers.
+    fn on_message(&self, message: String) -> Option<String> {
+        Some(message)
+    }
+}
+
+/// Where [`AuthToken`] puts the token.
+#[derive(Debug, Clone, PartialEq)]
+enum TokenPlacement {
+    Header,
+    Query(String),
+}
+
+/// Adds an authentication token to every connection attempt.
+///
+/// The token is fetched again for every attempt, so refreshed tokens are picked up when reconnecting.
+#[derive(Clone)]
+pub struct AuthToken {
+    token: Rc<dyn Fn() -> Option<String>>,
+    placement: TokenPlacement,
+}
+
+impl AuthToken {
+    /// Send the token in an `Authorization: Bearer` header.
+    ///
+    /// Browsers don't allow setting headers on WebSockets or event sources, so use [`AuthToken::query`] on the web.
+    pub fn bearer(token: impl Fn() -> Option<String> + 'static) -> Self {
+        Self {
+            token: Rc::new(token),
+            placement: TokenPlacement::Header,
+        }
+    }
+
+    /// Send the token in a query parameter with the given name.
+    pub fn query(name: impl ToString, token: impl Fn() -> Option<String> + 'static) -> Self {
+        Self {
+            token: Rc::new(token),
+            placement: TokenPlacement::Query(name.to_string()),
+        }
+    }
+}
+
+impl Middleware for AuthToken {
+    fn on_connect(&self, request: &mut ConnectRequest) {
+        let Some(token) = (self.token)() else {
+            return;
+        };
+        match &self.placement {
+            TokenPlacement::Header => request
+                .headers
+                .push(("Authorization".to_string(), format!("Bearer {}", token))),
+            TokenPlacement::Query(name) => request.query(name, &token),
+        }
+    }
+}
+
+/// Sends a ping message periodically to keep the connection alive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Heartbeat {
+    interval: Duration,
+    ping: String,
+    pong: Option<String>,
+}
+
+impl Heartbeat {
+    /// Send `ping` every `interval` while the connection is open.
+    pub fn new(interval: Duration, ping: impl ToString) -> Self {
+        Self {
+            interval,
+            ping: ping.to_string(),
+            pong: None,
+        }
+    }
+
+    /// Drop replies equal to `pong` so subscribers don't receive them.
+    pub fn pong(mut self, pong: impl ToString) -> Self {
+        self.pong = Some(pong.to_string());
+        self
+    }
+}
+
+impl Middleware for Heartbeat {
+    fn heartbeat(&self) -> Option<(Duration, String)> {
+        Some((self.interval, self.ping.clone()))
+    }
+
+    fn on_message(&self, message: String) -> Option<String> {
+        match &self.pong {
+            Some(pong) if *pong == message => None,
+            _ => Some(message),
+        }
+    }
+}
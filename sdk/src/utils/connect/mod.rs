@@ -0,0 +1,16 @@
+//! Reconnecting WebSocket and server-sent event clients.
+
+mod connection;
+pub use connection::*;
+
+mod middleware;
+pub use middleware::*;
+
+mod sse;
+pub use sse::*;
+
+mod use_connection;
+pub use use_connection::*;
+
+mod websocket;
+pub use websocket::*;
@@ -0,0 +1,221 @@
+use super::{ConnectRequest, Connection, ConnectionError, MessageSink, MessageStream};
+
+/// A server-sent events transport. It can only receive messages.
+///
+/// Each event's data is delivered as one message.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct EventSource;
+
+/// A sink for connections that can't send messages.
+fn receive_only_sink() -> MessageSink {
+    Box::pin(futures::sink::unfold(
+        (),
+        |_, _message: String| async move { Err::<(), _>(ConnectionError::ReceiveOnly) },
+    ))
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl Connection for EventSource {
+    async fn open(
+        &self,
+        request: ConnectRequest,
+    ) -> Result<(MessageSink, MessageStream), ConnectionError> {
+        let mut builder = reqwest::Client::new()
+            .get(&request.url)
+            .header(reqwest::header::ACCEPT, "text/event-stream");
+        for (name, value) in &request.headers {
+            builder = builder.header(name, value);
+        }
+
+        let response = builder
+            .send()
+            .await
+            .map_err(|e| ConnectionError::Connect(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(ConnectionError::Connect(format!(
+                "the server responded with {}",
+                response.status()
+            )));
+        }
+
+        let stream = futures::stream::unfold(
+            (response, EventParser::default()),
+            |(mut response, mut parser)| async move {
+                if parser.closed {
+                    return None;
+                }
+                loop {
+                    if let Some(data) = parser.next_event() {
+                        return Some((Ok(data), (response, parser)));
+                    }
+                    match response.chunk().await {
+                        Ok(Some(chunk)) => parser.push(&chunk),
+                        Ok(None) => return None,
+                        Err(e) => {
+                            // The stream ends after the error is reported.
+                            parser.closed = true;
+                            let error = ConnectionError::Closed(e.to_string());
+                            return Some((Err(error), (response, parser)));
+                        }
+                    }
+                }
+            },
+        );
+
+        let stream: MessageStream = Box::pin(stream);
+        Ok((receive_only_sink(), stream))
+    }
+}
+
+/// Splits a stream of text into server-sent events.
+#[cfg(not(target_family = "wasm"))]
+#[derive(Default)]
+struct EventParser {
+    /// The bytes after the last complete line, which may end in the middle of a character.
+    partial: Vec<u8>,
+    /// Complete lines that haven't been split into events yet.
+    buffer: String,
+    closed: bool,
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl EventParser {
+    fn push(&mut self, chunk: &[u8]) {
+        self.partial.extend_from_slice(chunk);
+        // A newline is never part of a multi-byte character, so the complete lines can be decoded on their own.
+        let Some(end) = self.partial.iter().rposition(|&byte| byte == b'\n') else {
+            return;
+        };
+        let lines: Vec<u8> = self.partial.drain(..=end).collect();
+        self.buffer
+            .push_str(&String::from_utf8_lossy(&lines).replace("\r\n", "\n"));
+    }
+
+    /// Takes the data of the next complete event out of the buffer.
+    fn next_event(&mut self) -> Option<String> {
+        loop {
+            let end = self.buffer.find("\n\n")?;
+            let block: String = self.buffer.drain(..end + 2).collect();
+            let data = block
+                .lines()
+                .filter_map(|line| line.strip_prefix("data:"))
+                .map(|data| data.strip_prefix(' ').unwrap_or(data))
+                .collect::<Vec<_>>();
+            // Blocks without data, like comments used as keep-alives, aren't events.
+            if !data.is_empty() {
+                return Some(data.join("\n"));
+            }
+        }
+    }
+}
+
+#[cfg(target_family = "wasm")]
+impl Connection for EventSource {
+    async fn open(
+        &self,
+        request: ConnectRequest,
+    ) -> Result<(MessageSink, MessageStream), ConnectionError> {
+        use futures::{
+            channel::{mpsc, oneshot},
+            StreamExt,
+        };
+        use std::{cell::RefCell, rc::Rc};
+        use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+
+        if !request.headers.is_empty() {
+            tracing::warn!("Browsers don't support headers on event sources, so they were ignored");
+        }
+
+        let source = web_sys::EventSource::new(&request.url)
+            .map_err(|e| ConnectionError::Connect(format!("{:?}", e)))?;
+        let (tx, rx) = mpsc::unbounded::<Result<String, ConnectionError>>();
+        let (open_tx, open_rx) = oneshot::channel::<Result<(), ConnectionError>>();
+        let open_tx = Rc::new(RefCell::new(Some(open_tx)));
+
+        let onopen = {
+            let open_tx = open_tx.clone();
+            Closure::<dyn FnMut(JsValue)>::new(move |_: JsValue| {
+                if let Some(open_tx) = open_tx.borrow_mut().take() {
+                    open_tx.send(Ok(())).ok();
+                }
+            })
+        };
+        let onerror = {
+            let source = source.clone();
+            let tx = tx.clone();
+            Closure::<dyn FnMut(JsValue)>::new(move |_: JsValue| {
+                // The browser retries on its own, so only give up once it closed the source.
+                if source.ready_state() != web_sys::EventSource::CLOSED {
+                    return;
+                }
+                match open_tx.borrow_mut().take() {
+                    Some(open_tx) => {
+                        open_tx
+                            .send(Err(ConnectionError::Connect(
+                                "the event source failed to open".to_string(),
+                            )))
+                            .ok();
+                    }
+                    None => tx.close_channel(),
+                }
+            })
+        };
+        let onmessage = Closure::<dyn FnMut(web_sys::MessageEvent)>::new(
+            move |event: web_sys::MessageEvent| {
+                if let Some(data) = event.data().as_string() {
+                    tx.unbounded_send(Ok(data)).ok();
+                }
+            },
+        );
+
+        source.set_onopen(Some(onopen.as_ref().unchecked_ref()));
+        source.set_onerror(Some(onerror.as_ref().unchecked_ref()));
+        source.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+
+        let guard = SourceGuard {
+            source,
+            _callbacks: [onopen, onerror],
+            _onmessage: onmessage,
+        };
+
+        open_rx
+            .await
+            .map_err(|_| ConnectionError::Connect("the event source was dropped".to_string()))??;
+
+        // Keep the source open as long as the stream is alive.
+        let stream = rx.map(move |message| {
+            let _ = &guard;
+            message
+        });
+
+        let stream: MessageStream = Box::pin(stream);
+        Ok((receive_only_sink(), stream))
+    }
+}
+
+/// Closes the event source once the stream is dropped.
+#[cfg(target_family = "wasm")]
+struct SourceGuard {
+    source: web_sys::EventSource,
+    _callbacks: [wasm_bindgen::closure::Closure<dyn FnMut(wasm_bindgen::JsValue)>; 2],
+    _onmessage: wasm_bindgen::closure::Closure<dyn FnMut(web_sys::MessageEvent)>,
+}
+
+#[cfg(target_family = "wasm")]
+impl Drop for SourceGuard {
+    fn drop(&mut self) {
+        self.source.close();
+    }
+}
+
+#[cfg(all(test, not(target_family = "wasm")))]
+#[test]
+fn test_event_split_inside_character() {
+    let mut parser = EventParser::default();
+    let event = "data: caf\u{e9}\r\n\r\n".as_bytes();
+    // The chunk boundary falls between the two bytes of "é".
+    parser.push(&event[..10]);
+    assert_eq!(parser.next_event(), None);
+    parser.push(&event[10..]);
+    assert_eq!(parser.next_event().as_deref(), Some("caf\u{e9}"));
+}
@@ -0,0 +1,310 @@
+use dioxus::prelude::*;
+use futures::{
+    channel::mpsc::{self, UnboundedReceiver, UnboundedSender},
+    future,
+    stream::{self, LocalBoxStream},
+    SinkExt, StreamExt,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{rc::Rc, time::Duration};
+
+use super::{ConnectRequest, Connection, ConnectionError, Middleware};
+use crate::utils::timing::clock;
+
+/// The state of a connection managed by [`use_connection`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConnectionState {
+    /// The connection is being opened.
+    Connecting,
+    /// The connection is open.
+    Open,
+    /// The connection failed and will be retried after a delay.
+    Backoff {
+        /// The number of failed attempts in a row.
+        attempt: u32,
+        /// How long until the next attempt.
+        retry_in: Duration,
+    },
+    /// The connection was closed with [`UseConnection::close`].
+    Closed,
+}
+
+/// How long to wait before reconnecting. The delay doubles after every failed attempt.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Backoff {
+    /// The delay after the first failed attempt.
+    pub initial: Duration,
+    /// The longest delay between attempts.
+    pub max: Duration,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_millis(500),
+            max: Duration::from_secs(30),
+        }
+    }
+}
+
+impl Backoff {
+    /// Get the delay before retrying after the given number of failed attempts.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial.saturating_mul(factor).min(self.max)
+    }
+}
+
+/// Describes the connection [`use_connection`] keeps open.
+pub struct ConnectionOptions<C: Connection> {
+    connection: C,
+    url: String,
+    middleware: Vec<Rc<dyn Middleware>>,
+    backoff: Backoff,
+}
+
+impl<C: Connection> ConnectionOptions<C> {
+    /// Connect to the url with the given transport.
+    pub fn new(connection: C, url: impl ToString) -> Self {
+        Self {
+            connection,
+            url: url.to_string(),
+            middleware: Vec::new(),
+            backoff: Backoff::default(),
+        }
+    }
+
+    /// Add middleware. Middleware runs in the order it was added.
+    pub fn middleware(mut self, middleware: impl Middleware) -> Self {
+        self.middleware.push(Rc::new(middleware));
+        self
+    }
+
+    /// Set how long to wait before reconnecting.
+    pub fn backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+}
+
+/// The interface for a connection managed by [`use_connection`].
+#[derive(Clone, Copy, PartialEq)]
+pub struct UseConnection {
+    state: Signal<ConnectionState>,
+    outgoing: Signal<UnboundedSender<String>>,
+    listeners: Signal<Vec<UnboundedSender<Rc<String>>>>,
+    task: Signal<Option<Task>>,
+}
+
+impl UseConnection {
+    /// Get the state of the connection.
+    pub fn state(&self) -> ReadOnlySignal<ConnectionState> {
+        ReadOnlySignal::new(self.state)
+    }
+
+    /// Send a message. Messages sent while the connection is down are sent once it reconnects.
+    pub fn send(&self, message: impl ToString) -> Result<(), ConnectionError> {
+        self.outgoing
+            .peek()
+            .unbounded_send(message.to_string())
+            .map_err(|_| ConnectionError::Closed("the connection was closed".to_string()))
+    }
+
+    /// Serialize a value as JSON and send it.
+    pub fn send_json<T: Serialize>(&self, value: &T) -> Result<(), ConnectionError> {
+        let message =
+            serde_json::to_string(value).map_err(|e| ConnectionError::Serde(e.to_string()))?;
+        self.send(message)
+    }
+
+    /// Close the connection and stop reconnecting.
+    pub fn close(&mut self) {
+        if let Some(task) = self.task.write().take() {
+            task.cancel();
+        }
+        self.outgoing.peek().close_channel();
+        self.state.set(ConnectionState::Closed);
+    }
+
+    /// Deliver an incoming message to every subscriber.
+    fn dispatch(&mut self, message: String) {
+        let message = Rc::new(message);
+        self.listeners
+            .write()
+            .retain(|listener| listener.unbounded_send(message.clone()).is_ok());
+    }
+}
+
+/// A hook for keeping a WebSocket or server-sent event connection open.
+///
+/// The connection is opened immediately and reopened with an exponential backoff whenever it fails.
+/// Use [`use_subscription`] to receive typed messages. The connection is closed when the component is dropped.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::connect::{
+///     use_connection, use_subscription, AuthToken, ConnectionOptions, Heartbeat, WebSocket,
+/// };
+/// use serde::Deserialize;
+/// use std::time::Duration;
+///
+/// #[derive(Deserialize)]
+/// struct Price {
+///     symbol: String,
+///     price: f64,
+/// }
+///
+/// fn App() -> Element {
+///     let connection = use_connection(|| {
+///         ConnectionOptions::new(WebSocket, "wss://example.com/prices")
+///             .middleware(AuthToken::query("token", || Some("secret".to_string())))
+///             .middleware(Heartbeat::new(Duration::from_secs(30), "ping").pong("pong"))
+///     });
+///     let price = use_subscription::<Price>(connection);
+///
+///     rsx! {
+///         p { "{connection.state():?}" }
+///         if let Some(price) = &*price.read() {
+///             p { "{price.symbol}: {price.price}" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_connection<C: Connection>(init: impl FnOnce() -> ConnectionOptions<C>) -> UseConnection {
+    use_hook(|| {
+        let (sender, receiver) = mpsc::unbounded();
+        let mut connection = UseConnection {
+            state: Signal::new(ConnectionState::Connecting),
+            outgoing: Signal::new(sender),
+            listeners: Signal::new(Vec::new()),
+            task: Signal::new(None),
+        };
+
+        let task = spawn(run(init(), connection, receiver));
+        connection.task.set(Some(task));
+        connection
+    })
+}
+
+/// A hook for receiving the latest message from a connection, deserialized from JSON.
+///
+/// Messages that can't be deserialized as `T` are skipped, so several subscriptions with different types
+/// can share one connection.
+pub fn use_subscription<T: DeserializeOwned + 'static>(
+    mut connection: UseConnection,
+) -> ReadOnlySignal<Option<T>> {
+    let value = use_hook(|| {
+        let (sender, mut receiver) = mpsc::unbounded::<Rc<String>>();
+        connection.listeners.write().push(sender);
+
+        let mut value = Signal::new(None);
+        spawn(async move {
+            while let Some(message) = receiver.next().await {
+                match serde_json::from_str::<T>(&message) {
+                    Ok(decoded) => value.set(Some(decoded)),
+                    Err(e) => tracing::trace!("Skipping message that doesn't match: {}", e),
+                }
+            }
+        });
+        value
+    });
+
+    ReadOnlySignal::new(value)
+}
+
+enum Event {
+    Send(String),
+    Received(Result<String, ConnectionError>),
+    Ended,
+}
+
+/// Keep the connection open until the task is cancelled.
+async fn run<C: Connection>(
+    options: ConnectionOptions<C>,
+    mut connection: UseConnection,
+    mut outgoing: UnboundedReceiver<String>,
+) {
+    let mut attempt = 0;
+    loop {
+        connection.state.set(ConnectionState::Connecting);
+        let mut request = ConnectRequest::new(&options.url);
+        for middleware in &options.middleware {
+            middleware.on_connect(&mut request);
+        }
+
+        match options.connection.open(request).await {
+            Ok((mut sink, incoming)) => {
+                attempt = 0;
+                connection.state.set(ConnectionState::Open);
+
+                let greetings = options.middleware.iter().flat_map(|m| m.on_open());
+                let mut greetings =
+                    stream::iter(greetings.map(Ok::<_, ConnectionError>).collect::<Vec<_>>());
+                if let Err(e) = sink.send_all(&mut greetings).await {
+                    tracing::warn!("Failed to send the opening messages: {}", e);
+                }
+
+                let heartbeats = stream::select_all(
+                    options
+                        .middleware
+                        .iter()
+                        .filter_map(|m| m.heartbeat())
+                        .map(heartbeat),
+                );
+                let incoming = incoming
+                    .map(Event::Received)
+                    .chain(stream::once(future::ready(Event::Ended)));
+                let mut events = stream::select(
+                    (&mut outgoing).map(Event::Send),
+                    stream::select(incoming, heartbeats),
+                );
+
+                while let Some(event) = events.next().await {
+                    match event {
+                        Event::Send(message) => {
+                            if let Err(e) = sink.send(message).await {
+                                tracing::warn!("Failed to send a message: {}", e);
+                                if e != ConnectionError::ReceiveOnly {
+                                    break;
+                                }
+                            }
+                        }
+                        Event::Received(Ok(message)) => {
+                            let message = options
+                                .middleware
+                                .iter()
+                                .try_fold(message, |message, m| m.on_message(message));
+                            if let Some(message) = message {
+                                connection.dispatch(message);
+                            }
+                        }
+                        Event::Received(Err(e)) => {
+                            tracing::warn!("The connection failed: {}", e);
+                            break;
+                        }
+                        Event::Ended => break,
+                    }
+                }
+            }
+            Err(e) => tracing::warn!("Failed to connect: {}", e),
+        }
+
+        attempt += 1;
+        let retry_in = options.backoff.delay(attempt);
+        connection
+            .state
+            .set(ConnectionState::Backoff { attempt, retry_in });
+        clock::sleep(retry_in).await;
+    }
+}
+
+/// Emit the heartbeat message every interval.
+fn heartbeat((interval, message): (Duration, String)) -> LocalBoxStream<'static, Event> {
+    stream::unfold(message, move |message| async move {
+        clock::sleep(interval).await;
+        Some((Event::Send(message.clone()), message))
+    })
+    .boxed_local()
+}
@@ -0,0 +1,175 @@
+use super::{ConnectRequest, Connection, ConnectionError, MessageSink, MessageStream};
+
+/// A WebSocket transport that sends and receives text messages.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct WebSocket;
+
+#[cfg(not(target_family = "wasm"))]
+impl Connection for WebSocket {
+    async fn open(
+        &self,
+        request: ConnectRequest,
+    ) -> Result<(MessageSink, MessageStream), ConnectionError> {
+        use futures::{future, SinkExt, StreamExt};
+        use tokio_tungstenite::tungstenite::{
+            client::IntoClientRequest,
+            http::{HeaderName, HeaderValue},
+            Message,
+        };
+
+        let mut ws_request = request
+            .url
+            .as_str()
+            .into_client_request()
+            .map_err(|e| ConnectionError::Connect(e.to_string()))?;
+        for (name, value) in &request.headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| ConnectionError::Connect(e.to_string()))?;
+            let value = HeaderValue::from_str(value)
+                .map_err(|e| ConnectionError::Connect(e.to_string()))?;
+            ws_request.headers_mut().insert(name, value);
+        }
+
+        let (socket, _) = tokio_tungstenite::connect_async(ws_request)
+            .await
+            .map_err(|e| ConnectionError::Connect(e.to_string()))?;
+        let (sink, stream) = socket.split();
+
+        let sink = sink
+            .with(|message: String| {
+                future::ready(Ok::<_, tokio_tungstenite::tungstenite::Error>(
+                    Message::Text(message),
+                ))
+            })
+            .sink_map_err(|e: tokio_tungstenite::tungstenite::Error| {
+                ConnectionError::Closed(e.to_string())
+            });
+        let stream = stream.filter_map(|message| {
+            future::ready(match message {
+                Ok(Message::Text(text)) => Some(Ok(text)),
+                Ok(Message::Binary(bytes)) => String::from_utf8(bytes).ok().map(Ok),
+                // Pings are answered automatically and close frames end the stream.
+                Ok(_) => None,
+                Err(e) => Some(Err(ConnectionError::Closed(e.to_string()))),
+            })
+        });
+
+        let sink: MessageSink = Box::pin(sink);
+        let stream: MessageStream = Box::pin(stream);
+        Ok((sink, stream))
+    }
+}
+
+#[cfg(target_family = "wasm")]
+impl Connection for WebSocket {
+    async fn open(
+        &self,
+        request: ConnectRequest,
+    ) -> Result<(MessageSink, MessageStream), ConnectionError> {
+        use futures::{
+            channel::{mpsc, oneshot},
+            StreamExt,
+        };
+        use std::{cell::RefCell, rc::Rc};
+        use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+
+        if !request.headers.is_empty() {
+            tracing::warn!("Browsers don't support headers on WebSockets, so they were ignored");
+        }
+
+        let socket = web_sys::WebSocket::new(&request.url)
+            .map_err(|e| ConnectionError::Connect(format!("{:?}", e)))?;
+        let (tx, rx) = mpsc::unbounded::<Result<String, ConnectionError>>();
+        let (open_tx, open_rx) = oneshot::channel::<Result<(), ConnectionError>>();
+        let open_tx = Rc::new(RefCell::new(Some(open_tx)));
+
+        let onopen = {
+            let open_tx = open_tx.clone();
+            Closure::<dyn FnMut(JsValue)>::new(move |_: JsValue| {
+                if let Some(open_tx) = open_tx.borrow_mut().take() {
+                    open_tx.send(Ok(())).ok();
+                }
+            })
+        };
+        let onerror = {
+            let tx = tx.clone();
+            Closure::<dyn FnMut(JsValue)>::new(move |_: JsValue| {
+                match open_tx.borrow_mut().take() {
+                    Some(open_tx) => {
+                        open_tx
+                            .send(Err(ConnectionError::Connect(
+                                "the WebSocket failed to open".to_string(),
+                            )))
+                            .ok();
+                    }
+                    None => {
+                        tx.unbounded_send(Err(ConnectionError::Closed(
+                            "the WebSocket failed".to_string(),
+                        )))
+                        .ok();
+                    }
+                }
+            })
+        };
+        let onmessage = {
+            let tx = tx.clone();
+            Closure::<dyn FnMut(web_sys::MessageEvent)>::new(move |event: web_sys::MessageEvent| {
+                if let Some(text) = event.data().as_string() {
+                    tx.unbounded_send(Ok(text)).ok();
+                }
+            })
+        };
+        let onclose = Closure::<dyn FnMut(JsValue)>::new(move |_: JsValue| tx.close_channel());
+
+        socket.set_onopen(Some(onopen.as_ref().unchecked_ref()));
+        socket.set_onerror(Some(onerror.as_ref().unchecked_ref()));
+        socket.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+        socket.set_onclose(Some(onclose.as_ref().unchecked_ref()));
+
+        let guard = Rc::new(SocketGuard {
+            socket: socket.clone(),
+            _callbacks: [onopen, onerror, onclose],
+            _onmessage: onmessage,
+        });
+
+        open_rx
+            .await
+            .map_err(|_| ConnectionError::Connect("the WebSocket was dropped".to_string()))??;
+
+        let sink_guard = guard.clone();
+        let sink = futures::sink::unfold(socket, move |socket, message: String| {
+            // Keep the socket open as long as the sink is alive.
+            let _ = &sink_guard;
+            async move {
+                socket
+                    .send_with_str(&message)
+                    .map_err(|e| ConnectionError::Closed(format!("{:?}", e)))?;
+                Ok(socket)
+            }
+        });
+        // Keep the socket open as long as the stream is alive.
+        let stream = rx.map(move |message| {
+            let _ = &guard;
+            message
+        });
+
+        let sink: MessageSink = Box::pin(sink);
+        let stream: MessageStream = Box::pin(stream);
+        Ok((sink, stream))
+    }
+}
+
+/// Closes the socket once both halves of the connection are dropped.
+#[cfg(target_family = "wasm")]
+struct SocketGuard {
+    socket: web_sys::WebSocket,
+    _callbacks: [wasm_bindgen::closure::Closure<dyn FnMut(wasm_bindgen::JsValue)>; 3],
+    _onmessage: wasm_bindgen::closure::Closure<dyn FnMut(web_sys::MessageEvent)>,
+}
+
+#[cfg(target_family = "wasm")]
+impl Drop for SocketGuard {
+    fn drop(&mut self) {
+        self.socket.close().ok();
+    }
+}
@@ -0,0 +1,4 @@
+//! Watch files and directories for changes.
+
+mod watcher;
+pub use watcher::*;
@@ -0,0 +1,265 @@
+use dioxus::prelude::*;
+use std::{error::Error, fmt::Display, path::PathBuf, time::Duration};
+
+/// How long to wait for more changes before reporting a batch.
+const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Represents an error when watching the file system.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FileWatchError {
+    /// Watching the file system is not supported on this platform.
+    Unsupported,
+    /// The path could not be watched.
+    WatchFailed(String),
+    /// The watched file could not be read.
+    ReadFailed(String),
+    /// The watched file could not be parsed.
+    ParseFailed(String),
+}
+
+impl Error for FileWatchError {}
+impl Display for FileWatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Unsupported => write!(f, "the current platform is not supported"),
+            Self::WatchFailed(e) => write!(f, "failed to watch the path: {e}"),
+            Self::ReadFailed(e) => write!(f, "failed to read the file: {e}"),
+            Self::ParseFailed(e) => write!(f, "failed to parse the file: {e}"),
+        }
+    }
+}
+
+/// The kind of change made to a path.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FileChangeKind {
+    Created,
+    Modified,
+    Removed,
+    Other,
+}
+
+/// A change made to one or more paths.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileChange {
+    pub kind: FileChangeKind,
+    pub paths: Vec<PathBuf>,
+}
+
+/// The interface for a file system watcher.
+///
+/// See [`use_file_watcher`] for more information.
+#[derive(Clone, Copy, PartialEq)]
+pub struct UseFileWatcher {
+    changes: Signal<Vec<FileChange>>,
+    error: Signal<Option<FileWatchError>>,
+}
+
+impl UseFileWatcher {
+    /// Get the most recent batch of changes.
+    pub fn changes(&self) -> ReadOnlySignal<Vec<FileChange>> {
+        ReadOnlySignal::new(self.changes)
+    }
+
+    /// Get the error that stopped the watcher, if any.
+    pub fn error(&self) -> ReadOnlySignal<Option<FileWatchError>> {
+        ReadOnlySignal::new(self.error)
+    }
+}
+
+/// A hook for watching a file or directory for changes.
+///
+/// Changes are debounced, so a burst of changes, like an editor saving a file, is reported as a single batch.
+/// Directories are watched recursively. The watcher stops when the component is dropped.
+///
+/// Supported on desktop. On the web, the error is always [`FileWatchError::Unsupported`].
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::fs_watch::use_file_watcher;
+///
+/// fn App() -> Element {
+///     let watcher = use_file_watcher("./assets");
+///     let changes = watcher.changes();
+///
+///     rsx! {
+///         for change in changes.read().iter() {
+///             p { "{change.kind:?}: {change.paths:?}" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_file_watcher(path: impl Into<PathBuf>) -> UseFileWatcher {
+    use_file_watcher_with_debounce(path, DEFAULT_DEBOUNCE)
+}
+
+/// A hook for watching a file or directory for changes with a custom debounce duration.
+///
+/// See [`use_file_watcher`] for more information.
+pub fn use_file_watcher_with_debounce(
+    path: impl Into<PathBuf>,
+    debounce: Duration,
+) -> UseFileWatcher {
+    use_hook(|| {
+        let path = path.into();
+        let mut watcher = UseFileWatcher {
+            changes: Signal::new(Vec::new()),
+            error: Signal::new(None),
+        };
+
+        spawn(async move {
+            let result = watch(path, true, debounce, move |batch| {
+                watcher.changes.set(batch);
+            })
+            .await;
+            if let Err(e) = result {
+                watcher.error.set(Some(e));
+            }
+        });
+
+        watcher
+    })
+}
+
+/// A hook that keeps a signal in sync with the parsed contents of a file.
+///
+/// The file is read and parsed when the hook is created and again every time it changes.
+/// This is useful for editors or previews that need to react to files changed by other programs.
+///
+/// Supported on desktop. On the web, the value is always [`FileWatchError::Unsupported`].
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::fs_watch::use_watched_file;
+///
+/// fn App() -> Element {
+///     let notes = use_watched_file("notes.md", |contents| Ok::<_, String>(contents.to_string()));
+///
+///     match &*notes.read() {
+///         Ok(notes) => rsx! { pre { "{notes}" } },
+///         Err(e) => rsx! { p { "Failed to load notes: {e}" } },
+///     }
+/// }
+/// ```
+pub fn use_watched_file<T, E>(
+    path: impl Into<PathBuf>,
+    parse: impl Fn(&str) -> Result<T, E> + 'static,
+) -> ReadOnlySignal<Result<T, FileWatchError>>
+where
+    T: 'static,
+    E: Display,
+{
+    let value = use_hook(|| {
+        let path = path.into();
+        let load = move |path: &PathBuf| -> Result<T, FileWatchError> {
+            let contents = std::fs::read_to_string(path)
+                .map_err(|e| FileWatchError::ReadFailed(e.to_string()))?;
+            parse(&contents).map_err(|e| FileWatchError::ParseFailed(e.to_string()))
+        };
+
+        let mut value = Signal::new(match cfg!(target_family = "wasm") {
+            true => Err(FileWatchError::Unsupported),
+            false => load(&path),
+        });
+
+        spawn(async move {
+            // Watch the parent directory, as editors often replace files instead of writing to them.
+            let parent = match path.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+                _ => PathBuf::from("."),
+            };
+            let file_name = path.file_name().map(|name| name.to_os_string());
+            let watched = path.clone();
+            let result = watch(parent, false, DEFAULT_DEBOUNCE, move |batch| {
+                let touched = batch
+                    .iter()
+                    .flat_map(|change| &change.paths)
+                    .any(|changed| {
+                        changed.file_name().map(|name| name.to_os_string()) == file_name
+                    });
+                if touched {
+                    value.set(load(&watched));
+                }
+            })
+            .await;
+            if let Err(e) = result {
+                value.set(Err(e));
+            }
+        });
+
+        value
+    });
+
+    ReadOnlySignal::new(value)
+}
+
+/// Watch the path until the watcher fails, reporting debounced batches of changes.
+#[cfg(not(target_family = "wasm"))]
+async fn watch(
+    path: PathBuf,
+    recursive: bool,
+    debounce: Duration,
+    mut on_batch: impl FnMut(Vec<FileChange>),
+) -> Result<(), FileWatchError> {
+    use futures::StreamExt;
+    use notify::{RecursiveMode, Watcher};
+
+    let (tx, mut rx) = futures::channel::mpsc::unbounded();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let _ = tx.unbounded_send(event);
+    })
+    .map_err(|e| FileWatchError::WatchFailed(e.to_string()))?;
+
+    let mode = match recursive {
+        true => RecursiveMode::Recursive,
+        false => RecursiveMode::NonRecursive,
+    };
+    watcher
+        .watch(&path, mode)
+        .map_err(|e| FileWatchError::WatchFailed(e.to_string()))?;
+
+    let mut batch = Vec::new();
+    while let Some(event) = rx.next().await {
+        batch.extend(to_change(event)?);
+        while let Ok(Some(event)) = tokio::time::timeout(debounce, rx.next()).await {
+            batch.extend(to_change(event)?);
+        }
+        if !batch.is_empty() {
+            on_batch(std::mem::take(&mut batch));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn to_change(event: notify::Result<notify::Event>) -> Result<Option<FileChange>, FileWatchError> {
+    use notify::EventKind;
+
+    let event = event.map_err(|e| FileWatchError::WatchFailed(e.to_string()))?;
+    let kind = match event.kind {
+        // Reading a file is not a change, and reporting it would loop when the file is re-read.
+        EventKind::Access(_) => return Ok(None),
+        EventKind::Create(_) => FileChangeKind::Created,
+        EventKind::Modify(_) => FileChangeKind::Modified,
+        EventKind::Remove(_) => FileChangeKind::Removed,
+        _ => FileChangeKind::Other,
+    };
+    Ok(Some(FileChange {
+        kind,
+        paths: event.paths,
+    }))
+}
+
+#[cfg(target_family = "wasm")]
+async fn watch(
+    _path: PathBuf,
+    _recursive: bool,
+    _debounce: Duration,
+    _on_batch: impl FnMut(Vec<FileChange>),
+) -> Result<(), FileWatchError> {
+    Err(FileWatchError::Unsupported)
+}
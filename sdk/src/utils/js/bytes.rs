@@ -0,0 +1,120 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{
+    de::{self, Deserializer},
+    ser::{SerializeMap, Serializer},
+    Deserialize, Serialize,
+};
+
+/// The key binary data is tagged with while it is sent to or from JavaScript.
+const BYTES_KEY: &str = "__dioxus_bytes";
+
+/// Helpers injected before the called module, which turn tagged bytes into a `Uint8Array` for the called function and
+/// tag the `ArrayBuffer`s and typed arrays it returns.
+pub(super) const SHIM: &str = r#"
+const __toBase64 = (bytes) => {
+    let binary = "";
+    for (let i = 0; i < bytes.length; i += 0x8000) {
+        binary += String.fromCharCode(...bytes.subarray(i, i + 0x8000));
+    }
+    return btoa(binary);
+};
+const __decodeBytes = (value) => {
+    if (Array.isArray(value)) return value.map(__decodeBytes);
+    if (value && typeof value === "object") {
+        if (typeof value.__dioxus_bytes === "string") {
+            return Uint8Array.from(atob(value.__dioxus_bytes), (c) => c.charCodeAt(0));
+        }
+        for (const key in value) value[key] = __decodeBytes(value[key]);
+    }
+    return value;
+};
+const __encodeBytes = (value) => {
+    if (value instanceof ArrayBuffer) return { __dioxus_bytes: __toBase64(new Uint8Array(value)) };
+    if (ArrayBuffer.isView(value)) {
+        return { __dioxus_bytes: __toBase64(new Uint8Array(value.buffer, value.byteOffset, value.byteLength)) };
+    }
+    if (Array.isArray(value)) return value.map(__encodeBytes);
+    if (value && typeof value === "object" && value.constructor === Object) {
+        const encoded = {};
+        for (const key in value) encoded[key] = __encodeBytes(value[key]);
+        return encoded;
+    }
+    return value;
+};
+"#;
+
+/// Binary data passed to or returned from JavaScript.
+///
+/// The script channel only carries JSON, so a `Vec<u8>` argument would arrive as an array of numbers. Wrapping it in
+/// `JsBytes` sends it compactly encoded instead, and the called function receives a `Uint8Array`. `ArrayBuffer`s and
+/// typed arrays the function returns, also inside arrays and objects, can be read back as `JsBytes`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use dioxus_sdk::{call_js_inline, utils::js::JsBytes};
+///
+/// let image = JsBytes(std::fs::read("photo.png")?);
+/// let gray = call_js_inline!(
+///     r#"export async function grayscale(png) { /* ... */ return new Uint8Array(pixels); }"#,
+///     grayscale(image)
+/// )
+/// .await?;
+/// let gray: JsBytes = serde_json::from_value(gray)?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct JsBytes(pub Vec<u8>);
+
+impl From<Vec<u8>> for JsBytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<JsBytes> for Vec<u8> {
+    fn from(bytes: JsBytes) -> Self {
+        bytes.0
+    }
+}
+
+impl Serialize for JsBytes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(BYTES_KEY, &STANDARD.encode(&self.0))?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for JsBytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Encoded {
+            Tagged {
+                #[serde(rename = "__dioxus_bytes")]
+                bytes: String,
+            },
+            // Plain arrays of numbers, like a typed array converted with `Array.from`.
+            Array(Vec<u8>),
+        }
+
+        match Encoded::deserialize(deserializer)? {
+            Encoded::Tagged { bytes } => {
+                STANDARD.decode(bytes).map(Self).map_err(de::Error::custom)
+            }
+            Encoded::Array(bytes) => Ok(Self(bytes)),
+        }
+    }
+}
+
+#[test]
+fn test_bytes_round_trip() {
+    let bytes = JsBytes(vec![0, 1, 254, 255]);
+    let value = serde_json::to_value(&bytes).unwrap();
+    assert_eq!(value, serde_json::json!({ "__dioxus_bytes": "AAH+/w==" }));
+    assert_eq!(serde_json::from_value::<JsBytes>(value).unwrap(), bytes);
+    assert_eq!(
+        serde_json::from_value::<JsBytes>(serde_json::json!([1, 2])).unwrap(),
+        JsBytes(vec![1, 2])
+    );
+}
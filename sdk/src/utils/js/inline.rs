@@ -0,0 +1,115 @@
+use dioxus::prelude::*;
+use serde_json::Value;
+use std::fmt;
+
+use super::bytes::SHIM;
+
+#[doc(hidden)]
+pub use serde_json::to_value as __to_value;
+
+/// Represents errors when calling JavaScript.
+#[derive(Debug)]
+pub enum CallJsError {
+    /// An argument could not be serialized.
+    Serialize(String),
+    /// The script failed to run or the result could not be received.
+    Eval(EvalError),
+}
+
+impl std::error::Error for CallJsError {}
+impl fmt::Display for CallJsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CallJsError::Serialize(e) => write!(f, "failed to serialize argument: {}", e),
+            CallJsError::Eval(e) => write!(f, "failed to call javascript: {:?}", e),
+        }
+    }
+}
+
+/// Call `function` from the inline JavaScript `module` with the given arguments.
+///
+/// You probably want to use [`call_js_inline!`](crate::call_js_inline).
+pub async fn call_js_inline(
+    module: &str,
+    function: &str,
+    args: Vec<Result<Value, serde_json::Error>>,
+) -> Result<Value, CallJsError> {
+    let args = args
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| CallJsError::Serialize(e.to_string()))?;
+
+    let script = inline_script(module, function);
+    let mut eval = eval(&script);
+    eval.send(Value::Array(args)).map_err(CallJsError::Eval)?;
+    eval.recv().await.map_err(CallJsError::Eval)
+}
+
+/// Build the script that imports the inline `module` and calls `function` with the arguments it receives.
+///
+/// The module is imported from a blob URL, so it is parsed as a real ES module instead of being edited into a
+/// function body. The source and the name are embedded as JSON strings, which are valid JavaScript string literals.
+fn inline_script(module: &str, function: &str) -> String {
+    let source = Value::String(module.to_string());
+    let function = Value::String(function.to_string());
+    format!(
+        r#"{SHIM}
+const __url = URL.createObjectURL(new Blob([{source}], {{ type: "text/javascript" }}));
+const __module = await import(__url).finally(() => URL.revokeObjectURL(__url));
+if (typeof __module[{function}] !== "function") {{
+    throw new Error("the inline module doesn't export a function named " + {function});
+}}
+const __args = __decodeBytes(await dioxus.recv());
+dioxus.send(__encodeBytes(await __module[{function}](...__args)));"#
+    )
+}
+
+/// Call a function from an inline JavaScript snippet.
+///
+/// The snippet is embedded in the binary, so small helpers don't need a separate asset file. It is loaded as an
+/// ES module, so it can use any `export` form, including `export default` (called as `default`) and export lists.
+/// It has no URL of its own, so it can only import other modules by their absolute URL.
+/// Arguments are serialized with `serde` and the function's return value (which may be a promise)
+/// is sent back as a [`serde_json::Value`]. Use [`JsBytes`](crate::utils::js::JsBytes) to pass binary data.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::call_js_inline;
+///
+/// fn App() -> Element {
+///     rsx! {
+///         button {
+///             onclick: move |_| async move {
+///                 let sum = call_js_inline!(
+///                     r#"export function add(a, b) { return a + b; }"#,
+///                     add(1, 2)
+///                 )
+///                 .await;
+///                 println!("{sum:?}");
+///             },
+///             "Add"
+///         }
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! call_js_inline {
+    ($module:literal, $function:ident ( $($arg:expr),* $(,)? )) => {
+        $crate::utils::js::call_js_inline(
+            $module,
+            stringify!($function),
+            vec![$($crate::utils::js::__to_value(&$arg)),*],
+        )
+    };
+}
+
+#[test]
+fn test_inline_script_keeps_module_intact() {
+    let module = r#"export default function greet(name) { return "export " + name; }"#;
+    let script = inline_script(module, "default");
+    // The module is passed to the import as a string, without editing its exports.
+    assert!(script.contains(&Value::String(module.to_string()).to_string()));
+    assert!(script.contains(r#"__module["default"](...__args)"#));
+}
@@ -0,0 +1,6 @@
+//! Call JavaScript from Rust.
+
+mod bytes;
+mod inline;
+pub use bytes::JsBytes;
+pub use inline::*;
@@ -7,7 +7,16 @@ cfg_if::cfg_if! {
 }
 
 cfg_if::cfg_if! {
-    if #[cfg(feature = "window_size")] {
+    if #[cfg(any(
+        feature = "window_size",
+        feature = "window_controls",
+        feature = "window_effects",
+        feature = "page_visibility",
+        feature = "document_title",
+        feature = "prevent_close",
+        feature = "window_events",
+        feature = "system_idle",
+    ))] {
         pub mod window;
     }
 }
@@ -17,3 +26,69 @@ cfg_if::cfg_if! {
         pub mod timing;
     }
 }
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "sync")] {
+        pub mod sync;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "transfer")] {
+        pub mod transfer;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "js")] {
+        pub mod js;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "process")] {
+        pub mod process;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "fs_watch")] {
+        pub mod fs_watch;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(any(feature = "intersection_observer", feature = "mutation_observer"))] {
+        pub mod observer;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "connect")] {
+        pub mod connect;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "reachability")] {
+        pub mod reachability;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "open")] {
+        pub mod open;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "text_metrics")] {
+        pub mod text;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "accent_color")] {
+        pub mod color;
+    }
+}
@@ -0,0 +1,202 @@
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+/// How often the element's position is checked on desktop.
+#[cfg(not(target_family = "wasm"))]
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Options for [`use_intersection_observer`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct IntersectionOptions {
+    /// The visible ratio (`0.0..=1.0`) of the element at which it counts as in view.
+    pub threshold: f64,
+    /// Grows the viewport by this many pixels on every side before checking for intersections.
+    /// Negative values shrink it.
+    pub root_margin: f64,
+}
+
+/// How much of an element is visible in the viewport.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct IntersectionState {
+    /// The visible ratio (`0.0..=1.0`) of the element.
+    pub ratio: f64,
+    /// Whether the element is visible and past the threshold.
+    pub is_intersecting: bool,
+}
+
+/// A hook for tracking how much of an element is visible in the viewport.
+///
+/// This is useful for lazy-loading content or tracking which content the user has seen.
+/// Uses an `IntersectionObserver` on the web and checks the element's position periodically on desktop.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::observer::{use_intersection_observer, IntersectionOptions};
+///
+/// fn App() -> Element {
+///     let mut element = use_signal(|| None);
+///     let visibility = use_intersection_observer(element, IntersectionOptions::default());
+///
+///     rsx! {
+///         div { height: "200vh" }
+///         div {
+///             onmounted: move |event| element.set(Some(event.data())),
+///             if visibility().is_intersecting {
+///                 img { src: "large-image.png" }
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_intersection_observer(
+    element: impl Into<ReadOnlySignal<Option<Rc<MountedData>>>>,
+    options: IntersectionOptions,
+) -> ReadOnlySignal<IntersectionState> {
+    let element = use_hook(|| element.into());
+    let state = use_signal(IntersectionState::default);
+
+    #[cfg(target_family = "wasm")]
+    {
+        let mut observer = use_hook(|| CopyValue::new(None::<web::Observer>));
+        use_effect(move || {
+            let element = element.read().clone();
+            if let Some(old) = observer.write().take() {
+                old.disconnect();
+            }
+            if let Some(element) = element
+                .as_ref()
+                .and_then(|element| element.downcast::<web_sys::Element>())
+            {
+                *observer.write() = web::observe(element, &options, state);
+            }
+        });
+        use_drop(move || {
+            if let Some(old) = observer.write().take() {
+                old.disconnect();
+            }
+        });
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    {
+        let mut task = use_signal(|| None::<Task>);
+        use_effect(move || {
+            let element = element.read().clone();
+            if let Some(old) = task.write().take() {
+                old.cancel();
+            }
+            if let Some(element) = element {
+                task.set(Some(spawn(desktop::poll(element, options.clone(), state))));
+            }
+        });
+    }
+
+    use_hook(|| ReadOnlySignal::new(state))
+}
+
+#[cfg(target_family = "wasm")]
+mod web {
+    use super::{IntersectionOptions, IntersectionState};
+    use dioxus::prelude::*;
+    use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+    use web_sys::{IntersectionObserver, IntersectionObserverEntry, IntersectionObserverInit};
+
+    /// An `IntersectionObserver` and the callback it calls.
+    pub struct Observer {
+        observer: IntersectionObserver,
+        _callback: Closure<dyn FnMut(js_sys::Array)>,
+    }
+
+    impl Observer {
+        pub fn disconnect(self) {
+            self.observer.disconnect();
+        }
+    }
+
+    pub fn observe(
+        element: &web_sys::Element,
+        options: &IntersectionOptions,
+        mut state: Signal<IntersectionState>,
+    ) -> Option<Observer> {
+        let threshold = options.threshold;
+        let callback = Closure::new(move |entries: js_sys::Array| {
+            if let Some(entry) = entries.iter().last() {
+                let entry: IntersectionObserverEntry = entry.unchecked_into();
+                let ratio = entry.intersection_ratio();
+                state.set(IntersectionState {
+                    ratio,
+                    is_intersecting: entry.is_intersecting() && ratio >= threshold,
+                });
+            }
+        });
+
+        // The observer only reports crossing a threshold, so include the edges to keep the ratio up to date.
+        let thresholds = js_sys::Array::of3(
+            &JsValue::from_f64(0.0),
+            &JsValue::from_f64(threshold),
+            &JsValue::from_f64(1.0),
+        );
+        let init = IntersectionObserverInit::new();
+        init.set_threshold(&thresholds);
+        init.set_root_margin(&format!("{}px", options.root_margin));
+
+        let observer =
+            IntersectionObserver::new_with_options(callback.as_ref().unchecked_ref(), &init)
+                .ok()?;
+        observer.observe(element);
+
+        Some(Observer {
+            observer,
+            _callback: callback,
+        })
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+mod desktop {
+    use super::{IntersectionOptions, IntersectionState, POLL_INTERVAL};
+    use dioxus::prelude::*;
+    use std::rc::Rc;
+
+    /// Check the element's position against the viewport until the task is cancelled.
+    pub async fn poll(
+        element: Rc<MountedData>,
+        options: IntersectionOptions,
+        mut state: Signal<IntersectionState>,
+    ) {
+        loop {
+            if let (Ok(rect), Some((width, height))) =
+                (element.get_client_rect().await, viewport_size().await)
+            {
+                let margin = options.root_margin;
+                let visible_width =
+                    (rect.max_x().min(width + margin) - rect.min_x().max(-margin)).max(0.0);
+                let visible_height =
+                    (rect.max_y().min(height + margin) - rect.min_y().max(-margin)).max(0.0);
+                let area = rect.area();
+                let ratio = match area > 0.0 {
+                    true => (visible_width * visible_height / area).min(1.0),
+                    false => 0.0,
+                };
+
+                let next = IntersectionState {
+                    ratio,
+                    is_intersecting: ratio > 0.0 && ratio >= options.threshold,
+                };
+                if *state.peek() != next {
+                    state.set(next);
+                }
+            }
+
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    async fn viewport_size() -> Option<(f64, f64)> {
+        let mut eval = eval("dioxus.send([window.innerWidth, window.innerHeight]);");
+        let size = eval.recv().await.ok()?;
+        Some((size.get(0)?.as_f64()?, size.get(1)?.as_f64()?))
+    }
+}
@@ -0,0 +1,15 @@
+//! Observe elements rendered by Dioxus.
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "intersection_observer")] {
+        mod intersection;
+        pub use intersection::*;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "mutation_observer")] {
+        mod mutation;
+        pub use mutation::*;
+    }
+}
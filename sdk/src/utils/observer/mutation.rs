@@ -0,0 +1,203 @@
+use dioxus::prelude::*;
+use serde_json::{json, Value};
+
+use crate::utils::js::CallJsError;
+
+/// Resolves with the first element matching the selector, waiting for it to be added to the page if needed.
+const WAIT_FOR_ELEMENT: &str = r#"
+const waitForElement = (selector) => new Promise((resolve) => {
+    const found = document.querySelector(selector);
+    if (found) {
+        return resolve(found);
+    }
+    const observer = new MutationObserver(() => {
+        const found = document.querySelector(selector);
+        if (found) {
+            observer.disconnect();
+            resolve(found);
+        }
+    });
+    observer.observe(document.documentElement, { childList: true, subtree: true });
+});
+"#;
+
+/// Observes the element and sends every mutation record back, until a second message asks it to stop.
+const OBSERVE: &str = r#"
+const { selector, init } = await dioxus.recv();
+const target = await waitForElement(selector);
+const observer = new MutationObserver((records) => {
+    for (const record of records) {
+        dioxus.send({
+            type: record.type,
+            target: record.target.nodeName,
+            attributeName: record.attributeName,
+            oldValue: record.oldValue,
+            addedNodes: record.addedNodes.length,
+            removedNodes: record.removedNodes.length,
+        });
+    }
+});
+observer.observe(target, init);
+await dioxus.recv();
+observer.disconnect();
+"#;
+
+/// Options for [`use_mutation_observer`]. These mirror the options of the JavaScript `MutationObserver`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MutationOptions {
+    /// Report nodes being added to or removed from the element.
+    pub child_list: bool,
+    /// Report changes to the element's attributes.
+    pub attributes: bool,
+    /// Report changes to the text of the element.
+    pub character_data: bool,
+    /// Report changes to all descendants of the element, not just the element itself.
+    pub subtree: bool,
+    /// Include the previous value of changed attributes.
+    pub attribute_old_value: bool,
+    /// Include the previous text of changed text nodes.
+    pub character_data_old_value: bool,
+    /// Only report changes to these attributes.
+    pub attribute_filter: Option<Vec<String>>,
+}
+
+impl Default for MutationOptions {
+    fn default() -> Self {
+        Self {
+            child_list: true,
+            attributes: true,
+            character_data: false,
+            subtree: true,
+            attribute_old_value: false,
+            character_data_old_value: false,
+            attribute_filter: None,
+        }
+    }
+}
+
+impl MutationOptions {
+    fn to_init(&self) -> Value {
+        let mut init = json!({
+            "childList": self.child_list,
+            "attributes": self.attributes,
+            "characterData": self.character_data,
+            "subtree": self.subtree,
+            "attributeOldValue": self.attribute_old_value,
+            "characterDataOldValue": self.character_data_old_value,
+        });
+        if let Some(filter) = &self.attribute_filter {
+            init["attributeFilter"] = json!(filter);
+        }
+        init
+    }
+}
+
+/// The kind of change a [`MutationRecord`] describes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MutationKind {
+    ChildList,
+    Attributes,
+    CharacterData,
+}
+
+/// A change made to an observed element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MutationRecord {
+    pub kind: MutationKind,
+    /// The node name of the changed node, like `DIV` or `#text`.
+    pub target: String,
+    /// The name of the changed attribute.
+    pub attribute_name: Option<String>,
+    /// The previous value, if the options asked for it.
+    pub old_value: Option<String>,
+    /// The number of nodes that were added.
+    pub added_nodes: usize,
+    /// The number of nodes that were removed.
+    pub removed_nodes: usize,
+}
+
+impl MutationRecord {
+    fn from_value(value: &Value) -> Option<Self> {
+        let kind = match value["type"].as_str()? {
+            "childList" => MutationKind::ChildList,
+            "attributes" => MutationKind::Attributes,
+            "characterData" => MutationKind::CharacterData,
+            _ => return None,
+        };
+        let string = |key: &str| value[key].as_str().map(|s| s.to_string());
+        let count = |key: &str| value[key].as_u64().unwrap_or_default() as usize;
+
+        Some(Self {
+            kind,
+            target: string("target").unwrap_or_default(),
+            attribute_name: string("attributeName"),
+            old_value: string("oldValue"),
+            added_nodes: count("addedNodes"),
+            removed_nodes: count("removedNodes"),
+        })
+    }
+}
+
+/// A hook for receiving changes to the DOM inside an element.
+///
+/// This is useful for integrating third-party JavaScript widgets that modify the page themselves.
+/// The element is found with a CSS selector. If it doesn't exist yet, the hook waits for it to be added,
+/// so it can be used before the element is rendered. Observation stops when the component is dropped.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::observer::{use_mutation_observer, MutationOptions};
+///
+/// fn App() -> Element {
+///     let mut changes = use_signal(|| 0);
+///     use_mutation_observer("#chart", MutationOptions::default(), move |_| changes += 1);
+///
+///     rsx! {
+///         div { id: "chart" }
+///         p { "The chart changed {changes} times" }
+///     }
+/// }
+/// ```
+pub fn use_mutation_observer(
+    selector: impl ToString,
+    options: MutationOptions,
+    mut handler: impl FnMut(MutationRecord) + 'static,
+) {
+    let eval = use_hook(|| {
+        let mut eval = eval(&format!("{WAIT_FOR_ELEMENT}{OBSERVE}"));
+        let message = json!({ "selector": selector.to_string(), "init": options.to_init() });
+        match eval.send(message) {
+            Ok(()) => {
+                spawn(async move {
+                    while let Ok(value) = eval.recv().await {
+                        if let Some(record) = MutationRecord::from_value(&value) {
+                            handler(record);
+                        }
+                    }
+                });
+            }
+            Err(e) => tracing::error!("Failed to start the mutation observer: {:?}", e),
+        }
+        eval
+    });
+
+    // The observer lives in the page, so it would keep sending records after the component is gone.
+    use_drop(move || {
+        eval.send(Value::Null).ok();
+    });
+}
+
+/// Wait until an element matching the CSS selector exists on the page.
+///
+/// This is useful for running JavaScript that expects the element to be rendered, like mounting a third-party widget.
+pub async fn wait_for_element(selector: &str) -> Result<(), CallJsError> {
+    let mut eval = eval(&format!(
+        "{WAIT_FOR_ELEMENT}\nawait waitForElement(await dioxus.recv());\ndioxus.send(true);"
+    ));
+    eval.send(Value::String(selector.to_string()))
+        .map_err(CallJsError::Eval)?;
+    eval.recv().await.map_err(CallJsError::Eval)?;
+    Ok(())
+}
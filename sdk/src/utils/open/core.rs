@@ -0,0 +1,101 @@
+use std::{fmt, path::Path};
+
+use super::platform;
+
+/// Represents errors when opening URLs and files.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OpenError {
+    /// The platform can't do this, like opening local files on the web.
+    NotSupported,
+    /// The path doesn't exist.
+    NotFound,
+    /// The system failed to open the URL or file.
+    Failed(String),
+}
+
+impl std::error::Error for OpenError {}
+impl fmt::Display for OpenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OpenError::NotSupported => write!(f, "not supported on this platform"),
+            OpenError::NotFound => write!(f, "the path doesn't exist"),
+            OpenError::Failed(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// Open a URL with the app the user chose for it, like a web page in the default browser or a `mailto:` link in
+/// the mail app.
+///
+/// This uses the system's launcher on desktop, a new tab on the web, an intent on Android, and `UIApplication` on
+/// iOS.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::open::open_url;
+///
+/// fn App() -> Element {
+///     rsx! {
+///         button {
+///             onclick: move |_| {
+///                 if let Err(e) = open_url("https://dioxuslabs.com") {
+///                     eprintln!("Failed to open the browser: {e}");
+///                 }
+///             },
+///             "Open in browser"
+///         }
+///     }
+/// }
+/// ```
+pub fn open_url(url: &str) -> Result<(), OpenError> {
+    platform::open_url(url)
+}
+
+/// Open a file or folder with the app the user chose for it.
+///
+/// This is only supported on desktop.
+pub fn open_path(path: impl AsRef<Path>) -> Result<(), OpenError> {
+    let path = path.as_ref();
+    check_exists(path)?;
+    platform::open_path(path)
+}
+
+/// Show a file or folder in the system's file manager, with it selected.
+///
+/// This is "Show in Explorer" on Windows and "Show in Finder" on macOS. On Linux, this asks the file manager over
+/// D-Bus in the background, and opens the parent folder if the file manager doesn't support it, so only errors
+/// finding the path are returned. This is only supported on desktop.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::open::reveal_in_file_manager;
+///
+/// #[component]
+/// fn Download(path: String) -> Element {
+///     rsx! {
+///         button {
+///             onclick: move |_| {
+///                 reveal_in_file_manager(&path).ok();
+///             },
+///             "Show in folder"
+///         }
+///     }
+/// }
+/// ```
+pub fn reveal_in_file_manager(path: impl AsRef<Path>) -> Result<(), OpenError> {
+    let path = path.as_ref();
+    check_exists(path)?;
+    platform::reveal(path)
+}
+
+/// Check the path exists, so a missing file is reported the same way everywhere. There are no files on the web.
+fn check_exists(path: &Path) -> Result<(), OpenError> {
+    match cfg!(target_family = "wasm") || path.exists() {
+        true => Ok(()),
+        false => Err(OpenError::NotFound),
+    }
+}
@@ -0,0 +1,6 @@
+//! Open URLs and files with the apps the user chose for them, and show files in the file manager.
+
+mod core;
+mod platform;
+
+pub use self::core::*;
@@ -0,0 +1,46 @@
+use jni::objects::JValue;
+use std::path::Path;
+
+use crate::android::with_activity;
+use crate::utils::open::OpenError;
+
+/// `Intent.ACTION_VIEW`
+const ACTION_VIEW: &str = "android.intent.action.VIEW";
+
+pub fn open_url(url: &str) -> Result<(), OpenError> {
+    with_activity(|env, activity| {
+        let url = env.new_string(url)?;
+        let uri = env
+            .call_static_method(
+                "android/net/Uri",
+                "parse",
+                "(Ljava/lang/String;)Landroid/net/Uri;",
+                &[JValue::Object(&url)],
+            )?
+            .l()?;
+        let action = env.new_string(ACTION_VIEW)?;
+        let intent = env.new_object(
+            "android/content/Intent",
+            "(Ljava/lang/String;Landroid/net/Uri;)V",
+            &[JValue::Object(&action), JValue::Object(&uri)],
+        )?;
+        env.call_method(
+            activity,
+            "startActivity",
+            "(Landroid/content/Intent;)V",
+            &[JValue::Object(&intent)],
+        )?;
+        Ok(())
+    })
+    .map_err(OpenError::Failed)
+}
+
+/// Sharing files with other apps requires a content provider declared by the app, so paths can't be opened directly.
+pub fn open_path(_path: &Path) -> Result<(), OpenError> {
+    Err(OpenError::NotSupported)
+}
+
+/// Android has no file manager every device ships with.
+pub fn reveal(_path: &Path) -> Result<(), OpenError> {
+    Err(OpenError::NotSupported)
+}
@@ -0,0 +1,106 @@
+use std::{path::Path, process::Command};
+
+use crate::utils::open::OpenError;
+
+pub fn open_url(url: &str) -> Result<(), OpenError> {
+    open::that_detached(url).map_err(|e| OpenError::Failed(e.to_string()))
+}
+
+pub fn open_path(path: &Path) -> Result<(), OpenError> {
+    open::that_detached(path).map_err(|e| OpenError::Failed(e.to_string()))
+}
+
+#[cfg(target_os = "windows")]
+pub fn reveal(path: &Path) -> Result<(), OpenError> {
+    // Explorer expects the flag and the path as a single argument.
+    let mut select = std::ffi::OsString::from("/select,");
+    select.push(path);
+    spawn(Command::new("explorer").arg(select))
+}
+
+#[cfg(target_os = "macos")]
+pub fn reveal(path: &Path) -> Result<(), OpenError> {
+    spawn(Command::new("open").arg("-R").arg(path))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub fn reveal(path: &Path) -> Result<(), OpenError> {
+    let path = path
+        .canonicalize()
+        .map_err(|e| OpenError::Failed(e.to_string()))?;
+
+    // The file manager can take a while to start, so don't wait for it on the UI thread.
+    std::thread::spawn(move || {
+        if show_items(&path) {
+            return;
+        }
+        // Without a file manager that supports selecting items, open the folder the item is in.
+        let opened = match path.parent() {
+            Some(parent) if path.is_file() => open_path(parent),
+            _ => open_path(&path),
+        };
+        if let Err(e) = opened {
+            tracing::warn!("failed to show {path:?} in the file manager: {e}");
+        }
+    });
+    Ok(())
+}
+
+/// Ask the file manager to show an item over D-Bus, returning whether it did.
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn show_items(path: &Path) -> bool {
+    Command::new("dbus-send")
+        .args([
+            "--session",
+            "--print-reply",
+            "--dest=org.freedesktop.FileManager1",
+            "--type=method_call",
+            "/org/freedesktop/FileManager1",
+            "org.freedesktop.FileManager1.ShowItems",
+        ])
+        .arg(format!("array:string:{}", file_uri(path)))
+        .arg("string:")
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// The `file://` URI of an absolute path.
+///
+/// Everything but unreserved characters and separators is percent-encoded. This includes commas, which `dbus-send`
+/// would otherwise split the array argument on.
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn file_uri(path: &Path) -> String {
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut uri = String::from("file://");
+    for &byte in path.as_os_str().as_bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    uri
+}
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+fn spawn(command: &mut Command) -> Result<(), OpenError> {
+    command
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| OpenError::Failed(e.to_string()))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+#[test]
+fn test_file_uri() {
+    assert_eq!(
+        file_uri(Path::new("/home/me/Report, final.pdf")),
+        "file:///home/me/Report%2C%20final.pdf"
+    );
+    assert_eq!(
+        file_uri(Path::new("/tmp/caf\u{e9}")),
+        "file:///tmp/caf%C3%A9"
+    );
+}
@@ -0,0 +1,44 @@
+use objc::{class, msg_send, runtime::Object, sel, sel_impl};
+use std::{ffi::c_void, path::Path, ptr};
+
+use crate::utils::open::OpenError;
+
+/// `NSUTF8StringEncoding`
+const UTF8_ENCODING: usize = 4;
+
+pub fn open_url(url: &str) -> Result<(), OpenError> {
+    unsafe {
+        let string: *mut Object = msg_send![class!(NSString), alloc];
+        let string: *mut Object = msg_send![
+            string,
+            initWithBytes: url.as_ptr() as *const c_void
+            length: url.len()
+            encoding: UTF8_ENCODING
+        ];
+        let url: *mut Object = msg_send![class!(NSURL), URLWithString: string];
+        let _: () = msg_send![string, release];
+        if url.is_null() {
+            return Err(OpenError::Failed("invalid URL".to_string()));
+        }
+
+        let app: *mut Object = msg_send![class!(UIApplication), sharedApplication];
+        let options: *mut Object = msg_send![class!(NSDictionary), dictionary];
+        let _: () = msg_send![
+            app,
+            openURL: url
+            options: options
+            completionHandler: ptr::null_mut::<c_void>()
+        ];
+    }
+    Ok(())
+}
+
+/// Apps can only open their own files through a document interaction controller, which needs a view to present.
+pub fn open_path(_path: &Path) -> Result<(), OpenError> {
+    Err(OpenError::NotSupported)
+}
+
+/// The Files app can't be asked to select a file.
+pub fn reveal(_path: &Path) -> Result<(), OpenError> {
+    Err(OpenError::NotSupported)
+}
@@ -0,0 +1,15 @@
+cfg_if::cfg_if! {
+    if #[cfg(target_family = "wasm")] {
+        mod wasm;
+        pub(super) use self::wasm::*;
+    } else if #[cfg(target_os = "android")] {
+        mod android;
+        pub(super) use self::android::*;
+    } else if #[cfg(target_os = "ios")] {
+        mod ios;
+        pub(super) use self::ios::*;
+    } else {
+        mod desktop;
+        pub(super) use self::desktop::*;
+    }
+}
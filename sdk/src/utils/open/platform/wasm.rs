@@ -0,0 +1,23 @@
+use std::path::Path;
+
+use crate::utils::open::OpenError;
+
+pub fn open_url(url: &str) -> Result<(), OpenError> {
+    let window = web_sys::window().ok_or(OpenError::NotSupported)?;
+    match window.open_with_url_and_target(url, "_blank") {
+        Ok(Some(_)) => Ok(()),
+        // The browser returns no window when a popup blocker stopped it.
+        Ok(None) => Err(OpenError::Failed("the popup was blocked".to_string())),
+        Err(e) => Err(OpenError::Failed(format!("{:?}", e))),
+    }
+}
+
+/// Web pages can't reach the user's files.
+pub fn open_path(_path: &Path) -> Result<(), OpenError> {
+    Err(OpenError::NotSupported)
+}
+
+/// Web pages can't reach the user's files.
+pub fn reveal(_path: &Path) -> Result<(), OpenError> {
+    Err(OpenError::NotSupported)
+}
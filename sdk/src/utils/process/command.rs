@@ -0,0 +1,290 @@
+use dioxus::prelude::*;
+use std::{error::Error, fmt::Display};
+
+/// Represents an error when running a child process.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProcessError {
+    /// Launching processes is not supported on this platform.
+    Unsupported,
+    /// The process could not be started.
+    SpawnFailed(String),
+    /// Reading the output of or waiting on the process failed.
+    Io(String),
+}
+
+impl Error for ProcessError {}
+impl Display for ProcessError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Unsupported => write!(f, "the current platform is not supported"),
+            Self::SpawnFailed(e) => write!(f, "failed to start the process: {e}"),
+            Self::Io(e) => write!(f, "failed to communicate with the process: {e}"),
+        }
+    }
+}
+
+/// A description of a process to launch with [`use_command`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Command {
+    program: String,
+    args: Vec<String>,
+    envs: Vec<(String, String)>,
+    current_dir: Option<String>,
+}
+
+impl Command {
+    /// Create a command that runs the given program.
+    pub fn new(program: impl ToString) -> Self {
+        Self {
+            program: program.to_string(),
+            args: Vec::new(),
+            envs: Vec::new(),
+            current_dir: None,
+        }
+    }
+
+    /// Add an argument to pass to the program.
+    pub fn arg(mut self, arg: impl ToString) -> Self {
+        self.args.push(arg.to_string());
+        self
+    }
+
+    /// Add multiple arguments to pass to the program.
+    pub fn args<I: IntoIterator<Item = S>, S: ToString>(mut self, args: I) -> Self {
+        self.args
+            .extend(args.into_iter().map(|arg| arg.to_string()));
+        self
+    }
+
+    /// Set an environment variable for the process.
+    pub fn env(mut self, key: impl ToString, value: impl ToString) -> Self {
+        self.envs.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Set the working directory for the process.
+    pub fn current_dir(mut self, dir: impl ToString) -> Self {
+        self.current_dir = Some(dir.to_string());
+        self
+    }
+}
+
+/// A line of output from a child process.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OutputLine {
+    /// A line written to stdout.
+    Stdout(String),
+    /// A line written to stderr.
+    Stderr(String),
+}
+
+/// The state of a child process.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProcessStatus {
+    /// The process is running.
+    Running,
+    /// The process exited. The code is `None` if the process was terminated by a signal.
+    Exited(Option<i32>),
+    /// The process was killed with [`UseCommand::kill`].
+    Killed,
+    /// The process could not be run.
+    Failed(ProcessError),
+}
+
+/// The interface for a child process launched with [`use_command`].
+#[derive(Clone, Copy, PartialEq)]
+pub struct UseCommand {
+    command: Signal<Command>,
+    output: Signal<Vec<OutputLine>>,
+    status: Signal<ProcessStatus>,
+    task: Signal<Option<Task>>,
+}
+
+impl UseCommand {
+    /// Get all output lines of the process in the order they were written.
+    pub fn output(&self) -> ReadOnlySignal<Vec<OutputLine>> {
+        ReadOnlySignal::new(self.output)
+    }
+
+    /// Get the lines the process wrote to stdout.
+    pub fn stdout(&self) -> Vec<String> {
+        self.output
+            .read()
+            .iter()
+            .filter_map(|line| match line {
+                OutputLine::Stdout(line) => Some(line.clone()),
+                OutputLine::Stderr(_) => None,
+            })
+            .collect()
+    }
+
+    /// Get the lines the process wrote to stderr.
+    pub fn stderr(&self) -> Vec<String> {
+        self.output
+            .read()
+            .iter()
+            .filter_map(|line| match line {
+                OutputLine::Stderr(line) => Some(line.clone()),
+                OutputLine::Stdout(_) => None,
+            })
+            .collect()
+    }
+
+    /// Get the current state of the process.
+    pub fn status(&self) -> ReadOnlySignal<ProcessStatus> {
+        ReadOnlySignal::new(self.status)
+    }
+
+    /// Whether the process is still running.
+    pub fn is_running(&self) -> bool {
+        *self.status.read() == ProcessStatus::Running
+    }
+
+    /// Kill the process if it is running.
+    pub fn kill(&mut self) {
+        if let Some(task) = self.task.write().take() {
+            // Dropping the task drops the child, which kills it.
+            task.cancel();
+            if *self.status.peek() == ProcessStatus::Running {
+                self.status.set(ProcessStatus::Killed);
+            }
+        }
+    }
+
+    /// Kill the process if it is running and launch it again, clearing its output.
+    pub fn restart(&mut self) {
+        self.kill();
+        self.output.write().clear();
+        self.start();
+    }
+
+    fn start(&mut self) {
+        let command = self.command.peek().clone();
+        let mut handle = *self;
+
+        #[cfg(not(target_family = "wasm"))]
+        {
+            self.status.set(ProcessStatus::Running);
+            let task = spawn(async move {
+                let status = match native::run(command, handle.output).await {
+                    Ok(code) => ProcessStatus::Exited(code),
+                    Err(e) => ProcessStatus::Failed(e),
+                };
+                handle.status.set(status);
+                handle.task.set(None);
+            });
+            self.task.set(Some(task));
+        }
+
+        #[cfg(target_family = "wasm")]
+        {
+            let _ = command;
+            handle
+                .status
+                .set(ProcessStatus::Failed(ProcessError::Unsupported));
+        }
+    }
+}
+
+/// A hook for launching a child process and following its output.
+///
+/// The process is started immediately. Its stdout and stderr are collected line by line,
+/// and it is killed when the component is dropped.
+///
+/// Supported on desktop. On the web, the status is always [`ProcessError::Unsupported`].
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::process::{use_command, Command, OutputLine};
+///
+/// fn App() -> Element {
+///     let mut build = use_command(Command::new("cargo").arg("build"));
+///     let output = build.output();
+///
+///     rsx! {
+///         p { "Status: {build.status():?}" }
+///         button { onclick: move |_| build.restart(), "Rebuild" }
+///         for line in output.read().iter() {
+///             match line {
+///                 OutputLine::Stdout(line) => rsx! { pre { "{line}" } },
+///                 OutputLine::Stderr(line) => rsx! { pre { color: "red", "{line}" } },
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_command(command: Command) -> UseCommand {
+    use_hook(|| {
+        let mut handle = UseCommand {
+            command: Signal::new(command),
+            output: Signal::new(Vec::new()),
+            status: Signal::new(ProcessStatus::Running),
+            task: Signal::new(None),
+        };
+        handle.start();
+        handle
+    })
+}
+
+#[cfg(not(target_family = "wasm"))]
+mod native {
+    use super::{Command, OutputLine, ProcessError};
+    use dioxus::prelude::*;
+    use std::process::Stdio;
+    use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+
+    /// Run the command to completion, pushing its output into the signal.
+    pub async fn run(
+        command: Command,
+        output: Signal<Vec<OutputLine>>,
+    ) -> Result<Option<i32>, ProcessError> {
+        let mut process = tokio::process::Command::new(&command.program);
+        process
+            .args(&command.args)
+            .envs(command.envs.iter().map(|(k, v)| (k, v)))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        if let Some(dir) = &command.current_dir {
+            process.current_dir(dir);
+        }
+
+        let mut child = process
+            .spawn()
+            .map_err(|e| ProcessError::SpawnFailed(e.to_string()))?;
+
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let stderr = child.stderr.take().expect("stderr is piped");
+        let (stdout, stderr) = futures::join!(
+            read_lines(stdout, output, OutputLine::Stdout),
+            read_lines(stderr, output, OutputLine::Stderr),
+        );
+        stdout?;
+        stderr?;
+
+        let status = child
+            .wait()
+            .await
+            .map_err(|e| ProcessError::Io(e.to_string()))?;
+        Ok(status.code())
+    }
+
+    async fn read_lines(
+        pipe: impl AsyncRead + Unpin,
+        mut output: Signal<Vec<OutputLine>>,
+        wrap: fn(String) -> OutputLine,
+    ) -> Result<(), ProcessError> {
+        let mut lines = BufReader::new(pipe).lines();
+        while let Some(line) = lines
+            .next_line()
+            .await
+            .map_err(|e| ProcessError::Io(e.to_string()))?
+        {
+            output.write().push(wrap(line));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,4 @@
+//! Launch and monitor child processes.
+
+mod command;
+pub use command::*;
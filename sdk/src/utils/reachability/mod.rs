@@ -0,0 +1,6 @@
+//! Check whether a server can be reached, beyond what the network status reports.
+
+mod platform;
+mod use_reachability;
+
+pub use use_reachability::*;
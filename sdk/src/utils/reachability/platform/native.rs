@@ -0,0 +1,39 @@
+use futures::channel::mpsc::UnboundedSender;
+use reqwest::Client;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// The client shared by all probes so connections can be reused.
+static CLIENT: OnceLock<Client> = OnceLock::new();
+
+/// Send a `HEAD` request to `url` and measure how long the response takes, giving up after `timeout`.
+pub(crate) async fn probe(url: &str, timeout: Duration) -> Result<Duration, String> {
+    let start = Instant::now();
+    let response = CLIENT
+        .get_or_init(Client::new)
+        .head(url)
+        .timeout(timeout)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let status = response.status();
+    if status.is_server_error() {
+        return Err(format!("the server responded with {status}"));
+    }
+    Ok(start.elapsed())
+}
+
+/// Whether the device is connected to a network.
+///
+/// Native platforms can't tell reliably, so this is always `true` and the probe decides.
+pub(crate) fn is_online() -> bool {
+    true
+}
+
+/// Native platforms don't report network changes, so there is nothing to listen to.
+pub(crate) struct NetworkListener;
+
+pub(crate) fn listen_network(_wake: UnboundedSender<()>) -> NetworkListener {
+    NetworkListener
+}
@@ -0,0 +1,86 @@
+use futures::{
+    channel::mpsc::UnboundedSender,
+    future::{self, Either},
+};
+use std::time::Duration;
+use wasm_bindgen::{closure::Closure, JsCast};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{AbortController, Request, RequestInit};
+
+use crate::utils::timing::clock;
+
+/// Send a `HEAD` request to `url` and measure how long the response takes, giving up after `timeout`.
+pub(crate) async fn probe(url: &str, timeout: Duration) -> Result<Duration, String> {
+    let window = web_sys::window().ok_or("there is no window")?;
+    let start = js_sys::Date::now();
+
+    let controller = AbortController::new().map_err(|e| format!("{e:?}"))?;
+    let init = RequestInit::new();
+    init.set_method("HEAD");
+    init.set_signal(Some(&controller.signal()));
+    let request = Request::new_with_str_and_init(url, &init).map_err(|e| format!("{e:?}"))?;
+
+    let fetch = JsFuture::from(window.fetch_with_request(&request));
+    let response =
+        match future::select(std::pin::pin!(fetch), std::pin::pin!(clock::sleep(timeout))).await {
+            Either::Left((response, _)) => response.map_err(|e| format!("{e:?}"))?,
+            Either::Right(_) => {
+                controller.abort();
+                return Err(format!("no response after {timeout:?}"));
+            }
+        };
+    let response = response
+        .dyn_into::<web_sys::Response>()
+        .map_err(|e| format!("{e:?}"))?;
+
+    if response.status() >= 500 {
+        return Err(format!("the server responded with {}", response.status()));
+    }
+    Ok(Duration::from_secs_f64(
+        (js_sys::Date::now() - start).max(0.0) / 1000.0,
+    ))
+}
+
+/// Whether the browser is connected to a network.
+pub(crate) fn is_online() -> bool {
+    web_sys::window()
+        .map(|window| window.navigator().on_line())
+        .unwrap_or(true)
+}
+
+/// Listens for the browser going online or offline until it is dropped.
+pub(crate) struct NetworkListener {
+    listener: Option<Closure<dyn FnMut()>>,
+}
+
+impl Drop for NetworkListener {
+    fn drop(&mut self) {
+        let (Some(window), Some(listener)) = (web_sys::window(), &self.listener) else {
+            return;
+        };
+        for event in ["online", "offline"] {
+            window
+                .remove_event_listener_with_callback(event, listener.as_ref().unchecked_ref())
+                .ok();
+        }
+    }
+}
+
+/// Send to `wake` whenever the browser goes online or offline, so the server is probed again right away.
+pub(crate) fn listen_network(wake: UnboundedSender<()>) -> NetworkListener {
+    let Some(window) = web_sys::window() else {
+        return NetworkListener { listener: None };
+    };
+
+    let listener = Closure::wrap(Box::new(move || {
+        wake.unbounded_send(()).ok();
+    }) as Box<dyn FnMut()>);
+    for event in ["online", "offline"] {
+        window
+            .add_event_listener_with_callback(event, listener.as_ref().unchecked_ref())
+            .ok();
+    }
+    NetworkListener {
+        listener: Some(listener),
+    }
+}
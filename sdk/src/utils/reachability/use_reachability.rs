@@ -0,0 +1,275 @@
+use dioxus::prelude::*;
+use futures::{
+    channel::mpsc::{self, UnboundedReceiver, UnboundedSender},
+    future::{self, Either},
+    StreamExt,
+};
+use std::{collections::VecDeque, time::Duration};
+
+use super::platform;
+use crate::utils::timing::clock;
+
+/// Whether the server checked by [`use_reachability`] can be reached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reachability {
+    /// The first probe hasn't finished yet.
+    Unknown,
+    /// The latest probe succeeded in time.
+    Reachable,
+    /// The latest probe was slow, or failed fewer times in a row than [`ReachabilityOptions::failures`].
+    Degraded,
+    /// The device is offline, or the probe failed too many times in a row.
+    Unreachable,
+}
+
+/// Latency statistics over the recent successful probes.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LatencyStats {
+    /// The latency of the latest successful probe.
+    pub last: Option<Duration>,
+    /// The average latency of the recent successful probes.
+    pub average: Option<Duration>,
+    /// The lowest latency of the recent successful probes.
+    pub min: Option<Duration>,
+    /// The highest latency of the recent successful probes.
+    pub max: Option<Duration>,
+    /// The number of probes, successful or not, sent so far.
+    pub probes: u32,
+    /// The number of failed probes in a row.
+    pub failures: u32,
+}
+
+/// The options for [`use_reachability_with_options`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReachabilityOptions {
+    interval: Duration,
+    max_backoff: Duration,
+    degraded_after: Duration,
+    timeout: Duration,
+    failures: u32,
+    samples: usize,
+}
+
+impl ReachabilityOptions {
+    /// Probe every `interval` while the server is reachable.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            max_backoff: Duration::from_secs(5 * 60),
+            degraded_after: Duration::from_secs(1),
+            timeout: Duration::from_secs(10),
+            failures: 3,
+            samples: 10,
+        }
+    }
+
+    /// The longest delay between probes. The delay doubles after every failed probe. Defaults to 5 minutes.
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Report [`Reachability::Degraded`] when a probe takes longer than this. Defaults to 1 second.
+    pub fn degraded_after(mut self, latency: Duration) -> Self {
+        self.degraded_after = latency;
+        self
+    }
+
+    /// Count a probe as failed when the server doesn't respond within this time. Defaults to 10 seconds.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Report [`Reachability::Unreachable`] after this many failed probes in a row. Defaults to 3.
+    pub fn failures(mut self, failures: u32) -> Self {
+        self.failures = failures.max(1);
+        self
+    }
+
+    /// The number of recent probes the [`LatencyStats`] are computed from. Defaults to 10.
+    pub fn samples(mut self, samples: usize) -> Self {
+        self.samples = samples.max(1);
+        self
+    }
+
+    /// Get the delay before the next probe after the given number of failed probes in a row.
+    fn delay(&self, failures: u32) -> Duration {
+        let factor = 2u32.saturating_pow(failures);
+        self.interval
+            .saturating_mul(factor)
+            .min(self.max_backoff.max(self.interval))
+    }
+}
+
+/// The interface for a reachability probe.
+///
+/// See [`use_reachability`] for more information.
+#[derive(Clone, Copy, PartialEq)]
+pub struct UseReachability {
+    status: Signal<Reachability>,
+    latency: Signal<LatencyStats>,
+    wake: Signal<UnboundedSender<()>>,
+}
+
+impl UseReachability {
+    /// Get whether the server can be reached.
+    pub fn status(&self) -> ReadOnlySignal<Reachability> {
+        self.status.into()
+    }
+
+    /// Get the latency statistics of the recent probes.
+    pub fn latency(&self) -> ReadOnlySignal<LatencyStats> {
+        self.latency.into()
+    }
+
+    /// Whether the latest probe reached the server, even if it was slow.
+    pub fn is_reachable(&self) -> bool {
+        matches!(
+            *self.status.read(),
+            Reachability::Reachable | Reachability::Degraded
+        )
+    }
+
+    /// Probe again right away, for example after a request to the server failed.
+    pub fn check(&self) {
+        let _ = self.wake.peek().unbounded_send(());
+    }
+}
+
+/// A hook for checking whether a server can be reached with a `HEAD` request every `interval`.
+///
+/// Being online doesn't mean the backend is up, so this sends a lightweight probe and reports
+/// [`Reachability`] along with [`LatencyStats`]. Probes that get no response within 10 seconds count as failed.
+/// While the browser reports the device offline no probes are sent and the server is reported as unreachable, and
+/// the server is probed again as soon as the network status changes. Failed probes are retried with an exponential
+/// backoff.
+/// See [`use_reachability_with_options`] to tune the thresholds.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::reachability::{use_reachability, Reachability};
+/// use std::time::Duration;
+///
+/// fn App() -> Element {
+///     let reachability = use_reachability("https://example.com/health", Duration::from_secs(30));
+///     let latency = reachability.latency();
+///
+///     rsx! {
+///         match reachability.status()() {
+///             Reachability::Unknown => rsx! { "Checking the connection..." },
+///             Reachability::Reachable => rsx! { "Connected" },
+///             Reachability::Degraded => rsx! { "The connection is slow ({latency().average:?})" },
+///             Reachability::Unreachable => rsx! {
+///                 "The server can't be reached. "
+///                 button { onclick: move |_| reachability.check(), "Retry" }
+///             },
+///         }
+///     }
+/// }
+/// ```
+pub fn use_reachability(url: impl ToString, interval: Duration) -> UseReachability {
+    use_reachability_with_options(url, ReachabilityOptions::new(interval))
+}
+
+/// A hook for checking whether a server can be reached with the given [`ReachabilityOptions`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::reachability::{use_reachability_with_options, ReachabilityOptions};
+/// use std::time::Duration;
+///
+/// fn App() -> Element {
+///     let reachability = use_reachability_with_options(
+///         "/api/health",
+///         ReachabilityOptions::new(Duration::from_secs(10))
+///             .degraded_after(Duration::from_millis(500))
+///             .failures(2),
+///     );
+///
+///     rsx! { "{reachability.status():?}" }
+/// }
+/// ```
+pub fn use_reachability_with_options(
+    url: impl ToString,
+    options: ReachabilityOptions,
+) -> UseReachability {
+    use_hook(|| {
+        let (sender, receiver) = mpsc::unbounded();
+        // Probe again as soon as the network status changes.
+        let network = platform::listen_network(sender.clone());
+        let reachability = UseReachability {
+            status: Signal::new(Reachability::Unknown),
+            latency: Signal::new(LatencyStats::default()),
+            wake: Signal::new(sender),
+        };
+
+        spawn(async move {
+            let _network = network;
+            run(url.to_string(), options, reachability, receiver).await
+        });
+        reachability
+    })
+}
+
+/// Probe the server until the task is dropped.
+async fn run(
+    url: String,
+    options: ReachabilityOptions,
+    mut reachability: UseReachability,
+    mut wake: UnboundedReceiver<()>,
+) {
+    let mut samples = VecDeque::with_capacity(options.samples);
+    let mut failures = 0;
+    loop {
+        let status = if !platform::is_online() {
+            Reachability::Unreachable
+        } else {
+            let latency = match platform::probe(&url, options.timeout).await {
+                Ok(latency) => {
+                    failures = 0;
+                    if samples.len() == options.samples {
+                        samples.pop_front();
+                    }
+                    samples.push_back(latency);
+                    Some(latency)
+                }
+                Err(e) => {
+                    tracing::debug!("Failed to reach {}: {}", url, e);
+                    failures += 1;
+                    None
+                }
+            };
+
+            let mut stats = reachability.latency.write();
+            stats.probes += 1;
+            stats.failures = failures;
+            if latency.is_some() {
+                stats.last = latency;
+                stats.average = Some(samples.iter().sum::<Duration>() / samples.len() as u32);
+                stats.min = samples.iter().min().copied();
+                stats.max = samples.iter().max().copied();
+            }
+
+            match latency {
+                Some(latency) if latency <= options.degraded_after => Reachability::Reachable,
+                _ if failures >= options.failures => Reachability::Unreachable,
+                _ => Reachability::Degraded,
+            }
+        };
+
+        if *reachability.status.peek() != status {
+            reachability.status.set(status);
+        }
+
+        // Wait for the next probe, or until `check` is called.
+        let sleep = std::pin::pin!(clock::sleep(options.delay(failures)));
+        if let Either::Right((None, _)) = future::select(sleep, wake.next()).await {
+            return;
+        }
+    }
+}
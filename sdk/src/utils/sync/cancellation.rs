@@ -0,0 +1,210 @@
+use dioxus::prelude::*;
+use futures::future::{self, Either};
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::{pin, Pin},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex, Weak,
+    },
+    task::{Context, Poll, Waker},
+};
+
+#[derive(Default)]
+struct TokenState {
+    /// The tasks waiting for the token to be cancelled, by the id of their [`Cancelled`] future.
+    waiters: HashMap<u64, Waker>,
+    /// The tokens derived from this one.
+    children: Vec<Weak<TokenInner>>,
+}
+
+#[derive(Default)]
+struct TokenInner {
+    cancelled: AtomicBool,
+    state: Mutex<TokenState>,
+}
+
+impl TokenInner {
+    fn cancel(&self) {
+        if self.cancelled.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let state = std::mem::take(&mut *self.state.lock().unwrap());
+        for waker in state.waiters.into_values() {
+            waker.wake();
+        }
+        for child in state.children.iter().filter_map(Weak::upgrade) {
+            child.cancel();
+        }
+    }
+}
+
+/// A token for telling long-running tasks to stop.
+///
+/// Clones share the same state, so cancelling any of them cancels all of them. Tokens derived with
+/// [`CancellationToken::child_token`] are cancelled along with their parent, but can also be cancelled on their own.
+///
+/// See [`use_cancellation_token`] for a token that is cancelled when a component unmounts.
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<TokenInner>,
+}
+
+impl CancellationToken {
+    /// Create a token that isn't cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel the token and every token derived from it.
+    pub fn cancel(&self) {
+        self.inner.cancel();
+    }
+
+    /// Whether the token was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Derive a token that is cancelled when this one is, without cancelling this one when it is.
+    pub fn child_token(&self) -> CancellationToken {
+        let child = CancellationToken::new();
+        {
+            let mut state = self.inner.state.lock().unwrap();
+            if !self.is_cancelled() {
+                state.children.retain(|child| child.strong_count() > 0);
+                state.children.push(Arc::downgrade(&child.inner));
+                return child;
+            }
+        }
+        child.cancel();
+        child
+    }
+
+    /// Wait until the token is cancelled.
+    pub fn cancelled(&self) -> Cancelled {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        Cancelled {
+            inner: self.inner.clone(),
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    /// Run the future until it finishes or the token is cancelled.
+    ///
+    /// Returns the output of the future, or `None` if the token was cancelled first.
+    pub async fn run_until_cancelled<F: Future>(&self, future: F) -> Option<F::Output> {
+        if self.is_cancelled() {
+            return None;
+        }
+        match future::select(pin!(future), self.cancelled()).await {
+            Either::Left((output, _)) => Some(output),
+            Either::Right(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+/// A future that finishes once a [`CancellationToken`] is cancelled.
+///
+/// See [`CancellationToken::cancelled`] for more information.
+pub struct Cancelled {
+    inner: Arc<TokenInner>,
+    id: u64,
+}
+
+impl Future for Cancelled {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.inner.cancelled.load(Ordering::SeqCst) {
+            return Poll::Ready(());
+        }
+        let mut state = self.inner.state.lock().unwrap();
+        // The token may have been cancelled while the lock was taken.
+        if self.inner.cancelled.load(Ordering::SeqCst) {
+            return Poll::Ready(());
+        }
+        state.waiters.insert(self.id, cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl Drop for Cancelled {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.inner.state.lock() {
+            state.waiters.remove(&self.id);
+        }
+    }
+}
+
+/// A hook for a [`CancellationToken`] that is cancelled when the component unmounts.
+///
+/// Tasks spawned with `spawn` stop with the component, but work handed to other runtimes, threads, or
+/// subscriptions doesn't. Pass them the token, or a token derived from it, so they stop reliably when the user
+/// navigates away.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::sync::use_cancellation_token;
+///
+/// fn Export() -> Element {
+///     let token = use_cancellation_token();
+///
+///     rsx! {
+///         button {
+///             onclick: move |_| {
+///                 let token = token.child_token();
+///                 std::thread::spawn(move || {
+///                     for chunk in chunks() {
+///                         if token.is_cancelled() {
+///                             return;
+///                         }
+///                         write_chunk(chunk);
+///                     }
+///                 });
+///             },
+///             "Export"
+///         }
+///     }
+/// }
+/// ```
+pub fn use_cancellation_token() -> CancellationToken {
+    use_hook_with_cleanup(CancellationToken::new, |token| token.cancel())
+}
+
+#[test]
+fn test_cancellation_token() {
+    use futures::executor::block_on;
+
+    let parent = CancellationToken::new();
+    let child = parent.child_token();
+    let grandchild = child.child_token();
+
+    // Cancelling a child leaves its parent running.
+    grandchild.cancel();
+    assert!(grandchild.is_cancelled());
+    assert!(!child.is_cancelled());
+
+    parent.cancel();
+    assert!(child.is_cancelled());
+    block_on(child.cancelled());
+    assert_eq!(block_on(child.run_until_cancelled(async { 1 })), None);
+
+    // Tokens derived after the parent was cancelled start out cancelled.
+    assert!(parent.child_token().is_cancelled());
+    assert_eq!(
+        block_on(CancellationToken::new().run_until_cancelled(async { 1 })),
+        Some(1)
+    );
+}
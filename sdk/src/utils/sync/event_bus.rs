@@ -0,0 +1,216 @@
+use dioxus::prelude::*;
+use futures::{
+    channel::mpsc::{self, UnboundedSender as Sender},
+    StreamExt,
+};
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    collections::HashMap,
+    marker::PhantomData,
+    rc::Rc,
+};
+
+thread_local! {
+    /// The bus shared by every window running on this thread.
+    static SHARED_BUS: EventBus = EventBus::default();
+}
+
+/// Subscribers are keyed by the event type and the topic they listen to.
+type SubscriberKey = (TypeId, Option<String>);
+
+#[derive(Default)]
+struct EventBusInner {
+    next_id: usize,
+    // Each entry holds an `UnboundedSender<T>` for the event type in the key.
+    subscribers: HashMap<SubscriberKey, Vec<(usize, Box<dyn Any>)>>,
+}
+
+/// A bus that delivers typed events from publishers to subscribers.
+///
+/// See [`provide_event_bus`] for more information.
+#[derive(Clone, Default)]
+pub struct EventBus {
+    inner: Rc<RefCell<EventBusInner>>,
+}
+
+impl PartialEq for EventBus {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl EventBus {
+    /// Get the bus shared by all windows of the app.
+    pub fn shared() -> Self {
+        SHARED_BUS.with(|bus| bus.clone())
+    }
+
+    /// Send an event to every subscriber of its type, or of its type and topic if one is given.
+    pub fn publish<T: Clone + 'static>(&self, topic: Option<&str>, event: T) {
+        let key = (TypeId::of::<T>(), topic.map(|topic| topic.to_string()));
+        let mut inner = self.inner.borrow_mut();
+        let Some(subscribers) = inner.subscribers.get_mut(&key) else {
+            return;
+        };
+
+        // Drop subscribers whose component is gone.
+        subscribers.retain(|(_, sender)| {
+            sender
+                .downcast_ref::<Sender<T>>()
+                .is_some_and(|sender| sender.unbounded_send(event.clone()).is_ok())
+        });
+    }
+
+    fn subscribe<T: 'static>(&self, topic: Option<String>, sender: Sender<T>) -> usize {
+        let mut inner = self.inner.borrow_mut();
+        let id = inner.next_id;
+        inner.next_id += 1;
+        inner
+            .subscribers
+            .entry((TypeId::of::<T>(), topic))
+            .or_default()
+            .push((id, Box::new(sender)));
+        id
+    }
+
+    fn unsubscribe<T: 'static>(&self, topic: Option<String>, id: usize) {
+        let mut inner = self.inner.borrow_mut();
+        if let Some(subscribers) = inner.subscribers.get_mut(&(TypeId::of::<T>(), topic)) {
+            subscribers.retain(|(subscriber, _)| *subscriber != id);
+        }
+    }
+}
+
+/// Provide the app's event bus to all child components.
+///
+/// Components can then publish events with [`use_publish`] and receive them with [`use_subscribe`]
+/// without passing props or sharing signals. Events are matched by type, and optionally by a topic string.
+///
+/// All windows of a desktop app share the same bus, so events published in one window are delivered to the others.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::sync::{provide_event_bus, use_publish, use_subscribe};
+///
+/// #[derive(Clone)]
+/// struct LoggedOut;
+///
+/// fn App() -> Element {
+///     provide_event_bus();
+///     rsx! {
+///         LogoutButton {}
+///         Header {}
+///     }
+/// }
+///
+/// #[component]
+/// fn LogoutButton() -> Element {
+///     let publisher = use_publish::<LoggedOut>();
+///     rsx! { button { onclick: move |_| publisher.publish(LoggedOut), "Log out" } }
+/// }
+///
+/// #[component]
+/// fn Header() -> Element {
+///     let mut user = use_signal(|| Some("Ferris".to_string()));
+///     use_subscribe(move |_: LoggedOut| user.set(None));
+///     rsx! { "{user:?}" }
+/// }
+/// ```
+pub fn provide_event_bus() -> EventBus {
+    use_hook(|| provide_context(EventBus::shared()))
+}
+
+/// A handle for publishing events of type `T`.
+///
+/// See [`use_publish`] for more information.
+pub struct Publisher<T> {
+    bus: Signal<EventBus>,
+    _marker: PhantomData<fn(T)>,
+}
+
+// Manually implement Clone, Copy, and PartialEq as #[derive] thinks that T needs to implement these (it doesn't).
+
+impl<T> Clone for Publisher<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Publisher<T> {}
+
+impl<T> PartialEq for Publisher<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bus == other.bus
+    }
+}
+
+impl<T: Clone + 'static> Publisher<T> {
+    /// Send an event to every subscriber of `T` that isn't listening to a topic.
+    pub fn publish(&self, event: T) {
+        self.bus.peek().publish(None, event);
+    }
+
+    /// Send an event to every subscriber of `T` listening to the given topic.
+    pub fn publish_to(&self, topic: &str, event: T) {
+        self.bus.peek().publish(Some(topic), event);
+    }
+}
+
+/// A hook for publishing events of type `T` on the event bus.
+///
+/// # Panics
+///
+/// Panics if no parent component called [`provide_event_bus`].
+pub fn use_publish<T: Clone + 'static>() -> Publisher<T> {
+    use_hook(|| Publisher {
+        bus: Signal::new(consume_context::<EventBus>()),
+        _marker: PhantomData,
+    })
+}
+
+/// A hook for receiving events of type `T` from the event bus.
+///
+/// The handler only receives events published without a topic. Use [`use_subscribe_topic`] to listen to a topic.
+/// Events are delivered asynchronously, and the subscription ends when the component is dropped.
+///
+/// # Panics
+///
+/// Panics if no parent component called [`provide_event_bus`].
+pub fn use_subscribe<T: Clone + 'static>(handler: impl FnMut(T) + 'static) {
+    use_subscription(None, handler)
+}
+
+/// A hook for receiving events of type `T` published to the given topic on the event bus.
+///
+/// See [`use_subscribe`] for more information.
+pub fn use_subscribe_topic<T: Clone + 'static>(
+    topic: impl ToString,
+    handler: impl FnMut(T) + 'static,
+) {
+    use_subscription(Some(topic.to_string()), handler)
+}
+
+fn use_subscription<T: Clone + 'static>(
+    topic: Option<String>,
+    mut handler: impl FnMut(T) + 'static,
+) {
+    let (bus, topic, id) = use_hook(|| {
+        let bus = consume_context::<EventBus>();
+        let (sender, mut receiver) = mpsc::unbounded::<T>();
+        let id = bus.subscribe(topic.clone(), sender);
+
+        // Run the handler in a task owned by this component, even if the event was published from another window.
+        spawn(async move {
+            while let Some(event) = receiver.next().await {
+                handler(event);
+            }
+        });
+
+        (bus, topic, id)
+    });
+
+    use_drop(move || bus.unsubscribe::<T>(topic, id));
+}
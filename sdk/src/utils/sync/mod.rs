@@ -0,0 +1,16 @@
+//! Structured concurrency and state sharing utilities.
+
+mod service;
+pub use service::*;
+
+mod event_bus;
+pub use event_bus::*;
+
+mod state_machine;
+pub use state_machine::*;
+
+mod once;
+pub use once::*;
+
+mod cancellation;
+pub use cancellation::*;
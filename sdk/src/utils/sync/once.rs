@@ -0,0 +1,142 @@
+use dioxus::prelude::*;
+use std::{collections::BTreeSet, future::Future, panic::Location, sync::Mutex};
+
+/// The keys of every once task that was started in this process.
+static STARTED: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
+
+/// The keys of the once tasks that finished in this app.
+#[derive(Clone, Copy)]
+struct Finished(Signal<BTreeSet<String>>);
+
+/// Whether a once task should run.
+enum Claim {
+    /// The task hasn't run yet, so this caller runs it.
+    Run,
+    /// The task was started by another caller in this process.
+    Started,
+    /// The task ran before this process started.
+    Done,
+}
+
+/// Claim a task for this process, if no other caller did yet.
+fn claim_in_process(key: &str) -> Claim {
+    match STARTED.lock().unwrap().insert(key.to_string()) {
+        true => Claim::Run,
+        false => Claim::Started,
+    }
+}
+
+/// A hook that runs an async task once per app process, no matter how often the calling component is mounted.
+///
+/// Useful for migrations, warmup requests, and other work that should only happen once. The task is identified by
+/// where the hook is called, and keeps running when the component that started it unmounts. Returns whether the
+/// task has finished. In apps with several windows, only the window that ran the task sees it finish.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::sync::use_app_once;
+///
+/// fn Dashboard() -> Element {
+///     let warmed_up = use_app_once(|| async move {
+///         prefetch_reports().await;
+///     });
+///
+///     rsx! {
+///         if !warmed_up() { p { "Loading..." } }
+///     }
+/// }
+/// ```
+#[track_caller]
+pub fn use_app_once<F>(task: impl FnOnce() -> F) -> Memo<bool>
+where
+    F: Future<Output = ()> + 'static,
+{
+    let key = Location::caller().to_string();
+    use_once(key, claim_in_process, task)
+}
+
+/// A hook that runs an async task once per session, like [`use_app_once`] but remembered across page reloads.
+///
+/// The task is remembered under `key` in [`SessionStorage`](crate::storage::SessionStorage), so on the web it
+/// runs once per browser tab, and on other platforms once per app process. Returns whether the task has finished
+/// or already ran earlier in the session.
+///
+/// The task is marked as started before it runs, so a task that is interrupted by a reload isn't retried.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::sync::use_session_once;
+///
+/// fn App() -> Element {
+///     use_session_once("telemetry-ping", || async move {
+///         send_ping().await;
+///     });
+///
+///     rsx! {}
+/// }
+/// ```
+#[cfg(feature = "storage")]
+pub fn use_session_once<F>(key: impl ToString, task: impl FnOnce() -> F) -> Memo<bool>
+where
+    F: Future<Output = ()> + 'static,
+{
+    use crate::storage::{SessionStorage, StorageBacking};
+
+    let key = format!("dioxus-once:{}", key.to_string());
+    use_once(
+        key,
+        |key| match claim_in_process(key) {
+            Claim::Run if SessionStorage::get::<bool>(&key.to_string()).is_some() => Claim::Done,
+            Claim::Run => {
+                SessionStorage::set(key.to_string(), &true);
+                Claim::Run
+            }
+            claim => claim,
+        },
+        task,
+    )
+}
+
+/// Run `task` if `claim` allows it for the key, and track when it finishes.
+fn use_once<F>(
+    key: String,
+    claim: impl FnOnce(&str) -> Claim,
+    task: impl FnOnce() -> F,
+) -> Memo<bool>
+where
+    F: Future<Output = ()> + 'static,
+{
+    let (finished, key, done_before) = use_hook(|| {
+        let finished = match try_consume_context::<Finished>() {
+            Some(finished) => finished,
+            None => provide_root_context(Finished(Signal::new_in_scope(
+                BTreeSet::new(),
+                ScopeId::ROOT,
+            ))),
+        };
+
+        let mut keys = finished.0;
+        let done_before = match claim(&key) {
+            Claim::Run => {
+                let task = task();
+                let key = key.clone();
+                // Spawned in the root scope so the task keeps running if the calling component unmounts.
+                spawn_forever(async move {
+                    task.await;
+                    keys.write().insert(key);
+                });
+                false
+            }
+            Claim::Started => false,
+            // Signals can't be written while rendering, so a task that ran before is only tracked by this hook.
+            Claim::Done => true,
+        };
+        (finished, key, done_before)
+    });
+
+    use_memo(move || done_before || finished.0.read().contains(&key))
+}
@@ -0,0 +1,165 @@
+use dioxus::prelude::*;
+use futures::{
+    channel::{
+        mpsc::{self, UnboundedSender as Sender},
+        oneshot,
+    },
+    StreamExt,
+};
+use std::{fmt, future::Future};
+
+/// A long-lived service that owns some state and processes typed requests one at a time.
+///
+/// See [`use_provide_service`] for more information.
+pub trait Service: 'static {
+    /// The request messages the service accepts.
+    type Request: 'static;
+    /// The reply the service sends for each request.
+    type Response: 'static;
+
+    /// Handle a single request. Requests are processed in the order they were sent.
+    fn handle(&mut self, request: Self::Request) -> impl Future<Output = Self::Response>;
+}
+
+/// Represents errors when calling a service.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ServiceError {
+    /// The service has stopped, likely because the scope that provided it was dropped.
+    Stopped,
+}
+
+impl std::error::Error for ServiceError {}
+impl fmt::Display for ServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ServiceError::Stopped => write!(f, "the service has stopped"),
+        }
+    }
+}
+
+type Envelope<S> = (
+    <S as Service>::Request,
+    Option<oneshot::Sender<<S as Service>::Response>>,
+);
+
+/// A handle for sending requests to a [`Service`].
+pub struct UseService<S: Service> {
+    sender: Signal<Sender<Envelope<S>>>,
+}
+
+// Manually implement Clone, Copy, and PartialEq as #[derive] thinks that S needs to implement these (it doesn't).
+
+impl<S: Service> Clone for UseService<S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S: Service> Copy for UseService<S> {}
+
+impl<S: Service> PartialEq for UseService<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.sender == other.sender
+    }
+}
+
+impl<S: Service> UseService<S> {
+    /// Send a request to the service and wait for its reply.
+    pub async fn call(&self, request: S::Request) -> Result<S::Response, ServiceError> {
+        let (tx, rx) = oneshot::channel();
+        self.sender
+            .peek()
+            .unbounded_send((request, Some(tx)))
+            .map_err(|_| ServiceError::Stopped)?;
+        rx.await.map_err(|_| ServiceError::Stopped)
+    }
+
+    /// Send a request to the service without waiting for its reply.
+    pub fn send(&self, request: S::Request) -> Result<(), ServiceError> {
+        self.sender
+            .peek()
+            .unbounded_send((request, None))
+            .map_err(|_| ServiceError::Stopped)
+    }
+}
+
+/// Start a service and provide it to all child components.
+///
+/// The service runs in a task owned by the current component and is stopped when the component is dropped.
+/// Child components can get a handle to the service with [`use_service`].
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::sync::{use_provide_service, use_service, Service};
+///
+/// struct Counter(u32);
+///
+/// enum CounterRequest {
+///     Increment,
+///     Get,
+/// }
+///
+/// impl Service for Counter {
+///     type Request = CounterRequest;
+///     type Response = u32;
+///
+///     async fn handle(&mut self, request: CounterRequest) -> u32 {
+///         if let CounterRequest::Increment = request {
+///             self.0 += 1;
+///         }
+///         self.0
+///     }
+/// }
+///
+/// fn App() -> Element {
+///     use_provide_service(|| Counter(0));
+///     rsx! { Child {} }
+/// }
+///
+/// #[component]
+/// fn Child() -> Element {
+///     let counter = use_service::<Counter>();
+///     let mut count = use_signal(|| 0);
+///
+///     rsx! {
+///         button {
+///             onclick: move |_| async move {
+///                 if let Ok(value) = counter.call(CounterRequest::Increment).await {
+///                     count.set(value);
+///                 }
+///             },
+///             "Clicked {count} times"
+///         }
+///     }
+/// }
+/// ```
+pub fn use_provide_service<S: Service>(init: impl FnOnce() -> S) -> UseService<S> {
+    use_hook(|| {
+        let (sender, mut receiver) = mpsc::unbounded::<Envelope<S>>();
+        let mut service = init();
+
+        spawn(async move {
+            while let Some((request, reply)) = receiver.next().await {
+                let response = service.handle(request).await;
+                if let Some(reply) = reply {
+                    reply.send(response).ok();
+                }
+            }
+        });
+
+        provide_context(UseService {
+            sender: Signal::new(sender),
+        })
+    })
+}
+
+/// Get a handle to a service provided by a parent component with [`use_provide_service`].
+///
+/// # Panics
+///
+/// Panics if no parent component provides the service.
+pub fn use_service<S: Service>() -> UseService<S> {
+    use_hook(consume_context::<UseService<S>>)
+}
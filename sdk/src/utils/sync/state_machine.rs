@@ -0,0 +1,268 @@
+use dioxus::prelude::*;
+use std::{fmt, rc::Rc};
+
+/// The transitions of a state machine, and the hooks that run when states are entered or exited.
+///
+/// See [`use_state_machine`] for more information.
+pub struct Transitions<S, E> {
+    rules: Vec<Rule<S, E>>,
+    on_enter: Vec<(S, Rc<dyn Fn()>)>,
+    on_exit: Vec<(S, Rc<dyn Fn()>)>,
+}
+
+struct Rule<S, E> {
+    from: S,
+    event: E,
+    to: S,
+    guard: Option<Rc<dyn Fn() -> bool>>,
+}
+
+impl<S: PartialEq + Clone + 'static, E: PartialEq + 'static> Transitions<S, E> {
+    /// Create a state machine without any transitions.
+    pub fn new() -> Self {
+        Self {
+            rules: Vec::new(),
+            on_enter: Vec::new(),
+            on_exit: Vec::new(),
+        }
+    }
+
+    /// Move from `from` to `to` when `event` is sent.
+    pub fn on(mut self, from: S, event: E, to: S) -> Self {
+        self.rules.push(Rule {
+            from,
+            event,
+            to,
+            guard: None,
+        });
+        self
+    }
+
+    /// Move from `from` to `to` when `event` is sent, but only if `guard` returns `true`.
+    ///
+    /// If several transitions match an event, the first one whose guard allows it is taken.
+    pub fn on_if(mut self, from: S, event: E, to: S, guard: impl Fn() -> bool + 'static) -> Self {
+        self.rules.push(Rule {
+            from,
+            event,
+            to,
+            guard: Some(Rc::new(guard)),
+        });
+        self
+    }
+
+    /// Run `hook` whenever the machine moves into `state`.
+    pub fn on_enter(mut self, state: S, hook: impl Fn() + 'static) -> Self {
+        self.on_enter.push((state, Rc::new(hook)));
+        self
+    }
+
+    /// Run `hook` whenever the machine moves out of `state`.
+    pub fn on_exit(mut self, state: S, hook: impl Fn() + 'static) -> Self {
+        self.on_exit.push((state, Rc::new(hook)));
+        self
+    }
+
+    /// Find the state `event` moves `state` to.
+    fn next(&self, state: &S, event: &E) -> Result<S, TransitionError> {
+        let mut result = Err(TransitionError::NoTransition);
+        for rule in self
+            .rules
+            .iter()
+            .filter(|rule| rule.from == *state && rule.event == *event)
+        {
+            match &rule.guard {
+                Some(guard) if !guard() => result = Err(TransitionError::Blocked),
+                _ => return Ok(rule.to.clone()),
+            }
+        }
+        result
+    }
+
+    fn hooks(hooks: &[(S, Rc<dyn Fn()>)], state: &S) -> Vec<Rc<dyn Fn()>> {
+        hooks
+            .iter()
+            .filter(|(hook_state, _)| hook_state == state)
+            .map(|(_, hook)| hook.clone())
+            .collect()
+    }
+}
+
+impl<S: PartialEq + Clone + 'static, E: PartialEq + 'static> Default for Transitions<S, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Represents errors when sending an event to a state machine.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransitionError {
+    /// The current state has no transition for the event.
+    NoTransition,
+    /// Every transition for the event was rejected by its guard.
+    Blocked,
+}
+
+impl std::error::Error for TransitionError {}
+impl fmt::Display for TransitionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TransitionError::NoTransition => {
+                write!(f, "the current state has no transition for the event")
+            }
+            TransitionError::Blocked => write!(f, "the transition was rejected by its guard"),
+        }
+    }
+}
+
+/// A handle to a state machine created with [`use_state_machine`].
+pub struct UseStateMachine<S: 'static, E: 'static> {
+    state: Signal<S>,
+    transitions: CopyValue<Transitions<S, E>>,
+}
+
+// Manually implement Clone, Copy, and PartialEq as #[derive] thinks that S and E need to implement these (they don't).
+
+impl<S, E> Clone for UseStateMachine<S, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S, E> Copy for UseStateMachine<S, E> {}
+
+impl<S, E> PartialEq for UseStateMachine<S, E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.state == other.state
+    }
+}
+
+impl<S: PartialEq + Clone + 'static, E: PartialEq + 'static> UseStateMachine<S, E> {
+    /// Get the current state.
+    pub fn current(&self) -> S {
+        self.state.read().clone()
+    }
+
+    /// Get a signal of the current state.
+    pub fn state(&self) -> ReadOnlySignal<S> {
+        self.state.into()
+    }
+
+    /// Check whether sending `event` would change the state, without changing it.
+    pub fn can(&self, event: &E) -> bool {
+        self.transitions
+            .read()
+            .next(&self.state.peek(), event)
+            .is_ok()
+    }
+
+    /// Send an event to the machine, moving it to the next state.
+    ///
+    /// The exit hooks of the current state run before the state changes, and the entry hooks of the next state
+    /// run after it. Hooks may send further events.
+    pub fn send(&mut self, event: E) -> Result<S, TransitionError> {
+        let current = self.state.peek().clone();
+        let (next, on_exit, on_enter) = {
+            let transitions = self.transitions.read();
+            let next = transitions.next(&current, &event)?;
+            let on_exit = Transitions::<S, E>::hooks(&transitions.on_exit, &current);
+            let on_enter = Transitions::<S, E>::hooks(&transitions.on_enter, &next);
+            (next, on_exit, on_enter)
+        };
+
+        for hook in on_exit {
+            hook();
+        }
+        self.state.set(next.clone());
+        for hook in on_enter {
+            hook();
+        }
+        Ok(next)
+    }
+}
+
+/// A hook for a typed state machine, a structured alternative to an enum that is matched on in every event handler.
+///
+/// Events only change the state if `transitions` allows it, so impossible flows like submitting a form twice are
+/// rejected in one place.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::sync::{use_state_machine, Transitions};
+///
+/// #[derive(Clone, PartialEq, Debug)]
+/// enum Checkout {
+///     Cart,
+///     Paying,
+///     Done,
+/// }
+///
+/// #[derive(PartialEq)]
+/// enum Event {
+///     Pay,
+///     Paid,
+///     Cancel,
+/// }
+///
+/// fn App() -> Element {
+///     let accepted_terms = use_signal(|| false);
+///     let mut checkout = use_state_machine(Checkout::Cart, move || {
+///         Transitions::new()
+///             .on_if(Checkout::Cart, Event::Pay, Checkout::Paying, move || accepted_terms())
+///             .on(Checkout::Paying, Event::Paid, Checkout::Done)
+///             .on(Checkout::Paying, Event::Cancel, Checkout::Cart)
+///             .on_enter(Checkout::Done, || tracing::info!("Order placed"))
+///     });
+///
+///     rsx! {
+///         p { "{checkout.current():?}" }
+///         button {
+///             disabled: !checkout.can(&Event::Pay),
+///             onclick: move |_| {
+///                 checkout.send(Event::Pay).ok();
+///             },
+///             "Pay"
+///         }
+///     }
+/// }
+/// ```
+pub fn use_state_machine<S: PartialEq + Clone + 'static, E: PartialEq + 'static>(
+    initial: S,
+    transitions: impl FnOnce() -> Transitions<S, E>,
+) -> UseStateMachine<S, E> {
+    use_hook(|| UseStateMachine {
+        state: Signal::new(initial),
+        transitions: CopyValue::new(transitions()),
+    })
+}
+
+/// A hook for a state machine like [`use_state_machine`] whose state is persisted across application reloads.
+///
+/// The state is stored with [`use_persistent`](crate::storage::use_persistent) under `key`. Entry hooks don't run
+/// for the restored state.
+#[cfg(feature = "storage")]
+#[track_caller]
+pub fn use_persistent_state_machine<S, E>(
+    key: impl ToString,
+    initial: S,
+    transitions: impl FnOnce() -> Transitions<S, E>,
+) -> UseStateMachine<S, E>
+where
+    S: serde::Serialize
+        + serde::de::DeserializeOwned
+        + Default
+        + Clone
+        + Send
+        + Sync
+        + PartialEq
+        + 'static,
+    E: PartialEq + 'static,
+{
+    let state = crate::storage::use_persistent(key, || initial);
+    use_hook(|| UseStateMachine {
+        state,
+        transitions: CopyValue::new(transitions()),
+    })
+}
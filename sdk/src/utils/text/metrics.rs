@@ -0,0 +1,292 @@
+use dioxus::prelude::*;
+
+/// The font to measure text in.
+///
+/// This mirrors the CSS font properties, so the measurements match text rendered with the same style.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextStyle {
+    /// A CSS font family list, like `Inter, sans-serif`.
+    pub font_family: String,
+    /// The font size in pixels.
+    pub font_size: f64,
+    /// The font weight, from `100` to `900`.
+    pub font_weight: u16,
+    /// Whether the text is italic.
+    pub italic: bool,
+    /// The extra space after every character in pixels.
+    pub letter_spacing: f64,
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        Self {
+            font_family: "sans-serif".to_string(),
+            font_size: 16.0,
+            font_weight: 400,
+            italic: false,
+            letter_spacing: 0.0,
+        }
+    }
+}
+
+impl TextStyle {
+    /// Create a style for a font family and size in pixels.
+    pub fn new(font_family: impl Into<String>, font_size: f64) -> Self {
+        Self {
+            font_family: font_family.into(),
+            font_size,
+            ..Default::default()
+        }
+    }
+
+    /// The style as a value of the CSS `font` property, like `italic 700 16px Inter`.
+    pub fn css_font(&self) -> String {
+        let style = if self.italic { "italic " } else { "" };
+        format!(
+            "{style}{} {}px {}",
+            self.font_weight, self.font_size, self.font_family
+        )
+    }
+}
+
+/// The size of a line of text.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TextMetrics {
+    /// The width of the text in pixels.
+    pub width: f64,
+    /// How far the font reaches above the baseline in pixels.
+    pub ascent: f64,
+    /// How far the font reaches below the baseline in pixels.
+    pub descent: f64,
+}
+
+impl TextMetrics {
+    /// The height of a line of the font in pixels, without extra line spacing.
+    pub fn height(&self) -> f64 {
+        self.ascent + self.descent
+    }
+}
+
+/// Measure a line of text in a style, without adding it to the page.
+///
+/// This uses the canvas `measureText` API of the web page or the desktop webview, so the width matches the rendered
+/// text, including fonts loaded by the page. Where there is no page to measure with, like during server side
+/// rendering, the size is estimated with [`estimate_text_metrics`].
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::text::{measure_text, TextStyle};
+///
+/// #[component]
+/// fn Label(text: String) -> Element {
+///     let width = use_resource(move || {
+///         let text = text.clone();
+///         async move { measure_text(&text, &TextStyle::new("Inter", 14.0)).await.width }
+///     });
+///
+///     rsx! { "The label is {width().unwrap_or_default()}px wide" }
+/// }
+/// ```
+pub async fn measure_text(text: &str, style: &TextStyle) -> TextMetrics {
+    measure_texts(&[text], style)
+        .await
+        .pop()
+        .unwrap_or_default()
+}
+
+/// Measure many lines of text in the same style at once, like the rows of a virtualized list.
+///
+/// This is faster than measuring every line with [`measure_text`], which needs a round trip to the webview each on
+/// desktop.
+pub async fn measure_texts(texts: &[&str], style: &TextStyle) -> Vec<TextMetrics> {
+    match measure_platform(texts, style).await {
+        Some(mut metrics) if metrics.len() == texts.len() => {
+            // Canvas letter spacing isn't supported everywhere, so it is added here instead.
+            for (metrics, text) in metrics.iter_mut().zip(texts) {
+                metrics.width += style.letter_spacing * text.chars().count() as f64;
+            }
+            metrics
+        }
+        _ => texts
+            .iter()
+            .map(|text| estimate_text_metrics(text, style))
+            .collect(),
+    }
+}
+
+/// Estimate the size of a line of text from the typical widths of characters, without a font.
+///
+/// This is only a rough guess, as fonts differ a lot. It is what [`measure_text`] falls back to where there is no
+/// page to measure with, and can size placeholders before the real measurement arrives.
+pub fn estimate_text_metrics(text: &str, style: &TextStyle) -> TextMetrics {
+    let monospace = style.font_family.to_lowercase().contains("mono");
+    let ems: f64 = text
+        .chars()
+        .map(|c| match c {
+            _ if monospace => 0.6,
+            _ if is_wide(c) => 1.0,
+            'i' | 'j' | 'l' | '.' | ',' | '\'' | '|' | '!' | ':' | ';' => 0.25,
+            ' ' | 'f' | 'r' | 't' | '(' | ')' | '[' | ']' | '-' => 0.32,
+            'm' | 'w' | 'M' | 'W' | '@' => 0.85,
+            '0'..='9' => 0.55,
+            _ if c.is_uppercase() => 0.65,
+            _ => 0.52,
+        })
+        .sum();
+    let weight = if style.font_weight >= 600 { 1.06 } else { 1.0 };
+
+    TextMetrics {
+        width: ems * weight * style.font_size + style.letter_spacing * text.chars().count() as f64,
+        ascent: 0.8 * style.font_size,
+        descent: 0.2 * style.font_size,
+    }
+}
+
+/// Whether a character takes a whole em, like CJK characters.
+fn is_wide(c: char) -> bool {
+    matches!(c,
+        '\u{1100}'..='\u{115F}'
+        | '\u{2E80}'..='\u{A4CF}'
+        | '\u{AC00}'..='\u{D7A3}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{FE30}'..='\u{FE4F}'
+        | '\u{FF00}'..='\u{FF60}'
+        | '\u{FFE0}'..='\u{FFE6}'
+        | '\u{1F300}'..='\u{1FAFF}'
+        | '\u{20000}'..='\u{3FFFD}'
+    )
+}
+
+/// A hook for the size of a line of text, measured again whenever the text or style changes.
+///
+/// The size is `None` until the first measurement arrives. See [`measure_text`] for how text is measured.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::text::{use_text_metrics, TextStyle};
+///
+/// fn Editor() -> Element {
+///     let mut line = use_signal(|| "Hello".to_string());
+///     let style = use_signal(|| TextStyle::new("monospace", 14.0));
+///     let metrics = use_text_metrics(line.into(), style.into());
+///
+///     rsx! {
+///         input { value: "{line}", oninput: move |e| line.set(e.value()) }
+///         if let Some(metrics) = metrics() {
+///             div { style: "width: {metrics.width}px; height: 2px; background: blue;" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_text_metrics(
+    text: ReadOnlySignal<String>,
+    style: ReadOnlySignal<TextStyle>,
+) -> ReadOnlySignal<Option<TextMetrics>> {
+    let measured = use_resource(move || async move { measure_text(&text(), &style()).await });
+    use_memo(move || *measured.read()).into()
+}
+
+#[cfg(target_family = "wasm")]
+async fn measure_platform(texts: &[&str], style: &TextStyle) -> Option<Vec<TextMetrics>> {
+    use wasm_bindgen::JsCast;
+    use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+
+    thread_local! {
+        /// The canvas context is reused, as creating a canvas for every measurement is slow.
+        static CONTEXT: Option<CanvasRenderingContext2d> = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.create_element("canvas").ok())
+            .and_then(|canvas| canvas.dyn_into::<HtmlCanvasElement>().ok())
+            .and_then(|canvas| canvas.get_context("2d").ok().flatten())
+            .and_then(|context| context.dyn_into().ok());
+    }
+
+    CONTEXT.with(|context| {
+        let context = context.as_ref()?;
+        context.set_font(&style.css_font());
+        texts
+            .iter()
+            .map(|text| {
+                let metrics = context.measure_text(text).ok()?;
+                Some(TextMetrics {
+                    width: metrics.width(),
+                    ascent: metrics.font_bounding_box_ascent(),
+                    descent: metrics.font_bounding_box_descent(),
+                })
+            })
+            .collect()
+    })
+}
+
+#[cfg(not(target_family = "wasm"))]
+async fn measure_platform(texts: &[&str], style: &TextStyle) -> Option<Vec<TextMetrics>> {
+    /// Measures the texts with a canvas in the webview. Older webviews don't have the font bounding box, so the
+    /// bounding box of the text itself is used there.
+    const MEASURE: &str = r#"
+        const [font, texts] = await dioxus.recv();
+        window.__dioxusMeasureText ??= document.createElement("canvas").getContext("2d");
+        const context = window.__dioxusMeasureText;
+        context.font = font;
+        dioxus.send(texts.map((text) => {
+            const metrics = context.measureText(text);
+            return [
+                metrics.width,
+                metrics.fontBoundingBoxAscent ?? metrics.actualBoundingBoxAscent,
+                metrics.fontBoundingBoxDescent ?? metrics.actualBoundingBoxDescent,
+            ];
+        }));
+    "#;
+
+    // There is no webview to measure with outside of a running app.
+    current_scope_id()?;
+    let mut eval = eval(MEASURE);
+    eval.send(serde_json::json!([style.css_font(), texts]))
+        .ok()?;
+    let measured = eval.recv().await.ok()?;
+    measured
+        .as_array()?
+        .iter()
+        .map(|metrics| {
+            Some(TextMetrics {
+                width: metrics.get(0)?.as_f64()?,
+                ascent: metrics.get(1)?.as_f64()?,
+                descent: metrics.get(2)?.as_f64()?,
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn test_estimate_text_metrics() {
+    let style = TextStyle::new("monospace", 10.0);
+    let metrics = estimate_text_metrics("abcd", &style);
+    assert!((metrics.width - 24.0).abs() < 1e-9);
+    assert_eq!(metrics.height(), 10.0);
+
+    // Narrow characters are estimated narrower than wide ones, and CJK characters take a whole em.
+    let style = TextStyle::new("Inter", 10.0);
+    assert!(
+        estimate_text_metrics("iiii", &style).width < estimate_text_metrics("MMMM", &style).width
+    );
+    assert_eq!(estimate_text_metrics("漢字", &style).width, 20.0);
+
+    let spaced = TextStyle {
+        letter_spacing: 1.0,
+        ..TextStyle::new("monospace", 10.0)
+    };
+    assert!((estimate_text_metrics("abcd", &spaced).width - 28.0).abs() < 1e-9);
+
+    assert_eq!(
+        TextStyle {
+            italic: true,
+            font_weight: 700,
+            ..TextStyle::new("Inter, sans-serif", 14.0)
+        }
+        .css_font(),
+        "italic 700 14px Inter, sans-serif"
+    );
+}
@@ -0,0 +1,4 @@
+//! Measure text without rendering it.
+
+mod metrics;
+pub use metrics::*;
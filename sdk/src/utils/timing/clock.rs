@@ -0,0 +1,226 @@
+//! The timer abstraction used by all timing utilities.
+
+use std::time::Duration;
+
+/// Wait for the given [`Duration`] to pass.
+///
+/// All timers in this crate go through this function, so they can be driven by a [`MockClock`] in tests.
+pub(crate) async fn sleep(duration: Duration) {
+    #[cfg(feature = "test-utils")]
+    if let Some(clock) = MockClock::current() {
+        return clock.sleep(duration).await;
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    tokio::time::sleep(duration).await;
+
+    #[cfg(target_family = "wasm")]
+    gloo_timers::future::sleep(duration).await;
+}
+
+#[cfg(feature = "test-utils")]
+pub use mock::MockClock;
+
+#[cfg(feature = "test-utils")]
+mod mock {
+    use std::{
+        cell::RefCell,
+        future::Future,
+        pin::Pin,
+        rc::Rc,
+        task::{Context, Poll, Waker},
+        time::Duration,
+    };
+
+    thread_local! {
+        /// The mock clock installed on this thread.
+        static CURRENT: RefCell<Option<MockClock>> = const { RefCell::new(None) };
+    }
+
+    #[derive(Default)]
+    struct MockClockInner {
+        now: Duration,
+        /// The id of the next timer.
+        next_id: u64,
+        sleepers: Vec<Sleeper>,
+    }
+
+    /// A timer waiting for the clock to reach its deadline.
+    struct Sleeper {
+        id: u64,
+        deadline: Duration,
+        waker: Waker,
+    }
+
+    /// A virtual clock that drives all timers in this crate for testing.
+    ///
+    /// Once installed, intervals, debounces, and other timers created on the current thread
+    /// only make progress when the clock is advanced with [`MockClock::advance`], so tests
+    /// don't have to wait in real time.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use dioxus_sdk::utils::timing::MockClock;
+    /// use std::time::Duration;
+    ///
+    /// let clock = MockClock::install();
+    /// let mut dom = VirtualDom::new(app);
+    /// dom.rebuild_in_place();
+    ///
+    /// clock.advance(Duration::from_secs(1));
+    /// dom.wait_for_work().await;
+    /// ```
+    #[derive(Clone, Default)]
+    pub struct MockClock {
+        inner: Rc<RefCell<MockClockInner>>,
+    }
+
+    impl MockClock {
+        /// Install a new mock clock on the current thread, replacing any previously installed clock.
+        pub fn install() -> Self {
+            let clock = Self::default();
+            CURRENT.with(|current| *current.borrow_mut() = Some(clock.clone()));
+            clock
+        }
+
+        /// Remove the mock clock from the current thread so timers use real time again.
+        pub fn uninstall() {
+            CURRENT.with(|current| current.borrow_mut().take());
+        }
+
+        /// Get the mock clock installed on the current thread, if any.
+        pub fn current() -> Option<Self> {
+            CURRENT.with(|current| current.borrow().clone())
+        }
+
+        /// The time that has passed on this clock since it was installed.
+        pub fn now(&self) -> Duration {
+            self.inner.borrow().now
+        }
+
+        /// Move the clock forward, waking every timer whose deadline has passed.
+        pub fn advance(&self, duration: Duration) {
+            let woken = {
+                let mut inner = self.inner.borrow_mut();
+                inner.now += duration;
+                let now = inner.now;
+                let (woken, sleeping) = std::mem::take(&mut inner.sleepers)
+                    .into_iter()
+                    .partition::<Vec<_>, _>(|sleeper| sleeper.deadline <= now);
+                inner.sleepers = sleeping;
+                woken
+            };
+
+            for sleeper in woken {
+                sleeper.waker.wake();
+            }
+        }
+
+        /// The number of timers waiting for the clock.
+        #[cfg(test)]
+        pub(crate) fn sleepers(&self) -> usize {
+            self.inner.borrow().sleepers.len()
+        }
+
+        pub(crate) fn sleep(&self, duration: Duration) -> MockSleep {
+            let mut inner = self.inner.borrow_mut();
+            let id = inner.next_id;
+            inner.next_id += 1;
+            MockSleep {
+                clock: self.clone(),
+                id,
+                deadline: inner.now + duration,
+            }
+        }
+    }
+
+    /// A future that completes once the mock clock reaches its deadline.
+    pub(crate) struct MockSleep {
+        clock: MockClock,
+        id: u64,
+        deadline: Duration,
+    }
+
+    impl Future for MockSleep {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            let mut inner = self.clock.inner.borrow_mut();
+            if inner.now >= self.deadline {
+                return Poll::Ready(());
+            }
+            // A timer polled again only keeps its latest waker, so it is woken once.
+            match inner
+                .sleepers
+                .iter_mut()
+                .find(|sleeper| sleeper.id == self.id)
+            {
+                Some(sleeper) => {
+                    if !sleeper.waker.will_wake(cx.waker()) {
+                        sleeper.waker = cx.waker().clone();
+                    }
+                }
+                None => inner.sleepers.push(Sleeper {
+                    id: self.id,
+                    deadline: self.deadline,
+                    waker: cx.waker().clone(),
+                }),
+            }
+            Poll::Pending
+        }
+    }
+
+    impl Drop for MockSleep {
+        fn drop(&mut self) {
+            if let Ok(mut inner) = self.clock.inner.try_borrow_mut() {
+                inner.sleepers.retain(|sleeper| sleeper.id != self.id);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "test-utils")]
+#[test]
+fn test_mock_clock() {
+    use futures::{executor::block_on, future::poll_fn, FutureExt};
+    use std::task::Poll;
+
+    let clock = MockClock::install();
+    let mut timer = Box::pin(sleep(Duration::from_secs(2)));
+
+    block_on(poll_fn(|cx| {
+        assert!(timer.poll_unpin(cx).is_pending());
+        Poll::Ready(())
+    }));
+    clock.advance(Duration::from_secs(1));
+    block_on(poll_fn(|cx| {
+        assert!(timer.poll_unpin(cx).is_pending());
+        Poll::Ready(())
+    }));
+    clock.advance(Duration::from_secs(1));
+    block_on(timer);
+
+    MockClock::uninstall();
+}
+
+#[cfg(feature = "test-utils")]
+#[test]
+fn test_mock_clock_keeps_one_waker_per_timer() {
+    use futures::{executor::block_on, future::poll_fn, FutureExt};
+    use std::task::Poll;
+
+    let clock = MockClock::install();
+    let mut timer = Box::pin(sleep(Duration::from_secs(1)));
+    for _ in 0..3 {
+        block_on(poll_fn(|cx| {
+            assert!(timer.poll_unpin(cx).is_pending());
+            Poll::Ready(())
+        }));
+    }
+    assert_eq!(clock.sleepers(), 1);
+    drop(timer);
+    assert_eq!(clock.sleepers(), 0);
+
+    MockClock::uninstall();
+}
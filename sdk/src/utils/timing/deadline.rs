@@ -0,0 +1,170 @@
+use dioxus::prelude::{spawn, use_hook, Readable, Signal, Task, Writable};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::{
+    clock,
+    debugger::{self, TimerKind},
+};
+
+/// The longest a wall-clock timer sleeps before checking the system clock again.
+///
+/// Timers run on the monotonic clock, so this bounds how late a [`SystemTime`] deadline fires
+/// after the system clock is changed or the device wakes up from sleep.
+const WALL_CLOCK_CHECK: Duration = Duration::from_secs(1);
+
+/// A point in time for [`sleep_until`] and [`use_timeout_at`] to wait for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Deadline {
+    /// A point on the monotonic clock. Not affected by changes to the system clock.
+    ///
+    /// Not available on the web, where [`std::time::Instant`] can't be read.
+    #[cfg(not(target_family = "wasm"))]
+    Instant(std::time::Instant),
+    /// A point on the system clock, like a calendar date.
+    ///
+    /// If the system clock changes while waiting, the deadline follows it.
+    SystemTime(SystemTime),
+}
+
+impl Deadline {
+    /// The time left until the deadline, or zero if it has passed.
+    pub fn remaining(&self) -> Duration {
+        match self {
+            #[cfg(not(target_family = "wasm"))]
+            Self::Instant(instant) => instant.saturating_duration_since(std::time::Instant::now()),
+            Self::SystemTime(time) => time
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .saturating_sub(now_since_epoch()),
+        }
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl From<std::time::Instant> for Deadline {
+    fn from(instant: std::time::Instant) -> Self {
+        Self::Instant(instant)
+    }
+}
+
+impl From<SystemTime> for Deadline {
+    fn from(time: SystemTime) -> Self {
+        Self::SystemTime(time)
+    }
+}
+
+/// The current system time as a [`Duration`] since the Unix epoch.
+#[cfg(not(target_family = "wasm"))]
+pub(super) fn now_since_epoch() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+/// The current system time as a [`Duration`] since the Unix epoch.
+#[cfg(target_family = "wasm")]
+pub(super) fn now_since_epoch() -> Duration {
+    // `SystemTime::now` panics on the web, so ask the browser instead.
+    Duration::from_secs_f64(js_sys::Date::now() / 1000.0)
+}
+
+/// Wait until the given [`Deadline`] has passed.
+///
+/// Deadlines that already passed complete right away. [`SystemTime`] deadlines are checked against the
+/// system clock at least every second, so they still fire on time when the clock is adjusted.
+///
+/// With a [`MockClock`](super::MockClock) installed, the time left is measured once and then waited
+/// for on the mock clock.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use dioxus_sdk::utils::timing::sleep_until;
+/// use std::time::{Duration, SystemTime};
+///
+/// sleep_until(SystemTime::now() + Duration::from_secs(60)).await;
+/// ```
+pub async fn sleep_until(deadline: impl Into<Deadline>) {
+    let deadline = deadline.into();
+
+    #[cfg(feature = "test-utils")]
+    if clock::MockClock::current().is_some() {
+        return clock::sleep(deadline.remaining()).await;
+    }
+
+    loop {
+        let remaining = deadline.remaining();
+        if remaining.is_zero() {
+            break;
+        }
+
+        match deadline {
+            #[cfg(not(target_family = "wasm"))]
+            Deadline::Instant(_) => clock::sleep(remaining).await,
+            Deadline::SystemTime(_) => clock::sleep(remaining.min(WALL_CLOCK_CHECK)).await,
+        }
+    }
+}
+
+/// The interface for a pending timeout.
+///
+/// See [`use_timeout_at`] for more information.
+#[derive(Clone, PartialEq, Copy)]
+pub struct UseTimeoutAt {
+    inner: Signal<Option<Task>>,
+}
+
+impl UseTimeoutAt {
+    /// Cancel the timeout if it hasn't run yet.
+    pub fn cancel(&mut self) {
+        if let Some(timeout) = self.inner.write().take() {
+            timeout.cancel();
+        }
+    }
+
+    /// Whether the timeout is still waiting to run.
+    pub fn is_pending(&self) -> bool {
+        self.inner.read().is_some()
+    }
+}
+
+/// Calls a function once the given [`Deadline`] has passed.
+///
+/// Accepts an [`Instant`](std::time::Instant) or a [`SystemTime`]. Use a [`SystemTime`] for calendar
+/// events and reminders, so the timeout follows changes to the system clock. See [`sleep_until`] for details.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::timing::use_timeout_at;
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// #[component]
+/// fn Reminder(at_unix_secs: u64) -> Element {
+///     let mut due = use_signal(|| false);
+///     use_timeout_at(UNIX_EPOCH + Duration::from_secs(at_unix_secs), move || due.set(true));
+///
+///     rsx! {
+///         if due() { "It's time!" } else { "Waiting..." }
+///     }
+/// }
+/// ```
+pub fn use_timeout_at(
+    deadline: impl Into<Deadline>,
+    action: impl FnOnce() + 'static,
+) -> UseTimeoutAt {
+    let inner = use_hook(|| {
+        let deadline = deadline.into();
+        let mut inner = Signal::new(None);
+        inner.set(Some(spawn(async move {
+            let _timer = debugger::track(TimerKind::Timeout, deadline.remaining());
+            sleep_until(deadline).await;
+            inner.set(None);
+            action();
+        })));
+        inner
+    });
+
+    UseTimeoutAt { inner }
+}
@@ -3,7 +3,19 @@ use futures::{
     channel::mpsc::{self, UnboundedSender as Sender},
     StreamExt,
 };
-use std::time::Duration;
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use super::{
+    clock,
+    debugger::{self, TimerKind},
+};
 
 /// The interface for calling a debounce.
 ///
@@ -77,12 +89,8 @@ pub fn use_debounce<T>(time: Duration, cb: impl FnOnce(T) + Copy + 'static) -> U
                     }
 
                     current_task = Some(spawn(async move {
-                        #[cfg(not(target_family = "wasm"))]
-                        tokio::time::sleep(time).await;
-
-                        #[cfg(target_family = "wasm")]
-                        gloo_timers::future::sleep(time).await;
-
+                        let _timer = debugger::track(TimerKind::Debounce, time);
+                        clock::sleep(time).await;
                         cb(data);
                     }));
                 }
@@ -92,3 +100,94 @@ pub fn use_debounce<T>(time: Duration, cb: impl FnOnce(T) + Copy + 'static) -> U
         debouncer
     })
 }
+
+/// A debounce that can be used outside of components, like in background tasks or services.
+///
+/// Every call to [`Debouncer::wait`] or [`Debouncer::run`] restarts the countdown, and only the latest call
+/// finishes once the [`Duration`] has passed without another call. Clones share the same countdown, so a
+/// debouncer can be handed to every task that produces work. It uses the same timers as [`use_debounce`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use dioxus_sdk::utils::timing::Debouncer;
+/// use std::time::Duration;
+///
+/// let debouncer = Debouncer::new(Duration::from_millis(500));
+///
+/// // Called for every local change, but only syncs once the changes settle.
+/// async fn on_change(debouncer: Debouncer) {
+///     debouncer.run(|| async { println!("syncing") }).await;
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Debouncer {
+    duration: Duration,
+    generation: Arc<AtomicU64>,
+}
+
+impl Debouncer {
+    /// Create a debouncer that waits for the provided [`Duration`] without calls.
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            generation: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Get the duration the debouncer waits for.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Restart the countdown and wait for it.
+    ///
+    /// Returns `true` if no other call was made meanwhile, and `false` if this call was superseded or cancelled.
+    pub async fn wait(&self) -> bool {
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let _timer = debugger::track(TimerKind::Debounce, self.duration);
+        clock::sleep(self.duration).await;
+        self.generation.load(Ordering::SeqCst) == generation
+    }
+
+    /// Restart the countdown and call `f` once it finishes, unless another call was made meanwhile.
+    ///
+    /// Returns the output of `f`, or `None` if this call was superseded or cancelled.
+    pub async fn run<F: Future>(&self, f: impl FnOnce() -> F) -> Option<F::Output> {
+        if self.wait().await {
+            Some(f().await)
+        } else {
+            None
+        }
+    }
+
+    /// Cancel the pending call, if any.
+    pub fn cancel(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(feature = "test-utils")]
+#[test]
+fn test_debouncer() {
+    use futures::{executor::block_on, future::poll_fn, FutureExt};
+    use std::task::Poll;
+
+    let clock = super::MockClock::install();
+    let debouncer = Debouncer::new(Duration::from_secs(1));
+    let mut first = Box::pin(debouncer.run(|| async { 1 }));
+    let mut second = Box::pin(debouncer.run(|| async { 2 }));
+
+    block_on(poll_fn(|cx| {
+        assert!(first.poll_unpin(cx).is_pending());
+        assert!(second.poll_unpin(cx).is_pending());
+        Poll::Ready(())
+    }));
+    clock.advance(Duration::from_secs(1));
+
+    // Only the latest call runs.
+    assert_eq!(block_on(first), None);
+    assert_eq!(block_on(second), Some(2));
+
+    super::MockClock::uninstall();
+}
@@ -0,0 +1,158 @@
+//! Tracking of the timers created by this crate.
+//!
+//! Timers register themselves with [`track`]. The registry is only kept with the `timer-debugger` feature,
+//! otherwise tracking compiles to nothing.
+
+use std::time::Duration;
+
+/// What created a timer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimerKind {
+    /// A [`use_interval`](super::use_interval).
+    Interval,
+    /// The pending call of a [`use_debounce`](super::use_debounce) or a [`Debouncer`](super::Debouncer).
+    Debounce,
+    /// A call waiting on a [`Throttler`](super::Throttler).
+    Throttle,
+    /// A playing [`use_tween`](super::use_tween).
+    Tween,
+    /// A pending [`use_timeout_at`](super::use_timeout_at).
+    Timeout,
+    /// A running [`use_poll`](super::use_poll).
+    Poll,
+    /// A running [`use_stopwatch`](super::use_stopwatch) or [`use_countdown`](super::use_countdown).
+    Stopwatch,
+    /// A pending [`use_long_press`](super::use_long_press) or [`use_double_click`](super::use_double_click).
+    Gesture,
+    /// A task waiting for an idle period, like [`run_when_idle`](super::run_when_idle).
+    Idle,
+}
+
+/// Keeps a timer registered until it is dropped. Create it inside the timer's task, so it is
+/// dropped with the task.
+pub(crate) struct TimerGuard {
+    #[cfg(feature = "timer-debugger")]
+    id: usize,
+}
+
+/// Register a timer until the returned guard is dropped.
+#[cfg(feature = "timer-debugger")]
+pub(crate) fn track(kind: TimerKind, period: Duration) -> TimerGuard {
+    registry::insert(kind, period)
+}
+
+/// Register a timer until the returned guard is dropped.
+#[cfg(not(feature = "timer-debugger"))]
+pub(crate) fn track(_kind: TimerKind, _period: Duration) -> TimerGuard {
+    TimerGuard {}
+}
+
+#[cfg(feature = "timer-debugger")]
+pub use registry::{use_timer_debugger, TimerInfo};
+
+#[cfg(feature = "timer-debugger")]
+mod registry {
+    use super::{TimerGuard, TimerKind};
+    use dioxus::prelude::*;
+    use std::{cell::RefCell, collections::BTreeMap, time::Duration};
+
+    /// How often [`use_timer_debugger`] refreshes its list.
+    const REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+
+    thread_local! {
+        /// The timers that are currently active on this thread, by id.
+        static TIMERS: RefCell<(usize, BTreeMap<usize, TimerInfo>)> = const { RefCell::new((0, BTreeMap::new())) };
+    }
+
+    /// An active timer created by this crate.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct TimerInfo {
+        /// A unique id for the timer.
+        pub id: usize,
+        pub kind: TimerKind,
+        /// How long the timer waits between runs.
+        pub period: Duration,
+        /// The scope that owns the timer's task.
+        pub scope: Option<ScopeId>,
+    }
+
+    impl TimerInfo {
+        /// Whether the scope that owns the timer has been unmounted while the timer is still active.
+        ///
+        /// Timers normally stop when their component unmounts, so this points at a leaked timer.
+        /// This must be called from inside the app that created the timer.
+        pub fn is_orphaned(&self) -> bool {
+            self.scope.is_some_and(|scope| scope.generation().is_none())
+        }
+    }
+
+    impl Drop for TimerGuard {
+        fn drop(&mut self) {
+            TIMERS.with(|timers| timers.borrow_mut().1.remove(&self.id));
+        }
+    }
+
+    pub(super) fn insert(kind: TimerKind, period: Duration) -> TimerGuard {
+        TIMERS.with(|timers| {
+            let (next_id, timers) = &mut *timers.borrow_mut();
+            let id = *next_id;
+            *next_id += 1;
+            timers.insert(
+                id,
+                TimerInfo {
+                    id,
+                    kind,
+                    period,
+                    scope: current_scope_id(),
+                },
+            );
+            TimerGuard { id }
+        })
+    }
+
+    fn snapshot() -> Vec<TimerInfo> {
+        TIMERS.with(|timers| timers.borrow().1.values().cloned().collect())
+    }
+
+    /// A hook for listing every active interval, debounce, tween, and timeout created through this crate.
+    ///
+    /// This is useful for diagnosing timer leaks, like timers that keep running after their component unmounted.
+    /// The list is refreshed periodically. Requires the `timer-debugger` feature.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use dioxus::prelude::*;
+    /// use dioxus_sdk::utils::timing::use_timer_debugger;
+    ///
+    /// fn TimerOverlay() -> Element {
+    ///     let timers = use_timer_debugger();
+    ///
+    ///     rsx! {
+    ///         p { "{timers.read().len()} active timers" }
+    ///         for timer in timers.read().iter() {
+    ///             p {
+    ///                 "{timer.kind:?} every {timer.period:?} in {timer.scope:?}"
+    ///                 if timer.is_orphaned() { " (leaked)" }
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn use_timer_debugger() -> ReadOnlySignal<Vec<TimerInfo>> {
+        let mut timers = use_signal(snapshot);
+        use_hook(|| {
+            spawn(async move {
+                loop {
+                    super::super::clock::sleep(REFRESH_INTERVAL).await;
+                    let current = snapshot();
+                    if *timers.peek() != current {
+                        timers.set(current);
+                    }
+                }
+            })
+        });
+
+        ReadOnlySignal::new(timers)
+    }
+}
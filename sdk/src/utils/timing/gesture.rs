@@ -0,0 +1,311 @@
+use dioxus::{html::geometry::ClientPoint, prelude::*};
+use futures::{
+    channel::mpsc::{self, UnboundedReceiver, UnboundedSender},
+    future::{self, Either},
+    StreamExt,
+};
+use std::{pin::pin, time::Duration};
+
+use super::{
+    clock,
+    debugger::{self, TimerGuard, TimerKind},
+    interval::now,
+};
+
+/// How far, in pixels, the pointer may move before a gesture is cancelled.
+const MOVE_TOLERANCE: f64 = 10.0;
+
+enum PressInput {
+    Down(ClientPoint),
+    Move(ClientPoint),
+    Up,
+}
+
+/// The interface for a long press.
+///
+/// See [`use_long_press`] for more information.
+#[derive(Clone, Copy, PartialEq)]
+pub struct UseLongPress {
+    sender: Signal<UnboundedSender<PressInput>>,
+    pressing: Signal<bool>,
+    fired: Signal<bool>,
+}
+
+impl UseLongPress {
+    /// The handler for `onpointerdown`, which starts the press.
+    pub fn onpointerdown(&self) -> impl FnMut(PointerEvent) + Copy {
+        let press = *self;
+        move |event: PointerEvent| {
+            if event.is_primary() && event.trigger_button() == Some(MouseButton::Primary) {
+                press.send(PressInput::Down(event.client_coordinates()));
+            }
+        }
+    }
+
+    /// The handler for `onpointermove`, which cancels the press once the pointer moved too far.
+    pub fn onpointermove(&self) -> impl FnMut(PointerEvent) + Copy {
+        let press = *self;
+        move |event: PointerEvent| press.send(PressInput::Move(event.client_coordinates()))
+    }
+
+    /// The handler for `onpointerup` and `onpointercancel`, which cancels a press that didn't fire yet.
+    pub fn onpointerup(&self) -> impl FnMut(PointerEvent) + Copy {
+        let press = *self;
+        move |_: PointerEvent| press.send(PressInput::Up)
+    }
+
+    /// The handler for `onpointerleave`, which cancels a press that didn't fire yet.
+    pub fn onpointerleave(&self) -> impl FnMut(PointerEvent) + Copy {
+        self.onpointerup()
+    }
+
+    /// Whether the pointer is held down and the press didn't fire or get cancelled yet.
+    pub fn is_pressing(&self) -> ReadOnlySignal<bool> {
+        self.pressing.into()
+    }
+
+    /// Whether the latest press was a long press.
+    ///
+    /// The click that follows a long press is still delivered, so check this in `onclick` to ignore it.
+    pub fn is_long_press(&self) -> bool {
+        *self.fired.peek()
+    }
+
+    fn send(&self, input: PressInput) {
+        self.sender.peek().unbounded_send(input).ok();
+    }
+}
+
+/// A hook for running a callback when an element is held down for a provided [`Duration`].
+///
+/// Attach the returned handlers to the element. The press is cancelled when the pointer is released,
+/// leaves the element, or moves more than a few pixels before the duration has passed.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::timing::use_long_press;
+/// use std::time::Duration;
+///
+/// fn App() -> Element {
+///     let press = use_long_press(Duration::from_millis(500), || println!("long press"));
+///
+///     rsx! {
+///         button {
+///             onpointerdown: press.onpointerdown(),
+///             onpointermove: press.onpointermove(),
+///             onpointerup: press.onpointerup(),
+///             onpointerleave: press.onpointerleave(),
+///             onclick: move |_| {
+///                 if !press.is_long_press() {
+///                     println!("click");
+///                 }
+///             },
+///             "Hold me"
+///         }
+///     }
+/// }
+/// ```
+pub fn use_long_press(
+    duration: Duration,
+    mut on_long_press: impl FnMut() + 'static,
+) -> UseLongPress {
+    use_hook(|| {
+        let (sender, mut receiver) = mpsc::unbounded();
+        let mut long_press = UseLongPress {
+            sender: Signal::new(sender),
+            pressing: Signal::new(false),
+            fired: Signal::new(false),
+        };
+
+        spawn(async move {
+            // Where and when the current press started.
+            let mut press: Option<(ClientPoint, Duration, TimerGuard)> = None;
+            loop {
+                let input = match &press {
+                    None => receiver.next().await,
+                    Some((_, started, _)) => {
+                        let remaining = duration.saturating_sub(now().saturating_sub(*started));
+                        match next_within(&mut receiver, remaining).await {
+                            Some(input) => input,
+                            None => {
+                                press = None;
+                                long_press.pressing.set(false);
+                                long_press.fired.set(true);
+                                on_long_press();
+                                continue;
+                            }
+                        }
+                    }
+                };
+
+                match input {
+                    Some(PressInput::Down(point)) => {
+                        let timer = debugger::track(TimerKind::Gesture, duration);
+                        press = Some((point, now(), timer));
+                        long_press.fired.set(false);
+                        long_press.pressing.set(true);
+                    }
+                    Some(PressInput::Move(point)) => {
+                        if press
+                            .as_ref()
+                            .is_some_and(|(origin, ..)| moved(*origin, point))
+                        {
+                            press = None;
+                            long_press.pressing.set(false);
+                        }
+                    }
+                    Some(PressInput::Up) => {
+                        if press.take().is_some() {
+                            long_press.pressing.set(false);
+                        }
+                    }
+                    None => return,
+                }
+            }
+        });
+
+        long_press
+    })
+}
+
+enum ClickInput {
+    Click(MouseEvent),
+    Move(ClientPoint),
+    Leave,
+}
+
+/// The interface for detecting double clicks.
+///
+/// See [`use_double_click`] for more information.
+#[derive(Clone, Copy, PartialEq)]
+pub struct UseDoubleClick {
+    sender: Signal<UnboundedSender<ClickInput>>,
+}
+
+impl UseDoubleClick {
+    /// The handler for `onclick`.
+    pub fn onclick(&self) -> impl FnMut(MouseEvent) + Copy {
+        let double_click = *self;
+        move |event: MouseEvent| double_click.send(ClickInput::Click(event))
+    }
+
+    /// The handler for `onpointermove`, which forgets the first click once the pointer moved too far.
+    pub fn onpointermove(&self) -> impl FnMut(PointerEvent) + Copy {
+        let double_click = *self;
+        move |event: PointerEvent| double_click.send(ClickInput::Move(event.client_coordinates()))
+    }
+
+    /// The handler for `onpointerleave`, which forgets the first click.
+    pub fn onpointerleave(&self) -> impl FnMut(PointerEvent) + Copy {
+        let double_click = *self;
+        move |_: PointerEvent| double_click.send(ClickInput::Leave)
+    }
+
+    fn send(&self, input: ClickInput) {
+        self.sender.peek().unbounded_send(input).ok();
+    }
+}
+
+/// A hook for running a callback when an element is clicked twice within the provided [`Duration`].
+///
+/// Unlike `ondoubleclick`, the time between the clicks is chosen by the app instead of the system, and the
+/// first click is forgotten when the pointer leaves the element or moves more than a few pixels in between.
+/// The handler receives the second click.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::timing::use_double_click;
+/// use std::time::Duration;
+///
+/// fn App() -> Element {
+///     let mut likes = use_signal(|| 0);
+///     let double_click = use_double_click(Duration::from_millis(300), move |_| likes += 1);
+///
+///     rsx! {
+///         img {
+///             src: "photo.png",
+///             onclick: double_click.onclick(),
+///             onpointermove: double_click.onpointermove(),
+///             onpointerleave: double_click.onpointerleave(),
+///         }
+///         p { "{likes} likes" }
+///     }
+/// }
+/// ```
+pub fn use_double_click(
+    window: Duration,
+    mut handler: impl FnMut(MouseEvent) + 'static,
+) -> UseDoubleClick {
+    use_hook(|| {
+        let (sender, mut receiver) = mpsc::unbounded();
+
+        spawn(async move {
+            // Where and when the first click happened.
+            let mut first: Option<(ClientPoint, Duration, TimerGuard)> = None;
+            loop {
+                let input = match &first {
+                    None => receiver.next().await,
+                    Some((_, clicked, _)) => {
+                        let remaining = window.saturating_sub(now().saturating_sub(*clicked));
+                        match next_within(&mut receiver, remaining).await {
+                            Some(input) => input,
+                            None => {
+                                first = None;
+                                continue;
+                            }
+                        }
+                    }
+                };
+
+                match input {
+                    Some(ClickInput::Click(event)) => {
+                        let point = event.client_coordinates();
+                        match first.take() {
+                            Some((origin, ..)) if !moved(origin, point) => handler(event),
+                            // A click far away from the first one starts over.
+                            _ => {
+                                let timer = debugger::track(TimerKind::Gesture, window);
+                                first = Some((point, now(), timer));
+                            }
+                        }
+                    }
+                    Some(ClickInput::Move(point)) => {
+                        if first
+                            .as_ref()
+                            .is_some_and(|(origin, ..)| moved(*origin, point))
+                        {
+                            first = None;
+                        }
+                    }
+                    Some(ClickInput::Leave) => first = None,
+                    None => return,
+                }
+            }
+        });
+
+        UseDoubleClick {
+            sender: Signal::new(sender),
+        }
+    })
+}
+
+/// Wait for the next input, or return `None` once `timeout` has passed.
+async fn next_within<T>(
+    receiver: &mut UnboundedReceiver<T>,
+    timeout: Duration,
+) -> Option<Option<T>> {
+    let sleep = pin!(clock::sleep(timeout));
+    match future::select(sleep, receiver.next()).await {
+        Either::Left(_) => None,
+        Either::Right((input, _)) => Some(input),
+    }
+}
+
+/// Whether the pointer moved too far from where the gesture started.
+fn moved(origin: ClientPoint, point: ClientPoint) -> bool {
+    (point - origin).length() > MOVE_TOLERANCE
+}
@@ -0,0 +1,266 @@
+use dioxus::prelude::*;
+use futures::{
+    channel::mpsc::{self, UnboundedSender},
+    future::{self, Either},
+    StreamExt,
+};
+use std::{pin::pin, time::Duration};
+
+use super::{
+    clock,
+    debugger::{self, TimerKind},
+    interval::now,
+};
+
+/// The frame the idle heuristic measures the load with.
+const FRAME: Duration = Duration::from_millis(16);
+/// How late a frame may end and still count as idle.
+const MAX_LAG: Duration = Duration::from_millis(4);
+/// How many idle frames in a row start an idle period.
+const IDLE_FRAMES: u32 = 3;
+/// The length of an idle period found by the heuristic. Browsers don't hand out longer periods either.
+const IDLE_PERIOD: Duration = Duration::from_millis(50);
+
+/// The time left in the idle period an idle callback runs in.
+///
+/// Keep the work within [`IdleDeadline::time_remaining`] and schedule the rest for the next idle period, so the app
+/// stays responsive.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IdleDeadline {
+    end: Duration,
+    did_timeout: bool,
+}
+
+impl IdleDeadline {
+    /// Get how much of the idle period is left.
+    pub fn time_remaining(&self) -> Duration {
+        self.end.saturating_sub(now())
+    }
+
+    /// Whether the callback runs because its timeout passed before the app became idle.
+    pub fn did_timeout(&self) -> bool {
+        self.did_timeout
+    }
+
+    fn timed_out() -> Self {
+        Self {
+            end: now(),
+            did_timeout: true,
+        }
+    }
+}
+
+/// Wait until the app is idle, or until `timeout` has passed.
+///
+/// On the web, this uses `requestIdleCallback` where the browser supports it. Elsewhere, the app is considered idle
+/// once a few timers in a row fire on time, which means nothing else is keeping the runtime busy.
+pub async fn wait_for_idle(timeout: Duration) -> IdleDeadline {
+    #[cfg(target_family = "wasm")]
+    if let Some(deadline) = web::request_idle_callback(timeout).await {
+        return deadline;
+    }
+
+    let started = now();
+    let mut idle_frames = 0;
+    while idle_frames < IDLE_FRAMES {
+        if now().saturating_sub(started) >= timeout {
+            return IdleDeadline::timed_out();
+        }
+        let frame_started = now();
+        clock::sleep(FRAME).await;
+        let lag = now().saturating_sub(frame_started).saturating_sub(FRAME);
+        idle_frames = if lag <= MAX_LAG { idle_frames + 1 } else { 0 };
+    }
+
+    IdleDeadline {
+        end: now() + IDLE_PERIOD,
+        did_timeout: false,
+    }
+}
+
+/// Run a low-priority task once the app is idle, or once `timeout` has passed.
+///
+/// Use this for work that can wait, like pruning caches or compacting storage, so it doesn't compete with user
+/// interaction. The task is owned by the current component and is dropped with it. See [`wait_for_idle`] for how idle
+/// periods are found.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::timing::run_when_idle;
+/// use std::time::Duration;
+///
+/// fn App() -> Element {
+///     use_hook(|| {
+///         run_when_idle(
+///             |deadline| {
+///                 while deadline.time_remaining() > Duration::ZERO && prune_one_cache_entry() {}
+///             },
+///             Duration::from_secs(10),
+///         )
+///     });
+///
+///     rsx! { "..." }
+/// }
+/// ```
+pub fn run_when_idle(task: impl FnOnce(IdleDeadline) + 'static, timeout: Duration) -> Task {
+    spawn(async move {
+        let _timer = debugger::track(TimerKind::Idle, timeout);
+        let deadline = wait_for_idle(timeout).await;
+        task(deadline);
+    })
+}
+
+enum IdleInput {
+    Schedule,
+    Cancel,
+}
+
+/// The interface for scheduling an idle callback.
+///
+/// See [`use_idle_callback`] for more information.
+#[derive(Clone, Copy, PartialEq)]
+pub struct UseIdleCallback {
+    sender: Signal<UnboundedSender<IdleInput>>,
+    pending: Signal<bool>,
+}
+
+impl UseIdleCallback {
+    /// Run the callback in the next idle period. Scheduling it while it is pending does nothing.
+    pub fn schedule(&self) {
+        self.sender.peek().unbounded_send(IdleInput::Schedule).ok();
+    }
+
+    /// Cancel the pending callback, if any.
+    pub fn cancel(&self) {
+        self.sender.peek().unbounded_send(IdleInput::Cancel).ok();
+    }
+
+    /// Whether the callback is waiting for an idle period.
+    pub fn is_pending(&self) -> ReadOnlySignal<bool> {
+        self.pending.into()
+    }
+}
+
+/// A hook for running a callback in an idle period every time it is scheduled.
+///
+/// The callback runs once the app is idle, or once `timeout` has passed since it was scheduled. See
+/// [`wait_for_idle`] for how idle periods are found.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::timing::use_idle_callback;
+/// use std::time::Duration;
+///
+/// fn Editor() -> Element {
+///     let compact = use_idle_callback(Duration::from_secs(5), |deadline| {
+///         compact_history(deadline.time_remaining());
+///     });
+///
+///     rsx! {
+///         textarea { oninput: move |_| compact.schedule() }
+///     }
+/// }
+/// ```
+pub fn use_idle_callback(
+    timeout: Duration,
+    mut callback: impl FnMut(IdleDeadline) + 'static,
+) -> UseIdleCallback {
+    use_hook(|| {
+        let (sender, mut receiver) = mpsc::unbounded();
+        let mut idle_callback = UseIdleCallback {
+            sender: Signal::new(sender),
+            pending: Signal::new(false),
+        };
+
+        spawn(async move {
+            loop {
+                match receiver.next().await {
+                    Some(IdleInput::Schedule) => {}
+                    Some(IdleInput::Cancel) => continue,
+                    None => return,
+                }
+
+                idle_callback.pending.set(true);
+                let _timer = debugger::track(TimerKind::Idle, timeout);
+                let mut idle = pin!(wait_for_idle(timeout));
+                let deadline = loop {
+                    match future::select(idle.as_mut(), receiver.next()).await {
+                        Either::Left((deadline, _)) => break Some(deadline),
+                        Either::Right((Some(IdleInput::Schedule), _)) => {}
+                        Either::Right((Some(IdleInput::Cancel), _)) => break None,
+                        Either::Right((None, _)) => return,
+                    }
+                };
+                idle_callback.pending.set(false);
+
+                if let Some(deadline) = deadline {
+                    callback(deadline);
+                }
+            }
+        });
+
+        idle_callback
+    })
+}
+
+#[cfg(target_family = "wasm")]
+mod web {
+    use super::IdleDeadline;
+    use futures::channel::oneshot;
+    use std::time::Duration;
+    use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+    use web_sys::{IdleRequestOptions, Window};
+
+    /// Cancels the idle callback when the wait is dropped.
+    struct IdleRequest {
+        window: Window,
+        handle: u32,
+        _callback: Closure<dyn FnMut(web_sys::IdleDeadline)>,
+    }
+
+    impl Drop for IdleRequest {
+        fn drop(&mut self) {
+            self.window.cancel_idle_callback(self.handle);
+        }
+    }
+
+    /// Wait for `requestIdleCallback`, or return `None` if the browser doesn't support it.
+    pub(super) async fn request_idle_callback(timeout: Duration) -> Option<IdleDeadline> {
+        let window = web_sys::window()?;
+        // Safari doesn't support idle callbacks.
+        if !js_sys::Reflect::has(&window, &JsValue::from_str("requestIdleCallback"))
+            .unwrap_or(false)
+        {
+            return None;
+        }
+
+        let (tx, rx) = oneshot::channel();
+        let mut tx = Some(tx);
+        let callback = Closure::wrap(Box::new(move |deadline: web_sys::IdleDeadline| {
+            let idle = IdleDeadline {
+                end: super::now() + Duration::from_secs_f64(deadline.time_remaining() / 1000.0),
+                did_timeout: deadline.did_timeout(),
+            };
+            if let Some(tx) = tx.take() {
+                tx.send(idle).ok();
+            }
+        }) as Box<dyn FnMut(web_sys::IdleDeadline)>);
+
+        let options = IdleRequestOptions::new();
+        options.set_timeout(timeout.as_millis().min(u32::MAX as u128) as u32);
+        let handle = window
+            .request_idle_callback_with_options(callback.as_ref().unchecked_ref(), &options)
+            .ok()?;
+        let _request = IdleRequest {
+            window,
+            handle,
+            _callback: callback,
+        };
+
+        rx.await.ok()
+    }
+}
@@ -1,63 +1,481 @@
-use dioxus::prelude::{use_hook, Writable};
-use std::time::Duration;
+use dioxus::prelude::{spawn, use_effect, use_hook, ReadOnlySignal, Signal, Task, Writable};
+use std::{
+    any::Any,
+    cell::RefCell,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    rc::Rc,
+    time::Duration,
+};
+
+use super::{
+    clock,
+    deadline::now_since_epoch,
+    debugger::{self, TimerKind},
+};
 
 #[derive(Clone, PartialEq, Copy)]
 pub struct UseInterval {
-    inner: dioxus::prelude::Signal<InnerUseInterval>,
+    inner: Signal<InnerUseInterval>,
+    error: Signal<Option<TimerError>>,
 }
 
 struct InnerUseInterval {
-    #[cfg(target_family = "wasm")]
-    pub(crate) interval: Option<gloo_timers::callback::Interval>,
-
-    #[cfg(not(target_family = "wasm"))]
-    pub(crate) interval: Option<dioxus::prelude::Task>,
+    pub(crate) interval: Option<Task>,
+    /// Keeps [`use_interval_when`] from starting the interval again.
+    pub(crate) cancelled: bool,
 }
 
-#[cfg(target_family = "wasm")]
-impl Drop for InnerUseInterval {
-    fn drop(&mut self) {
-        if let Some(interval) = self.interval.take() {
+impl UseInterval {
+    /// Cancel the interval
+    pub fn cancel(&mut self) {
+        let mut inner = self.inner.write();
+        inner.cancelled = true;
+        if let Some(interval) = inner.interval.take() {
             interval.cancel();
         }
     }
+
+    /// Get the error of the last tick that failed, if any.
+    pub fn error(&self) -> ReadOnlySignal<Option<TimerError>> {
+        self.error.into()
+    }
 }
 
-impl UseInterval {
-    /// Cancel the interval
-    pub fn cancel(&mut self) {
-        if let Some(interval) = self.inner.write().interval.take() {
-            interval.cancel();
+/// Describes errors that may occur while running a timer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimerError {
+    /// The action panicked on the given tick.
+    Panicked { tick: u64, message: String },
+}
+
+impl std::error::Error for TimerError {}
+impl fmt::Display for TimerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Panicked { tick, message } => {
+                write!(f, "the action panicked on tick {tick}: {message}")
+            }
+        }
+    }
+}
+
+/// Information about a tick of an interval, passed to actions that take an argument.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TickInfo {
+    /// The number of this tick, starting at 1.
+    pub count: u64,
+    /// The time since the interval started.
+    pub elapsed: Duration,
+    /// How many ticks were skipped since the previous tick, because the app was busy or asleep.
+    pub missed: u64,
+}
+
+/// An action an interval can run on every tick.
+///
+/// This is implemented for closures that take no arguments and for closures that take a [`TickInfo`], so existing
+/// intervals keep working. Closures that take a [`TickInfo`] need to name its type, like `|tick: TickInfo|`.
+pub trait IntervalAction<Marker>: 'static {
+    /// Run the action for a tick.
+    fn tick(&mut self, info: TickInfo);
+}
+
+impl<F: FnMut() + 'static> IntervalAction<()> for F {
+    fn tick(&mut self, _info: TickInfo) {
+        self()
+    }
+}
+
+impl<F: FnMut(TickInfo) + 'static> IntervalAction<TickInfo> for F {
+    fn tick(&mut self, info: TickInfo) {
+        self(info)
+    }
+}
+
+/// A wall-clock boundary for the ticks of an interval to line up with.
+///
+/// Boundaries are measured in UTC, so hours don't line up in time zones that are offset by a fraction of an hour.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AlignTo {
+    Second,
+    Minute,
+    Hour,
+}
+
+impl AlignTo {
+    fn unit(&self) -> Duration {
+        match self {
+            Self::Second => Duration::from_secs(1),
+            Self::Minute => Duration::from_secs(60),
+            Self::Hour => Duration::from_secs(60 * 60),
         }
     }
 }
 
+/// The options for [`use_interval_with_options`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IntervalOptions {
+    period: Duration,
+    align_to: Option<AlignTo>,
+    stop_on_panic: bool,
+}
+
+impl IntervalOptions {
+    /// Tick every `period`, starting one period from now.
+    pub fn new(period: Duration) -> Self {
+        Self {
+            period,
+            align_to: None,
+            stop_on_panic: false,
+        }
+    }
+
+    /// Tick on wall-clock boundaries instead, starting at the next boundary.
+    ///
+    /// Ticks stay on the boundaries even if the app is busy, which is useful for clocks that should update exactly
+    /// when the second changes. The boundary is found on the system clock when the interval starts, and ticks
+    /// follow a clock that doesn't move with the system time after that, so changing the system time doesn't
+    /// realign them until the interval restarts. The period should be a multiple of the boundary,
+    /// like every 5 seconds aligned to the second.
+    pub fn align_to(mut self, align_to: AlignTo) -> Self {
+        self.align_to = Some(align_to);
+        self
+    }
+
+    /// Stop ticking once the action panics.
+    ///
+    /// By default, a panic is caught and reported through [`UseInterval::error`], and the interval keeps ticking so
+    /// one bad tick doesn't stop a periodic refresh for good. Panics can only be caught when the app is built with
+    /// `panic = "unwind"`, which isn't the case on the web.
+    pub fn stop_on_panic(mut self) -> Self {
+        self.stop_on_panic = true;
+        self
+    }
+}
+
 /// Repeatedly calls a function every a certain period.
-pub fn use_interval(period: Duration, action: impl FnMut() + 'static) -> UseInterval {
+///
+/// The function can take a [`TickInfo`] to find out which tick it is. If it panics, the panic is reported through
+/// [`UseInterval::error`] and the interval keeps ticking.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::timing::{use_interval, TickInfo};
+/// use std::time::Duration;
+///
+/// fn App() -> Element {
+///     let mut saves = use_signal(|| 0);
+///     use_interval(Duration::from_secs(1), move |tick: TickInfo| {
+///         // Save every 10 seconds.
+///         if tick.count % 10 == 0 {
+///             saves += 1;
+///         }
+///     });
+///
+///     rsx! { "Saved {saves} times" }
+/// }
+/// ```
+pub fn use_interval<M>(period: Duration, action: impl IntervalAction<M>) -> UseInterval {
+    use_interval_with_options(IntervalOptions::new(period), action)
+}
+
+/// Repeatedly calls a function with the given [`IntervalOptions`].
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::timing::{use_interval_with_options, AlignTo, IntervalOptions};
+/// use std::time::Duration;
+///
+/// fn Clock() -> Element {
+///     let mut seconds = use_signal(|| 0);
+///     use_interval_with_options(
+///         IntervalOptions::new(Duration::from_secs(1)).align_to(AlignTo::Second),
+///         move || seconds += 1,
+///     );
+///
+///     rsx! { "{seconds}" }
+/// }
+/// ```
+pub fn use_interval_with_options<M>(
+    options: IntervalOptions,
+    action: impl IntervalAction<M>,
+) -> UseInterval {
+    let error = use_hook(|| Signal::new(None));
     let inner = use_hook(|| {
-        let mut action = Box::new(action);
-
-        #[cfg(target_family = "wasm")]
-        return dioxus::prelude::Signal::new(InnerUseInterval {
-            interval: Some(gloo_timers::callback::Interval::new(
-                period.as_millis() as u32,
-                move || {
-                    action();
-                },
-            )),
-        });
+        Signal::new(InnerUseInterval {
+            interval: Some(spawn(run(options, action, error))),
+            cancelled: false,
+        })
+    });
 
-        #[cfg(not(target_family = "wasm"))]
-        dioxus::prelude::Signal::new(InnerUseInterval {
-            interval: Some(dioxus::prelude::spawn(async move {
-                let mut interval = tokio::time::interval(period);
-                loop {
-                    interval.tick().await;
-                    action();
-                }
-            })),
+    UseInterval { inner, error }
+}
+
+/// Repeatedly calls a function every a certain period, but only while `when` is `true`.
+///
+/// The interval starts over when `when` becomes `true` again. Combined with
+/// [`use_page_visibility`](crate::utils::window::use_page_visibility), this stops polling while the page is in
+/// the background.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::{
+///     timing::use_interval_when,
+///     window::{use_page_visibility, PageVisibility},
+/// };
+/// use std::time::Duration;
+///
+/// fn Feed() -> Element {
+///     let visibility = use_page_visibility();
+///     let visible = use_memo(move || visibility() == PageVisibility::Visible);
+///     use_interval_when(Duration::from_secs(30), visible, move || {
+///         // Poll for new posts
+///     });
+///
+///     rsx! {}
+/// }
+/// ```
+pub fn use_interval_when<M>(
+    period: Duration,
+    when: impl Into<ReadOnlySignal<bool>>,
+    action: impl IntervalAction<M>,
+) -> UseInterval {
+    let when = use_hook(|| when.into());
+    let action = use_hook(|| Rc::new(RefCell::new(action)));
+    let error = use_hook(|| Signal::new(None));
+    let mut inner = use_hook(|| {
+        Signal::new(InnerUseInterval {
+            interval: None,
+            cancelled: false,
         })
     });
 
-    UseInterval { inner }
+    use_effect(move || {
+        let active = when();
+        let mut inner = inner.write();
+        if let Some(interval) = inner.interval.take() {
+            interval.cancel();
+        }
+        if active && !inner.cancelled {
+            let action = action.clone();
+            inner.interval = Some(spawn(run(
+                IntervalOptions::new(period),
+                move |info: TickInfo| action.borrow_mut().tick(info),
+                error,
+            )));
+        }
+    });
+
+    UseInterval { inner, error }
+}
+
+/// Call `action` on every tick of the interval, until it panics with [`IntervalOptions::stop_on_panic`] set.
+async fn run<M>(
+    options: IntervalOptions,
+    mut action: impl IntervalAction<M>,
+    mut error: Signal<Option<TimerError>>,
+) {
+    // Returns whether to keep ticking.
+    let mut tick =
+        |info: TickInfo| match panic::catch_unwind(AssertUnwindSafe(|| action.tick(info))) {
+            Ok(()) => true,
+            Err(payload) => {
+                let message = panic_message(payload.as_ref());
+                tracing::error!(
+                    "Interval action panicked on tick {}: {}",
+                    info.count,
+                    message
+                );
+                error.set(Some(TimerError::Panicked {
+                    tick: info.count,
+                    message,
+                }));
+                !options.stop_on_panic
+            }
+        };
+
+    let _timer = debugger::track(TimerKind::Interval, options.period);
+    let started = monotonic_now();
+    // Deadlines are counted from the first tick rather than from when the action returned, so the time the action
+    // takes doesn't make the interval drift. A zero period would never give the rest of the app a turn.
+    let unit = options.align_to.map(|align_to| align_to.unit());
+    let period = match unit {
+        Some(unit) => options.period.max(unit),
+        None => options.period.max(Duration::from_millis(1)),
+    };
+    // Only the first boundary of an aligned interval comes from the wall clock. Every tick is scheduled on the
+    // monotonic clock, so adjusting the system time doesn't make ticks fire early or get skipped.
+    let first = match unit {
+        Some(unit) => {
+            let wall = now();
+            started + (next_boundary(wall, unit) - wall)
+        }
+        None => started + period,
+    };
+    let mut count = 0;
+    let mut next = first;
+    let mut previous = None;
+    loop {
+        // Timers on the web are rounded to whole milliseconds and can wake up just before the deadline, so keep
+        // waiting until it passed.
+        loop {
+            let current = monotonic_now();
+            if current >= next {
+                break;
+            }
+            clock::sleep(next - current).await;
+        }
+        count += 1;
+        let keep_ticking = tick(TickInfo {
+            count,
+            elapsed: monotonic_now().saturating_sub(started),
+            missed: previous.map_or(0, |previous: Duration| {
+                missed_ticks(next.saturating_sub(previous), period)
+            }),
+        });
+        if !keep_ticking {
+            return;
+        }
+        previous = Some(next);
+        next = next_tick(first, period, monotonic_now());
+    }
+}
+
+/// Get the message a panic was started with.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    match payload.downcast_ref::<&str>() {
+        Some(message) => message.to_string(),
+        None => payload
+            .downcast_ref::<String>()
+            .cloned()
+            .unwrap_or_else(|| "unknown panic".to_string()),
+    }
+}
+
+/// The current time on the wall clock, or on the mock clock if one is installed.
+pub(super) fn now() -> Duration {
+    #[cfg(feature = "test-utils")]
+    if let Some(clock) = clock::MockClock::current() {
+        return clock.now();
+    }
+
+    now_since_epoch()
+}
+
+/// The time since the app started on a clock that the system time doesn't move, or on the mock clock if one is
+/// installed.
+pub(super) fn monotonic_now() -> Duration {
+    #[cfg(feature = "test-utils")]
+    if let Some(clock) = clock::MockClock::current() {
+        return clock.now();
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    {
+        static STARTED: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
+        STARTED.get_or_init(std::time::Instant::now).elapsed()
+    }
+
+    // `Instant::now` panics on the web, so ask the browser instead.
+    #[cfg(target_family = "wasm")]
+    match web_sys::window().and_then(|window| window.performance()) {
+        Some(performance) => Duration::from_secs_f64(performance.now() / 1000.0),
+        None => now_since_epoch(),
+    }
+}
+
+/// The first multiple of `unit` after `now`.
+fn next_boundary(now: Duration, unit: Duration) -> Duration {
+    let units = now.as_nanos() / unit.as_nanos() + 1;
+    Duration::from_nanos((units * unit.as_nanos()) as u64)
+}
+
+/// How many ticks of `period` fit in the `gap` between two ticks, besides the tick that ended it.
+fn missed_ticks(gap: Duration, period: Duration) -> u64 {
+    if period.is_zero() {
+        return 0;
+    }
+    (gap.as_nanos() / period.as_nanos()).saturating_sub(1) as u64
+}
+
+/// The first tick after `now` of an interval that ticked at `first`.
+///
+/// Ticks are counted from the first one rather than the last, so they don't drift, and ticks that were missed
+/// while the app was busy are skipped.
+fn next_tick(first: Duration, period: Duration, now: Duration) -> Duration {
+    if now < first {
+        return first;
+    }
+    let ticks = (now - first).as_nanos() / period.as_nanos() + 1;
+    first + Duration::from_nanos((ticks * period.as_nanos()) as u64)
+}
+
+#[test]
+fn test_aligned_ticks() {
+    let second = Duration::from_secs(1);
+    let ms = Duration::from_millis;
+
+    assert_eq!(next_boundary(ms(1_250), second), ms(2_000));
+    assert_eq!(next_boundary(ms(2_000), second), ms(3_000));
+
+    let first = ms(2_000);
+    assert_eq!(next_tick(first, second, ms(2_010)), ms(3_000));
+    // Missed ticks are skipped.
+    assert_eq!(next_tick(first, second, ms(5_500)), ms(6_000));
+    assert_eq!(next_tick(first, ms(5_000), ms(2_010)), ms(7_000));
+
+    assert_eq!(missed_ticks(ms(1_010), second), 0);
+    assert_eq!(missed_ticks(ms(4_000), second), 3);
+}
+
+#[test]
+fn test_panic_message() {
+    let payload = panic::catch_unwind(|| panic!("tick {} failed", 3)).unwrap_err();
+    assert_eq!(panic_message(payload.as_ref()), "tick 3 failed");
+    let payload = panic::catch_unwind(|| panic!("static message")).unwrap_err();
+    assert_eq!(panic_message(payload.as_ref()), "static message");
+}
+
+#[cfg(feature = "test-utils")]
+#[test]
+fn test_interval_keeps_its_schedule() {
+    use super::MockClock;
+    use dioxus::prelude::*;
+
+    thread_local! {
+        static TICKS: RefCell<Vec<Duration>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn app() -> Element {
+        use_interval(Duration::from_secs(1), || {
+            let clock = MockClock::current().unwrap();
+            TICKS.with(|ticks| ticks.borrow_mut().push(clock.now()));
+            // The action takes a while, which shouldn't push back the next tick.
+            clock.advance(Duration::from_millis(300));
+        });
+        rsx! {}
+    }
+
+    let clock = MockClock::install();
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    dom.process_events();
+    clock.advance(Duration::from_secs(1));
+    dom.process_events();
+    for _ in 0..2 {
+        clock.advance(Duration::from_millis(700));
+        dom.process_events();
+    }
+
+    let ms = Duration::from_millis;
+    let ticks = TICKS.with(|ticks| ticks.borrow().clone());
+    assert_eq!(ticks, vec![ms(1_000), ms(2_000), ms(3_000)]);
+
+    MockClock::uninstall();
 }
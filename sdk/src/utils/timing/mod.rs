@@ -1,7 +1,36 @@
 //! Timing utilities.
 
+pub(crate) mod clock;
+#[cfg(feature = "test-utils")]
+pub use clock::MockClock;
+
+mod debugger;
+#[cfg(feature = "timer-debugger")]
+pub use debugger::{use_timer_debugger, TimerInfo, TimerKind};
+
 mod interval;
 pub use interval::*;
 
 mod debounce;
 pub use debounce::*;
+
+mod throttle;
+pub use throttle::*;
+
+mod deadline;
+pub use deadline::*;
+
+mod tween;
+pub use tween::*;
+
+mod poll;
+pub use poll::*;
+
+mod stopwatch;
+pub use stopwatch::*;
+
+mod gesture;
+pub use gesture::*;
+
+mod idle;
+pub use idle::*;
@@ -0,0 +1,309 @@
+use dioxus::prelude::*;
+use futures::future::{self, Either};
+use std::{future::Future, time::Duration};
+
+use super::{
+    clock,
+    debugger::{self, TimerKind},
+    interval::now,
+};
+
+/// The state of a [`use_poll`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PollStatus {
+    /// Still waiting for the predicate to pass.
+    Polling,
+    /// The predicate passed for the latest value.
+    Done,
+    /// The maximum duration passed before the predicate did.
+    TimedOut,
+    /// Polling was stopped with [`UsePoll::cancel`].
+    Cancelled,
+}
+
+/// The options for [`use_poll_with_options`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PollOptions {
+    interval: Duration,
+    max_duration: Option<Duration>,
+}
+
+impl PollOptions {
+    /// Poll every `interval`, without a time limit.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            max_duration: None,
+        }
+    }
+
+    /// Give up once `max_duration` has passed since polling started, dropping an attempt that is still running.
+    pub fn max_duration(mut self, max_duration: Duration) -> Self {
+        self.max_duration = Some(max_duration);
+        self
+    }
+}
+
+/// The interface for a running poll.
+///
+/// See [`use_poll`] for more information.
+pub struct UsePoll<T: 'static> {
+    value: Signal<Option<T>>,
+    attempts: Signal<u32>,
+    status: Signal<PollStatus>,
+    task: Signal<Option<Task>>,
+}
+
+// Manually implement Clone, Copy, and PartialEq as #[derive] thinks that T needs to implement these (it doesn't).
+
+impl<T> Clone for UsePoll<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for UsePoll<T> {}
+
+impl<T> PartialEq for UsePoll<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T> UsePoll<T> {
+    /// Get the value of the latest attempt, or `None` before the first one finished.
+    pub fn value(&self) -> ReadOnlySignal<Option<T>> {
+        self.value.into()
+    }
+
+    /// Get the number of attempts that finished so far.
+    pub fn attempts(&self) -> ReadOnlySignal<u32> {
+        self.attempts.into()
+    }
+
+    /// Get the state of the poll.
+    pub fn status(&self) -> ReadOnlySignal<PollStatus> {
+        self.status.into()
+    }
+
+    /// Whether the poll is still running.
+    pub fn is_polling(&self) -> bool {
+        *self.status.read() == PollStatus::Polling
+    }
+
+    /// Stop polling. An attempt that is running is dropped.
+    pub fn cancel(&mut self) {
+        if let Some(task) = self.task.write().take() {
+            task.cancel();
+            self.status.set(PollStatus::Cancelled);
+        }
+    }
+}
+
+/// Repeatedly calls an async function every `interval` until `until` returns `true` for its result.
+///
+/// The first attempt starts right away, and the next one `interval` after the previous one finished, so slow
+/// requests never overlap. See [`use_poll_with_options`] to give up after a maximum duration.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::timing::{use_poll, PollStatus};
+/// use std::time::Duration;
+///
+/// #[component]
+/// fn Job(id: u32) -> Element {
+///     let mut poll = use_poll(
+///         Duration::from_secs(2),
+///         move || fetch_job_status(id),
+///         |status| status.finished,
+///     );
+///     let value = poll.value();
+///
+///     rsx! {
+///         match &*value.read() {
+///             Some(status) => rsx! { "{status.progress}% after {poll.attempts()} checks" },
+///             None => rsx! { "Checking..." },
+///         }
+///         if poll.is_polling() {
+///             button { onclick: move |_| poll.cancel(), "Stop checking" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_poll<T, F>(
+    interval: Duration,
+    fetcher: impl FnMut() -> F + 'static,
+    until: impl FnMut(&T) -> bool + 'static,
+) -> UsePoll<T>
+where
+    T: 'static,
+    F: Future<Output = T> + 'static,
+{
+    use_poll_with_options(PollOptions::new(interval), fetcher, until)
+}
+
+/// Repeatedly calls an async function with the given [`PollOptions`] until `until` returns `true` for its result.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::timing::{use_poll_with_options, PollOptions, PollStatus};
+/// use std::time::Duration;
+///
+/// fn Export() -> Element {
+///     let poll = use_poll_with_options(
+///         PollOptions::new(Duration::from_secs(1)).max_duration(Duration::from_secs(60)),
+///         fetch_export,
+///         |export| export.is_ready(),
+///     );
+///
+///     rsx! {
+///         if poll.status()() == PollStatus::TimedOut { "The export is taking longer than expected." }
+///     }
+/// }
+/// ```
+pub fn use_poll_with_options<T, F>(
+    options: PollOptions,
+    mut fetcher: impl FnMut() -> F + 'static,
+    mut until: impl FnMut(&T) -> bool + 'static,
+) -> UsePoll<T>
+where
+    T: 'static,
+    F: Future<Output = T> + 'static,
+{
+    use_hook(|| {
+        let mut poll = UsePoll {
+            value: Signal::new(None),
+            attempts: Signal::new(0),
+            status: Signal::new(PollStatus::Polling),
+            task: Signal::new(None),
+        };
+
+        let task = spawn(async move {
+            let _timer = debugger::track(TimerKind::Poll, options.interval);
+            let started = now();
+            let status = loop {
+                let attempt = fetcher();
+                let value = match options.max_duration {
+                    Some(max_duration) => {
+                        let remaining = max_duration.saturating_sub(now().saturating_sub(started));
+                        let deadline = clock::sleep(remaining);
+                        match future::select(std::pin::pin!(attempt), std::pin::pin!(deadline))
+                            .await
+                        {
+                            Either::Left((value, _)) => value,
+                            Either::Right(_) => break PollStatus::TimedOut,
+                        }
+                    }
+                    None => attempt.await,
+                };
+                let done = until(&value);
+                poll.value.set(Some(value));
+                poll.attempts += 1;
+                if done {
+                    break PollStatus::Done;
+                }
+
+                let remaining = options
+                    .max_duration
+                    .map(|max_duration| max_duration.saturating_sub(now().saturating_sub(started)));
+                match remaining {
+                    // Another attempt wouldn't start before the deadline.
+                    Some(remaining) if remaining <= options.interval => {
+                        clock::sleep(remaining).await;
+                        break PollStatus::TimedOut;
+                    }
+                    _ => clock::sleep(options.interval).await,
+                }
+            };
+            poll.task.set(None);
+            poll.status.set(status);
+        });
+
+        poll.task.set(Some(task));
+        poll
+    })
+}
+
+#[cfg(feature = "test-utils")]
+#[test]
+fn test_poll_until_done() {
+    use super::MockClock;
+    use std::cell::{Cell, RefCell};
+
+    thread_local! {
+        static POLL: RefCell<Option<UsePoll<u32>>> = const { RefCell::new(None) };
+    }
+
+    fn app() -> Element {
+        let count = use_hook(|| std::rc::Rc::new(Cell::new(0)));
+        let poll = use_poll(
+            Duration::from_secs(1),
+            move || {
+                count.set(count.get() + 1);
+                std::future::ready(count.get())
+            },
+            |count| *count == 3,
+        );
+        POLL.with(|cell| *cell.borrow_mut() = Some(poll));
+        rsx! {}
+    }
+
+    let clock = MockClock::install();
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    dom.process_events();
+    for _ in 0..3 {
+        clock.advance(Duration::from_secs(1));
+        dom.process_events();
+    }
+
+    let poll = POLL.with(|cell| cell.borrow_mut().take()).unwrap();
+    dom.in_runtime(|| {
+        assert_eq!(*poll.status.peek(), PollStatus::Done);
+        assert_eq!(*poll.attempts.peek(), 3);
+        assert_eq!(*poll.value.peek(), Some(3));
+    });
+
+    MockClock::uninstall();
+}
+
+#[cfg(feature = "test-utils")]
+#[test]
+fn test_poll_times_out_during_attempt() {
+    use super::MockClock;
+    use std::cell::RefCell;
+
+    thread_local! {
+        static POLL: RefCell<Option<UsePoll<()>>> = const { RefCell::new(None) };
+    }
+
+    fn app() -> Element {
+        // The request never finishes before the deadline.
+        let poll = use_poll_with_options(
+            PollOptions::new(Duration::from_secs(1)).max_duration(Duration::from_secs(3)),
+            || clock::sleep(Duration::from_secs(60)),
+            |_| true,
+        );
+        POLL.with(|cell| *cell.borrow_mut() = Some(poll));
+        rsx! {}
+    }
+
+    let clock = MockClock::install();
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    dom.process_events();
+    clock.advance(Duration::from_secs(3));
+    dom.process_events();
+
+    let poll = POLL.with(|cell| cell.borrow_mut().take()).unwrap();
+    dom.in_runtime(|| {
+        assert_eq!(*poll.status.peek(), PollStatus::TimedOut);
+        assert_eq!(*poll.attempts.peek(), 0);
+    });
+
+    MockClock::uninstall();
+}
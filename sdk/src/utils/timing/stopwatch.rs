@@ -0,0 +1,344 @@
+use dioxus::prelude::*;
+use std::time::Duration;
+
+use super::{
+    clock,
+    debugger::{self, TimerKind},
+    interval::now,
+};
+#[cfg(feature = "storage")]
+use crate::storage::{new_storage, LocalStorage};
+
+/// Where a stopwatch is anchored in wall-clock time.
+///
+/// This is what gets persisted, so a restored stopwatch keeps counting from when it was started rather than
+/// from when the app was reloaded.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "storage", derive(serde::Serialize, serde::Deserialize))]
+struct Anchor {
+    /// When the stopwatch was last started, as time since the Unix epoch, or `None` while it is paused.
+    started_at: Option<Duration>,
+    /// The time counted before it was last started.
+    counted: Duration,
+}
+
+impl Anchor {
+    fn elapsed(&self, now: Duration) -> Duration {
+        let running = self
+            .started_at
+            .map(|started_at| now.saturating_sub(started_at))
+            .unwrap_or_default();
+        self.counted + running
+    }
+}
+
+/// The options for [`use_stopwatch_with_options`] and [`use_countdown_with_options`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct StopwatchOptions {
+    tick: Duration,
+    #[cfg(feature = "storage")]
+    persist_key: Option<String>,
+}
+
+impl Default for StopwatchOptions {
+    fn default() -> Self {
+        Self {
+            tick: Duration::from_millis(100),
+            #[cfg(feature = "storage")]
+            persist_key: None,
+        }
+    }
+}
+
+impl StopwatchOptions {
+    /// Update the time every `tick` while running. Defaults to 100 milliseconds.
+    pub fn tick(mut self, tick: Duration) -> Self {
+        self.tick = tick;
+        self
+    }
+
+    /// Persist when the timer was started under `key` in [`LocalStorage`](crate::storage::LocalStorage).
+    ///
+    /// A running timer keeps counting while the app is closed, and resumes at the right time after a page
+    /// refresh or an app restart. Only starting, pausing, and resetting write to storage, not every tick.
+    #[cfg(feature = "storage")]
+    pub fn persist(mut self, key: impl ToString) -> Self {
+        self.persist_key = Some(key.to_string());
+        self
+    }
+}
+
+/// The interface for a stopwatch.
+///
+/// See [`use_stopwatch`] for more information.
+#[derive(Clone, Copy, PartialEq)]
+pub struct UseStopwatch {
+    anchor: Signal<Anchor>,
+    elapsed: Signal<Duration>,
+    task: Signal<Option<Task>>,
+    tick: Duration,
+    /// Where a countdown stops.
+    limit: Option<Duration>,
+}
+
+impl UseStopwatch {
+    fn new(options: StopwatchOptions, limit: Option<Duration>) -> Self {
+        #[cfg(feature = "storage")]
+        let anchor = match options.persist_key {
+            Some(key) => new_storage::<LocalStorage, _>(key, Anchor::default),
+            None => Signal::new(Anchor::default()),
+        };
+        #[cfg(not(feature = "storage"))]
+        let anchor = Signal::new(Anchor::default());
+
+        let mut stopwatch = Self {
+            anchor,
+            elapsed: Signal::new(Duration::ZERO),
+            task: Signal::new(None),
+            tick: options.tick,
+            limit,
+        };
+        // A restored stopwatch may still be running.
+        if !stopwatch.sync() && stopwatch.is_running() {
+            stopwatch.run();
+        }
+        stopwatch
+    }
+
+    /// Get the time counted so far.
+    pub fn elapsed(&self) -> ReadOnlySignal<Duration> {
+        self.elapsed.into()
+    }
+
+    /// Whether the stopwatch is counting.
+    pub fn is_running(&self) -> bool {
+        self.anchor.read().started_at.is_some()
+    }
+
+    /// Start counting, or continue counting after a pause.
+    pub fn start(&mut self) {
+        let anchor = *self.anchor.peek();
+        if anchor.started_at.is_some() || self.limit.is_some_and(|limit| anchor.counted >= limit) {
+            return;
+        }
+
+        self.anchor.set(Anchor {
+            started_at: Some(now()),
+            counted: anchor.counted,
+        });
+        self.run();
+    }
+
+    /// Stop counting, keeping the time counted so far.
+    pub fn pause(&mut self) {
+        self.stop_task();
+        let anchor = *self.anchor.peek();
+        if anchor.started_at.is_some() {
+            self.anchor.set(Anchor {
+                started_at: None,
+                counted: self.capped(anchor.elapsed(now())),
+            });
+        }
+        self.sync();
+    }
+
+    /// Stop counting and go back to zero.
+    pub fn reset(&mut self) {
+        self.stop_task();
+        self.anchor.set(Anchor::default());
+        self.elapsed.set(Duration::ZERO);
+    }
+
+    fn capped(&self, elapsed: Duration) -> Duration {
+        match self.limit {
+            Some(limit) => elapsed.min(limit),
+            None => elapsed,
+        }
+    }
+
+    /// Update the elapsed time, stopping at the limit. Returns whether the limit was reached.
+    fn sync(&mut self) -> bool {
+        let anchor = *self.anchor.peek();
+        let elapsed = self.capped(anchor.elapsed(now()));
+        if *self.elapsed.peek() != elapsed {
+            self.elapsed.set(elapsed);
+        }
+
+        let finished = self.limit.is_some_and(|limit| elapsed >= limit);
+        if finished && anchor.started_at.is_some() {
+            self.anchor.set(Anchor {
+                started_at: None,
+                counted: elapsed,
+            });
+        }
+        finished
+    }
+
+    fn run(&mut self) {
+        self.stop_task();
+        let mut stopwatch = *self;
+        let task = spawn(async move {
+            let _timer = debugger::track(TimerKind::Stopwatch, stopwatch.tick);
+            loop {
+                clock::sleep(stopwatch.tick).await;
+                if stopwatch.sync() {
+                    break;
+                }
+            }
+            stopwatch.task.set(None);
+        });
+        self.task.set(Some(task));
+    }
+
+    fn stop_task(&mut self) {
+        if let Some(task) = self.task.write().take() {
+            task.cancel();
+        }
+    }
+}
+
+/// A hook for a stopwatch that counts up from zero once started.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::timing::use_stopwatch;
+///
+/// fn App() -> Element {
+///     let mut stopwatch = use_stopwatch();
+///     let elapsed = stopwatch.elapsed();
+///
+///     rsx! {
+///         p { "{elapsed().as_secs_f32():.1}s" }
+///         button { onclick: move |_| stopwatch.start(), "Start" }
+///         button { onclick: move |_| stopwatch.pause(), "Pause" }
+///         button { onclick: move |_| stopwatch.reset(), "Reset" }
+///     }
+/// }
+/// ```
+pub fn use_stopwatch() -> UseStopwatch {
+    use_stopwatch_with_options(StopwatchOptions::default())
+}
+
+/// A hook for a stopwatch like [`use_stopwatch`] that can be configured with [`StopwatchOptions`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::timing::{use_stopwatch_with_options, StopwatchOptions};
+///
+/// #[component]
+/// fn Workout(id: u32) -> Element {
+///     // Keeps counting across page refreshes.
+///     let stopwatch = use_stopwatch_with_options(StopwatchOptions::default().persist(format!("workout-{id}")));
+///     let elapsed = stopwatch.elapsed();
+///
+///     rsx! { "{elapsed().as_secs()}s" }
+/// }
+/// ```
+pub fn use_stopwatch_with_options(options: StopwatchOptions) -> UseStopwatch {
+    use_hook(|| UseStopwatch::new(options, None))
+}
+
+/// The interface for a countdown.
+///
+/// See [`use_countdown`] for more information.
+#[derive(Clone, Copy, PartialEq)]
+pub struct UseCountdown {
+    stopwatch: UseStopwatch,
+    remaining: Memo<Duration>,
+}
+
+impl UseCountdown {
+    /// Get the time left.
+    pub fn remaining(&self) -> Memo<Duration> {
+        self.remaining
+    }
+
+    /// Whether the countdown reached zero.
+    pub fn is_finished(&self) -> bool {
+        (self.remaining)().is_zero()
+    }
+
+    /// Whether the countdown is counting down.
+    pub fn is_running(&self) -> bool {
+        self.stopwatch.is_running()
+    }
+
+    /// Start counting down, or continue counting after a pause.
+    pub fn start(&mut self) {
+        self.stopwatch.start();
+    }
+
+    /// Stop counting down, keeping the time left.
+    pub fn pause(&mut self) {
+        self.stopwatch.pause();
+    }
+
+    /// Stop counting down and go back to the full duration.
+    pub fn reset(&mut self) {
+        self.stopwatch.reset();
+    }
+}
+
+/// A hook for a countdown from `duration` to zero once started.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::timing::use_countdown;
+/// use std::time::Duration;
+///
+/// fn App() -> Element {
+///     let mut countdown = use_countdown(Duration::from_secs(60));
+///     let remaining = countdown.remaining();
+///
+///     rsx! {
+///         if countdown.is_finished() {
+///             p { "Time's up!" }
+///         } else {
+///             p { "{remaining().as_secs()}s left" }
+///         }
+///         button { onclick: move |_| countdown.start(), "Start" }
+///         button { onclick: move |_| countdown.reset(), "Reset" }
+///     }
+/// }
+/// ```
+pub fn use_countdown(duration: Duration) -> UseCountdown {
+    use_countdown_with_options(duration, StopwatchOptions::default())
+}
+
+/// A hook for a countdown like [`use_countdown`] that can be configured with [`StopwatchOptions`].
+///
+/// With [`StopwatchOptions::persist`], a countdown that ran out while the app was closed is finished when the
+/// app starts again.
+pub fn use_countdown_with_options(duration: Duration, options: StopwatchOptions) -> UseCountdown {
+    let stopwatch = use_hook(|| UseStopwatch::new(options, Some(duration)));
+    let remaining = use_memo(move || duration.saturating_sub((stopwatch.elapsed)()));
+
+    UseCountdown {
+        stopwatch,
+        remaining,
+    }
+}
+
+#[test]
+fn test_anchor_elapsed() {
+    let secs = Duration::from_secs;
+
+    let paused = Anchor {
+        started_at: None,
+        counted: secs(5),
+    };
+    assert_eq!(paused.elapsed(secs(100)), secs(5));
+
+    // A stopwatch restored after a restart includes the time the app was closed.
+    let running = Anchor {
+        started_at: Some(secs(90)),
+        counted: secs(5),
+    };
+    assert_eq!(running.elapsed(secs(100)), secs(15));
+}
@@ -0,0 +1,110 @@
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use super::{
+    clock,
+    debugger::{self, TimerKind},
+    interval::now,
+};
+
+/// A throttle that can be used outside of components, like in background tasks or services.
+///
+/// At most one call is let through per interval. [`Throttler::try_acquire`] drops the calls in between, while
+/// [`Throttler::acquire`] and [`Throttler::run`] wait for their turn. Clones share the same interval, so a throttler
+/// can limit the rate of many tasks together. It uses the same timers as the timing hooks.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use dioxus_sdk::utils::timing::Throttler;
+/// use std::time::Duration;
+///
+/// let throttler = Throttler::new(Duration::from_secs(1));
+///
+/// // Sends at most one request per second, however often it is called.
+/// async fn upload(throttler: Throttler, chunk: Vec<u8>) {
+///     throttler.run(|| async move { println!("uploading {} bytes", chunk.len()) }).await;
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Throttler {
+    interval: Duration,
+    /// When the latest call was let through.
+    last: Arc<Mutex<Option<Duration>>>,
+}
+
+impl Throttler {
+    /// Create a throttler that lets one call through per [`Duration`].
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Get the interval between calls.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Let a call through if the interval has passed since the latest one.
+    ///
+    /// Returns `false` without waiting if it hasn't.
+    pub fn try_acquire(&self) -> bool {
+        self.remaining().is_none()
+    }
+
+    /// Wait until the interval has passed since the latest call, then let this call through.
+    pub async fn acquire(&self) {
+        while let Some(remaining) = self.remaining() {
+            let _timer = debugger::track(TimerKind::Throttle, remaining);
+            clock::sleep(remaining).await;
+        }
+    }
+
+    /// Wait for the turn of this call, then call `f`.
+    pub async fn run<F: Future>(&self, f: impl FnOnce() -> F) -> F::Output {
+        self.acquire().await;
+        f().await
+    }
+
+    /// Forget the latest call, so the next one is let through right away.
+    pub fn reset(&self) {
+        *self.last.lock().unwrap() = None;
+    }
+
+    /// Take the turn if the interval has passed, or get how long is left until it does.
+    fn remaining(&self) -> Option<Duration> {
+        let mut last = self.last.lock().unwrap();
+        let now = now();
+        let remaining = last
+            .map(|last| self.interval.saturating_sub(now.saturating_sub(last)))
+            .filter(|remaining| !remaining.is_zero());
+        if remaining.is_none() {
+            *last = Some(now);
+        }
+        remaining
+    }
+}
+
+#[cfg(feature = "test-utils")]
+#[test]
+fn test_throttler() {
+    let clock = super::MockClock::install();
+    let throttler = Throttler::new(Duration::from_secs(1));
+
+    assert!(throttler.try_acquire());
+    assert!(!throttler.try_acquire());
+    clock.advance(Duration::from_millis(500));
+    assert!(!throttler.try_acquire());
+    clock.advance(Duration::from_millis(500));
+    assert!(throttler.try_acquire());
+
+    throttler.reset();
+    assert!(throttler.try_acquire());
+
+    super::MockClock::uninstall();
+}
@@ -0,0 +1,286 @@
+use dioxus::prelude::*;
+use std::time::Duration;
+
+use super::{
+    clock,
+    debugger::{self, TimerKind},
+};
+
+/// How often the tween value is updated while it is playing.
+const FRAME: Duration = Duration::from_millis(16);
+
+/// A value that can be linearly interpolated by [`use_tween`].
+pub trait Lerp: Clone + 'static {
+    /// Interpolate between `self` and `to` where `t` is in the range `0.0..=1.0`.
+    ///
+    /// `t` may fall slightly outside of this range for easing functions that overshoot.
+    fn lerp(&self, to: &Self, t: f64) -> Self;
+}
+
+impl Lerp for f64 {
+    fn lerp(&self, to: &Self, t: f64) -> Self {
+        self + (to - self) * t
+    }
+}
+
+impl Lerp for f32 {
+    fn lerp(&self, to: &Self, t: f64) -> Self {
+        self + (to - self) * t as f32
+    }
+}
+
+impl<A: Lerp, B: Lerp> Lerp for (A, B) {
+    fn lerp(&self, to: &Self, t: f64) -> Self {
+        (self.0.lerp(&to.0, t), self.1.lerp(&to.1, t))
+    }
+}
+
+impl<A: Lerp, B: Lerp, C: Lerp> Lerp for (A, B, C) {
+    fn lerp(&self, to: &Self, t: f64) -> Self {
+        (
+            self.0.lerp(&to.0, t),
+            self.1.lerp(&to.1, t),
+            self.2.lerp(&to.2, t),
+        )
+    }
+}
+
+/// Standard easing functions for [`use_tween`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Easing {
+    Linear,
+    EaseInQuad,
+    EaseOutQuad,
+    EaseInOutQuad,
+    EaseInCubic,
+    EaseOutCubic,
+    EaseInOutCubic,
+    EaseInSine,
+    EaseOutSine,
+    EaseInOutSine,
+    /// Overshoots the target slightly before settling.
+    EaseOutBack,
+}
+
+impl Easing {
+    /// Map linear progress (`0.0..=1.0`) to eased progress.
+    pub fn apply(&self, t: f64) -> f64 {
+        use std::f64::consts::PI;
+
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::EaseInQuad => t * t,
+            Self::EaseOutQuad => 1.0 - (1.0 - t) * (1.0 - t),
+            Self::EaseInOutQuad => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+                }
+            }
+            Self::EaseInCubic => t * t * t,
+            Self::EaseOutCubic => 1.0 - (1.0 - t).powi(3),
+            Self::EaseInOutCubic => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Self::EaseInSine => 1.0 - (t * PI / 2.0).cos(),
+            Self::EaseOutSine => (t * PI / 2.0).sin(),
+            Self::EaseInOutSine => -((PI * t).cos() - 1.0) / 2.0,
+            Self::EaseOutBack => {
+                let c1 = 1.70158;
+                let c3 = c1 + 1.0;
+                1.0 + c3 * (t - 1.0).powi(3) + c1 * (t - 1.0).powi(2)
+            }
+        }
+    }
+}
+
+/// The direction a tween is playing in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Direction {
+    Forward,
+    Backward,
+}
+
+/// The interface for controlling a tween.
+///
+/// See [`use_tween`] for more information.
+pub struct UseTween<T: Lerp> {
+    value: Signal<T>,
+    from: Signal<T>,
+    to: Signal<T>,
+    // Linear progress in the range `0.0..=1.0`.
+    progress: Signal<f64>,
+    direction: Signal<Direction>,
+    task: Signal<Option<Task>>,
+    duration: Duration,
+    easing: Easing,
+}
+
+// Manually implement Clone, Copy, and PartialEq as #[derive] thinks that T needs to implement these (it doesn't).
+
+impl<T: Lerp> Clone for UseTween<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Lerp> Copy for UseTween<T> {}
+
+impl<T: Lerp> PartialEq for UseTween<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Lerp> UseTween<T> {
+    /// Get the current animated value.
+    pub fn value(&self) -> ReadOnlySignal<T> {
+        ReadOnlySignal::new(self.value)
+    }
+
+    /// Whether the tween is currently animating.
+    pub fn is_playing(&self) -> bool {
+        self.task.read().is_some()
+    }
+
+    /// Play the tween forward from its current position towards `to`.
+    pub fn play(&mut self) {
+        self.start(Direction::Forward);
+    }
+
+    /// Play the tween backward from its current position towards `from`.
+    pub fn reverse(&mut self) {
+        self.start(Direction::Backward);
+    }
+
+    /// Jump back to `from` and play the tween forward again.
+    pub fn restart(&mut self) {
+        self.progress.set(0.0);
+        self.sync_value();
+        self.start(Direction::Forward);
+    }
+
+    /// Pause the tween at its current position.
+    pub fn pause(&mut self) {
+        if let Some(task) = self.task.write().take() {
+            task.cancel();
+        }
+    }
+
+    /// Change the values being animated between and restart the tween.
+    pub fn set_range(&mut self, from: T, to: T) {
+        self.from.set(from);
+        self.to.set(to);
+        self.restart();
+    }
+
+    fn sync_value(&mut self) {
+        let t = self.easing.apply(*self.progress.peek());
+        let value = self.from.peek().lerp(&self.to.peek(), t);
+        self.value.set(value);
+    }
+
+    fn start(&mut self, direction: Direction) {
+        self.pause();
+        self.direction.set(direction);
+
+        let mut tween = *self;
+        let step = match tween.duration.is_zero() {
+            true => 1.0,
+            false => FRAME.as_secs_f64() / tween.duration.as_secs_f64(),
+        };
+
+        let task = spawn(async move {
+            let _timer = debugger::track(TimerKind::Tween, FRAME);
+            loop {
+                let progress = *tween.progress.peek();
+                let next = match *tween.direction.peek() {
+                    Direction::Forward => (progress + step).min(1.0),
+                    Direction::Backward => (progress - step).max(0.0),
+                };
+                tween.progress.set(next);
+                tween.sync_value();
+
+                if next <= 0.0 || next >= 1.0 {
+                    break;
+                }
+
+                clock::sleep(FRAME).await;
+            }
+            tween.task.set(None);
+        });
+
+        self.task.set(Some(task));
+    }
+}
+
+/// A hook for animating a value between `from` and `to` over the given [`Duration`].
+///
+/// The tween starts paused at `from`. Use [`UseTween::play`], [`UseTween::reverse`],
+/// and [`UseTween::restart`] to control it.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::timing::{use_tween, Easing};
+/// use std::time::Duration;
+///
+/// fn App() -> Element {
+///     let mut tween = use_tween(0.0, 100.0, Duration::from_millis(500), Easing::EaseInOutQuad);
+///     let width = tween.value();
+///
+///     rsx! {
+///         div { style: "width: {width}px; height: 20px; background: red;" }
+///         button { onclick: move |_| tween.play(), "Grow" }
+///         button { onclick: move |_| tween.reverse(), "Shrink" }
+///     }
+/// }
+/// ```
+pub fn use_tween<T: Lerp>(from: T, to: T, duration: Duration, easing: Easing) -> UseTween<T> {
+    use_hook(|| UseTween {
+        value: Signal::new(from.clone()),
+        from: Signal::new(from),
+        to: Signal::new(to),
+        progress: Signal::new(0.0),
+        direction: Signal::new(Direction::Forward),
+        task: Signal::new(None),
+        duration,
+        easing,
+    })
+}
+
+#[test]
+fn test_easing_endpoints() {
+    let easings = [
+        Easing::Linear,
+        Easing::EaseInQuad,
+        Easing::EaseOutQuad,
+        Easing::EaseInOutQuad,
+        Easing::EaseInCubic,
+        Easing::EaseOutCubic,
+        Easing::EaseInOutCubic,
+        Easing::EaseInSine,
+        Easing::EaseOutSine,
+        Easing::EaseInOutSine,
+        Easing::EaseOutBack,
+    ];
+
+    for easing in easings {
+        assert!(
+            easing.apply(0.0).abs() < 1e-9,
+            "{easing:?} should start at 0"
+        );
+        assert!(
+            (easing.apply(1.0) - 1.0).abs() < 1e-9,
+            "{easing:?} should end at 1"
+        );
+    }
+    assert_eq!(0.5f64.lerp(&1.5, 0.5), 1.0);
+}
@@ -0,0 +1,246 @@
+use dioxus::prelude::*;
+
+use super::{platform, RateMeter, TransferError, TransferProgress, TransferState};
+
+/// Where a download is written to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DownloadTarget {
+    /// Write the download to a file, appending to it when resuming.
+    #[cfg(not(target_family = "wasm"))]
+    File(std::path::PathBuf),
+    /// Keep the download in memory. The bytes can be read with [`UseDownload::bytes`].
+    Memory,
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl From<std::path::PathBuf> for DownloadTarget {
+    fn from(path: std::path::PathBuf) -> Self {
+        Self::File(path)
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl From<&std::path::Path> for DownloadTarget {
+    fn from(path: &std::path::Path) -> Self {
+        Self::File(path.to_path_buf())
+    }
+}
+
+/// The interface for controlling a download.
+///
+/// See [`use_download`] for more information.
+#[derive(Clone, Copy, PartialEq)]
+pub struct UseDownload {
+    url: Signal<String>,
+    target: Signal<DownloadTarget>,
+    progress: Signal<TransferProgress>,
+    state: Signal<TransferState>,
+    bytes: Signal<Vec<u8>>,
+    task: Signal<Option<Task>>,
+}
+
+impl UseDownload {
+    /// Get the progress of the download.
+    pub fn progress(&self) -> ReadOnlySignal<TransferProgress> {
+        ReadOnlySignal::new(self.progress)
+    }
+
+    /// Get the state of the download.
+    pub fn state(&self) -> ReadOnlySignal<TransferState> {
+        ReadOnlySignal::new(self.state)
+    }
+
+    /// Get the downloaded bytes when downloading to [`DownloadTarget::Memory`].
+    pub fn bytes(&self) -> ReadOnlySignal<Vec<u8>> {
+        ReadOnlySignal::new(self.bytes)
+    }
+
+    /// Start the download from the beginning, discarding any previous progress.
+    pub fn start(&mut self) {
+        self.run(0);
+    }
+
+    /// Pause the download. It can be continued with [`UseDownload::resume`].
+    pub fn pause(&mut self) {
+        if let Some(task) = self.task.write().take() {
+            task.cancel();
+            self.state.set(TransferState::Paused);
+        }
+    }
+
+    /// Resume a paused or failed download where it left off.
+    ///
+    /// If the server doesn't support range requests, the download will restart from the beginning.
+    pub fn resume(&mut self) {
+        let transferred = self.progress.peek().transferred;
+        self.run(transferred);
+    }
+
+    /// Cancel the download and discard anything that has been downloaded.
+    pub fn cancel(&mut self) {
+        if let Some(task) = self.task.write().take() {
+            task.cancel();
+        }
+
+        match &*self.target.peek() {
+            #[cfg(not(target_family = "wasm"))]
+            DownloadTarget::File(path) => {
+                std::fs::remove_file(path).ok();
+            }
+            DownloadTarget::Memory => self.bytes.write().clear(),
+        }
+
+        self.progress.set(TransferProgress::default());
+        self.state.set(TransferState::Cancelled);
+    }
+
+    fn run(&mut self, offset: u64) {
+        if let Some(task) = self.task.write().take() {
+            task.cancel();
+        }
+
+        let mut download = *self;
+        let task = spawn(async move {
+            download.state.set(TransferState::Running);
+            let state = match download.download(offset).await {
+                Ok(()) => TransferState::Completed,
+                Err(e) => TransferState::Failed(e),
+            };
+            download.state.set(state);
+            download.task.set(None);
+        });
+        self.task.set(Some(task));
+    }
+
+    async fn download(&mut self, offset: u64) -> Result<(), TransferError> {
+        let url = self.url.peek().clone();
+        let mut response = platform::get(&url, offset).await?;
+
+        // The server may ignore the range and send the whole body instead.
+        let mut transferred = if response.partial { offset } else { 0 };
+        let total = response.length.map(|length| length + transferred);
+        self.progress.set(TransferProgress {
+            transferred,
+            total,
+            rate: 0.0,
+        });
+
+        let target = self.target.peek().clone();
+        let mut sink = Sink::open(target, self.bytes, transferred).await?;
+        let meter = RateMeter::new(transferred);
+
+        while let Some(chunk) = response.chunk().await? {
+            sink.write(&chunk).await?;
+            transferred += chunk.len() as u64;
+            self.progress.set(TransferProgress {
+                transferred,
+                total,
+                rate: meter.rate(transferred),
+            });
+        }
+
+        sink.finish().await
+    }
+}
+
+/// The destination the downloaded chunks are written to.
+enum Sink {
+    #[cfg(not(target_family = "wasm"))]
+    File(tokio::fs::File),
+    Memory(Signal<Vec<u8>>),
+}
+
+impl Sink {
+    async fn open(
+        target: DownloadTarget,
+        mut bytes: Signal<Vec<u8>>,
+        offset: u64,
+    ) -> Result<Self, TransferError> {
+        match target {
+            #[cfg(not(target_family = "wasm"))]
+            DownloadTarget::File(path) => {
+                let file = tokio::fs::OpenOptions::new()
+                    .create(true)
+                    .write(true)
+                    .append(offset > 0)
+                    .truncate(offset == 0)
+                    .open(path)
+                    .await
+                    .map_err(|e| TransferError::Io(e.to_string()))?;
+                Ok(Self::File(file))
+            }
+            DownloadTarget::Memory => {
+                bytes.write().truncate(offset as usize);
+                Ok(Self::Memory(bytes))
+            }
+        }
+    }
+
+    async fn write(&mut self, chunk: &[u8]) -> Result<(), TransferError> {
+        match self {
+            #[cfg(not(target_family = "wasm"))]
+            Self::File(file) => {
+                use tokio::io::AsyncWriteExt;
+                file.write_all(chunk)
+                    .await
+                    .map_err(|e| TransferError::Io(e.to_string()))
+            }
+            Self::Memory(bytes) => {
+                bytes.write().extend_from_slice(chunk);
+                Ok(())
+            }
+        }
+    }
+
+    async fn finish(&mut self) -> Result<(), TransferError> {
+        match self {
+            #[cfg(not(target_family = "wasm"))]
+            Self::File(file) => {
+                use tokio::io::AsyncWriteExt;
+                file.flush()
+                    .await
+                    .map_err(|e| TransferError::Io(e.to_string()))
+            }
+            Self::Memory(_) => Ok(()),
+        }
+    }
+}
+
+/// A hook for downloading a file with progress tracking, pause/resume, and cancellation.
+///
+/// The download doesn't start until [`UseDownload::start`] is called. Pausing and resuming
+/// uses HTTP range requests so only the remaining bytes are fetched.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::transfer::use_download;
+/// use std::path::PathBuf;
+///
+/// fn App() -> Element {
+///     let mut download = use_download(
+///         "https://example.com/large-file.zip",
+///         PathBuf::from("large-file.zip"),
+///     );
+///     let progress = download.progress();
+///
+///     rsx! {
+///         p { "{progress().percent().unwrap_or_default():.1}%" }
+///         button { onclick: move |_| download.start(), "Start" }
+///         button { onclick: move |_| download.pause(), "Pause" }
+///         button { onclick: move |_| download.resume(), "Resume" }
+///         button { onclick: move |_| download.cancel(), "Cancel" }
+///     }
+/// }
+/// ```
+pub fn use_download(url: impl ToString, dest: impl Into<DownloadTarget>) -> UseDownload {
+    use_hook(|| UseDownload {
+        url: Signal::new(url.to_string()),
+        target: Signal::new(dest.into()),
+        progress: Signal::new(TransferProgress::default()),
+        state: Signal::new(TransferState::Idle),
+        bytes: Signal::new(Vec::new()),
+        task: Signal::new(None),
+    })
+}
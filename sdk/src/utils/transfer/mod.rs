@@ -0,0 +1,98 @@
+//! Download and upload files with progress tracking, pause/resume, and cancellation.
+
+mod download;
+mod platform;
+mod upload;
+
+pub use download::*;
+pub use upload::*;
+
+use std::fmt;
+
+/// The progress of a download or upload.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TransferProgress {
+    /// The number of bytes transferred so far.
+    pub transferred: u64,
+    /// The total number of bytes, if known.
+    pub total: Option<u64>,
+    /// The average transfer rate in bytes per second since the transfer was last started or resumed.
+    pub rate: f64,
+}
+
+impl TransferProgress {
+    /// The percentage of the transfer that has completed, if the total size is known.
+    pub fn percent(&self) -> Option<f64> {
+        match self.total {
+            Some(0) => Some(100.0),
+            Some(total) => Some(self.transferred as f64 / total as f64 * 100.0),
+            None => None,
+        }
+    }
+}
+
+/// The state of a download or upload.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum TransferState {
+    /// The transfer has not been started.
+    #[default]
+    Idle,
+    /// The transfer is in progress.
+    Running,
+    /// The transfer was paused and can be resumed.
+    Paused,
+    /// The transfer has finished.
+    Completed,
+    /// The transfer was cancelled.
+    Cancelled,
+    /// The transfer failed.
+    Failed(TransferError),
+}
+
+/// Represents errors when transferring files.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransferError {
+    /// The request could not be sent or the response could not be read.
+    Request(String),
+    /// The server responded with an unsuccessful status code.
+    Status(u16),
+    /// Reading or writing the local file failed.
+    Io(String),
+    /// Transfers are not supported on this platform.
+    Unsupported,
+}
+
+impl std::error::Error for TransferError {}
+impl fmt::Display for TransferError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TransferError::Request(e) => write!(f, "request failed: {}", e),
+            TransferError::Status(status) => write!(f, "server responded with status {}", status),
+            TransferError::Io(e) => write!(f, "an io error has occurred: {}", e),
+            TransferError::Unsupported => write!(f, "transfers are not supported on this platform"),
+        }
+    }
+}
+
+/// Measures the average transfer rate since it was created.
+struct RateMeter {
+    started: f64,
+    start_bytes: u64,
+}
+
+impl RateMeter {
+    fn new(start_bytes: u64) -> Self {
+        Self {
+            started: platform::now(),
+            start_bytes,
+        }
+    }
+
+    fn rate(&self, transferred: u64) -> f64 {
+        let elapsed = platform::now() - self.started;
+        if elapsed <= 0.0 {
+            return 0.0;
+        }
+        transferred.saturating_sub(self.start_bytes) as f64 / elapsed
+    }
+}
@@ -0,0 +1,90 @@
+use reqwest::{
+    header::{CONTENT_RANGE, RANGE},
+    Client, StatusCode,
+};
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::utils::transfer::TransferError;
+
+/// The client shared by all transfers so connections can be reused.
+static CLIENT: OnceLock<Client> = OnceLock::new();
+
+fn client() -> &'static Client {
+    CLIENT.get_or_init(Client::new)
+}
+
+/// A response to a download request.
+pub(crate) struct Response {
+    /// Whether the server honored the requested range.
+    pub partial: bool,
+    /// The length of the response body, if known.
+    pub length: Option<u64>,
+    inner: reqwest::Response,
+}
+
+impl Response {
+    /// Get the next chunk of the response body.
+    pub async fn chunk(&mut self) -> Result<Option<Vec<u8>>, TransferError> {
+        self.inner
+            .chunk()
+            .await
+            .map(|chunk| chunk.map(|bytes| bytes.to_vec()))
+            .map_err(|e| TransferError::Request(e.to_string()))
+    }
+}
+
+/// Request the resource at `url`, starting at the byte `offset`.
+pub(crate) async fn get(url: &str, offset: u64) -> Result<Response, TransferError> {
+    let mut request = client().get(url);
+    if offset > 0 {
+        request = request.header(RANGE, format!("bytes={}-", offset));
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| TransferError::Request(e.to_string()))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(TransferError::Status(status.as_u16()));
+    }
+
+    Ok(Response {
+        partial: status == StatusCode::PARTIAL_CONTENT,
+        length: response.content_length(),
+        inner: response,
+    })
+}
+
+/// Upload a chunk of a file to `url`, describing its position with a `Content-Range` header.
+pub(crate) async fn put(
+    url: &str,
+    chunk: Vec<u8>,
+    start: u64,
+    total: u64,
+) -> Result<(), TransferError> {
+    let end = start + chunk.len() as u64 - 1;
+    let response = client()
+        .put(url)
+        .header(CONTENT_RANGE, format!("bytes {}-{}/{}", start, end, total))
+        .body(chunk)
+        .send()
+        .await
+        .map_err(|e| TransferError::Request(e.to_string()))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(TransferError::Status(status.as_u16()));
+    }
+    Ok(())
+}
+
+/// The current time in seconds.
+pub(crate) fn now() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or_default()
+}
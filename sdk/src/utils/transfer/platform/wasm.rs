@@ -0,0 +1,114 @@
+use js_sys::{Reflect, Uint8Array};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{ReadableStreamDefaultReader, Request, RequestInit};
+
+use crate::utils::transfer::TransferError;
+
+/// A response to a download request.
+pub(crate) struct Response {
+    /// Whether the server honored the requested range.
+    pub partial: bool,
+    /// The length of the response body, if known.
+    pub length: Option<u64>,
+    reader: Option<ReadableStreamDefaultReader>,
+}
+
+impl Response {
+    /// Get the next chunk of the response body.
+    pub async fn chunk(&mut self) -> Result<Option<Vec<u8>>, TransferError> {
+        let Some(reader) = &self.reader else {
+            return Ok(None);
+        };
+
+        let result = JsFuture::from(reader.read()).await.map_err(js_error)?;
+        let done = Reflect::get(&result, &JsValue::from_str("done"))
+            .map_err(js_error)?
+            .as_bool()
+            .unwrap_or(true);
+        if done {
+            return Ok(None);
+        }
+
+        let value = Reflect::get(&result, &JsValue::from_str("value")).map_err(js_error)?;
+        let value = value.dyn_into::<Uint8Array>().map_err(js_error)?;
+        Ok(Some(value.to_vec()))
+    }
+}
+
+/// Request the resource at `url`, starting at the byte `offset`.
+pub(crate) async fn get(url: &str, offset: u64) -> Result<Response, TransferError> {
+    let init = RequestInit::new();
+    init.set_method("GET");
+    let request = Request::new_with_str_and_init(url, &init).map_err(js_error)?;
+    if offset > 0 {
+        request
+            .headers()
+            .set("Range", &format!("bytes={}-", offset))
+            .map_err(js_error)?;
+    }
+
+    let response = fetch(&request).await?;
+    let length = response
+        .headers()
+        .get("Content-Length")
+        .ok()
+        .flatten()
+        .and_then(|length| length.parse().ok());
+    let reader = response.body().map(|body| {
+        body.get_reader()
+            .unchecked_into::<ReadableStreamDefaultReader>()
+    });
+
+    Ok(Response {
+        partial: response.status() == 206,
+        length,
+        reader,
+    })
+}
+
+/// Upload a chunk of a file to `url`, describing its position with a `Content-Range` header.
+pub(crate) async fn put(
+    url: &str,
+    chunk: Vec<u8>,
+    start: u64,
+    total: u64,
+) -> Result<(), TransferError> {
+    let end = start + chunk.len() as u64 - 1;
+    let init = RequestInit::new();
+    init.set_method("PUT");
+    init.set_body(&Uint8Array::from(chunk.as_slice()).into());
+    let request = Request::new_with_str_and_init(url, &init).map_err(js_error)?;
+    request
+        .headers()
+        .set(
+            "Content-Range",
+            &format!("bytes {}-{}/{}", start, end, total),
+        )
+        .map_err(js_error)?;
+
+    fetch(&request).await?;
+    Ok(())
+}
+
+/// The current time in seconds.
+pub(crate) fn now() -> f64 {
+    js_sys::Date::now() / 1000.0
+}
+
+async fn fetch(request: &Request) -> Result<web_sys::Response, TransferError> {
+    let window = web_sys::window().ok_or(TransferError::Unsupported)?;
+    let response = JsFuture::from(window.fetch_with_request(request))
+        .await
+        .map_err(js_error)?;
+    let response = response.dyn_into::<web_sys::Response>().map_err(js_error)?;
+
+    if !response.ok() {
+        return Err(TransferError::Status(response.status()));
+    }
+    Ok(response)
+}
+
+fn js_error(e: JsValue) -> TransferError {
+    TransferError::Request(format!("{:?}", e))
+}
@@ -0,0 +1,201 @@
+use dioxus::prelude::*;
+
+use super::{platform, RateMeter, TransferError, TransferProgress, TransferState};
+
+/// The size of each uploaded chunk.
+const CHUNK_SIZE: u64 = 256 * 1024;
+
+/// What an upload reads from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UploadSource {
+    /// Read the upload from a file.
+    #[cfg(not(target_family = "wasm"))]
+    File(std::path::PathBuf),
+    /// Upload bytes that are already in memory.
+    Bytes(Vec<u8>),
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl From<std::path::PathBuf> for UploadSource {
+    fn from(path: std::path::PathBuf) -> Self {
+        Self::File(path)
+    }
+}
+
+impl From<Vec<u8>> for UploadSource {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::Bytes(bytes)
+    }
+}
+
+impl UploadSource {
+    async fn len(&self) -> Result<u64, TransferError> {
+        match self {
+            #[cfg(not(target_family = "wasm"))]
+            Self::File(path) => tokio::fs::metadata(path)
+                .await
+                .map(|metadata| metadata.len())
+                .map_err(|e| TransferError::Io(e.to_string())),
+            Self::Bytes(bytes) => Ok(bytes.len() as u64),
+        }
+    }
+
+    async fn read(&self, offset: u64, len: u64) -> Result<Vec<u8>, TransferError> {
+        match self {
+            #[cfg(not(target_family = "wasm"))]
+            Self::File(path) => {
+                use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+                let mut file = tokio::fs::File::open(path)
+                    .await
+                    .map_err(|e| TransferError::Io(e.to_string()))?;
+                file.seek(std::io::SeekFrom::Start(offset))
+                    .await
+                    .map_err(|e| TransferError::Io(e.to_string()))?;
+                let mut chunk = vec![0; len as usize];
+                file.read_exact(&mut chunk)
+                    .await
+                    .map_err(|e| TransferError::Io(e.to_string()))?;
+                Ok(chunk)
+            }
+            Self::Bytes(bytes) => Ok(bytes[offset as usize..(offset + len) as usize].to_vec()),
+        }
+    }
+}
+
+/// The interface for controlling an upload.
+///
+/// See [`use_upload`] for more information.
+#[derive(Clone, Copy, PartialEq)]
+pub struct UseUpload {
+    url: Signal<String>,
+    source: Signal<UploadSource>,
+    progress: Signal<TransferProgress>,
+    state: Signal<TransferState>,
+    task: Signal<Option<Task>>,
+}
+
+impl UseUpload {
+    /// Get the progress of the upload.
+    pub fn progress(&self) -> ReadOnlySignal<TransferProgress> {
+        ReadOnlySignal::new(self.progress)
+    }
+
+    /// Get the state of the upload.
+    pub fn state(&self) -> ReadOnlySignal<TransferState> {
+        ReadOnlySignal::new(self.state)
+    }
+
+    /// Start the upload from the beginning.
+    pub fn start(&mut self) {
+        self.run(0);
+    }
+
+    /// Pause the upload right away. It can be continued with [`UseUpload::resume`].
+    ///
+    /// A chunk that is still being sent is dropped, and sent again from its start when the upload resumes.
+    pub fn pause(&mut self) {
+        if let Some(task) = self.task.write().take() {
+            task.cancel();
+            self.state.set(TransferState::Paused);
+        }
+    }
+
+    /// Resume a paused or failed upload from the last chunk the server accepted.
+    pub fn resume(&mut self) {
+        let transferred = self.progress.peek().transferred;
+        self.run(transferred);
+    }
+
+    /// Cancel the upload.
+    pub fn cancel(&mut self) {
+        if let Some(task) = self.task.write().take() {
+            task.cancel();
+        }
+        self.progress.set(TransferProgress::default());
+        self.state.set(TransferState::Cancelled);
+    }
+
+    fn run(&mut self, offset: u64) {
+        if let Some(task) = self.task.write().take() {
+            task.cancel();
+        }
+
+        let mut upload = *self;
+        let task = spawn(async move {
+            upload.state.set(TransferState::Running);
+            let state = match upload.upload(offset).await {
+                Ok(()) => TransferState::Completed,
+                Err(e) => TransferState::Failed(e),
+            };
+            upload.state.set(state);
+            upload.task.set(None);
+        });
+        self.task.set(Some(task));
+    }
+
+    async fn upload(&mut self, offset: u64) -> Result<(), TransferError> {
+        let url = self.url.peek().clone();
+        let source = self.source.peek().clone();
+        let total = source.len().await?;
+
+        let mut transferred = offset.min(total);
+        self.progress.set(TransferProgress {
+            transferred,
+            total: Some(total),
+            rate: 0.0,
+        });
+        let meter = RateMeter::new(transferred);
+
+        while transferred < total {
+            let len = CHUNK_SIZE.min(total - transferred);
+            let chunk = source.read(transferred, len).await?;
+            platform::put(&url, chunk, transferred, total).await?;
+
+            // Only count the chunk once the server accepted it so resuming starts from the right place.
+            transferred += len;
+            self.progress.set(TransferProgress {
+                transferred,
+                total: Some(total),
+                rate: meter.rate(transferred),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// A hook for uploading a file with progress tracking, pause/resume, and cancellation.
+///
+/// The file is sent in chunks with `PUT` requests that carry a `Content-Range` header,
+/// so the server can reassemble the file and an interrupted upload can resume from the last accepted chunk.
+/// The upload doesn't start until [`UseUpload::start`] is called.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::transfer::use_upload;
+/// use std::path::PathBuf;
+///
+/// fn App() -> Element {
+///     let mut upload = use_upload("https://example.com/upload", PathBuf::from("video.mp4"));
+///     let progress = upload.progress();
+///
+///     rsx! {
+///         p { "{progress().transferred} bytes at {progress().rate:.0} B/s" }
+///         button { onclick: move |_| upload.start(), "Upload" }
+///         button { onclick: move |_| upload.pause(), "Pause" }
+///         button { onclick: move |_| upload.resume(), "Resume" }
+///     }
+/// }
+/// ```
+pub fn use_upload(url: impl ToString, file: impl Into<UploadSource>) -> UseUpload {
+    use_hook(|| UseUpload {
+        url: Signal::new(url.to_string()),
+        source: Signal::new(file.into()),
+        progress: Signal::new(TransferProgress::default()),
+        state: Signal::new(TransferState::Idle),
+        task: Signal::new(None),
+    })
+}
@@ -0,0 +1,349 @@
+use dioxus::prelude::*;
+
+use super::controls::{WindowControlError, WindowControlResult};
+
+/// A layout [`arrange_window`] can move the window into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arrangement {
+    /// Fill the left half of the monitor.
+    LeftHalf,
+    /// Fill the right half of the monitor.
+    RightHalf,
+    /// Maximize the window.
+    Maximize,
+    /// Move the window back to where it was before it was arranged.
+    Restore,
+}
+
+/// How the window is currently laid out on its monitor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapState {
+    /// The window isn't snapped to any layout.
+    Floating,
+    /// The window fills the left half of the monitor.
+    LeftHalf,
+    /// The window fills the right half of the monitor.
+    RightHalf,
+    /// The window is maximized.
+    Maximized,
+}
+
+/// Move the window into an [`Arrangement`].
+///
+/// The halves are computed from the work area of the monitor the window is on, which leaves out the taskbar on
+/// Windows and the menu bar and dock on macOS. On Linux the halves cover the whole monitor, including any panels
+/// on it. The geometry the window had before it was first arranged is remembered, so
+/// [`Arrangement::Restore`] moves it back there. This is not supported on web.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::window::{arrange_window, use_snap_state, Arrangement, SnapState};
+///
+/// fn Toolbar() -> Element {
+///     let snap = use_snap_state();
+///
+///     rsx! {
+///         button { onclick: move |_| { arrange_window(Arrangement::LeftHalf).ok(); }, "Left" }
+///         button { onclick: move |_| { arrange_window(Arrangement::RightHalf).ok(); }, "Right" }
+///         if snap() != SnapState::Floating {
+///             button { onclick: move |_| { arrange_window(Arrangement::Restore).ok(); }, "Restore" }
+///         }
+///     }
+/// }
+/// ```
+pub fn arrange_window(arrangement: Arrangement) -> WindowControlResult {
+    arrange_window_platform(arrangement)
+}
+
+/// Get how the window is currently laid out on its monitor.
+///
+/// This is always [`SnapState::Floating`] on web.
+pub fn get_snap_state() -> SnapState {
+    get_snap_state_platform()
+}
+
+/// A hook for how the window is laid out on its monitor.
+///
+/// The signal updates when the window is moved or resized, including when the user snaps it with the system's own
+/// shortcuts. This is always [`SnapState::Floating`] on web.
+pub fn use_snap_state() -> ReadOnlySignal<SnapState> {
+    let snap_state = use_signal(get_snap_state);
+    use_snap_listener(snap_state);
+    ReadOnlySignal::new(snap_state)
+}
+
+// Web implementation of the window arrangement.
+#[cfg(target_family = "wasm")]
+fn arrange_window_platform(_arrangement: Arrangement) -> WindowControlResult {
+    Err(WindowControlError::NotSupported)
+}
+
+#[cfg(target_family = "wasm")]
+fn get_snap_state_platform() -> SnapState {
+    SnapState::Floating
+}
+
+#[cfg(target_family = "wasm")]
+fn use_snap_listener(_snap_state: Signal<SnapState>) {}
+
+// Desktop implementation of the window arrangement.
+#[cfg(not(target_family = "wasm"))]
+use desktop::*;
+
+#[cfg(not(target_family = "wasm"))]
+mod desktop {
+    use super::*;
+    use crate::utils::window::controls::desktop_window;
+    use dioxus_desktop::{
+        tao::{
+            dpi::{PhysicalPosition, PhysicalSize},
+            event::Event,
+            window::WindowId,
+        },
+        DesktopContext, WindowEvent,
+    };
+    use std::{cell::RefCell, collections::HashMap};
+
+    /// How many physical pixels the window may be off a layout and still count as snapped to it.
+    const SNAP_TOLERANCE: i32 = 8;
+
+    thread_local! {
+        /// The geometry of each window before it was arranged, for restoring it.
+        static FLOATING: RefCell<HashMap<WindowId, Rect>> = RefCell::new(HashMap::new());
+    }
+
+    /// A rectangle in physical pixels.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Rect {
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+    }
+
+    impl Rect {
+        fn half(&self, left: bool) -> Rect {
+            let width = self.width / 2;
+            Rect {
+                x: if left {
+                    self.x
+                } else {
+                    self.x + self.width - width
+                },
+                width,
+                ..*self
+            }
+        }
+
+        fn is_near(&self, other: &Rect) -> bool {
+            [
+                (self.x, other.x),
+                (self.y, other.y),
+                (self.width, other.width),
+                (self.height, other.height),
+            ]
+            .into_iter()
+            .all(|(a, b)| (a - b).abs() <= SNAP_TOLERANCE)
+        }
+    }
+
+    pub(super) fn arrange_window_platform(arrangement: Arrangement) -> WindowControlResult {
+        let window = desktop_window()?;
+        let id = window.window.id();
+
+        // Only the geometry of a floating window is worth going back to.
+        if arrangement != Arrangement::Restore && snap_state(&window) == SnapState::Floating {
+            if let Some(rect) = outer_rect(&window) {
+                FLOATING.with(|floating| floating.borrow_mut().insert(id, rect));
+            }
+        }
+
+        match arrangement {
+            Arrangement::LeftHalf | Arrangement::RightHalf => {
+                let area = work_area(&window)?;
+                window.set_maximized(false);
+                set_outer_rect(&window, area.half(arrangement == Arrangement::LeftHalf));
+            }
+            Arrangement::Maximize => window.set_maximized(true),
+            Arrangement::Restore => {
+                window.set_maximized(false);
+                if let Some(rect) = FLOATING.with(|floating| floating.borrow_mut().remove(&id)) {
+                    set_outer_rect(&window, rect);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub(super) fn get_snap_state_platform() -> SnapState {
+        desktop_window()
+            .map(|window| snap_state(&window))
+            .unwrap_or(SnapState::Floating)
+    }
+
+    pub(super) fn use_snap_listener(mut snap_state_signal: Signal<SnapState>) {
+        use_hook_with_cleanup(
+            move || {
+                let desktop = try_consume_context::<DesktopContext>()?;
+                let window_id = desktop.window.id();
+                let window = desktop.clone();
+
+                Some(desktop.create_wry_event_handler(move |event, _| {
+                    let Event::WindowEvent {
+                        event,
+                        window_id: id,
+                        ..
+                    } = event
+                    else {
+                        return;
+                    };
+                    if *id != window_id {
+                        return;
+                    }
+
+                    if let WindowEvent::Moved(_) | WindowEvent::Resized(_) = event {
+                        let state = snap_state(&window);
+                        if *snap_state_signal.peek() != state {
+                            snap_state_signal.set(state);
+                        }
+                    }
+                }))
+            },
+            |handler| {
+                if let Some(handler) = handler {
+                    handler.remove();
+                }
+            },
+        );
+    }
+
+    fn snap_state(window: &DesktopContext) -> SnapState {
+        if window.is_maximized() {
+            return SnapState::Maximized;
+        }
+        let (Ok(area), Some(rect)) = (work_area(window), outer_rect(window)) else {
+            return SnapState::Floating;
+        };
+
+        if rect.is_near(&area.half(true)) {
+            SnapState::LeftHalf
+        } else if rect.is_near(&area.half(false)) {
+            SnapState::RightHalf
+        } else {
+            SnapState::Floating
+        }
+    }
+
+    fn outer_rect(window: &DesktopContext) -> Option<Rect> {
+        let position = window.outer_position().ok()?;
+        let size = window.outer_size();
+        Some(Rect {
+            x: position.x,
+            y: position.y,
+            width: size.width as i32,
+            height: size.height as i32,
+        })
+    }
+
+    /// Move and resize the window so its outer frame covers the rectangle.
+    fn set_outer_rect(window: &DesktopContext, rect: Rect) {
+        // Only the inner size can be set, so the decorations are subtracted.
+        let outer = window.outer_size();
+        let inner = window.inner_size();
+        let decorations_width = outer.width.saturating_sub(inner.width) as i32;
+        let decorations_height = outer.height.saturating_sub(inner.height) as i32;
+
+        window.set_outer_position(PhysicalPosition::new(rect.x, rect.y));
+        window.set_inner_size(PhysicalSize::new(
+            (rect.width - decorations_width).max(1) as u32,
+            (rect.height - decorations_height).max(1) as u32,
+        ));
+    }
+
+    /// The part of the window's monitor that isn't covered by the taskbar.
+    #[cfg(windows)]
+    fn work_area(window: &DesktopContext) -> Result<Rect, WindowControlError> {
+        use dioxus_desktop::tao::platform::windows::WindowExtWindows;
+        use windows::Win32::{
+            Foundation::HWND,
+            Graphics::Gdi::{
+                GetMonitorInfoW, MonitorFromWindow, MONITORINFO, MONITOR_DEFAULTTONEAREST,
+            },
+        };
+
+        let hwnd = HWND(window.hwnd() as isize);
+        let mut info = MONITORINFO {
+            cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+            ..Default::default()
+        };
+        unsafe {
+            let monitor = MonitorFromWindow(hwnd, MONITOR_DEFAULTTONEAREST);
+            if !GetMonitorInfoW(monitor, &mut info).as_bool() {
+                return Err(WindowControlError::Failed);
+            }
+        }
+
+        let work = info.rcWork;
+        Ok(Rect {
+            x: work.left,
+            y: work.top,
+            width: work.right - work.left,
+            height: work.bottom - work.top,
+        })
+    }
+
+    /// The part of the window's screen that isn't covered by the menu bar or the dock.
+    #[cfg(target_os = "macos")]
+    fn work_area(window: &DesktopContext) -> Result<Rect, WindowControlError> {
+        use dioxus_desktop::tao::platform::macos::WindowExtMacOS;
+        use objc::{class, msg_send, runtime::Object, sel, sel_impl};
+
+        #[repr(C)]
+        struct NSRect {
+            x: f64,
+            y: f64,
+            width: f64,
+            height: f64,
+        }
+
+        let (visible, primary): (NSRect, NSRect) = unsafe {
+            let ns_window = window.ns_window() as *mut Object;
+            let screen: *mut Object = msg_send![ns_window, screen];
+            let screens: *mut Object = msg_send![class!(NSScreen), screens];
+            let primary: *mut Object = msg_send![screens, firstObject];
+            if screen.is_null() || primary.is_null() {
+                return Err(WindowControlError::Failed);
+            }
+            (msg_send![screen, visibleFrame], msg_send![primary, frame])
+        };
+
+        // Cocoa measures points up from the bottom of the primary screen, while windows are placed in pixels down
+        // from its top.
+        let scale = window.scale_factor();
+        let top = primary.height - (visible.y + visible.height);
+        let pixels = |points: f64| (points * scale).round() as i32;
+        Ok(Rect {
+            x: pixels(visible.x),
+            y: pixels(top),
+            width: pixels(visible.width),
+            height: pixels(visible.height),
+        })
+    }
+
+    /// The bounds of the window's monitor. Linux has no way to get the area taken by panels that works everywhere,
+    /// since Wayland doesn't expose it, so the panels are included.
+    #[cfg(not(any(windows, target_os = "macos")))]
+    fn work_area(window: &DesktopContext) -> Result<Rect, WindowControlError> {
+        let monitor = window.current_monitor().ok_or(WindowControlError::Failed)?;
+        let position = monitor.position();
+        let size = monitor.size();
+        Ok(Rect {
+            x: position.x,
+            y: position.y,
+            width: size.width as i32,
+            height: size.height as i32,
+        })
+    }
+}
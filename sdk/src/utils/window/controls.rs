@@ -0,0 +1,314 @@
+use dioxus::{html::input_data::MouseButton, prelude::*};
+use std::{
+    error::Error,
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+/// Represents an error when controlling the window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WindowControlError {
+    /// Controlling the window is not supported on this platform.
+    NotSupported,
+    /// The system returned an error while updating the window.
+    Failed,
+}
+
+impl Error for WindowControlError {}
+impl Display for WindowControlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::NotSupported => write!(f, "the current platform is not supported"),
+            Self::Failed => write!(f, "the system returned an error while updating the window"),
+        }
+    }
+}
+
+pub(super) type WindowControlResult = Result<(), WindowControlError>;
+
+/// A hook for keeping the window above all other windows.
+///
+/// Writing to the returned signal updates the window. This does nothing on web.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::window::use_always_on_top;
+///
+/// fn App() -> Element {
+///     let mut always_on_top = use_always_on_top(false);
+///
+///     rsx! {
+///         button {
+///             onclick: move |_| always_on_top.set(!always_on_top()),
+///             "Pin window: {always_on_top}"
+///         }
+///     }
+/// }
+/// ```
+pub fn use_always_on_top(initial: bool) -> Signal<bool> {
+    use_window_control(initial, set_always_on_top)
+}
+
+/// A hook for showing or hiding the window decorations, such as the titlebar and borders.
+///
+/// Writing to the returned signal updates the window. This does nothing on web.
+pub fn use_decorations(initial: bool) -> Signal<bool> {
+    use_window_control(initial, set_decorations)
+}
+
+/// A hook for setting the window opacity, from `0.0` (transparent) to `1.0` (opaque).
+///
+/// Writing to the returned signal updates the window. This does nothing on unsupported platforms.
+pub fn use_opacity(initial: f32) -> Signal<f32> {
+    use_window_control(initial, set_opacity)
+}
+
+/// A hook for hiding the window from screenshots, screen recordings, and screen sharing.
+///
+/// Writing to the returned signal updates the window. The window stays protected after the component is dropped, so
+/// call [`set_content_protection`] to turn it off. See [`set_content_protection`] for the supported platforms.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::window::use_content_protection;
+///
+/// fn Settings() -> Element {
+///     let mut protected = use_content_protection(true);
+///
+///     rsx! {
+///         label {
+///             input {
+///                 r#type: "checkbox",
+///                 checked: protected(),
+///                 oninput: move |event| protected.set(event.checked()),
+///             }
+///             "Hide the app from screen captures"
+///         }
+///     }
+/// }
+/// ```
+pub fn use_content_protection(initial: bool) -> Signal<bool> {
+    use_window_control(initial, set_content_protection)
+}
+
+/// How soon a second press must follow the first to count as a double-click.
+const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(500);
+
+/// A hook for making an element drag the window, like the titlebar of a window without decorations.
+///
+/// Attach the returned handler to the `onmousedown` event of the element. Pressing the primary mouse button on it
+/// moves the window until the button is released, and double-clicking it maximizes or restores the window.
+/// Interactive children such as buttons should stop the propagation of their `onmousedown` event so pressing them
+/// doesn't drag the window. This does nothing on web.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::window::use_window_drag;
+///
+/// fn App() -> Element {
+///     let drag = use_window_drag();
+///
+///     rsx! {
+///         div {
+///             class: "titlebar",
+///             onmousedown: drag,
+///             "My App"
+///             button {
+///                 onmousedown: |event| event.stop_propagation(),
+///                 "Close"
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_window_drag() -> impl FnMut(MouseEvent) + Copy {
+    let mut last_press = use_hook(|| CopyValue::new(None::<Instant>));
+
+    move |event: MouseEvent| {
+        // The web can't move the window, and `Instant::now` isn't available there.
+        if cfg!(target_family = "wasm") || event.trigger_button() != Some(MouseButton::Primary) {
+            return;
+        }
+
+        // The window is moved by the system, which swallows the release of the first click,
+        // so double-clicks are detected from the presses instead.
+        let now = Instant::now();
+        let double_click = last_press
+            .write()
+            .replace(now)
+            .is_some_and(|previous| now.duration_since(previous) < DOUBLE_CLICK_TIME);
+
+        let result = if double_click {
+            last_press.set(None);
+            toggle_maximized()
+        } else {
+            drag_window()
+        };
+        if let Err(WindowControlError::Failed) = result {
+            tracing::warn!("Failed to move the window");
+        }
+    }
+}
+
+fn use_window_control<T: Copy + 'static>(
+    initial: T,
+    setter: fn(T) -> WindowControlResult,
+) -> Signal<T> {
+    let value = use_signal(|| initial);
+    use_effect(move || {
+        // Unsupported platforms are a no-op.
+        if let Err(WindowControlError::Failed) = setter(value()) {
+            tracing::warn!("Failed to update the window");
+        }
+    });
+    value
+}
+
+/// Keep the window above all other windows.
+pub fn set_always_on_top(always_on_top: bool) -> WindowControlResult {
+    set_always_on_top_platform(always_on_top)
+}
+
+/// Show or hide the window decorations, such as the titlebar and borders.
+pub fn set_decorations(decorations: bool) -> WindowControlResult {
+    set_decorations_platform(decorations)
+}
+
+/// Set the window opacity, from `0.0` (transparent) to `1.0` (opaque).
+///
+/// This is currently only supported on Windows.
+pub fn set_opacity(opacity: f32) -> WindowControlResult {
+    set_opacity_platform(opacity.clamp(0.0, 1.0))
+}
+
+/// Hide the window from screenshots, screen recordings, and screen sharing, for apps that show sensitive data.
+///
+/// Captures show the window as black or leave it out. This is supported on Windows 10 and later and on macOS.
+///
+/// The protection is advisory: it is a request to the system, which capture tools may ignore, and it doesn't stop
+/// the screen from being photographed. On other platforms, including web, the window can't be protected, so this
+/// does nothing and still returns `Ok(())`. Don't rely on it to keep data secret.
+pub fn set_content_protection(enabled: bool) -> WindowControlResult {
+    set_content_protection_platform(enabled)
+}
+
+/// Move the window with the primary mouse button until it is released.
+///
+/// This must be called while the button is pressed, usually from an `onmousedown` handler.
+/// Fullscreen windows are not moved.
+pub fn drag_window() -> WindowControlResult {
+    drag_window_platform()
+}
+
+/// Maximize the window, or restore it if it is already maximized.
+pub fn toggle_maximized() -> WindowControlResult {
+    toggle_maximized_platform()
+}
+
+// Web implementations of the window controls.
+#[cfg(target_family = "wasm")]
+fn set_always_on_top_platform(_always_on_top: bool) -> WindowControlResult {
+    Err(WindowControlError::NotSupported)
+}
+
+#[cfg(target_family = "wasm")]
+fn set_decorations_platform(_decorations: bool) -> WindowControlResult {
+    Err(WindowControlError::NotSupported)
+}
+
+#[cfg(target_family = "wasm")]
+fn set_opacity_platform(_opacity: f32) -> WindowControlResult {
+    Err(WindowControlError::NotSupported)
+}
+
+#[cfg(target_family = "wasm")]
+fn set_content_protection_platform(_enabled: bool) -> WindowControlResult {
+    Ok(())
+}
+
+#[cfg(target_family = "wasm")]
+fn drag_window_platform() -> WindowControlResult {
+    Err(WindowControlError::NotSupported)
+}
+
+#[cfg(target_family = "wasm")]
+fn toggle_maximized_platform() -> WindowControlResult {
+    Err(WindowControlError::NotSupported)
+}
+
+// Desktop implementations of the window controls.
+#[cfg(not(target_family = "wasm"))]
+pub(super) fn desktop_window() -> Result<dioxus_desktop::DesktopContext, WindowControlError> {
+    try_consume_context::<dioxus_desktop::DesktopContext>().ok_or(WindowControlError::NotSupported)
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn set_always_on_top_platform(always_on_top: bool) -> WindowControlResult {
+    desktop_window()?.set_always_on_top(always_on_top);
+    Ok(())
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn set_decorations_platform(decorations: bool) -> WindowControlResult {
+    desktop_window()?.set_decorations(decorations);
+    Ok(())
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn drag_window_platform() -> WindowControlResult {
+    desktop_window()?.drag();
+    Ok(())
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn toggle_maximized_platform() -> WindowControlResult {
+    desktop_window()?.toggle_maximized();
+    Ok(())
+}
+
+#[cfg(windows)]
+fn set_opacity_platform(opacity: f32) -> WindowControlResult {
+    use dioxus_desktop::tao::platform::windows::WindowExtWindows;
+    use windows::Win32::{
+        Foundation::{COLORREF, HWND},
+        UI::WindowsAndMessaging::{
+            GetWindowLongW, SetLayeredWindowAttributes, SetWindowLongW, GWL_EXSTYLE, LWA_ALPHA,
+            WS_EX_LAYERED,
+        },
+    };
+
+    let window = desktop_window()?;
+    let hwnd = HWND(window.hwnd() as isize);
+
+    // Opacity can only be set on layered windows.
+    unsafe {
+        let style = GetWindowLongW(hwnd, GWL_EXSTYLE);
+        SetWindowLongW(hwnd, GWL_EXSTYLE, style | WS_EX_LAYERED.0 as i32);
+        SetLayeredWindowAttributes(hwnd, COLORREF(0), (opacity * 255.0) as u8, LWA_ALPHA)
+            .ok()
+            .map_err(|_| WindowControlError::Failed)
+    }
+}
+
+#[cfg(all(not(target_family = "wasm"), not(windows)))]
+fn set_opacity_platform(_opacity: f32) -> WindowControlResult {
+    Err(WindowControlError::NotSupported)
+}
+
+/// Uses `SetWindowDisplayAffinity` on Windows and the `sharingType` of the window on macOS.
+#[cfg(any(windows, target_os = "macos"))]
+fn set_content_protection_platform(enabled: bool) -> WindowControlResult {
+    desktop_window()?.window.set_content_protection(enabled);
+    Ok(())
+}
+
+#[cfg(all(not(target_family = "wasm"), not(any(windows, target_os = "macos"))))]
+fn set_content_protection_platform(_enabled: bool) -> WindowControlResult {
+    Ok(())
+}
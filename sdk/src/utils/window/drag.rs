@@ -0,0 +1,180 @@
+use dioxus::prelude::*;
+use std::{
+    error::Error,
+    fmt::Display,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use super::{use_window_events, WindowEvent, WindowEventKind};
+
+/// What is dragged with [`start_drag`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum DragPayload {
+    /// Text, like a note or a tab dragged out of a split-view editor.
+    Text(String),
+    /// Files on disk.
+    Files(Vec<PathBuf>),
+}
+
+/// Represents an error when starting a drag.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DragError {
+    /// Dragging out of the window is not supported on this platform.
+    NotSupported,
+    /// The drag could not be started.
+    Failed(String),
+}
+
+impl Error for DragError {}
+impl Display for DragError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::NotSupported => write!(f, "the current platform is not supported"),
+            Self::Failed(e) => write!(f, "failed to start the drag: {e}"),
+        }
+    }
+}
+
+/// The payload of the drag this app started, and the files it is carried in.
+///
+/// Operating systems only carry files between windows, so text is written to a file. When the file is dropped in a
+/// window of this app, it is turned back into the original payload.
+static ACTIVE_DRAG: Mutex<Option<(Vec<PathBuf>, DragPayload)>> = Mutex::new(None);
+
+/// Start an operating system drag of `payload` from the window the component is rendered in.
+///
+/// The payload can be dropped in other windows of the app, which receive it with [`use_payload_drop`], and in other
+/// apps, which receive text as a `.txt` file. This must be called while the primary mouse button is pressed, usually
+/// from an `onmousedown` handler. Only works on desktop.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::window::{start_drag, DragPayload};
+///
+/// fn Tab(title: String) -> Element {
+///     rsx! {
+///         div {
+///             onmousedown: move |_| {
+///                 start_drag(DragPayload::Text(title.clone())).ok();
+///             },
+///             "{title}"
+///         }
+///     }
+/// }
+/// ```
+pub fn start_drag(payload: DragPayload) -> Result<(), DragError> {
+    start_drag_platform(payload)
+}
+
+/// A hook that calls `handler` when a payload is dropped on the window the component is rendered in.
+///
+/// Payloads started with [`start_drag`] in any window of the app arrive as they were started. Files dragged in from
+/// other apps arrive as [`DragPayload::Files`]. Only works on desktop, and only if the webview doesn't handle the
+/// drop itself.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::window::{use_payload_drop, DragPayload};
+///
+/// fn Editor() -> Element {
+///     let mut tabs = use_signal(Vec::new);
+///     use_payload_drop(move |payload| {
+///         if let DragPayload::Text(title) = payload {
+///             tabs.write().push(title);
+///         }
+///     });
+///
+///     rsx! {
+///         for tab in tabs() {
+///             p { "{tab}" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_payload_drop(mut handler: impl FnMut(DragPayload) + 'static) {
+    use_window_events(&[WindowEventKind::File], move |event| {
+        if let WindowEvent::FileDropped(path) = event {
+            if let Some(payload) = payload_for(&path) {
+                handler(payload);
+            }
+        }
+    });
+}
+
+/// Get the payload that arrives with a dropped file.
+///
+/// A drag of several files drops each of them, so only the first file of an active drag is reported.
+fn payload_for(path: &Path) -> Option<DragPayload> {
+    let active = ACTIVE_DRAG.lock().unwrap();
+    match active.as_ref() {
+        Some((paths, payload)) if paths.first().map(PathBuf::as_path) == Some(path) => {
+            Some(payload.clone())
+        }
+        Some((paths, _)) if paths.iter().any(|p| p == path) => None,
+        _ => Some(DragPayload::Files(vec![path.to_path_buf()])),
+    }
+}
+
+/// A transparent 1x1 PNG, shown under the cursor while dragging.
+#[cfg(not(any(target_family = "wasm", target_os = "android", target_os = "ios")))]
+const DRAG_IMAGE: &[u8] = &[
+    0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52,
+    0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1f, 0x15, 0xc4,
+    0x89, 0x00, 0x00, 0x00, 0x0b, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9c, 0x63, 0x60, 0x00, 0x02, 0x00,
+    0x00, 0x05, 0x00, 0x01, 0x7a, 0x5e, 0xab, 0x3f, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44,
+    0xae, 0x42, 0x60, 0x82,
+];
+
+// Desktop implementation of dragging.
+#[cfg(not(any(target_family = "wasm", target_os = "android", target_os = "ios")))]
+fn start_drag_platform(payload: DragPayload) -> Result<(), DragError> {
+    let desktop =
+        try_consume_context::<dioxus_desktop::DesktopContext>().ok_or(DragError::NotSupported)?;
+
+    let paths = match &payload {
+        DragPayload::Files(paths) => paths.clone(),
+        DragPayload::Text(text) => {
+            let path = std::env::temp_dir().join(format!("dioxus-drag-{}.txt", std::process::id()));
+            std::fs::write(&path, text).map_err(|e| DragError::Failed(e.to_string()))?;
+            vec![path]
+        }
+    };
+    *ACTIVE_DRAG.lock().unwrap() = Some((paths.clone(), payload));
+
+    // On Linux, drags are started from the GTK window instead of the native window handle.
+    #[cfg(target_os = "linux")]
+    let handle = {
+        use dioxus_desktop::tao::platform::unix::WindowExtUnix;
+        desktop.window.gtk_window()
+    };
+    #[cfg(not(target_os = "linux"))]
+    let handle = &desktop.window;
+
+    drag::start_drag(
+        handle,
+        drag::DragItem::Files(paths),
+        drag::Image::Raw(DRAG_IMAGE.to_vec()),
+        |result, _| {
+            // Keep the payload until the drop was delivered to the window it was dropped on.
+            if let drag::DragResult::Cancel = result {
+                ACTIVE_DRAG.lock().unwrap().take();
+            }
+        },
+        Default::default(),
+    )
+    .map_err(|e| {
+        ACTIVE_DRAG.lock().unwrap().take();
+        DragError::Failed(e.to_string())
+    })
+}
+
+// Web and mobile implementation of dragging.
+#[cfg(any(target_family = "wasm", target_os = "android", target_os = "ios"))]
+fn start_drag_platform(_payload: DragPayload) -> Result<(), DragError> {
+    Err(DragError::NotSupported)
+}
@@ -0,0 +1,182 @@
+use dioxus::prelude::*;
+use std::{error::Error, fmt::Display};
+
+/// A translucent backdrop material for the window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Vibrancy {
+    /// Mica on Windows 11 and the under-window background material on macOS.
+    Mica,
+    /// Acrylic on Windows 10 and 11 and the HUD window material on macOS.
+    Acrylic,
+}
+
+/// The visual effects applied to the window background.
+///
+/// The default value removes all effects.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Effects {
+    /// The backdrop material to apply. This takes priority over `blur`.
+    pub vibrancy: Option<Vibrancy>,
+    /// Blur whatever is behind the window.
+    pub blur: bool,
+    /// The corner radius of the effect view.
+    ///
+    /// This is only supported on macOS. Windows 11 rounds window corners automatically.
+    pub corner_radius: Option<f64>,
+}
+
+/// Represents an error when applying window effects.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WindowEffectError {
+    /// The effect is not supported on this platform or version of the operating system.
+    NotSupported,
+    /// The system returned an error while applying the effect.
+    Failed,
+}
+
+impl Error for WindowEffectError {}
+impl Display for WindowEffectError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::NotSupported => write!(f, "the effect is not supported on the current platform"),
+            Self::Failed => write!(f, "the system returned an error while applying the effect"),
+        }
+    }
+}
+
+type WindowEffectResult = Result<(), WindowEffectError>;
+
+/// A hook for applying visual effects to the window background.
+///
+/// Writing to the returned signal updates the window. This does nothing on web and Linux.
+///
+/// See [`set_window_effects`] for more information.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::window::{use_window_effects, Effects, Vibrancy};
+///
+/// fn App() -> Element {
+///     let mut effects = use_window_effects(Effects {
+///         vibrancy: Some(Vibrancy::Mica),
+///         ..Default::default()
+///     });
+///
+///     rsx! {
+///         button {
+///             onclick: move |_| effects.set(Effects::default()),
+///             "Remove effects"
+///         }
+///     }
+/// }
+/// ```
+pub fn use_window_effects(initial: Effects) -> Signal<Effects> {
+    let effects = use_signal(|| initial);
+    use_effect(move || {
+        // Unsupported platforms are a no-op.
+        if let Err(WindowEffectError::Failed) = set_window_effects(effects()) {
+            tracing::warn!("Failed to apply window effects");
+        }
+    });
+    effects
+}
+
+/// Apply visual effects to the window background, replacing any effects applied before.
+///
+/// Mica and acrylic are used on Windows and an `NSVisualEffectView` is used on macOS.
+/// The effects are drawn behind the webview, so they are only visible if the window is transparent
+/// and the page doesn't paint an opaque background:
+///
+/// ```rust,ignore
+/// use dioxus::desktop::{Config, WindowBuilder};
+///
+/// LaunchBuilder::desktop()
+///     .with_cfg(Config::new().with_window(WindowBuilder::new().with_transparent(true)))
+///     .launch(app);
+/// ```
+pub fn set_window_effects(effects: Effects) -> WindowEffectResult {
+    set_window_effects_platform(effects)
+}
+
+// Web implementation of the window effects.
+#[cfg(target_family = "wasm")]
+fn set_window_effects_platform(_effects: Effects) -> WindowEffectResult {
+    Err(WindowEffectError::NotSupported)
+}
+
+// Desktop implementations of the window effects.
+#[cfg(any(windows, target_os = "macos"))]
+fn desktop_window() -> Result<dioxus_desktop::DesktopContext, WindowEffectError> {
+    try_consume_context::<dioxus_desktop::DesktopContext>().ok_or(WindowEffectError::NotSupported)
+}
+
+#[cfg(any(windows, target_os = "macos"))]
+impl From<window_vibrancy::Error> for WindowEffectError {
+    fn from(error: window_vibrancy::Error) -> Self {
+        match error {
+            window_vibrancy::Error::UnsupportedPlatform(_)
+            | window_vibrancy::Error::UnsupportedPlatformVersion(_) => Self::NotSupported,
+            _ => Self::Failed,
+        }
+    }
+}
+
+#[cfg(windows)]
+fn set_window_effects_platform(effects: Effects) -> WindowEffectResult {
+    use window_vibrancy::{
+        apply_acrylic, apply_blur, apply_mica, clear_acrylic, clear_blur, clear_mica,
+    };
+
+    let desktop = desktop_window()?;
+    let window = &desktop.window;
+
+    // Only one backdrop can be active at a time. Clearing an effect that isn't applied
+    // or isn't supported on this version of Windows fails, so those errors are ignored.
+    let _ = clear_mica(window);
+    let _ = clear_acrylic(window);
+    let _ = clear_blur(window);
+
+    match (effects.vibrancy, effects.blur) {
+        (Some(Vibrancy::Mica), _) => apply_mica(window, None)?,
+        (Some(Vibrancy::Acrylic), _) => apply_acrylic(window, None)?,
+        (None, true) => apply_blur(window, None)?,
+        (None, false) => {}
+    }
+
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn set_window_effects_platform(effects: Effects) -> WindowEffectResult {
+    use window_vibrancy::{
+        apply_vibrancy, clear_vibrancy, NSVisualEffectMaterial, NSVisualEffectState,
+    };
+
+    let desktop = desktop_window()?;
+    let window = &desktop.window;
+
+    clear_vibrancy(window)?;
+
+    let material = match (effects.vibrancy, effects.blur) {
+        (Some(Vibrancy::Mica), _) => NSVisualEffectMaterial::UnderWindowBackground,
+        (Some(Vibrancy::Acrylic), _) => NSVisualEffectMaterial::HudWindow,
+        (None, true) => NSVisualEffectMaterial::FullScreenUI,
+        (None, false) => return Ok(()),
+    };
+
+    apply_vibrancy(
+        window,
+        material,
+        Some(NSVisualEffectState::Active),
+        effects.corner_radius,
+    )?;
+
+    Ok(())
+}
+
+#[cfg(all(not(target_family = "wasm"), not(windows), not(target_os = "macos")))]
+fn set_window_effects_platform(_effects: Effects) -> WindowEffectResult {
+    Err(WindowEffectError::NotSupported)
+}
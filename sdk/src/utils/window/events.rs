@@ -0,0 +1,270 @@
+use dioxus::prelude::*;
+use futures::{channel::mpsc::UnboundedSender, StreamExt};
+use std::path::PathBuf;
+
+/// An event of the window the app runs in.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WindowEvent {
+    /// The window was moved to a new position on the screen, in physical pixels. Desktop only.
+    Moved { x: i32, y: i32 },
+    /// The window or viewport was resized, in physical pixels on desktop and CSS pixels on web.
+    Resized { width: u32, height: u32 },
+    /// The window gained or lost focus.
+    Focused(bool),
+    /// The system theme changed.
+    ThemeChanged(WindowTheme),
+    /// The ratio between physical and logical pixels changed, such as when the window moved to another monitor.
+    /// Desktop only.
+    ScaleFactorChanged(f64),
+    /// A file is being dragged over the window. Desktop only.
+    FileHovered(PathBuf),
+    /// A file is no longer being dragged over the window. Desktop only.
+    FileHoverCancelled,
+    /// A file was dropped on the window. Desktop only.
+    FileDropped(PathBuf),
+    /// The user asked to close the window. Desktop only.
+    CloseRequested,
+}
+
+impl WindowEvent {
+    /// Get the kind of the event, without its data.
+    pub fn kind(&self) -> WindowEventKind {
+        match self {
+            Self::Moved { .. } => WindowEventKind::Moved,
+            Self::Resized { .. } => WindowEventKind::Resized,
+            Self::Focused(_) => WindowEventKind::Focused,
+            Self::ThemeChanged(_) => WindowEventKind::ThemeChanged,
+            Self::ScaleFactorChanged(_) => WindowEventKind::ScaleFactorChanged,
+            Self::FileHovered(_) | Self::FileHoverCancelled | Self::FileDropped(_) => {
+                WindowEventKind::File
+            }
+            Self::CloseRequested => WindowEventKind::CloseRequested,
+        }
+    }
+}
+
+/// The kinds of [`WindowEvent`] to listen for with [`use_window_events`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowEventKind {
+    Moved,
+    Resized,
+    Focused,
+    ThemeChanged,
+    ScaleFactorChanged,
+    /// Files being hovered, hover being cancelled, and files being dropped.
+    File,
+    CloseRequested,
+}
+
+impl WindowEventKind {
+    /// Every kind of event.
+    pub const ALL: &'static [WindowEventKind] = &[
+        Self::Moved,
+        Self::Resized,
+        Self::Focused,
+        Self::ThemeChanged,
+        Self::ScaleFactorChanged,
+        Self::File,
+        Self::CloseRequested,
+    ];
+}
+
+/// The theme reported by [`WindowEvent::ThemeChanged`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowTheme {
+    Light,
+    Dark,
+}
+
+/// A hook for reacting to raw window events that don't have a dedicated hook.
+///
+/// `handler` is called for every event whose kind is in `kinds`. The kinds are only read on the first render.
+///
+/// - On desktop, every [`WindowEvent`] is reported for the window the component is rendered in. File events are
+///   only reported if the webview doesn't handle the drop itself.
+/// - On web, only resizing, focus, and theme changes are reported.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::window::{use_window_events, WindowEvent, WindowEventKind};
+///
+/// fn App() -> Element {
+///     let mut position = use_signal(|| (0, 0));
+///     use_window_events(&[WindowEventKind::Moved], move |event| {
+///         if let WindowEvent::Moved { x, y } = event {
+///             position.set((x, y));
+///         }
+///     });
+///
+///     rsx! {
+///         p { "The window is at {position:?}" }
+///     }
+/// }
+/// ```
+pub fn use_window_events(
+    kinds: &[WindowEventKind],
+    mut handler: impl FnMut(WindowEvent) + 'static,
+) {
+    let kinds = use_hook(|| kinds.to_vec());
+
+    // Window events arrive outside of the Dioxus runtime, so they are sent to a task.
+    let events = use_hook(|| {
+        let (tx, mut rx) = futures::channel::mpsc::unbounded::<WindowEvent>();
+        spawn(async move {
+            while let Some(event) = rx.next().await {
+                handler(event);
+            }
+        });
+        tx
+    });
+
+    use_event_listener(kinds, events);
+}
+
+// Desktop implementation of the event listener.
+#[cfg(not(target_family = "wasm"))]
+fn use_event_listener(kinds: Vec<WindowEventKind>, events: UnboundedSender<WindowEvent>) {
+    use dioxus_desktop::{
+        tao::{event::Event, window::Theme},
+        DesktopContext, WindowEvent as TaoWindowEvent,
+    };
+
+    use_hook_with_cleanup(
+        move || {
+            let desktop = try_consume_context::<DesktopContext>()?;
+            let window_id = desktop.window.id();
+
+            Some(desktop.create_wry_event_handler(move |event, _| {
+                let Event::WindowEvent {
+                    event,
+                    window_id: id,
+                    ..
+                } = event
+                else {
+                    return;
+                };
+                if *id != window_id {
+                    return;
+                }
+
+                let event = match event {
+                    TaoWindowEvent::Moved(position) => WindowEvent::Moved {
+                        x: position.x,
+                        y: position.y,
+                    },
+                    TaoWindowEvent::Resized(size) => WindowEvent::Resized {
+                        width: size.width,
+                        height: size.height,
+                    },
+                    TaoWindowEvent::Focused(focused) => WindowEvent::Focused(*focused),
+                    TaoWindowEvent::ThemeChanged(Theme::Dark) => {
+                        WindowEvent::ThemeChanged(WindowTheme::Dark)
+                    }
+                    TaoWindowEvent::ThemeChanged(_) => {
+                        WindowEvent::ThemeChanged(WindowTheme::Light)
+                    }
+                    TaoWindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                        WindowEvent::ScaleFactorChanged(*scale_factor)
+                    }
+                    TaoWindowEvent::HoveredFile(path) => WindowEvent::FileHovered(path.clone()),
+                    TaoWindowEvent::HoveredFileCancelled => WindowEvent::FileHoverCancelled,
+                    TaoWindowEvent::DroppedFile(path) => WindowEvent::FileDropped(path.clone()),
+                    TaoWindowEvent::CloseRequested => WindowEvent::CloseRequested,
+                    _ => return,
+                };
+                if kinds.contains(&event.kind()) {
+                    events.unbounded_send(event).ok();
+                }
+            }))
+        },
+        |handler| {
+            if let Some(handler) = handler {
+                handler.remove();
+            }
+        },
+    );
+}
+
+// Web implementation of the event listener.
+#[cfg(target_family = "wasm")]
+fn use_event_listener(kinds: Vec<WindowEventKind>, events: UnboundedSender<WindowEvent>) {
+    use std::rc::Rc;
+    use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+    use web_sys::EventTarget;
+
+    type Listener = (EventTarget, &'static str, Closure<dyn FnMut(JsValue)>);
+
+    use_hook_with_cleanup(
+        move || {
+            let window = web_sys::window()?;
+            let mut listeners: Vec<Listener> = Vec::new();
+            let mut listen =
+                |target: &EventTarget,
+                 name: &'static str,
+                 to_event: Box<dyn Fn(JsValue) -> WindowEvent>| {
+                    let events = events.clone();
+                    let listener = Closure::wrap(Box::new(move |event: JsValue| {
+                        events.unbounded_send(to_event(event)).ok();
+                    }) as Box<dyn FnMut(JsValue)>);
+                    if target
+                        .add_event_listener_with_callback(name, listener.as_ref().unchecked_ref())
+                        .is_ok()
+                    {
+                        listeners.push((target.clone(), name, listener));
+                    }
+                };
+
+            if kinds.contains(&WindowEventKind::Resized) {
+                let window2 = window.clone();
+                listen(
+                    &window,
+                    "resize",
+                    Box::new(move |_| {
+                        // We will fail silently for conversion errors.
+                        let size = |size: Result<JsValue, JsValue>| {
+                            size.ok().and_then(|size| size.as_f64()).unwrap_or(0.0) as u32
+                        };
+                        WindowEvent::Resized {
+                            width: size(window2.inner_width()),
+                            height: size(window2.inner_height()),
+                        }
+                    }),
+                );
+            }
+            if kinds.contains(&WindowEventKind::Focused) {
+                listen(&window, "focus", Box::new(|_| WindowEvent::Focused(true)));
+                listen(&window, "blur", Box::new(|_| WindowEvent::Focused(false)));
+            }
+            if kinds.contains(&WindowEventKind::ThemeChanged) {
+                if let Ok(Some(query)) = window.match_media("(prefers-color-scheme: dark)") {
+                    listen(
+                        &query,
+                        "change",
+                        Box::new(|event| {
+                            let dark = js_sys::Reflect::get(&event, &"matches".into())
+                                .ok()
+                                .and_then(|matches| matches.as_bool())
+                                .unwrap_or(false);
+                            WindowEvent::ThemeChanged(match dark {
+                                true => WindowTheme::Dark,
+                                false => WindowTheme::Light,
+                            })
+                        }),
+                    );
+                }
+            }
+
+            Some(Rc::new(listeners))
+        },
+        |listeners| {
+            for (target, name, listener) in listeners.iter().flat_map(|listeners| listeners.iter())
+            {
+                target
+                    .remove_event_listener_with_callback(name, listener.as_ref().unchecked_ref())
+                    .ok();
+            }
+        },
+    );
+}
@@ -0,0 +1,197 @@
+use dioxus::prelude::*;
+use std::{error::Error, fmt::Display, time::Duration};
+
+/// How often [`use_system_locked`] checks whether the session is locked.
+#[cfg(not(target_family = "wasm"))]
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Represents an error with system idle utilities.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SystemIdleError {
+    /// System idle detection is not supported on this platform.
+    NotSupported,
+    /// The system returned an error while checking the idle state.
+    CheckFailed,
+}
+
+impl Error for SystemIdleError {}
+impl Display for SystemIdleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::NotSupported => write!(f, "the current platform is not supported"),
+            Self::CheckFailed => write!(
+                f,
+                "the system returned an error while checking the idle state"
+            ),
+        }
+    }
+}
+
+/// A hook for receiving whether the operating system session is locked.
+///
+/// This reflects the OS lock screen, not inactivity inside the app. The lock state is checked
+/// periodically, so it may take a moment to update.
+///
+/// Supported on Windows and on Linux with systemd-logind.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::window::use_system_locked;
+///
+/// fn App() -> Element {
+///     let locked = use_system_locked();
+///
+///     if let Ok(true) = locked() {
+///         return rsx! { div { "Locked" } };
+///     }
+///
+///     rsx! { p { "Sensitive data" } }
+/// }
+/// ```
+pub fn use_system_locked() -> ReadOnlySignal<Result<bool, SystemIdleError>> {
+    let locked = use_signal(get_system_locked);
+
+    #[cfg(not(target_family = "wasm"))]
+    use_hook(|| {
+        let mut locked = locked;
+        spawn(async move {
+            loop {
+                tokio::time::sleep(POLL_INTERVAL).await;
+                // Checking can start a process, like `loginctl` on Linux, which is too slow for the UI thread.
+                let new = tokio::task::spawn_blocking(get_system_locked)
+                    .await
+                    .unwrap_or(Err(SystemIdleError::CheckFailed));
+                if *locked.peek() != new {
+                    locked.set(new);
+                }
+            }
+        })
+    });
+
+    use_hook(|| ReadOnlySignal::new(locked))
+}
+
+/// Get whether the operating system session is locked.
+///
+/// On Linux this runs `loginctl`, so avoid calling it on the UI thread in a loop.
+pub fn get_system_locked() -> Result<bool, SystemIdleError> {
+    get_system_locked_platform()
+}
+
+/// Get how long it has been since the user last interacted with the system.
+///
+/// This measures input across the whole system, not just the app.
+/// Supported on Windows, macOS, and on Linux with systemd-logind when the desktop environment reports idle hints.
+/// On Linux this runs `loginctl`, so avoid calling it on the UI thread in a loop.
+pub fn get_idle_time() -> Result<Duration, SystemIdleError> {
+    get_idle_time_platform()
+}
+
+// Windows implementation.
+#[cfg(windows)]
+fn get_system_locked_platform() -> Result<bool, SystemIdleError> {
+    use windows::Win32::System::StationsAndDesktops::{
+        CloseDesktop, OpenInputDesktop, DESKTOP_CONTROL_FLAGS, DESKTOP_SWITCHDESKTOP,
+    };
+
+    // The input desktop can't be opened while the lock screen is shown.
+    unsafe {
+        match OpenInputDesktop(DESKTOP_CONTROL_FLAGS(0), false, DESKTOP_SWITCHDESKTOP) {
+            Ok(desktop) => {
+                let _ = CloseDesktop(desktop);
+                Ok(false)
+            }
+            Err(_) => Ok(true),
+        }
+    }
+}
+
+#[cfg(windows)]
+fn get_idle_time_platform() -> Result<Duration, SystemIdleError> {
+    use windows::Win32::{
+        System::SystemInformation::GetTickCount,
+        UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO},
+    };
+
+    let mut info = LASTINPUTINFO {
+        cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+        dwTime: 0,
+    };
+
+    unsafe {
+        if !GetLastInputInfo(&mut info).as_bool() {
+            return Err(SystemIdleError::CheckFailed);
+        }
+        let now = GetTickCount();
+        Ok(Duration::from_millis(now.wrapping_sub(info.dwTime) as u64))
+    }
+}
+
+// MacOS implementation.
+#[cfg(target_os = "macos")]
+fn get_system_locked_platform() -> Result<bool, SystemIdleError> {
+    Err(SystemIdleError::NotSupported)
+}
+
+#[cfg(target_os = "macos")]
+fn get_idle_time_platform() -> Result<Duration, SystemIdleError> {
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGEventSourceSecondsSinceLastEventType(source_state: i32, event_type: u32) -> f64;
+    }
+
+    // kCGEventSourceStateCombinedSessionState and kCGAnyInputEventType
+    let seconds = unsafe { CGEventSourceSecondsSinceLastEventType(0, u32::MAX) };
+    Duration::try_from_secs_f64(seconds).map_err(|_| SystemIdleError::CheckFailed)
+}
+
+// Linux implementation using systemd-logind.
+#[cfg(target_os = "linux")]
+fn session_property(property: &str) -> Result<String, SystemIdleError> {
+    let session = std::env::var("XDG_SESSION_ID").unwrap_or_else(|_| "auto".to_string());
+    let output = std::process::Command::new("loginctl")
+        .args(["show-session", &session, "--value", "-p", property])
+        .output()
+        .map_err(|_| SystemIdleError::NotSupported)?;
+
+    if !output.status.success() {
+        return Err(SystemIdleError::CheckFailed);
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn get_system_locked_platform() -> Result<bool, SystemIdleError> {
+    Ok(session_property("LockedHint")? == "yes")
+}
+
+#[cfg(target_os = "linux")]
+fn get_idle_time_platform() -> Result<Duration, SystemIdleError> {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    if session_property("IdleHint")? != "yes" {
+        return Ok(Duration::ZERO);
+    }
+
+    // The idle timestamp is in microseconds since the unix epoch.
+    let since: u64 = session_property("IdleSinceHint")?
+        .parse()
+        .map_err(|_| SystemIdleError::CheckFailed)?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|_| SystemIdleError::CheckFailed)?;
+    Ok(now.saturating_sub(Duration::from_micros(since)))
+}
+
+// Fallback for every other platform, including web.
+#[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
+fn get_system_locked_platform() -> Result<bool, SystemIdleError> {
+    Err(SystemIdleError::NotSupported)
+}
+
+#[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
+fn get_idle_time_platform() -> Result<Duration, SystemIdleError> {
+    Err(SystemIdleError::NotSupported)
+}
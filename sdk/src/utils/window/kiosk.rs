@@ -0,0 +1,294 @@
+use dioxus::prelude::*;
+
+use super::controls::{WindowControlError, WindowControlResult};
+
+/// The interface for kiosk mode.
+///
+/// See [`use_kiosk`] for more information.
+#[derive(Clone, Copy, PartialEq)]
+pub struct UseKiosk {
+    active: Memo<bool>,
+}
+
+impl UseKiosk {
+    /// Whether the window is in kiosk mode.
+    pub fn is_active(&self) -> ReadOnlySignal<bool> {
+        self.active.into()
+    }
+
+    /// Put the window in kiosk mode. See [`enter_kiosk_mode`].
+    pub fn enter(&self) -> WindowControlResult {
+        enter_kiosk_mode()
+    }
+
+    /// Take the window out of kiosk mode. See [`exit_kiosk_mode`].
+    pub fn exit(&self) -> WindowControlResult {
+        exit_kiosk_mode()
+    }
+}
+
+/// Put the window in kiosk mode, for point-of-sale terminals, signage, and other apps that own the whole screen.
+///
+/// The window is made fullscreen, kept above all other windows, and loses its decorations. Where the platform allows
+/// it, the shortcuts for leaving the app are turned off as well: the Windows key, Alt+Tab, Alt+F4, and Ctrl+Esc on
+/// Windows, and the Dock, the menu bar, and app switching on macOS. Secure shortcuts like Ctrl+Alt+Del always work.
+/// Calling this while the window is in kiosk mode does nothing. This is not supported on web.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::window::{enter_kiosk_mode, exit_kiosk_mode};
+///
+/// fn App() -> Element {
+///     use_hook(|| enter_kiosk_mode().ok());
+///
+///     rsx! {
+///         button {
+///             // Only staff know where the hidden button is.
+///             class: "hidden-corner",
+///             onclick: move |_| { exit_kiosk_mode().ok(); },
+///         }
+///     }
+/// }
+/// ```
+pub fn enter_kiosk_mode() -> WindowControlResult {
+    enter_kiosk_mode_platform()
+}
+
+/// Take the window out of kiosk mode, restoring the window and the shortcuts to how they were before.
+///
+/// Calling this while the window isn't in kiosk mode does nothing.
+pub fn exit_kiosk_mode() -> WindowControlResult {
+    exit_kiosk_mode_platform()
+}
+
+/// A hook for whether the window is in kiosk mode, with controls for entering and leaving it.
+///
+/// This is always inactive on web.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::window::use_kiosk;
+///
+/// fn Settings() -> Element {
+///     let kiosk = use_kiosk();
+///
+///     rsx! {
+///         button {
+///             onclick: move |_| {
+///                 let result = if kiosk.is_active()() { kiosk.exit() } else { kiosk.enter() };
+///                 result.ok();
+///             },
+///             if kiosk.is_active()() { "Leave kiosk mode" } else { "Enter kiosk mode" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_kiosk() -> UseKiosk {
+    UseKiosk {
+        active: use_memo(is_kiosk_platform),
+    }
+}
+
+// Web implementation of kiosk mode.
+#[cfg(target_family = "wasm")]
+fn enter_kiosk_mode_platform() -> WindowControlResult {
+    Err(WindowControlError::NotSupported)
+}
+
+#[cfg(target_family = "wasm")]
+fn exit_kiosk_mode_platform() -> WindowControlResult {
+    Err(WindowControlError::NotSupported)
+}
+
+#[cfg(target_family = "wasm")]
+fn is_kiosk_platform() -> bool {
+    false
+}
+
+// Desktop implementation of kiosk mode.
+#[cfg(not(target_family = "wasm"))]
+use desktop::*;
+
+#[cfg(not(target_family = "wasm"))]
+mod desktop {
+    use super::*;
+    use crate::utils::window::controls::desktop_window;
+    use dioxus_desktop::tao::window::{Fullscreen, WindowId};
+    use std::collections::HashMap;
+
+    /// The windows in kiosk mode, with how they were before.
+    static KIOSK_WINDOWS: GlobalSignal<HashMap<WindowId, SavedWindow>> =
+        Signal::global(HashMap::new);
+
+    /// The state of a window before it entered kiosk mode.
+    struct SavedWindow {
+        fullscreen: Option<Fullscreen>,
+        decorated: bool,
+        always_on_top: bool,
+    }
+
+    pub(super) fn enter_kiosk_mode_platform() -> WindowControlResult {
+        let desktop = desktop_window()?;
+        let window = &desktop.window;
+        let id = window.id();
+        if KIOSK_WINDOWS.peek().contains_key(&id) {
+            return Ok(());
+        }
+
+        let saved = SavedWindow {
+            fullscreen: window.fullscreen(),
+            decorated: window.is_decorated(),
+            always_on_top: window.is_always_on_top(),
+        };
+        window.set_fullscreen(Some(Fullscreen::Borderless(None)));
+        window.set_decorations(false);
+        window.set_always_on_top(true);
+
+        let first = KIOSK_WINDOWS.peek().is_empty();
+        KIOSK_WINDOWS.write().insert(id, saved);
+        if first {
+            shortcuts::block()?;
+        }
+        Ok(())
+    }
+
+    pub(super) fn exit_kiosk_mode_platform() -> WindowControlResult {
+        let desktop = desktop_window()?;
+        let window = &desktop.window;
+        let Some(saved) = KIOSK_WINDOWS.write().remove(&window.id()) else {
+            return Ok(());
+        };
+
+        window.set_always_on_top(saved.always_on_top);
+        window.set_decorations(saved.decorated);
+        window.set_fullscreen(saved.fullscreen);
+        if KIOSK_WINDOWS.peek().is_empty() {
+            shortcuts::unblock();
+        }
+        Ok(())
+    }
+
+    pub(super) fn is_kiosk_platform() -> bool {
+        desktop_window()
+            .map(|desktop| KIOSK_WINDOWS.read().contains_key(&desktop.window.id()))
+            .unwrap_or(false)
+    }
+
+    /// Turning off the shortcuts for leaving the app on Windows.
+    #[cfg(windows)]
+    mod shortcuts {
+        use super::WindowControlError;
+        use std::cell::Cell;
+        use windows::Win32::{
+            Foundation::{HINSTANCE, LPARAM, LRESULT, WPARAM},
+            System::LibraryLoader::GetModuleHandleW,
+            UI::{
+                Input::KeyboardAndMouse::{
+                    GetAsyncKeyState, VIRTUAL_KEY, VK_APPS, VK_CONTROL, VK_ESCAPE, VK_F4, VK_LWIN,
+                    VK_RWIN, VK_TAB,
+                },
+                WindowsAndMessaging::{
+                    CallNextHookEx, SetWindowsHookExW, UnhookWindowsHookEx, HC_ACTION, HHOOK,
+                    KBDLLHOOKSTRUCT, LLKHF_ALTDOWN, WH_KEYBOARD_LL,
+                },
+            },
+        };
+
+        thread_local! {
+            /// The keyboard hook, which gets the keys before the system handles its shortcuts.
+            static HOOK: Cell<Option<HHOOK>> = const { Cell::new(None) };
+        }
+
+        pub(super) fn block() -> Result<(), WindowControlError> {
+            if HOOK.with(Cell::get).is_some() {
+                return Ok(());
+            }
+            // The hook runs on this thread, whose event loop keeps it responsive.
+            let hook = unsafe {
+                let module = GetModuleHandleW(None).map_err(|_| WindowControlError::Failed)?;
+                SetWindowsHookExW(
+                    WH_KEYBOARD_LL,
+                    Some(block_shortcuts),
+                    HINSTANCE(module.0),
+                    0,
+                )
+            }
+            .map_err(|_| WindowControlError::Failed)?;
+            HOOK.with(|cell| cell.set(Some(hook)));
+            Ok(())
+        }
+
+        pub(super) fn unblock() {
+            if let Some(hook) = HOOK.with(Cell::take) {
+                unsafe { UnhookWindowsHookEx(hook) };
+            }
+        }
+
+        unsafe extern "system" fn block_shortcuts(
+            code: i32,
+            wparam: WPARAM,
+            lparam: LPARAM,
+        ) -> LRESULT {
+            if code == HC_ACTION as i32 {
+                let key = &*(lparam.0 as *const KBDLLHOOKSTRUCT);
+                let alt = key.flags.0 & LLKHF_ALTDOWN.0 != 0;
+                let ctrl = GetAsyncKeyState(VK_CONTROL.0 as i32) < 0;
+                let blocked = match VIRTUAL_KEY(key.vkCode as u16) {
+                    VK_LWIN | VK_RWIN | VK_APPS => true,
+                    VK_TAB | VK_F4 => alt,
+                    VK_ESCAPE => alt || ctrl,
+                    _ => false,
+                };
+                if blocked {
+                    return LRESULT(1);
+                }
+            }
+            CallNextHookEx(HHOOK::default(), code, wparam, lparam)
+        }
+    }
+
+    /// Turning off the Dock, the menu bar, and app switching on macOS.
+    #[cfg(target_os = "macos")]
+    mod shortcuts {
+        use super::WindowControlError;
+        use objc::{class, msg_send, runtime::Object, sel, sel_impl};
+
+        /// `NSApplicationPresentationHideDock | NSApplicationPresentationHideMenuBar |
+        /// NSApplicationPresentationDisableProcessSwitching | NSApplicationPresentationDisableForceQuit |
+        /// NSApplicationPresentationDisableSessionTermination | NSApplicationPresentationDisableHideApplication`
+        const KIOSK_OPTIONS: usize =
+            (1 << 1) | (1 << 3) | (1 << 5) | (1 << 6) | (1 << 7) | (1 << 8);
+
+        pub(super) fn block() -> Result<(), WindowControlError> {
+            set_presentation_options(KIOSK_OPTIONS);
+            Ok(())
+        }
+
+        pub(super) fn unblock() {
+            set_presentation_options(0);
+        }
+
+        fn set_presentation_options(options: usize) {
+            unsafe {
+                let app: *mut Object = msg_send![class!(NSApplication), sharedApplication];
+                let _: () = msg_send![app, setPresentationOptions: options];
+            }
+        }
+    }
+
+    /// Other platforms don't let apps turn off the system shortcuts.
+    #[cfg(not(any(windows, target_os = "macos")))]
+    mod shortcuts {
+        use super::WindowControlError;
+
+        pub(super) fn block() -> Result<(), WindowControlError> {
+            Ok(())
+        }
+
+        pub(super) fn unblock() {}
+    }
+}
@@ -0,0 +1,68 @@
+//! Utilities for the window.
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "window_size")] {
+        mod size;
+        pub use size::*;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "window_controls")] {
+        mod controls;
+        pub use controls::*;
+        mod arrange;
+        pub use arrange::*;
+        mod kiosk;
+        pub use kiosk::*;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "window_effects")] {
+        mod effects;
+        pub use effects::*;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "page_visibility")] {
+        mod visibility;
+        pub use visibility::*;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "document_title")] {
+        mod title;
+        pub use title::*;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "prevent_close")] {
+        mod prevent_close;
+        pub use prevent_close::*;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "system_idle")] {
+        mod idle;
+        pub use idle::*;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "window_events")] {
+        mod events;
+        pub use events::*;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "drag_and_drop")] {
+        mod drag;
+        pub use drag::*;
+    }
+}
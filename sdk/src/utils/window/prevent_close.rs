@@ -0,0 +1,155 @@
+use dioxus::prelude::*;
+use futures::{channel::mpsc::UnboundedSender, StreamExt};
+use std::{cell::Cell, rc::Rc};
+
+/// A hook for stopping the window from closing, such as while there are unsaved changes.
+///
+/// While `when` is `true`, requests to close the window are cancelled and `handler` is called,
+/// so the app can ask the user what to do. To close the window anyway, set `when` to `false` and close it again.
+///
+/// - On desktop, the app must be launched with [`WindowCloseBehaviour::LastWindowHides`](dioxus_desktop::WindowCloseBehaviour::LastWindowHides).
+///   The hook shows the window again if the close was cancelled, and closes it for real otherwise.
+/// - On web, closing or reloading the tab shows the browser's own confirmation dialog. Browsers don't allow pages
+///   to customize that dialog, so `handler` isn't called.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::window::use_prevent_close;
+///
+/// fn App() -> Element {
+///     let mut unsaved = use_signal(|| false);
+///     let mut show_prompt = use_signal(|| false);
+///     use_prevent_close(unsaved, move || show_prompt.set(true));
+///
+///     rsx! {
+///         textarea { oninput: move |_| unsaved.set(true) }
+///         if show_prompt() {
+///             p { "You have unsaved changes" }
+///             button { onclick: move |_| unsaved.set(false), "Discard them" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_prevent_close(
+    when: impl Into<ReadOnlySignal<bool>>,
+    mut handler: impl FnMut() + 'static,
+) {
+    let when = use_hook(|| when.into());
+
+    // Close requests arrive outside of the Dioxus runtime, so the current value is mirrored here.
+    let active = use_hook(|| Rc::new(Cell::new(false)));
+    use_effect({
+        let active = active.clone();
+        move || active.set(when())
+    });
+
+    let requests = use_hook(|| {
+        let (tx, mut rx) = futures::channel::mpsc::unbounded::<()>();
+        spawn(async move {
+            while rx.next().await.is_some() {
+                handler();
+            }
+        });
+        tx
+    });
+
+    use_close_listener(active, requests);
+}
+
+// Desktop implementation of the close listener.
+#[cfg(not(target_family = "wasm"))]
+fn use_close_listener(active: Rc<Cell<bool>>, requests: UnboundedSender<()>) {
+    use dioxus_desktop::{
+        tao::event::{Event, WindowEvent},
+        DesktopContext,
+    };
+
+    use_hook_with_cleanup(
+        move || {
+            let desktop = try_consume_context::<DesktopContext>()?;
+            let window = desktop.clone();
+            // Whether the window was hidden by a cancelled close and needs to be shown again.
+            let mut reshow = false;
+
+            Some(
+                desktop.create_wry_event_handler(move |event, target| match event {
+                    Event::WindowEvent {
+                        event: WindowEvent::CloseRequested,
+                        window_id,
+                        ..
+                    } if *window_id == window.window.id() => {
+                        if active.get() {
+                            // The window is hidden after the event handlers run, so it is shown again on the next tick.
+                            reshow = true;
+                            requests.unbounded_send(()).ok();
+                        } else {
+                            window.close();
+                        }
+                    }
+                    Event::RedrawEventsCleared if reshow => {
+                        reshow = false;
+                        show_window(&window, target);
+                    }
+                    _ => {}
+                }),
+            )
+        },
+        |handler| {
+            if let Some(handler) = handler {
+                handler.remove();
+            }
+        },
+    );
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[cfg_attr(not(target_os = "macos"), allow(unused_variables))]
+fn show_window<T>(
+    desktop: &dioxus_desktop::DesktopContext,
+    target: &dioxus_desktop::tao::event_loop::EventLoopWindowTarget<T>,
+) {
+    // Hiding the last window hides the whole app on macOS.
+    #[cfg(target_os = "macos")]
+    {
+        use dioxus_desktop::tao::platform::macos::EventLoopWindowTargetExtMacOS;
+        target.show_application();
+    }
+
+    desktop.window.set_visible(true);
+    desktop.window.set_focus();
+}
+
+// Web implementation of the close listener.
+#[cfg(target_family = "wasm")]
+fn use_close_listener(active: Rc<Cell<bool>>, _requests: UnboundedSender<()>) {
+    use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+
+    use_hook_with_cleanup(
+        move || {
+            let listener = Closure::wrap(Box::new(move |event: web_sys::Event| {
+                if active.get() {
+                    // Browsers show the confirmation dialog if the event is cancelled, and older ones also need a return value.
+                    event.prevent_default();
+                    js_sys::Reflect::set(&event, &"returnValue".into(), &JsValue::from_str(""))
+                        .ok();
+                }
+            }) as Box<dyn FnMut(web_sys::Event)>);
+
+            let window = web_sys::window()?;
+            window
+                .add_event_listener_with_callback("beforeunload", listener.as_ref().unchecked_ref())
+                .ok()?;
+            Some(Rc::new(listener))
+        },
+        |listener| {
+            if let (Some(listener), Some(window)) = (listener, web_sys::window()) {
+                let callback: &JsValue = (*listener).as_ref();
+                window
+                    .remove_event_listener_with_callback("beforeunload", callback.unchecked_ref())
+                    .ok();
+            }
+        },
+    );
+}
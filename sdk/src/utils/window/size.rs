@@ -0,0 +1,308 @@
+use dioxus::prelude::*;
+use std::sync::Once;
+
+#[allow(dead_code)]
+static INIT: Once = Once::new();
+
+/// Stores the width and height of a window, screen, or viewport.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WindowSize {
+    /// The horizontal size in logical pixels, which are the pixels CSS uses.
+    ///
+    /// Desktop apps used to get physical pixels here. Use [`WindowSize::physical_width`] for those.
+    pub width: u32,
+    /// The vertical size in logical pixels, which are the pixels CSS uses.
+    ///
+    /// Desktop apps used to get physical pixels here. Use [`WindowSize::physical_height`] for those.
+    pub height: u32,
+    /// The horizontal size in physical pixels of the display.
+    pub physical_width: u32,
+    /// The vertical size in physical pixels of the display.
+    pub physical_height: u32,
+    /// The number of physical pixels per logical pixel, like `2.0` on most high DPI displays.
+    pub scale_factor: f64,
+}
+
+impl WindowSize {
+    /// Build a size from physical pixels and the scale factor.
+    #[cfg(not(target_family = "wasm"))]
+    fn from_physical(physical_width: u32, physical_height: u32, scale_factor: f64) -> Self {
+        Self {
+            width: (physical_width as f64 / scale_factor).round() as u32,
+            height: (physical_height as f64 / scale_factor).round() as u32,
+            physical_width,
+            physical_height,
+            scale_factor,
+        }
+    }
+
+    /// Build a size from logical pixels and the scale factor.
+    #[cfg(target_family = "wasm")]
+    fn from_logical(width: f64, height: f64, scale_factor: f64) -> Self {
+        Self {
+            width: width as u32,
+            height: height as u32,
+            physical_width: (width * scale_factor).round() as u32,
+            physical_height: (height * scale_factor).round() as u32,
+            scale_factor,
+        }
+    }
+
+    /// Whether the size is taller than it is wide.
+    pub fn orientation(&self) -> Orientation {
+        if self.height > self.width {
+            Orientation::Portrait
+        } else {
+            Orientation::Landscape
+        }
+    }
+}
+
+/// Whether a window or screen is taller than it is wide.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Orientation {
+    Portrait,
+    /// Wider than tall, or square.
+    Landscape,
+}
+
+/// A hook for receiving the size of the Window.
+///
+/// The initial window size will be returned with this hook and
+/// updated continously as the window is resized.
+///
+/// The `width` and `height` are in logical pixels on every platform, so they match the sizes used in CSS. On
+/// desktop they used to be physical pixels, which differ on high DPI displays. Code that needs the physical size,
+/// like sizing a canvas, should use `physical_width` and `physical_height` instead.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::window::use_window_size;
+///
+/// fn App() -> Element {
+///     let size = use_window_size();
+///
+///     rsx! {
+///         p { "Width: {size().width}" }
+///         p { "Height: {size().height}" }
+///     }
+/// }
+/// ```
+pub fn use_window_size() -> ReadOnlySignal<WindowSize> {
+    let window_size = match try_use_context::<Signal<WindowSize>>() {
+        Some(w) => w,
+        // This should only run once.
+        None => {
+            let signal = Signal::new_in_scope(get_window_size(), ScopeId::ROOT);
+            let size = provide_root_context(signal);
+            listen(size);
+
+            size
+        }
+    };
+
+    use_hook(|| ReadOnlySignal::new(window_size))
+}
+
+// Listener for the web implementation.
+#[cfg(target_family = "wasm")]
+fn listen(mut window_size: Signal<WindowSize>) {
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    INIT.call_once(|| {
+        let window = web_sys::window().expect("no wasm window found; are you in wasm?");
+        let window2 = window.clone();
+
+        // Zooming the page changes the scale factor and fires a resize event too.
+        let on_resize = Closure::wrap(Box::new(move || {
+            window_size.set(web_window_size(&window2));
+        }) as Box<dyn FnMut()>);
+
+        let on_resize_cb = on_resize.as_ref().clone();
+        on_resize.forget();
+        window.set_onresize(Some(on_resize_cb.unchecked_ref()));
+    });
+}
+
+// Listener for anything but the web implementation.
+#[cfg(not(target_family = "wasm"))]
+fn listen(mut window_size: Signal<WindowSize>) {
+    use dioxus_desktop::{tao::event::Event, window, WindowEvent};
+
+    let window = window();
+    let id = window.window.id();
+    let mut current_scale_factor = window.scale_factor();
+    window.create_wry_event_handler(move |event, _| {
+        let Event::WindowEvent {
+            event, window_id, ..
+        } = event
+        else {
+            return;
+        };
+        if *window_id != id {
+            return;
+        }
+
+        match event {
+            WindowEvent::Resized(size) => {
+                window_size.set(WindowSize::from_physical(
+                    size.width,
+                    size.height,
+                    current_scale_factor,
+                ));
+            }
+            WindowEvent::ScaleFactorChanged {
+                scale_factor,
+                new_inner_size,
+            } => {
+                current_scale_factor = *scale_factor;
+                window_size.set(WindowSize::from_physical(
+                    new_inner_size.width,
+                    new_inner_size.height,
+                    *scale_factor,
+                ));
+            }
+            _ => {}
+        }
+    });
+}
+
+/// Get the size of the current window.
+///
+/// This function will return the current size of the window.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::window::get_window_size;
+///
+/// fn App() -> Element {
+///     let size = use_signal(get_window_size);
+///
+///     rsx! {
+///         p { "Width: {size().width}" }
+///         p { "Height: {size().height}" }
+///     }
+/// }
+/// ```
+pub fn get_window_size() -> WindowSize {
+    get_window_size_platform()
+}
+
+// Web implementation of size getter.
+#[cfg(target_family = "wasm")]
+fn get_window_size_platform() -> WindowSize {
+    let window = web_sys::window().expect("no wasm window found; are you in wasm?");
+    web_window_size(&window)
+}
+
+#[cfg(target_family = "wasm")]
+fn web_window_size(window: &web_sys::Window) -> WindowSize {
+    // We will fail silently for conversion errors.
+    let height = window
+        .inner_height()
+        .ok()
+        .and_then(|height| height.as_f64())
+        .unwrap_or(0.0);
+    let width = window
+        .inner_width()
+        .ok()
+        .and_then(|width| width.as_f64())
+        .unwrap_or(0.0);
+
+    WindowSize::from_logical(width, height, window.device_pixel_ratio())
+}
+
+// Desktop implementation of size getter.
+#[cfg(not(target_family = "wasm"))]
+fn get_window_size_platform() -> WindowSize {
+    let window = dioxus_desktop::window();
+    let size = window.inner_size();
+    WindowSize::from_physical(size.width, size.height, window.scale_factor())
+}
+
+/// A hook for whether the window is in portrait or landscape orientation, for responsive layouts.
+///
+/// On the web this follows the orientation of the screen where the browser reports it, like on phones and tablets.
+/// Everywhere else the orientation is derived from the window size.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::window::{use_orientation, Orientation};
+///
+/// fn App() -> Element {
+///     let orientation = use_orientation();
+///
+///     rsx! {
+///         if orientation() == Orientation::Portrait {
+///             p { "Rotate your device for the full view" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_orientation() -> Memo<Orientation> {
+    let size = use_window_size();
+    let screen = use_hook(screen_orientation);
+
+    use_memo(move || match screen {
+        Some(orientation) => orientation(),
+        None => size().orientation(),
+    })
+}
+
+/// The orientation of the screen, if the browser reports it.
+#[cfg(target_family = "wasm")]
+fn screen_orientation() -> Option<Signal<Orientation>> {
+    use js_sys::Reflect;
+    use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+
+    #[derive(Clone, Copy)]
+    struct ScreenOrientation(Option<Signal<Orientation>>);
+
+    if let Some(ScreenOrientation(orientation)) = try_consume_context() {
+        return orientation;
+    }
+
+    let read = |screen_orientation: &JsValue| {
+        let kind = Reflect::get(screen_orientation, &"type".into())
+            .ok()
+            .and_then(|kind| kind.as_string())?;
+        Some(match kind.starts_with("portrait") {
+            true => Orientation::Portrait,
+            false => Orientation::Landscape,
+        })
+    };
+
+    let orientation = web_sys::window()
+        .and_then(|window| Reflect::get(&window, &"screen".into()).ok())
+        .and_then(|screen| Reflect::get(&screen, &"orientation".into()).ok())
+        .filter(|orientation| !orientation.is_undefined() && !orientation.is_null())
+        .and_then(|screen_orientation| {
+            let mut signal = Signal::new_in_scope(read(&screen_orientation)?, ScopeId::ROOT);
+
+            let target = screen_orientation.clone();
+            let on_change = Closure::wrap(Box::new(move || {
+                if let Some(orientation) = read(&target) {
+                    signal.set(orientation);
+                }
+            }) as Box<dyn FnMut()>);
+            let target = screen_orientation.unchecked_ref::<web_sys::EventTarget>();
+            target
+                .add_event_listener_with_callback("change", on_change.as_ref().unchecked_ref())
+                .ok();
+            on_change.forget();
+
+            Some(signal)
+        });
+
+    provide_root_context(ScreenOrientation(orientation)).0
+}
+
+/// The orientation of the screen, if the browser reports it.
+#[cfg(not(target_family = "wasm"))]
+fn screen_orientation() -> Option<Signal<Orientation>> {
+    None
+}
@@ -0,0 +1,44 @@
+use dioxus::prelude::*;
+
+/// A hook for keeping the document title, shown in the browser tab or the window titlebar, in sync with a signal.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::window::use_document_title;
+///
+/// fn App() -> Element {
+///     let mut unread = use_signal(|| 0);
+///     use_document_title(use_memo(move || format!("Inbox ({unread})")));
+///
+///     rsx! {
+///         button { onclick: move |_| unread += 1, "New message" }
+///     }
+/// }
+/// ```
+pub fn use_document_title(title: impl Into<ReadOnlySignal<String>>) {
+    let title = use_hook(|| title.into());
+    use_effect(move || set_document_title(&title.read()));
+}
+
+/// Set the document title, shown in the browser tab or the window titlebar.
+pub fn set_document_title(title: &str) {
+    set_document_title_platform(title)
+}
+
+// Web implementation of the title setter.
+#[cfg(target_family = "wasm")]
+fn set_document_title_platform(title: &str) {
+    if let Some(document) = web_sys::window().and_then(|window| window.document()) {
+        document.set_title(title);
+    }
+}
+
+// Desktop implementation of the title setter.
+#[cfg(not(target_family = "wasm"))]
+fn set_document_title_platform(title: &str) {
+    if let Some(desktop) = try_consume_context::<dioxus_desktop::DesktopContext>() {
+        desktop.set_title(title);
+    }
+}
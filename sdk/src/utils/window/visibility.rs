@@ -0,0 +1,147 @@
+use dioxus::prelude::*;
+
+/// Whether the page can be seen by the user.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PageVisibility {
+    /// At least part of the page is visible.
+    Visible,
+    /// The page is in a background tab, or the window is minimized or hidden.
+    Hidden,
+    /// The page is being rendered before the user navigated to it. Only reported by some browsers.
+    Prerender,
+}
+
+/// The visibility shared by every [`use_page_visibility`] hook.
+#[derive(Clone, Copy)]
+struct SharedVisibility(Signal<PageVisibility>);
+
+/// A hook for receiving whether the page can be seen by the user.
+///
+/// - On web, this follows the Page Visibility API, so it is hidden while the tab is in the background.
+/// - On desktop, this is hidden while the window is minimized or hidden.
+///
+/// Combine it with [`use_interval_when`](crate::utils::timing::use_interval_when) to stop polling while
+/// nobody is looking.
+///
+/// # Example
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_sdk::utils::window::{use_page_visibility, PageVisibility};
+///
+/// fn App() -> Element {
+///     let visibility = use_page_visibility();
+///
+///     rsx! {
+///         if visibility() == PageVisibility::Hidden {
+///             p { "Welcome back!" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_page_visibility() -> ReadOnlySignal<PageVisibility> {
+    let visibility = match try_use_context::<SharedVisibility>() {
+        Some(SharedVisibility(visibility)) => visibility,
+        // This should only run once.
+        None => {
+            let signal = Signal::new_in_scope(get_page_visibility(), ScopeId::ROOT);
+            provide_root_context(SharedVisibility(signal));
+            listen(signal);
+
+            signal
+        }
+    };
+
+    use_hook(|| ReadOnlySignal::new(visibility))
+}
+
+/// Get whether the page can currently be seen by the user.
+///
+/// See [`use_page_visibility`] for more information.
+pub fn get_page_visibility() -> PageVisibility {
+    get_page_visibility_platform()
+}
+
+// Web implementation of the visibility getter.
+#[cfg(target_family = "wasm")]
+fn get_page_visibility_platform() -> PageVisibility {
+    let state = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| js_sys::Reflect::get(&document, &"visibilityState".into()).ok())
+        .and_then(|state| state.as_string());
+
+    match state.as_deref() {
+        Some("hidden") => PageVisibility::Hidden,
+        Some("prerender") => PageVisibility::Prerender,
+        _ => PageVisibility::Visible,
+    }
+}
+
+// Listener for the web implementation.
+#[cfg(target_family = "wasm")]
+fn listen(mut visibility: Signal<PageVisibility>) {
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return;
+    };
+
+    let on_change = Closure::wrap(Box::new(move || {
+        visibility.set(get_page_visibility_platform());
+    }) as Box<dyn FnMut()>);
+
+    document
+        .add_event_listener_with_callback("visibilitychange", on_change.as_ref().unchecked_ref())
+        .ok();
+    // The listener is shared by every hook, so it stays for the lifetime of the app.
+    on_change.forget();
+}
+
+// Desktop implementation of the visibility getter.
+#[cfg(not(target_family = "wasm"))]
+fn get_page_visibility_platform() -> PageVisibility {
+    match try_consume_context::<dioxus_desktop::DesktopContext>() {
+        Some(desktop) => window_visibility(&desktop),
+        None => PageVisibility::Visible,
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn window_visibility(desktop: &dioxus_desktop::DesktopContext) -> PageVisibility {
+    if desktop.window.is_visible() && !desktop.window.is_minimized() {
+        PageVisibility::Visible
+    } else {
+        PageVisibility::Hidden
+    }
+}
+
+// Listener for the desktop implementation.
+#[cfg(not(target_family = "wasm"))]
+fn listen(mut visibility: Signal<PageVisibility>) {
+    use dioxus_desktop::{
+        tao::event::{Event, WindowEvent},
+        DesktopContext,
+    };
+
+    let Some(desktop) = try_consume_context::<DesktopContext>() else {
+        return;
+    };
+    let window = desktop.clone();
+
+    // The system doesn't report minimizing directly, but the window is resized and loses focus when it happens.
+    desktop.create_wry_event_handler(move |event, _| {
+        if let Event::WindowEvent {
+            event: WindowEvent::Resized(_) | WindowEvent::Focused(_),
+            window_id,
+            ..
+        } = event
+        {
+            if *window_id == window.window.id() {
+                let current = window_visibility(&window);
+                if *visibility.peek() != current {
+                    visibility.set(current);
+                }
+            }
+        }
+    });
+}